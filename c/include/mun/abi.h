@@ -6,7 +6,7 @@
 /**
  * Defines the current ABI version
  */
-#define MUN_ABI_VERSION 300
+#define MUN_ABI_VERSION 400
 
 /**
  * Represents the kind of memory management a struct uses.
@@ -159,6 +159,11 @@ typedef struct MunFunctionDefinition {
      * Function pointer
      */
     const void *fn_ptr;
+    /**
+     * Path of the source file that defines the function, relative to the
+     * source directory, or null if unknown
+     */
+    const char *source_file;
 } MunFunctionDefinition;
 
 /**
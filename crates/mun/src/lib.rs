@@ -1,9 +1,12 @@
 mod ops;
+mod repl;
 
 use std::ffi::OsString;
 
 use clap::{Parser, Subcommand};
-use ops::{build, init, language_server, new, start};
+use ops::{build, init, language_server, new, repl, start};
+
+pub use crate::repl::run_repl;
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
@@ -29,6 +32,9 @@ enum Command {
 
     /// Invoke a function from a munlib
     Start(start::Args),
+
+    /// Start an interactive session to evaluate Mun expressions
+    Repl(repl::Args),
 }
 
 #[derive(Copy, Debug, Clone, PartialEq, Eq)]
@@ -59,5 +65,6 @@ where
         Command::New(args) => new::new(args),
         Command::Init(args) => init::init(args),
         Command::Start(args) => start::start(args),
+        Command::Repl(args) => repl::repl(args),
     }
 }
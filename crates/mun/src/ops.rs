@@ -2,4 +2,5 @@ pub mod build;
 pub mod init;
 pub mod language_server;
 pub mod new;
+pub mod repl;
 pub mod start;
@@ -34,6 +34,11 @@ pub struct Args {
     #[clap(long)]
     emit_ir: bool,
 
+    /// Emits only the metadata of the functions and structs instead of a
+    /// *.munlib, without generating machine code
+    #[clap(long, conflicts_with = "emit_ir")]
+    emit_metadata: bool,
+
     /// Run the compiler in watch mode. Watch input files and trigger
     /// recompilation on changes.
     #[clap(long)]
@@ -42,6 +47,19 @@ pub struct Args {
     /// Target for machine code
     #[clap(long, value_parser=parse_target_triple)]
     target: Option<Target>,
+
+    /// Enables a feature for `#[cfg(feature = "...")]` attributes
+    #[clap(long = "feature")]
+    features: Vec<String>,
+
+    /// Treats warnings as errors, failing the build if any warning is emitted
+    #[clap(long)]
+    deny_warnings: bool,
+
+    /// Compiles the built-in prelude, e.g. `math::sqrt`, together with the
+    /// project
+    #[clap(long)]
+    prelude: bool,
 }
 
 fn parse_target_triple(target_triple: &str) -> Result<Target, String> {
@@ -111,6 +129,11 @@ pub fn build(args: Args) -> Result<ExitStatus, anyhow::Error> {
         optimization_lvl,
         out_dir: None,
         emit_ir: args.emit_ir,
+        emit_metadata: args.emit_metadata,
+        features: args.features,
+        deny_warnings: args.deny_warnings,
+        prelude: args.prelude,
+        ..Config::default()
     };
 
     if args.watch {
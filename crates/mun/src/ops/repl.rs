@@ -0,0 +1,12 @@
+use std::io::{stdin, stdout};
+
+use crate::{run_repl, ExitStatus};
+
+#[derive(clap::Args)]
+pub struct Args {}
+
+/// Starts an interactive session that evaluates Mun expressions.
+pub fn repl(_args: Args) -> anyhow::Result<ExitStatus> {
+    run_repl(stdin().lock(), stdout())?;
+    Ok(ExitStatus::Success)
+}
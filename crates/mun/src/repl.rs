@@ -0,0 +1,348 @@
+//! A read-eval-print loop that compiles every entered expression into a
+//! function and evaluates it in a persistent [`Runtime`].
+
+use std::{
+    io::{BufRead, Write},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use mun_compiler::{Config, DisplayColor, Driver, FileId, PathOrInline, RelativePathBuf};
+use mun_hir::PrimitiveType;
+use mun_runtime::{Runtime, StructRef, Type};
+
+/// The name of the file in which all definitions are stored
+const FILE_NAME: &str = "mod.mun";
+
+/// Returns the primitive types that are tried, in order, as the type of an
+/// entered expression. The unit type is tried first, followed by the default
+/// types of integer and float literals.
+fn primitive_types() -> Vec<String> {
+    let mut types = PrimitiveType::ALL
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect::<Vec<_>>();
+    types.sort_by_key(|ty| !matches!(ty.as_str(), "i32" | "f64"));
+    types.insert(0, String::from("()"));
+    types
+}
+
+/// Runs a REPL that reads lines from `reader` and writes the results to
+/// `writer`.
+///
+/// A line that starts with a function or struct definition is added to the
+/// persistent module. Any other line is evaluated as an expression of which
+/// the value is printed.
+pub fn run_repl(reader: impl BufRead, mut writer: impl Write) -> anyhow::Result<()> {
+    let mut repl = Repl::new()?;
+
+    write!(writer, "> ")?;
+    writer.flush()?;
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() {
+            match repl.eval(line) {
+                Ok(Some(output)) => writeln!(writer, "{output}")?,
+                Ok(None) => {}
+                Err(e) => writeln!(writer, "{e}")?,
+            }
+        }
+
+        write!(writer, "> ")?;
+        writer.flush()?;
+    }
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+struct Repl {
+    driver: Driver,
+    file_id: FileId,
+    runtime: Runtime,
+
+    /// All successfully compiled definitions
+    definitions: Vec<String>,
+    /// The names of all defined structs
+    struct_names: Vec<String>,
+    /// The number of evaluated expressions, used to generate unique function
+    /// names
+    eval_count: usize,
+
+    // Keeps the output directory alive
+    _out_dir: tempfile::TempDir,
+}
+
+impl Repl {
+    fn new() -> anyhow::Result<Self> {
+        let out_dir = tempfile::tempdir()?;
+        let config = Config {
+            out_dir: Some(out_dir.path().to_path_buf()),
+            ..Config::default()
+        };
+
+        let (mut driver, file_id) = Driver::with_file(
+            config,
+            PathOrInline::Inline {
+                rel_path: RelativePathBuf::from(FILE_NAME),
+                contents: String::new(),
+            },
+        )?;
+        driver.write_all_assemblies(true)?;
+
+        // Safety: we compiled the library ourselves, so loading it should be safe
+        let runtime =
+            unsafe { Runtime::builder(driver.assembly_output_path_from_file(file_id)).finish() }?;
+
+        Ok(Self {
+            driver,
+            file_id,
+            runtime,
+            definitions: Vec::new(),
+            struct_names: Vec::new(),
+            eval_count: 0,
+            _out_dir: out_dir,
+        })
+    }
+
+    /// Evaluates a single line of input, returning the text to print.
+    fn eval(&mut self, line: &str) -> anyhow::Result<Option<String>> {
+        if is_definition(line) {
+            self.define(line)?;
+            return Ok(None);
+        }
+
+        let function_name = format!("repl_eval_{}", self.eval_count);
+        self.eval_count += 1;
+
+        // Find the first type for which the expression type checks.
+        let mut first_error = None;
+        for ty in primitive_types()
+            .iter()
+            .map(String::as_str)
+            .chain(self.struct_names.iter().map(String::as_str))
+        {
+            let function = if ty == "()" {
+                format!("pub fn {function_name}() {{ {line} }}")
+            } else {
+                format!("pub fn {function_name}() -> {ty} {{ {line} }}")
+            };
+            match self.check(&function)? {
+                None => {
+                    self.reload(&function)?;
+                    return self.invoke(&function_name).map(Some);
+                }
+                Some(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+
+        // Restore the previous state of the module
+        self.check("")?;
+        Err(anyhow!(
+            "{}",
+            first_error.unwrap_or_else(|| String::from("could not evaluate expression"))
+        ))
+    }
+
+    /// Adds a definition to the module.
+    fn define(&mut self, definition: &str) -> anyhow::Result<()> {
+        if let Some(error) = self.check(definition)? {
+            self.check("")?;
+            return Err(anyhow!("{}", error));
+        }
+
+        self.reload(definition)?;
+        self.definitions.push(definition.to_owned());
+        if let Some(name) = struct_name(definition) {
+            self.struct_names.push(name.to_owned());
+        }
+        Ok(())
+    }
+
+    /// Returns the source of the module, with `extra` appended to the existing
+    /// definitions.
+    fn source(&self, extra: &str) -> String {
+        let mut source = self.definitions.join("\n");
+        source.push('\n');
+        source.push_str(extra);
+        source
+    }
+
+    /// Type checks the module with `extra` appended. Returns the diagnostics if
+    /// there are any errors.
+    fn check(&mut self, extra: &str) -> anyhow::Result<Option<String>> {
+        self.driver.set_file_text(FILE_NAME, self.source(extra))?;
+        self.driver
+            .emit_diagnostics_to_string(DisplayColor::Disable)
+    }
+
+    /// Compiles the module with `extra` appended and waits for the runtime to
+    /// hot reload it.
+    fn reload(&mut self, extra: &str) -> anyhow::Result<()> {
+        self.driver.set_file_text(FILE_NAME, self.source(extra))?;
+        self.driver.write_all_assemblies(false)?;
+
+        let start_time = Instant::now();
+        // Safety: we compiled the library ourselves, so reloading it should be safe
+        while !unsafe { self.runtime.update() } {
+            if start_time.elapsed() > Duration::from_secs(10) {
+                return Err(anyhow!(
+                    "the runtime did not reload {} within 10 seconds",
+                    self.driver
+                        .assembly_output_path_from_file(self.file_id)
+                        .display()
+                ));
+            }
+            sleep(Duration::from_millis(1));
+        }
+
+        Ok(())
+    }
+
+    /// Invokes the function with the specified name and formats its result.
+    fn invoke(&self, function_name: &str) -> anyhow::Result<String> {
+        let return_type = self
+            .runtime
+            .get_function_definition(function_name)
+            .ok_or_else(|| anyhow!("failed to obtain function '{function_name}'"))?
+            .prototype
+            .signature
+            .return_type
+            .clone();
+
+        macro_rules! invoke_as {
+            ($($ty:ty),+) => {
+                $(
+                    if return_type.equals::<$ty>() {
+                        let result: $ty = self
+                            .runtime
+                            .invoke(function_name, ())
+                            .map_err(|e| anyhow!("{}", e))?;
+                        return Ok(format!("{result:?}"));
+                    }
+                )+
+            };
+        }
+        invoke_as!(
+            (),
+            bool,
+            f32,
+            f64,
+            i8,
+            i16,
+            i32,
+            i64,
+            i128,
+            isize,
+            u8,
+            u16,
+            u32,
+            u64,
+            u128,
+            usize
+        );
+
+        if return_type.is_struct() {
+            let result: StructRef<'_> = self
+                .runtime
+                .invoke(function_name, ())
+                .map_err(|e| anyhow!("{}", e))?;
+            return Ok(format_struct(&result));
+        }
+
+        Err(anyhow!(
+            "cannot print values of type `{}`",
+            return_type.name()
+        ))
+    }
+}
+
+/// Formats the fields of a struct, e.g. `Foo { a: 1, b: 2.0 }`.
+fn format_struct(value: &StructRef<'_>) -> String {
+    let ty = value.type_info();
+    let fields = ty
+        .as_struct()
+        .expect("a `StructRef` always refers to a struct")
+        .fields()
+        .iter()
+        .map(|field| {
+            format!(
+                "{}: {}",
+                field.name(),
+                format_field(value, field.name(), &field.ty())
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if fields.is_empty() {
+        ty.name().to_owned()
+    } else {
+        format!("{} {{ {} }}", ty.name(), fields.join(", "))
+    }
+}
+
+/// Formats the value of the field with the specified `name` and type `ty`.
+fn format_field(value: &StructRef<'_>, name: &str, ty: &Type) -> String {
+    macro_rules! format_as {
+        ($($ty:ty),+) => {
+            $(
+                if ty.equals::<$ty>() {
+                    return value
+                        .get::<$ty>(name)
+                        .map_or_else(|e| e, |v| format!("{v:?}"));
+                }
+            )+
+        };
+    }
+    format_as!(bool, f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+    if ty.is_struct() {
+        value.get::<Option<StructRef<'_>>>(name).map_or_else(
+            |e| e,
+            |v| {
+                v.as_ref()
+                    .map_or_else(|| String::from("null"), format_struct)
+            },
+        )
+    } else {
+        format!("<{}>", ty.name())
+    }
+}
+
+/// Returns true if the specified line contains an item definition rather than
+/// an expression.
+fn is_definition(line: &str) -> bool {
+    let line = line.strip_prefix("pub").map_or(line, |rest| {
+        // Skip the visibility specifier, e.g. `pub(package)`
+        let rest = rest.trim_start();
+        match rest.strip_prefix('(') {
+            Some(rest) => rest.split_once(')').map_or(rest, |(_, rest)| rest),
+            None => rest,
+        }
+    });
+    let line = line.trim_start();
+    ["fn ", "struct ", "struct(", "type "]
+        .iter()
+        .any(|keyword| line.starts_with(keyword))
+}
+
+/// Returns the name of the struct defined in `definition`, if any.
+fn struct_name(definition: &str) -> Option<&str> {
+    let (_, rest) = definition.split_once("struct")?;
+    let rest = rest.trim_start();
+
+    // Skip the memory kind, e.g. `struct(value)`
+    let rest = match rest.strip_prefix('(') {
+        Some(rest) => rest.split_once(')')?.1.trim_start(),
+        None => rest,
+    };
+
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    (end > 0).then(|| &rest[..end])
+}
@@ -53,6 +53,26 @@ fn mun_emit_ir() {
     assert!(ir_path.is_file());
 }
 
+/// Verifies that a newly created project can be used to emit metadata.
+#[test]
+fn mun_emit_metadata() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+    assert!(project_path.exists());
+
+    build(&project_path, &["--emit-metadata"]);
+
+    assert!(project_path.join("target/mod.munmeta").is_file());
+    assert!(!project_path.join("target/mod.munlib").exists());
+}
+
 fn build(project: &Path, args: &[&str]) {
     let args: Vec<OsString> = vec![
         OsString::from("mun"),
@@ -81,3 +101,14 @@ fn build_and_run(project: &Path) {
     let result: f64 = runtime.invoke("main", ()).unwrap();
     assert_eq!(result, 3.14159);
 }
+
+/// Evaluates a function call after defining the function in the REPL.
+#[test]
+fn repl_define_and_call() {
+    let input = "pub fn add(a: i32, b: i32) -> i32 { a + b }\nadd(2, 3)\n";
+    let mut output = Vec::new();
+    mun::run_repl(input.as_bytes(), &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(output, "> > 5\n> \n");
+}
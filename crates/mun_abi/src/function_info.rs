@@ -17,6 +17,9 @@ pub struct FunctionDefinition<'a> {
     pub prototype: FunctionPrototype<'a>,
     /// Function pointer
     pub fn_ptr: *const c_void,
+    /// Path of the source file that defines the function, relative to the
+    /// source directory, or null if unknown
+    pub source_file: *const c_char,
 }
 
 /// Represents a function prototype. A function prototype contains the name,
@@ -45,6 +48,18 @@ pub struct FunctionSignature<'a> {
 unsafe impl<'a> Send for FunctionDefinition<'a> {}
 unsafe impl<'a> Sync for FunctionDefinition<'a> {}
 
+impl<'a> FunctionDefinition<'a> {
+    /// Returns the path of the source file that defines the function, if
+    /// known.
+    pub fn source_file(&self) -> Option<&str> {
+        if self.source_file.is_null() {
+            None
+        } else {
+            Some(unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.source_file).to_bytes()) })
+        }
+    }
+}
+
 impl<'a> FunctionPrototype<'a> {
     /// Returns the function's name.
     pub fn name(&self) -> &str {
@@ -97,6 +112,7 @@ impl<'a> serde::Serialize for FunctionDefinition<'a> {
         let mut s = serializer.serialize_struct("FunctionDefinition", 1)?;
         s.serialize_field("prototype", &self.prototype)?;
         s.skip_field("fn_ptr")?;
+        s.skip_field("source_file")?;
         s.end()
     }
 }
@@ -133,11 +149,12 @@ impl<'a> serde::Serialize for FunctionSignature<'a> {
 
 #[cfg(test)]
 mod tests {
-    use std::ffi::CString;
+    use std::{ffi::CString, ptr};
 
     use crate::{
         test_utils::{fake_fn_prototype, fake_fn_signature, FAKE_FN_NAME},
         type_id::HasStaticTypeId,
+        FunctionDefinition,
     };
 
     #[test]
@@ -148,6 +165,21 @@ mod tests {
         assert_eq!(fn_signature.name(), FAKE_FN_NAME);
     }
 
+    #[test]
+    fn test_fn_definition_source_file() {
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let source_file = CString::new("foo/bar.mun").expect("Invalid source file.");
+        let mut fn_definition = FunctionDefinition {
+            prototype: fake_fn_prototype(&fn_name, &[], None),
+            fn_ptr: ptr::null(),
+            source_file: ptr::null(),
+        };
+        assert_eq!(fn_definition.source_file(), None);
+
+        fn_definition.source_file = source_file.as_ptr();
+        assert_eq!(fn_definition.source_file(), Some("foo/bar.mun"));
+    }
+
     #[test]
     fn test_fn_signature_arg_types_none() {
         let arg_types = &[];
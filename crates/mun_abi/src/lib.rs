@@ -9,6 +9,10 @@ use std::{ffi::CStr, fmt};
 pub use assembly_info::AssemblyInfo;
 pub use dispatch_table::DispatchTable;
 pub use function_info::{FunctionDefinition, FunctionPrototype, FunctionSignature};
+pub use metadata::{
+    AssemblyMetadata, FunctionMetadata, MetadataError, StructMetadata, MACHO_METADATA_SECTION_NAME,
+    MACHO_METADATA_SEGMENT_NAME, METADATA_SECTION_NAME,
+};
 pub use module_info::ModuleInfo;
 pub use primitive::PrimitiveType;
 pub use struct_info::{StructDefinition, StructMemoryKind};
@@ -20,6 +24,7 @@ pub use type_lut::TypeLut;
 mod assembly_info;
 mod dispatch_table;
 mod function_info;
+mod metadata;
 mod module_info;
 mod primitive;
 pub mod static_type_map;
@@ -33,7 +38,7 @@ mod test_utils;
 
 /// Defines the current ABI version
 #[allow(clippy::zero_prefixed_literal)]
-pub const ABI_VERSION: u32 = 00_03_00;
+pub const ABI_VERSION: u32 = 00_04_00;
 /// Defines the name for the `get_info` function
 pub const GET_INFO_FN_NAME: &str = "get_info";
 /// Defines the name for the `get_version` function
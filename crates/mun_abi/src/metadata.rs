@@ -0,0 +1,211 @@
+use std::{error::Error, fmt};
+
+use crate::StructMemoryKind;
+
+/// Defines the name of the section in which the metadata of an assembly is
+/// stored on targets that use ELF or COFF object files.
+pub const METADATA_SECTION_NAME: &str = ".munmeta";
+/// Defines the segment of the section in which the metadata of an assembly is
+/// stored on targets that use Mach-O object files.
+pub const MACHO_METADATA_SEGMENT_NAME: &str = "__DATA";
+/// Defines the name of the section in which the metadata of an assembly is
+/// stored on targets that use Mach-O object files.
+pub const MACHO_METADATA_SECTION_NAME: &str = "__munmeta";
+
+/// The tag that starts the serialized metadata.
+const HEADER_TAG: &str = "munmeta";
+
+/// The function and type metadata of an assembly.
+///
+/// Unlike the [`AssemblyInfo`](crate::AssemblyInfo), which can only be
+/// obtained by loading an assembly and calling into it, the metadata is stored
+/// in a section of the assembly and can be read directly from the file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AssemblyMetadata {
+    /// The ABI version of the assembly
+    pub abi_version: u32,
+    /// The functions of the assembly, sorted by name
+    pub functions: Vec<FunctionMetadata>,
+    /// The struct types of the assembly, sorted by name
+    pub structs: Vec<StructMetadata>,
+    /// The paths of the assemblies that the assembly depends on
+    pub dependencies: Vec<String>,
+}
+
+/// The metadata of a function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionMetadata {
+    /// The fully qualified name of the function
+    pub name: String,
+    /// The names of the argument types of the function
+    pub arg_types: Vec<String>,
+    /// The name of the return type of the function
+    pub return_type: String,
+}
+
+/// The metadata of a struct type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructMetadata {
+    /// The fully qualified name of the struct
+    pub name: String,
+    /// The memory kind of the struct
+    pub memory_kind: StructMemoryKind,
+    /// The names and type names of the fields of the struct
+    pub fields: Vec<(String, String)>,
+}
+
+/// An error that occurs when the serialized metadata of an assembly is
+/// malformed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MetadataError(String);
+
+impl fmt::Display for MetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "malformed assembly metadata: {}", self.0)
+    }
+}
+
+impl Error for MetadataError {}
+
+impl AssemblyMetadata {
+    /// Serializes the metadata.
+    ///
+    /// Every record is stored on its own line and its values are separated by
+    /// tabs, neither of which can occur in names.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut lines = vec![format!("{HEADER_TAG}\t{}", self.abi_version)];
+        lines.extend(self.functions.iter().map(|function| {
+            let mut values = vec!["fn", function.name.as_str(), function.return_type.as_str()];
+            values.extend(function.arg_types.iter().map(String::as_str));
+            values.join("\t")
+        }));
+        lines.extend(self.structs.iter().map(|s| {
+            let memory_kind = match s.memory_kind {
+                StructMemoryKind::Gc => "gc",
+                StructMemoryKind::Value => "value",
+            };
+            let mut values = vec!["struct", s.name.as_str(), memory_kind];
+            for (name, ty) in &s.fields {
+                values.push(name);
+                values.push(ty);
+            }
+            values.join("\t")
+        }));
+        lines.extend(
+            self.dependencies
+                .iter()
+                .map(|dependency| format!("dep\t{dependency}")),
+        );
+        lines.join("\n").into_bytes()
+    }
+
+    /// Deserializes metadata that was serialized with
+    /// [`AssemblyMetadata::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MetadataError> {
+        let text =
+            std::str::from_utf8(bytes).map_err(|e| MetadataError(format!("invalid UTF-8: {e}")))?;
+        let mut lines = text.lines();
+
+        let abi_version = match lines.next().map(|line| line.split_once('\t')) {
+            Some(Some((HEADER_TAG, version))) => version
+                .parse()
+                .map_err(|e| MetadataError(format!("invalid ABI version '{version}': {e}")))?,
+            _ => return Err(MetadataError("missing header".to_owned())),
+        };
+
+        let mut metadata = AssemblyMetadata {
+            abi_version,
+            ..AssemblyMetadata::default()
+        };
+        for line in lines {
+            let mut values = line.split('\t');
+            match (values.next(), values.next()) {
+                (Some("fn"), Some(name)) => {
+                    let return_type = values
+                        .next()
+                        .ok_or_else(|| MetadataError(format!("missing return type of `{name}`")))?;
+                    metadata.functions.push(FunctionMetadata {
+                        name: name.to_owned(),
+                        arg_types: values.map(str::to_owned).collect(),
+                        return_type: return_type.to_owned(),
+                    });
+                }
+                (Some("struct"), Some(name)) => {
+                    let memory_kind = match values.next() {
+                        Some("gc") => StructMemoryKind::Gc,
+                        Some("value") => StructMemoryKind::Value,
+                        _ => return Err(MetadataError(format!("invalid memory kind of `{name}`"))),
+                    };
+                    let values = values.collect::<Vec<_>>();
+                    if values.len() % 2 != 0 {
+                        return Err(MetadataError(format!("missing field type of `{name}`")));
+                    }
+                    metadata.structs.push(StructMetadata {
+                        name: name.to_owned(),
+                        memory_kind,
+                        fields: values
+                            .chunks(2)
+                            .map(|field| (field[0].to_owned(), field[1].to_owned()))
+                            .collect(),
+                    });
+                }
+                (Some("dep"), Some(dependency)) => {
+                    metadata.dependencies.push(dependency.to_owned());
+                }
+                _ => return Err(MetadataError(format!("invalid record '{line}'"))),
+            }
+        }
+
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AssemblyMetadata, FunctionMetadata, StructMetadata};
+    use crate::StructMemoryKind;
+
+    #[test]
+    fn test_roundtrip() {
+        let metadata = AssemblyMetadata {
+            abi_version: crate::ABI_VERSION,
+            functions: vec![
+                FunctionMetadata {
+                    name: "bar".to_owned(),
+                    arg_types: vec![],
+                    return_type: "core::empty".to_owned(),
+                },
+                FunctionMetadata {
+                    name: "foo".to_owned(),
+                    arg_types: vec!["core::f64".to_owned(), "[core::i32]".to_owned()],
+                    return_type: "Foo".to_owned(),
+                },
+            ],
+            structs: vec![
+                StructMetadata {
+                    name: "Empty".to_owned(),
+                    memory_kind: StructMemoryKind::Gc,
+                    fields: vec![],
+                },
+                StructMetadata {
+                    name: "Foo".to_owned(),
+                    memory_kind: StructMemoryKind::Value,
+                    fields: vec![("a".to_owned(), "*const core::i32".to_owned())],
+                },
+            ],
+            dependencies: vec!["foo.munlib".to_owned()],
+        };
+
+        assert_eq!(
+            AssemblyMetadata::from_bytes(&metadata.to_bytes()),
+            Ok(metadata)
+        );
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert!(AssemblyMetadata::from_bytes(b"").is_err());
+        assert!(AssemblyMetadata::from_bytes(b"munmeta\t400\nfn").is_err());
+        assert!(AssemblyMetadata::from_bytes(b"munmeta\t400\nstruct\tFoo\tgc\ta").is_err());
+    }
+}
@@ -117,6 +117,7 @@ mod tests {
         let fn_info = FunctionDefinition {
             prototype: fn_prototype,
             fn_ptr: ptr::null(),
+            source_file: ptr::null(),
         };
         let functions = &[fn_info];
 
@@ -44,16 +44,28 @@ impl<'db, 'ink, 'ctx> Assembly<'db, 'ink, 'ctx> {
     }
 }
 
-/// Builds an assembly for the specified file
+/// Builds an assembly for the specified file. If `export_public_functions` is
+/// set, all `pub` functions are visible as symbols of the assembly, not only
+/// the ones marked with `#[export]`.
 fn build_assembly<'db, 'ink, 'ctx>(
     db: &'db dyn CodeGenDatabase,
     code_gen: &'ctx CodeGenContext<'db, 'ink>,
     module_group_id: ModuleGroupId,
+    export_public_functions: bool,
 ) -> Assembly<'db, 'ink, 'ctx> {
     // Setup the code generation context
     let module_partition = db.module_partition();
 
-    let module_builder = AssemblyBuilder::new(code_gen, &module_partition, module_group_id);
+    let _span = tracing::info_span!(
+        "codegen",
+        module_group = %module_partition[module_group_id].name
+    )
+    .entered();
+
+    let mut module_builder = AssemblyBuilder::new(code_gen, &module_partition, module_group_id);
+    if export_public_functions {
+        module_builder = module_builder.export_public_functions();
+    }
     module_builder.build().expect("unable to create assembly")
 }
 
@@ -95,7 +107,7 @@ pub(crate) fn build_target_assembly(
     let code_gen_context = CodeGenContext::new(&inkwell_context, db);
 
     // Build an assembly for the module
-    let assembly = build_assembly(db, &code_gen_context, module_group);
+    let assembly = build_assembly(db, &code_gen_context, module_group, true);
 
     // Convert the assembly into an object file
     let obj_file = assembly
@@ -159,7 +171,7 @@ pub(crate) fn build_assembly_ir(
     let code_gen_context = CodeGenContext::new(&inkwell_context, db);
 
     // Build an assembly for the module
-    let assembly = build_assembly(db, &code_gen_context, module_group);
+    let assembly = build_assembly(db, &code_gen_context, module_group, false);
 
     // Construct a temporary file for the assembly
     let file = NamedTempFile::new().expect("could not create temp file for shared object");
@@ -171,3 +183,55 @@ pub(crate) fn build_assembly_ir(
 
     Arc::new(AssemblyIr { file })
 }
+
+/// An `AssemblyObject` is a reference to a relocatable object file stored on
+/// disk. Unlike a `TargetAssembly` it is not linked into a shared object,
+/// which enables statically linking the contained functions.
+#[derive(Debug)]
+pub struct AssemblyObject {
+    file: NamedTempFile,
+}
+
+impl PartialEq for AssemblyObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.path().eq(other.path())
+    }
+}
+
+impl Eq for AssemblyObject {}
+
+impl AssemblyObject {
+    pub const EXTENSION: &'static str = "o";
+
+    /// Returns the current location of the object file.
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Copies the object file to the specified location
+    pub fn copy_to<P: AsRef<Path>>(&self, destination: P) -> Result<(), std::io::Error> {
+        std::fs::copy(self.path(), destination).map(|_| ())
+    }
+}
+
+/// Builds a relocatable object file for the specified module.
+pub(crate) fn build_assembly_object(
+    db: &dyn CodeGenDatabase,
+    module_group: ModuleGroupId,
+) -> Arc<AssemblyObject> {
+    // Setup the code generation context
+    let inkwell_context = Context::create();
+    let code_gen_context = CodeGenContext::new(&inkwell_context, db);
+
+    // Build an assembly for the module
+    let assembly = build_assembly(db, &code_gen_context, module_group, false);
+
+    // Convert the assembly into an object file
+    let obj_file = assembly
+        .into_object_file()
+        .expect("unable to create object file");
+
+    Arc::new(AssemblyObject {
+        file: obj_file.into_temp_file(),
+    })
+}
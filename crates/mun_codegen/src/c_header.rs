@@ -0,0 +1,299 @@
+//! Generates C header files that describe the functions and structs exposed by
+//! a module group.
+
+use std::fmt::Write;
+
+use mun_hir::{
+    FloatBitness, HirDatabase, IntBitness, ModuleDef, Signedness, StructMemoryKind, Ty, TyKind,
+};
+use rustc_hash::FxHashSet;
+
+use crate::ModuleGroup;
+
+/// Generates the contents of a C header that contains a prototype for every
+/// function marked with `#[export]` in the `module_group` and a typedef for
+/// every struct.
+///
+/// Value structs are passed by value. Garbage collected structs and arrays are
+/// passed as handles, i.e. as a pointer to the pointer to the object. Functions
+/// whose signature cannot be expressed in C are omitted.
+pub fn generate_c_header(db: &dyn HirDatabase, module_group: &ModuleGroup) -> String {
+    let mut generator = CHeaderGenerator {
+        db,
+        emitted_structs: FxHashSet::default(),
+        declarations: String::new(),
+        definitions: String::new(),
+    };
+
+    let mut prototypes = String::new();
+    for def in module_group
+        .iter()
+        .flat_map(|module| module.declarations(db))
+    {
+        match def {
+            ModuleDef::Struct(s) => generator.emit_struct(s),
+            ModuleDef::Function(f) if !f.is_extern(db) && f.is_exported(db) => {
+                if let Some(prototype) = generator.prototype(f) {
+                    writeln!(prototypes, "{prototype};").unwrap();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let guard = if module_group.name.is_empty() {
+        String::from("MUN_H")
+    } else {
+        format!("MUN_{}_H", to_identifier(&module_group.name).to_uppercase())
+    };
+
+    let mut header = format!(
+        "#ifndef {guard}\n#define {guard}\n\n#include <stdbool.h>\n#include <stdint.h>\n\n\
+         #ifdef __cplusplus\nextern \"C\" {{\n#endif\n\n"
+    );
+    for section in [&generator.declarations, &generator.definitions, &prototypes] {
+        if !section.is_empty() {
+            header.push_str(section);
+            header.push('\n');
+        }
+    }
+    header.push_str("#ifdef __cplusplus\n}\n#endif\n\n#endif\n");
+    header
+}
+
+struct CHeaderGenerator<'db> {
+    db: &'db dyn HirDatabase,
+    emitted_structs: FxHashSet<mun_hir::Struct>,
+    declarations: String,
+    definitions: String,
+}
+
+impl<'db> CHeaderGenerator<'db> {
+    /// Emits the declaration and definition of the specified struct, after the
+    /// definitions of the value structs it contains.
+    fn emit_struct(&mut self, s: mun_hir::Struct) {
+        if !self.emitted_structs.insert(s) {
+            return;
+        }
+
+        // The declaration is emitted first, which allows (recursive) references to
+        // garbage collected structs.
+        let name = s.name(self.db).to_string();
+        writeln!(self.declarations, "typedef struct {name} {name};").unwrap();
+
+        let fields = s.fields(self.db);
+        if fields.is_empty() {
+            // C does not allow empty structs, so leave the struct incomplete
+            return;
+        }
+
+        let mut definition = format!("struct {name} {{\n");
+        for field in fields {
+            let ty = field.ty(self.db);
+            if let Some(field_struct) = ty.as_struct() {
+                self.emit_struct(field_struct);
+            }
+
+            let field_name = to_identifier(&field.name(self.db).to_string());
+            let declaration = self
+                .declaration(&ty, &field_name)
+                .unwrap_or_else(|| format!("/* unsupported type */ {field_name}"));
+            writeln!(definition, "    {declaration};").unwrap();
+        }
+        definition.push_str("};\n");
+
+        self.definitions.push_str(&definition);
+    }
+
+    /// Returns the C prototype of the specified function, or `None` if its
+    /// signature cannot be expressed in C.
+    fn prototype(&mut self, f: mun_hir::Function) -> Option<String> {
+        let params = f
+            .params(self.db)
+            .into_iter()
+            .map(|param| {
+                if let Some(s) = param.ty().as_struct() {
+                    self.emit_struct(s);
+                }
+
+                match param.name(self.db) {
+                    Some(name) => self.declaration(param.ty(), &name.to_string()),
+                    None => self.c_type(param.ty()),
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let params = if params.is_empty() {
+            String::from("void")
+        } else {
+            params.join(", ")
+        };
+        let declarator = format!("{}({params})", f.name(self.db));
+
+        let ret_ty = f.ret_type(self.db);
+        if ret_ty.is_empty() {
+            Some(format!("void {declarator}"))
+        } else {
+            if let Some(s) = ret_ty.as_struct() {
+                self.emit_struct(s);
+            }
+            self.declaration(&ret_ty, &declarator)
+        }
+    }
+
+    /// Returns the C declaration of `declarator` with type `ty`, e.g. a
+    /// variable or function name.
+    fn declaration(&self, ty: &Ty, declarator: &str) -> Option<String> {
+        let c_type = self.c_type(ty)?;
+        Some(if c_type.ends_with('*') {
+            format!("{c_type}{declarator}")
+        } else {
+            format!("{c_type} {declarator}")
+        })
+    }
+
+    /// Returns the C type that matches the ABI of the specified type.
+    fn c_type(&self, ty: &Ty) -> Option<String> {
+        let c_type = match ty.interned() {
+            TyKind::Bool => "bool",
+            TyKind::Float(float_ty) => match float_ty.bitness {
+                FloatBitness::X32 => "float",
+                FloatBitness::X64 => "double",
+            },
+            TyKind::Int(int_ty) => match (int_ty.signedness, int_ty.bitness) {
+                (Signedness::Signed, IntBitness::X8) => "int8_t",
+                (Signedness::Signed, IntBitness::X16) => "int16_t",
+                (Signedness::Signed, IntBitness::X32) => "int32_t",
+                (Signedness::Signed, IntBitness::X64) => "int64_t",
+                (Signedness::Signed, IntBitness::X128) => "__int128",
+                (Signedness::Signed, IntBitness::Xsize) => "intptr_t",
+                (Signedness::Unsigned, IntBitness::X8) => "uint8_t",
+                (Signedness::Unsigned, IntBitness::X16) => "uint16_t",
+                (Signedness::Unsigned, IntBitness::X32) => "uint32_t",
+                (Signedness::Unsigned, IntBitness::X64) => "uint64_t",
+                (Signedness::Unsigned, IntBitness::X128) => "unsigned __int128",
+                (Signedness::Unsigned, IntBitness::Xsize) => "uintptr_t",
+            },
+            TyKind::Struct(s) => {
+                let name = s.name(self.db);
+                return Some(match s.data(self.db.upcast()).memory_kind {
+                    StructMemoryKind::Gc => format!("{name} *const *"),
+                    StructMemoryKind::Value => name.to_string(),
+                });
+            }
+            TyKind::Array(_) => "void *const *",
+            _ => return None,
+        };
+
+        Some(c_type.to_owned())
+    }
+}
+
+/// Converts a Mun name into a valid C identifier.
+fn to_identifier(name: &str) -> String {
+    let identifier: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    // Tuple fields are named by their index
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{identifier}")
+    } else {
+        identifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mun_hir::{HirDatabase, Upcast};
+
+    use crate::{c_header::generate_c_header, mock::MockDatabase, CodeGenDatabase};
+
+    fn c_header(text: &str) -> String {
+        let (db, file_id) = MockDatabase::with_single_file(text);
+        let module_partition = db.module_partition();
+        let module_group_id = module_partition.group_for_file(file_id).unwrap();
+        let hir_db: &dyn HirDatabase = db.upcast();
+        generate_c_header(hir_db, &module_partition[module_group_id])
+    }
+
+    #[test]
+    fn function_prototypes() {
+        let header = c_header(
+            r#"
+        #[export]
+        pub fn add(a: i32, b: i32) -> i32 { a + b }
+        #[export]
+        pub fn scale(v: f64, factor: f32) -> f64 { v }
+        #[export]
+        pub fn invert(b: bool) -> bool { !b }
+        #[export]
+        pub fn nothing() {}
+        pub fn unexported(a: u8) -> u8 { a }
+        "#,
+        );
+
+        assert!(
+            header.contains("int32_t add(int32_t a, int32_t b);"),
+            "{header}"
+        );
+        assert!(
+            header.contains("double scale(double v, float factor);"),
+            "{header}"
+        );
+        assert!(header.contains("bool invert(bool b);"), "{header}");
+        assert!(header.contains("void nothing(void);"), "{header}");
+        assert!(!header.contains("unexported"), "{header}");
+    }
+
+    #[test]
+    fn struct_typedefs() {
+        insta::assert_snapshot!(c_header(
+            r#"
+        pub struct(value) Vec2 { x: f32, y: f32 }
+        pub struct Node { pos: Vec2, next: Node, children: [Node] }
+
+        #[export]
+        pub fn length(v: Vec2) -> f32 { v.x + v.y }
+        #[export]
+        pub fn position(node: Node) -> Vec2 { node.pos }
+        #[export]
+        pub fn next(node: Node) -> Node { node.next }
+        "#,
+        ), @r###"
+        #ifndef MUN_MOD_H
+        #define MUN_MOD_H
+
+        #include <stdbool.h>
+        #include <stdint.h>
+
+        #ifdef __cplusplus
+        extern "C" {
+        #endif
+
+        typedef struct Vec2 Vec2;
+        typedef struct Node Node;
+
+        struct Vec2 {
+            float x;
+            float y;
+        };
+        struct Node {
+            Vec2 pos;
+            Node *const *next;
+            void *const *children;
+        };
+
+        float length(Vec2 v);
+        Vec2 position(Node *const *node);
+        Node *const *next(Node *const *node);
+
+        #ifdef __cplusplus
+        }
+        #endif
+
+        #endif
+        "###);
+    }
+}
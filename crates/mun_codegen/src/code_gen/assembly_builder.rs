@@ -1,10 +1,11 @@
 use inkwell::module::{Linkage, Module};
-use rustc_hash::FxHashSet;
+use mun_hir::{HasVisibility, ModuleDef, Visibility};
 
 use crate::{
     assembly::Assembly,
     code_gen::{optimize_module, symbols, CodeGenContext, CodeGenerationError},
     ir::{file::gen_file_ir, file_group::gen_file_group_ir},
+    metadata::assembly_dependencies,
     value::{IrTypeContext, IrValueContext},
     ModuleGroupId, ModulePartition,
 };
@@ -15,6 +16,7 @@ pub struct AssemblyBuilder<'db, 'ink, 'ctx, 't> {
     module_group_partition: &'t ModulePartition,
     module_group_id: ModuleGroupId,
     assembly_module: Module<'ink>,
+    export_public_functions: bool,
 }
 
 impl<'db, 'ink, 'ctx, 't> AssemblyBuilder<'db, 'ink, 'ctx, 't> {
@@ -33,9 +35,18 @@ impl<'db, 'ink, 'ctx, 't> AssemblyBuilder<'db, 'ink, 'ctx, 't> {
             module_group_partition,
             module_group_id,
             assembly_module,
+            export_public_functions: false,
         }
     }
 
+    /// Keeps all `pub` functions visible as symbols of the assembly, in
+    /// addition to the functions marked with `#[export]`. This is used for
+    /// linked assemblies, which non-Rust hosts can load as a shared library.
+    pub fn export_public_functions(mut self) -> Self {
+        self.export_public_functions = true;
+        self
+    }
+
     /// Constructs an object file.
     pub fn build(self) -> Result<Assembly<'db, 'ink, 'ctx>, anyhow::Error> {
         let module_group = &self.module_group_partition[self.module_group_id];
@@ -76,22 +87,12 @@ impl<'db, 'ink, 'ctx, 't> AssemblyBuilder<'db, 'ink, 'ctx, 't> {
         };
 
         // Build the set of dependencies
-        let direct_children = module_group
-            .iter()
-            .flat_map(|module| module.children(self.code_gen.db))
-            .collect();
-        let dependencies = group_ir
-            .referenced_modules
-            .union(&direct_children)
-            .filter_map(|&module| self.module_group_partition.group_for_module(module))
-            .collect::<FxHashSet<_>>()
-            .into_iter()
-            .map(|group_id| {
-                self.module_group_partition[group_id]
-                    .relative_file_path()
-                    .to_string()
-            })
-            .collect();
+        let dependencies = assembly_dependencies(
+            self.code_gen.db,
+            self.module_group_partition,
+            module_group,
+            &group_ir.referenced_modules,
+        );
 
         // Generate the `get_info` method.
         symbols::gen_reflection_ir(
@@ -107,6 +108,30 @@ impl<'db, 'ink, 'ctx, 't> AssemblyBuilder<'db, 'ink, 'ctx, 't> {
             dependencies,
         );
 
+        // Only functions marked with `#[export]`, and `pub` functions if
+        // requested, are visible as symbols of the assembly. Other functions can
+        // still be invoked through the function definitions of the assembly.
+        for def in module_group
+            .iter()
+            .flat_map(|module| module.declarations(self.code_gen.db))
+        {
+            if let ModuleDef::Function(f) = def {
+                let db = self.code_gen.db;
+                let is_public = f.visibility(db) == Visibility::Public;
+                if f.is_extern(db)
+                    || f.is_exported(db)
+                    || (self.export_public_functions && is_public)
+                {
+                    continue;
+                }
+
+                let name = f.name(self.code_gen.db).to_string();
+                if let Some(value) = self.assembly_module.get_function(&name) {
+                    value.set_linkage(Linkage::Private);
+                }
+            }
+        }
+
         // Optimize the assembly module
         optimize_module(&self.assembly_module, self.code_gen.optimization_level);
 
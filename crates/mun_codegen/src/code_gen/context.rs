@@ -2,7 +2,7 @@ use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use inkwell::{context::Context, module::Module, targets::TargetMachine, types::StructType};
 
-use crate::{ir::ty::HirTypeCache, CodeGenDatabase};
+use crate::{ir::ty::HirTypeCache, CodeGenDatabase, OverflowBehavior};
 
 pub struct CodeGenContext<'db, 'ink> {
     /// The current LLVM context
@@ -20,6 +20,9 @@ pub struct CodeGenContext<'db, 'ink> {
     /// The optimization level
     pub optimization_level: inkwell::OptimizationLevel,
 
+    /// The behavior of integer arithmetic that overflows
+    pub overflow_behavior: OverflowBehavior,
+
     /// The target to generate code for
     pub target_machine: Rc<TargetMachine>,
 }
@@ -34,6 +37,7 @@ impl<'db, 'ink> CodeGenContext<'db, 'ink> {
             rust_types: RefCell::new(HashMap::default()),
             hir_types: HirTypeCache::new(context, db.upcast(), target_machine.get_target_data()),
             optimization_level: db.optimization_level(),
+            overflow_behavior: db.overflow_behavior(),
             target_machine,
             db: db.upcast(),
         }
@@ -34,6 +34,12 @@ impl ObjectFile {
         })
     }
 
+    /// Consumes the object file, returning the temporary file in which it is
+    /// stored.
+    pub fn into_temp_file(self) -> NamedTempFile {
+        self.obj_file
+    }
+
     /// Links the object file into a shared object.
     pub fn into_shared_object(self, output_path: &Path) -> Result<(), anyhow::Error> {
         // Construct a linker for the target
@@ -1,10 +1,10 @@
 use std::{collections::HashSet, convert::TryFrom, ffi::CString};
 
-use inkwell::{attributes::Attribute, module::Linkage, types::AnyType};
+use inkwell::{attributes::Attribute, module::Linkage, types::AnyType, AddressSpace};
 use ir_type_builder::TypeIdBuilder;
 use itertools::Itertools;
 use mun_abi as abi;
-use mun_hir::{HirDatabase, TyKind};
+use mun_hir::{HirDatabase, SourceDatabase, TyKind};
 
 use crate::{
     ir::{
@@ -14,6 +14,7 @@ use crate::{
         type_table::TypeTable,
         types as ir,
     },
+    metadata::assembly_metadata,
     type_info::HasStaticTypeId,
     value::{
         AsValue, CanInternalize, Global, IrValueContext, IterAsIrValue, SizedValueType, Value,
@@ -241,12 +242,21 @@ fn get_function_definition_array<'ink, 'a>(
             // Generate the signature from the function
             let prototype =
                 gen_prototype_from_function(db, context, *f, hir_types, ir_type_builder);
+
+            // Internalize the path of the file in which the function is defined
+            let full_name = f.full_name(db);
+            let source_file = CString::new(db.file_relative_path(f.file_id(db)).as_str())
+                .expect("source file path is not a valid CString")
+                .intern(format!("fn_def::<{full_name}>::source_file"), context)
+                .as_value(context);
+
             ir::FunctionDefinition {
                 prototype,
                 fn_ptr: Value::<*const fn()>::with_cast(
                     value.as_global_value().as_pointer_value(),
                     context,
                 ),
+                source_file,
             }
         })
         .into_value(context)
@@ -399,6 +409,16 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
 
     let type_lut = gen_type_lut(context, type_table, &ir_type_builder);
 
+    // Store the metadata of the assembly in a separate section
+    gen_metadata_section(
+        db,
+        context,
+        function_definitions,
+        type_definitions,
+        hir_types,
+        &dependencies,
+    );
+
     // Construct the actual `get_info` function
     gen_get_info_fn(
         db,
@@ -413,6 +433,56 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
     gen_get_version_fn(context);
 }
 
+/// Construct a global that stores the serialized [`abi::AssemblyMetadata`] of
+/// the assembly in a dedicated section. This enables tooling to read the
+/// metadata from the file without loading the assembly.
+fn gen_metadata_section<'ink>(
+    db: &dyn HirDatabase,
+    context: &IrValueContext<'ink, '_, '_>,
+    function_definitions: &HashSet<mun_hir::Function>,
+    type_definitions: &HashSet<mun_hir::Ty>,
+    hir_types: &HirTypeCache<'_, 'ink>,
+    dependencies: &[String],
+) {
+    let metadata = assembly_metadata(
+        db,
+        function_definitions,
+        type_definitions,
+        hir_types,
+        dependencies,
+    );
+
+    let data = context.context.const_string(&metadata.to_bytes(), false);
+    let global = context
+        .module
+        .add_global(data.get_type(), None, "assembly_metadata");
+    global.set_initializer(&data);
+    global.set_constant(true);
+    global.set_linkage(Linkage::Private);
+
+    let section = if db.target().options.is_like_osx {
+        format!(
+            "{},{}",
+            abi::MACHO_METADATA_SEGMENT_NAME,
+            abi::MACHO_METADATA_SECTION_NAME
+        )
+    } else {
+        abi::METADATA_SECTION_NAME.to_owned()
+    };
+    global.set_section(Some(&section));
+
+    // Nothing references the metadata, so add it to `llvm.used` to prevent it from
+    // being removed by optimizations or the linker.
+    let ptr_type = context.context.i8_type().ptr_type(AddressSpace::default());
+    let used = ptr_type.const_array(&[global.as_pointer_value().const_cast(ptr_type)]);
+    let used_global = context
+        .module
+        .add_global(used.get_type(), None, "llvm.used");
+    used_global.set_initializer(&used);
+    used_global.set_linkage(Linkage::Appending);
+    used_global.set_section(Some("llvm.metadata"));
+}
+
 /// Construct the actual `get_info` function.
 fn gen_get_info_fn<'ink>(
     db: &dyn HirDatabase,
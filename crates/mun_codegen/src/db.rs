@@ -3,7 +3,23 @@ use std::{rc::Rc, sync::Arc};
 use by_address::ByAddress;
 use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetTriple};
 
-use crate::{AssemblyIr, ModuleGroupId, ModulePartition, TargetAssembly};
+use crate::{AssemblyIr, AssemblyObject, ModuleGroupId, ModulePartition, TargetAssembly};
+
+/// Describes how integer addition, subtraction, and multiplication behave when
+/// the result does not fit in the integer type.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverflowBehavior {
+    /// The result wraps around at the boundary of the type.
+    #[default]
+    Wrapping,
+
+    /// The overflow is reported to the runtime as an execution error. The
+    /// execution continues with the wrapped result.
+    Checked,
+
+    /// The result is clamped to the minimum or maximum value of the type.
+    Saturating,
+}
 
 /// The `CodeGenDatabase` enables caching of code generation stages.
 /// Inkwell/LLVM objects are not stored in the cache because they are not
@@ -21,6 +37,15 @@ pub trait CodeGenDatabase:
     #[salsa::input]
     fn optimization_level(&self) -> inkwell::OptimizationLevel;
 
+    /// Set the behavior of integer arithmetic that overflows
+    #[salsa::input]
+    fn overflow_behavior(&self) -> OverflowBehavior;
+
+    /// Set whether all modules are compiled into a single assembly, instead of
+    /// an assembly per module
+    #[salsa::input]
+    fn single_assembly(&self) -> bool;
+
     /// Returns the current module partition
     #[salsa::invoke(crate::module_partition::build_partition)]
     fn module_partition(&self) -> Arc<ModulePartition>;
@@ -34,6 +59,10 @@ pub trait CodeGenDatabase:
     #[salsa::invoke(crate::assembly::build_assembly_ir)]
     fn assembly_ir(&self, module_group: ModuleGroupId) -> Arc<AssemblyIr>;
 
+    /// Returns a relocatable object file for the specified module.
+    #[salsa::invoke(crate::assembly::build_assembly_object)]
+    fn assembly_object(&self, module_group: ModuleGroupId) -> Arc<AssemblyObject>;
+
     /// Returns a fully linked shared object for the specified module.
     #[salsa::invoke(crate::assembly::build_target_assembly)]
     fn target_assembly(&self, module_group: ModuleGroupId) -> Arc<TargetAssembly>;
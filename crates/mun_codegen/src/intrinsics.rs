@@ -19,11 +19,25 @@ pub trait Intrinsic: Sync {
 
 intrinsics! {
     /// Allocates memory for the specified `type` in the allocator referred to by `alloc_handle`.
+    /// Returns null if the allocation failed.
     pub fn new(type_handle: *const ffi::c_void, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
 
     /// Allocates memory for an array of the specified `type` in the allocator referred to by
     /// `alloc_handle` with at least enough capacity to hold `length` elements.
     ///
     /// Note that the elements in the array are left uninitialized.
+    /// Returns null if the allocation failed.
     pub fn new_array(type_handle: *const ffi::c_void, length: usize, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+
+    /// Notifies the allocator referred to by `alloc_handle` that a reference to another object was
+    /// stored in the object referred to by `obj`.
+    pub fn write_barrier(obj: *const *mut ffi::c_void, alloc_handle: *mut ffi::c_void) -> ();
+
+    /// Notifies the runtime that an integer arithmetic operation overflowed in code that was
+    /// compiled with checked overflow behavior.
+    pub fn arithmetic_overflow() -> ();
+
+    /// Notifies the runtime that a `null` reference to a garbage collected struct was
+    /// dereferenced.
+    pub fn null_dereference() -> ();
 }
@@ -1,5 +1,5 @@
 macro_rules! intrinsics{
-    ($($(#[$attr:meta])* pub fn $name:ident($($arg_name:ident:$arg:ty),+) -> $ret:ty;)+) => {
+    ($($(#[$attr:meta])* pub fn $name:ident($($arg_name:ident:$arg:ty),*) -> $ret:ty;)+) => {
         $(
             paste::item! {
                 #[allow(non_camel_case_types)]
@@ -16,7 +16,7 @@ macro_rules! intrinsics{
                     }
 
                     fn ir_type<'ink>(&self, context: &'ink Context, target: &TargetData) -> FunctionType<'ink> {
-                        let args = vec![$(<$arg as crate::ir::IsBasicIrType>::ir_type(context, target).into()),*];
+                        let args: Vec<inkwell::types::BasicMetadataTypeEnum<'ink>> = vec![$(<$arg as crate::ir::IsBasicIrType>::ir_type(context, target).into()),*];
                         <$ret as crate::ir::IsFunctionReturnType>::fn_type(context, target, &args, false)
                     }
                 }
@@ -4,6 +4,9 @@ use inkwell::{
     basic_block::BasicBlock,
     builder::Builder,
     context::Context,
+    intrinsics::Intrinsic,
+    module::Module,
+    types::{BasicTypeEnum, IntType},
     values::{
         AggregateValueEnum, BasicMetadataValueEnum, BasicValueEnum, CallSiteValue, FloatValue,
         FunctionValue, GlobalValue, IntValue, PointerValue, StructValue,
@@ -13,18 +16,22 @@ use inkwell::{
 use mun_abi as abi;
 use mun_hir::{
     ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, HirDatabase, HirDisplay, InferenceResult,
-    Literal, LogicOp, Name, Ordering, Pat, PatId, Path, ResolveBitness, Resolver, Statement,
-    TyKind, UnaryOp, ValueNs,
+    LayoutQuery, Literal, LogicOp, Name, Ordering, Pat, PatId, Path, ResolveBitness, Resolver,
+    Statement, Ty, TyKind, UnaryOp, ValueNs,
 };
 
 use crate::{
     intrinsics,
     ir::{
-        dispatch_table::DispatchTable, ty::HirTypeCache, type_table::TypeTable, RuntimeArrayValue,
-        RuntimeReferenceValue,
+        dispatch_table::DispatchTable,
+        intrinsics::{is_overflowing_op, write_barrier_receiver},
+        ty::HirTypeCache,
+        type_table::TypeTable,
+        RuntimeArrayValue, RuntimeReferenceValue,
     },
     module_group::ModuleGroup,
     value::Global,
+    OverflowBehavior,
 };
 
 type BreakSources<'ink> = Vec<Option<(BasicValueEnum<'ink>, BasicBlock<'ink>)>>;
@@ -59,6 +66,8 @@ pub(crate) struct BodyIrGenerator<'db, 'ink, 't> {
     hir_function: mun_hir::Function,
     external_globals: ExternalGlobals<'ink>,
     module_group: &'t ModuleGroup,
+    llvm_module: &'t Module<'ink>,
+    overflow_behavior: OverflowBehavior,
 }
 
 impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
@@ -73,6 +82,8 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         external_globals: ExternalGlobals<'ink>,
         hir_types: &'t HirTypeCache<'db, 'ink>,
         module_group: &'t ModuleGroup,
+        llvm_module: &'t Module<'ink>,
+        overflow_behavior: OverflowBehavior,
     ) -> Self {
         let (hir_function, ir_function) = function;
 
@@ -103,6 +114,8 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             external_globals,
             hir_types,
             module_group,
+            llvm_module,
+            overflow_behavior,
         }
     }
 
@@ -237,16 +250,32 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             Expr::Call {
                 ref callee,
                 ref args,
+                ..
             } => {
                 // Get the callable definition from the map
                 match self.infer[*callee].as_callable_def() {
                     Some(mun_hir::CallableDef::Function(def)) => {
-                        // Get all the arguments
+                        // Get all the arguments in the order in which they appear in the source
                         let args: Vec<BasicMetadataValueEnum<'_>> = args
                             .iter()
                             .map(|expr| self.gen_expr(*expr).expect("expected a value").into())
                             .collect();
 
+                        // Arguments that are passed by name have to be reordered to match the
+                        // order of the parameters, omitted arguments are replaced by their
+                        // default value.
+                        let args = match self.infer.call_argument_order(expr).map(<[_]>::to_vec) {
+                            Some(order) => order
+                                .into_iter()
+                                .enumerate()
+                                .map(|(param_idx, arg_idx)| match arg_idx {
+                                    Some(arg_idx) => args[arg_idx],
+                                    None => self.gen_default_argument(def, param_idx).into(),
+                                })
+                                .collect(),
+                            None => args,
+                        };
+
                         self.gen_call(def, &args)
                             .try_as_basic_value()
                             .left()
@@ -275,23 +304,51 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             } => self.gen_if(expr, *condition, *then_branch, *else_branch),
             Expr::Return { expr: ret_expr } => self.gen_return(expr, *ret_expr),
             Expr::Loop { body } => self.gen_loop(expr, *body),
-            Expr::While { condition, body } => self.gen_while(expr, *condition, *body),
+            Expr::While {
+                condition,
+                body,
+                else_branch,
+            } => self.gen_while(expr, *condition, *body, *else_branch),
             Expr::Break { expr: break_expr } => self.gen_break(expr, *break_expr),
             Expr::Field {
                 expr: receiver_expr,
                 name,
             } => self.gen_field(expr, *receiver_expr, name),
             Expr::Array(exprs) => self.gen_array(expr, exprs).map(Into::into),
+            Expr::ArrayRepeat { initializer, count } => self
+                .gen_array_repeat(expr, *initializer, *count)
+                .map(Into::into),
             Expr::Index { base, index } => self.gen_index(expr, *base, *index),
+            Expr::TypeLayout { query, .. } => Some(self.gen_type_layout(expr, *query).into()),
             Expr::Missing => unimplemented!("unimplemented expr type {:?}", &body[expr]),
         }
     }
 
+    /// Generates a constant that holds the queried property of the memory
+    /// layout of a type, e.g. `size_of::<Foo>()`.
+    fn gen_type_layout(&self, expr: ExprId, query: LayoutQuery) -> IntValue<'ink> {
+        let ty = self
+            .infer
+            .layout_query_type(expr)
+            .expect("missing type of layout query");
+        let (size, alignment) = self.hir_types.get_layout(ty);
+        let value = match query {
+            LayoutQuery::Size => size,
+            LayoutQuery::Align => u64::from(alignment),
+        };
+        self.hir_types.get_usize_type().const_int(value, false)
+    }
+
     /// Generates an IR value that represents the given `Literal`.
     fn gen_literal(&mut self, lit: &Literal, expr: ExprId) -> BasicValueEnum<'ink> {
+        self.gen_literal_value(lit, &self.infer[expr])
+    }
+
+    /// Generates an IR value that represents the given `Literal` of type `ty`.
+    fn gen_literal_value(&self, lit: &Literal, ty: &Ty) -> BasicValueEnum<'ink> {
         match lit {
             Literal::Int(v) => {
-                let ty = match &self.infer[expr].interned() {
+                let ty = match ty.interned() {
                     TyKind::Int(int_ty) => int_ty,
                     _ => unreachable!(
                         "cannot construct an IR value for anything but an integral type"
@@ -318,7 +375,6 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             }
 
             Literal::Float(v) => {
-                let ty = &self.infer[expr];
                 let ty = match ty.interned()  {
                     TyKind::Float(float_ty) => float_ty,
                     _ => unreachable!("cannot construct an IR value for anything but a float type (literal type: {})", ty.display(self.db)),
@@ -342,10 +398,53 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 }
             }
 
+            Literal::Null => {
+                let hir_struct = ty
+                    .as_struct()
+                    .expect("cannot construct a null value for anything but a struct type");
+                self.hir_types
+                    .get_struct_reference_type(hir_struct)
+                    .into_pointer_type()
+                    .const_null()
+                    .into()
+            }
+
             Literal::String(_) => unimplemented!("string literals are not implemented yet"),
         }
     }
 
+    /// Generates the default value of the parameter at `param_idx` of the
+    /// given function. Default values are constant, so they can be evaluated
+    /// at the call site.
+    fn gen_default_argument(
+        &self,
+        function: mun_hir::Function,
+        param_idx: usize,
+    ) -> BasicValueEnum<'ink> {
+        let body = function.body(self.db);
+        let infer = function.infer(self.db);
+        let default_value = body.param_defaults()[param_idx].expect("expected a default value");
+        match &body[default_value] {
+            Expr::Literal(lit) => self.gen_literal_value(lit, &infer[default_value]),
+            Expr::UnaryOp {
+                expr,
+                op: UnaryOp::Neg,
+            } => match &body[*expr] {
+                Expr::Literal(lit) => match self.gen_literal_value(lit, &infer[*expr]) {
+                    BasicValueEnum::IntValue(value) => {
+                        self.builder.build_int_neg(value, "neg").into()
+                    }
+                    BasicValueEnum::FloatValue(value) => {
+                        self.builder.build_float_neg(value, "neg").into()
+                    }
+                    _ => unreachable!("only numbers can be negated"),
+                },
+                _ => unreachable!("default value is not constant"),
+            },
+            _ => unreachable!("default value is not constant"),
+        }
+    }
+
     /// Constructs an empty struct value e.g. `{}`
     fn gen_empty(&mut self) -> BasicValueEnum<'ink> {
         self.context.const_struct(&[], false).into()
@@ -419,6 +518,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .left()
             .unwrap()
             .into_pointer_value();
+        self.gen_return_if_null(untyped_reference);
 
         // Cast the object pointer to the struct type
         let typed_reference = self
@@ -443,6 +543,70 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         reference.into()
     }
 
+    /// Generates code that returns from the function if `reference` is null.
+    ///
+    /// The runtime returns a null reference when an allocation fails, e.g.
+    /// because the invocation exceeded its allocation limit. The runtime records
+    /// the error, so the zero value that is returned is never observed.
+    fn gen_return_if_null(&mut self, reference: PointerValue<'ink>) {
+        let is_null = self.builder.build_is_null(reference, "is_null");
+        let null_block = self.context.append_basic_block(self.fn_value, "null_ref");
+        let not_null_block = self
+            .context
+            .append_basic_block(self.fn_value, "not_null_ref");
+        self.builder
+            .build_conditional_branch(is_null, null_block, not_null_block);
+
+        self.builder.position_at_end(null_block);
+        self.gen_return_zero();
+
+        self.builder.position_at_end(not_null_block);
+    }
+
+    /// Generates code that notifies the runtime and returns from the function
+    /// if the reference to a gc struct `handle` is null. The runtime records
+    /// the error, so the zero value that is returned is never observed.
+    fn gen_null_check(&mut self, handle: PointerValue<'ink>) {
+        let is_null = self.builder.build_is_null(handle, "is_null");
+        let null_block = self.context.append_basic_block(self.fn_value, "null_deref");
+        let not_null_block = self
+            .context
+            .append_basic_block(self.fn_value, "not_null_deref");
+        self.builder
+            .build_conditional_branch(is_null, null_block, not_null_block);
+
+        self.builder.position_at_end(null_block);
+        let null_dereference_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::null_dereference,
+        );
+        self.builder.build_call(null_dereference_fn_ptr, &[], "");
+        self.gen_return_zero();
+
+        self.builder.position_at_end(not_null_block);
+    }
+
+    /// Generates a return of the zero value of the return type of the function.
+    fn gen_return_zero(&mut self) {
+        match self.fn_value.get_type().get_return_type() {
+            Some(ret_type) => {
+                let zero: BasicValueEnum<'ink> = match ret_type {
+                    BasicTypeEnum::ArrayType(ty) => ty.const_zero().into(),
+                    BasicTypeEnum::FloatType(ty) => ty.const_zero().into(),
+                    BasicTypeEnum::IntType(ty) => ty.const_zero().into(),
+                    BasicTypeEnum::PointerType(ty) => ty.const_zero().into(),
+                    BasicTypeEnum::StructType(ty) => ty.const_zero().into(),
+                    BasicTypeEnum::VectorType(ty) => ty.const_zero().into(),
+                };
+                self.builder.build_return(Some(&zero));
+            }
+            None => {
+                self.builder.build_return(None);
+            }
+        }
+    }
+
     /// Generates IR for a record literal, e.g. `Foo { a: 1.23, b: 4 }`
     fn gen_record_lit(
         &mut self,
@@ -697,10 +861,27 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .into_int_value();
         match op {
             UnaryOp::Neg => {
-                if signedness == mun_hir::Signedness::Signed {
+                if signedness == mun_hir::Signedness::Unsigned {
+                    unreachable!("negating an unsigned integer is a type error")
+                }
+
+                // Negating a literal results in a constant that has already been validated
+                // to be in range of its type, e.g. `-128_i8`.
+                if self.overflow_behavior == OverflowBehavior::Wrapping
+                    || matches!(self.body[expr], Expr::Literal(_))
+                {
                     Some(self.builder.build_int_neg(value, "neg").into())
                 } else {
-                    unimplemented!("Operator {:?} is not implemented for unsigned integer", op)
+                    let zero = value.get_type().const_zero();
+                    Some(
+                        self.gen_overflowing_arith_bin_op_int(
+                            zero,
+                            value,
+                            ArithOp::Subtract,
+                            signedness,
+                        )
+                        .into(),
+                    )
                 }
             }
             UnaryOp::Not => Some(self.builder.build_not(value, "not").into()),
@@ -876,10 +1057,53 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                     ),
                     None => rhs,
                 };
-                let place = self.gen_place_expr(lhs_expr)?;
-                self.builder.build_store(place, rhs);
+                match write_barrier_receiver(self.db, &self.body, &self.infer, lhs_expr) {
+                    Some(receiver_expr) => {
+                        let body = self.body.clone();
+                        let name = match &body[lhs_expr] {
+                            Expr::Field { name, .. } => name,
+                            _ => unreachable!("expected a field expression"),
+                        };
+
+                        // Keep the handle of the receiver around to notify the allocator
+                        let receiver_ptr = self.gen_place_expr(receiver_expr)?;
+                        let handle = self
+                            .builder
+                            .build_load(receiver_ptr, "handle")
+                            .into_pointer_value();
+                        self.gen_null_check(handle);
+
+                        // Safety: the handle of a gc struct is always a reference
+                        let data_ptr = unsafe { RuntimeReferenceValue::from_ptr_unchecked(handle) }
+                            .get_data_ptr(&self.builder);
+                        let place = self.gen_field_ptr(receiver_expr, data_ptr, name);
+                        self.builder.build_store(place, rhs);
+                        self.gen_write_barrier(handle, rhs);
+                    }
+                    None => {
+                        let place = self.gen_place_expr(lhs_expr)?;
+                        self.builder.build_store(place, rhs);
+                    }
+                }
                 Some(self.gen_empty())
             }
+            BinaryOp::CmpOp(CmpOp::Eq { negated }) => {
+                // References are equal if they refer to the same object
+                let lhs = self
+                    .gen_expr(lhs_expr)
+                    .expect("no lhs value")
+                    .into_pointer_value();
+                let (name, predicate) = if negated {
+                    ("neq", IntPredicate::NE)
+                } else {
+                    ("eq", IntPredicate::EQ)
+                };
+                Some(
+                    self.builder
+                        .build_int_compare(predicate, lhs, rhs, name)
+                        .into(),
+                )
+            }
             _ => unimplemented!("Operator {:?} is not implemented for struct", op),
         }
     }
@@ -992,6 +1216,10 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         op: ArithOp,
         signedness: mun_hir::Signedness,
     ) -> IntValue<'ink> {
+        if self.overflow_behavior != OverflowBehavior::Wrapping && is_overflowing_op(op) {
+            return self.gen_overflowing_arith_bin_op_int(lhs, rhs, op, signedness);
+        }
+
         match op {
             ArithOp::Add => self.builder.build_int_add(lhs, rhs, "add"),
             ArithOp::Subtract => self.builder.build_int_sub(lhs, rhs, "sub"),
@@ -1019,6 +1247,108 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
     }
 
+    /// Generates IR for an integer addition, subtraction, or multiplication
+    /// that handles overflow according to the [`OverflowBehavior`].
+    fn gen_overflowing_arith_bin_op_int(
+        &mut self,
+        lhs: IntValue<'ink>,
+        rhs: IntValue<'ink>,
+        op: ArithOp,
+        signedness: mun_hir::Signedness,
+    ) -> IntValue<'ink> {
+        let prefix = if signedness.is_signed() { 's' } else { 'u' };
+        let name = match op {
+            ArithOp::Add => "add",
+            ArithOp::Subtract => "sub",
+            ArithOp::Multiply => "mul",
+            _ => unreachable!("Operator {:?} cannot overflow", op),
+        };
+        let ty = lhs.get_type();
+
+        match self.overflow_behavior {
+            OverflowBehavior::Wrapping => unreachable!("wrapping operations cannot overflow"),
+            OverflowBehavior::Saturating => {
+                // There is no saturating multiplication intrinsic for integers, but a
+                // fixed-point multiplication with a scale of zero is equivalent.
+                if op == ArithOp::Multiply {
+                    let scale = self.context.i32_type().const_zero();
+                    self.gen_int_intrinsic_call(
+                        &format!("llvm.{prefix}mul.fix.sat"),
+                        ty,
+                        &[lhs.into(), rhs.into(), scale.into()],
+                    )
+                    .into_int_value()
+                } else {
+                    self.gen_int_intrinsic_call(
+                        &format!("llvm.{prefix}{name}.sat"),
+                        ty,
+                        &[lhs.into(), rhs.into()],
+                    )
+                    .into_int_value()
+                }
+            }
+            OverflowBehavior::Checked => {
+                let result = self
+                    .gen_int_intrinsic_call(
+                        &format!("llvm.{prefix}{name}.with.overflow"),
+                        ty,
+                        &[lhs.into(), rhs.into()],
+                    )
+                    .into_struct_value();
+                let value = self
+                    .builder
+                    .build_extract_value(result, 0, name)
+                    .expect("missing result of overflowing operation")
+                    .into_int_value();
+                let overflowed = self
+                    .builder
+                    .build_extract_value(result, 1, "overflowed")
+                    .expect("missing overflow flag of overflowing operation")
+                    .into_int_value();
+
+                let overflow_block = self.context.append_basic_block(self.fn_value, "overflow");
+                let end_block = self
+                    .context
+                    .append_basic_block(self.fn_value, "overflow_end");
+                self.builder
+                    .build_conditional_branch(overflowed, overflow_block, end_block);
+
+                // Notify the runtime of the overflow and continue with the wrapped value
+                self.builder.position_at_end(overflow_block);
+                let overflow_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+                    self.external_globals.dispatch_table,
+                    &self.builder,
+                    &intrinsics::arithmetic_overflow,
+                );
+                self.builder.build_call(overflow_fn_ptr, &[], "");
+                self.builder.build_unconditional_branch(end_block);
+
+                self.builder.position_at_end(end_block);
+                value
+            }
+        }
+    }
+
+    /// Generates a call to the LLVM intrinsic `name` that is overloaded on the
+    /// integer type `ty`.
+    fn gen_int_intrinsic_call(
+        &self,
+        name: &str,
+        ty: IntType<'ink>,
+        args: &[BasicMetadataValueEnum<'ink>],
+    ) -> BasicValueEnum<'ink> {
+        let intrinsic = Intrinsic::find(name)
+            .unwrap_or_else(|| panic!("could not find LLVM intrinsic `{name}`"));
+        let function = intrinsic
+            .get_declaration(self.llvm_module, &[ty.into()])
+            .unwrap_or_else(|| panic!("could not declare LLVM intrinsic `{name}`"));
+        self.builder
+            .build_call(function, args, name)
+            .try_as_basic_value()
+            .left()
+            .expect("LLVM intrinsic must return a value")
+    }
+
     fn gen_arith_bin_op_float(
         &mut self,
         lhs: FloatValue<'ink>,
@@ -1078,7 +1408,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     fn is_place_expr(&self, expr: ExprId) -> bool {
         let body = self.body.clone();
         match &body[expr] {
-            Expr::Path(..) | Expr::Array(_) => true,
+            Expr::Path(..) | Expr::Array(_) | Expr::ArrayRepeat { .. } => true,
             Expr::Field { expr, .. } => self.is_place_expr(*expr),
             Expr::Index { base, .. } => self.is_place_expr(*base),
             _ => false,
@@ -1282,13 +1612,15 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
     fn gen_while(
         &mut self,
-        _expr: ExprId,
+        expr: ExprId,
         condition_expr: ExprId,
         body_expr: ExprId,
+        else_expr: Option<ExprId>,
     ) -> Option<BasicValueEnum<'ink>> {
         let context = self.context;
         let cond_block = context.append_basic_block(self.fn_value, "whilecond");
         let loop_block = context.append_basic_block(self.fn_value, "while");
+        let else_block = else_expr.map(|_| context.append_basic_block(self.fn_value, "whileelse"));
         let exit_block = context.append_basic_block(self.fn_value, "afterwhile");
 
         // Insert an explicit fall through from the current block to the condition check
@@ -1303,7 +1635,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             self.builder.build_conditional_branch(
                 condition_ir.into_int_value(),
                 loop_block,
-                exit_block,
+                else_block.unwrap_or(exit_block),
             );
         } else {
             // If the condition doesn't return a value, we also immediately return without a
@@ -1313,15 +1645,37 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
         // Generate loop block
         self.builder.position_at_end(loop_block);
-        let (exit_block, _, value) = self.gen_loop_block_expr(body_expr, exit_block);
+        let (exit_block, mut break_values, value) = self.gen_loop_block_expr(body_expr, exit_block);
         if value.is_some() {
             self.builder.build_unconditional_branch(cond_block);
         }
 
-        // Generate exit block
-        self.builder.position_at_end(exit_block);
+        let (Some(else_block), Some(else_expr)) = (else_block, else_expr) else {
+            // Generate exit block
+            self.builder.position_at_end(exit_block);
+            return Some(self.gen_empty());
+        };
 
-        Some(self.gen_empty())
+        // Generate the else block, which is entered when the condition no longer holds. Its value
+        // is passed to the exit block just like the value of a `break` expression.
+        else_block
+            .move_after(self.builder.get_insert_block().unwrap())
+            .expect("programmer error, the current block is invalid");
+        self.builder.position_at_end(else_block);
+        if let Some(else_value) = self.gen_expr(else_expr) {
+            let else_value = if self.infer[expr].is_empty() {
+                None
+            } else {
+                Some((else_value, self.builder.get_insert_block().unwrap()))
+            };
+            break_values.push(else_value);
+            self.builder.build_unconditional_branch(exit_block);
+        }
+
+        exit_block
+            .move_after(self.builder.get_insert_block().unwrap())
+            .expect("programmer error, the current block is invalid");
+        self.gen_loop_exit(exit_block, break_values)
     }
 
     fn gen_loop(&mut self, _expr: ExprId, body_expr: ExprId) -> Option<BasicValueEnum<'ink>> {
@@ -1339,6 +1693,16 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             self.builder.build_unconditional_branch(loop_block);
         }
 
+        self.gen_loop_exit(exit_block, break_values)
+    }
+
+    /// Generates the block that is jumped to when exiting a loop. Returns the
+    /// value of the loop, which is built from the values of the `break_values`.
+    fn gen_loop_exit(
+        &mut self,
+        exit_block: BasicBlock<'ink>,
+        break_values: BreakSources<'ink>,
+    ) -> Option<BasicValueEnum<'ink>> {
         if break_values.is_empty() {
             // Not a single code entry point jumped to the exit block through a break.
             // Therefor we can completely remove the exit block since it doesnt
@@ -1388,9 +1752,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         let field_ir_name = &format!("{hir_struct_name}.{name}");
         if self.is_place_expr(receiver_expr) {
             let receiver_ptr = self.gen_place_expr(receiver_expr)?;
-            let receiver_ptr = self
-                .opt_deref_value(receiver_expr, receiver_ptr.into())
-                .into_pointer_value();
+            let receiver_ptr = self.gen_place_data_ptr(receiver_expr, receiver_ptr);
             let field_ptr = self
                 .builder
                 .build_struct_gep(
@@ -1406,6 +1768,9 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             Some(self.builder.build_load(field_ptr, field_ir_name))
         } else {
             let receiver_value = self.gen_expr(receiver_expr)?;
+            if self.infer[receiver_expr].is_gc_struct(self.db) {
+                self.gen_null_check(receiver_value.into_pointer_value());
+            }
             let receiver_value = self.opt_deref_value(receiver_expr, receiver_value);
             let receiver_struct = receiver_value.into_struct_value();
             Some(
@@ -1427,6 +1792,40 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         receiver_expr: ExprId,
         name: &Name,
     ) -> Option<PointerValue<'ink>> {
+        let receiver_ptr = self.gen_place_expr(receiver_expr)?;
+        let receiver_ptr = self.gen_place_data_ptr(receiver_expr, receiver_ptr);
+        Some(self.gen_field_ptr(receiver_expr, receiver_ptr, name))
+    }
+
+    /// Given a pointer to the place that holds the value of `expr`, returns a
+    /// pointer to the data of that value. The place of a gc struct holds a
+    /// reference, which is checked for null before it is dereferenced.
+    fn gen_place_data_ptr(
+        &mut self,
+        expr: ExprId,
+        place: PointerValue<'ink>,
+    ) -> PointerValue<'ink> {
+        if !self.infer[expr].is_gc_struct(self.db) {
+            return place;
+        }
+
+        // Safety: the place of a gc struct holds a reference
+        let handle =
+            unsafe { RuntimeReferenceValue::from_ptr_unchecked(place) }.get_data_ptr(&self.builder);
+        self.gen_null_check(handle);
+        self.builder
+            .build_load(handle, "deref")
+            .into_pointer_value()
+    }
+
+    /// Generates IR to get a pointer to the field with the specified `name` of
+    /// the struct pointed to by `receiver_ptr`.
+    fn gen_field_ptr(
+        &self,
+        receiver_expr: ExprId,
+        receiver_ptr: PointerValue<'ink>,
+        name: &Name,
+    ) -> PointerValue<'ink> {
         let hir_struct = self.infer[receiver_expr]
             .as_struct()
             .expect("expected a struct");
@@ -1438,28 +1837,160 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .expect("expected a struct field")
             .index(self.db);
 
-        let receiver_ptr = self.gen_place_expr(receiver_expr)?;
-        let receiver_ptr = self
-            .opt_deref_value(receiver_expr, receiver_ptr.into())
-            .into_pointer_value();
-        Some(
-            self.builder
-                .build_struct_gep(
-                    receiver_ptr,
-                    field_idx,
-                    &format!("{hir_struct_name}->{name}"),
+        self.builder
+            .build_struct_gep(
+                receiver_ptr,
+                field_idx,
+                &format!("{hir_struct_name}->{name}"),
+            )
+            .unwrap_or_else(|_| {
+                panic!(
+                    "could not get pointer to field `{hir_struct_name}::{name}` at index {field_idx}"
                 )
-                .unwrap_or_else(|_| {
-                    panic!(
-                        "could not get pointer to field `{hir_struct_name}::{name}` at index {field_idx}"
-                    )
-                }),
-        )
+            })
+    }
+
+    /// Generates IR to notify the allocator that the reference `value` was
+    /// stored in the object referred to by `handle`. Stores of null references
+    /// are skipped.
+    fn gen_write_barrier(&mut self, handle: PointerValue<'ink>, value: PointerValue<'ink>) {
+        let barrier_block = self
+            .context
+            .append_basic_block(self.fn_value, "write_barrier");
+        let end_block = self
+            .context
+            .append_basic_block(self.fn_value, "write_barrier_end");
+
+        let is_null = self.builder.build_is_null(value, "is_null");
+        self.builder
+            .build_conditional_branch(is_null, end_block, barrier_block);
+
+        self.builder.position_at_end(barrier_block);
+        let write_barrier_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::write_barrier,
+        );
+
+        // HACK: We should be able to use typed pointers for references in intrinsics
+        let handle = self.builder.build_bitcast(
+            handle,
+            self.context
+                .i8_type()
+                .ptr_type(AddressSpace::default())
+                .ptr_type(AddressSpace::default()),
+            "handle_to_i8_ptr_ptr",
+        );
+        let allocator_handle = self.get_allocator_handle_ptr();
+        self.builder.build_call(
+            write_barrier_fn_ptr,
+            &[handle.into(), allocator_handle.into()],
+            "",
+        );
+        self.builder.build_unconditional_branch(end_block);
+
+        self.builder.position_at_end(end_block);
     }
 
     /// Generates code to construct an array literal at runtime. Returns `None`
     /// if the code generation for the array literal never returns.
     fn gen_array(&mut self, expr: ExprId, exprs: &[ExprId]) -> Option<RuntimeArrayValue<'ink>> {
+        let capacity = self
+            .hir_types
+            .get_usize_type()
+            .const_int(exprs.len() as u64, false);
+        let array = self.gen_new_array(expr, capacity);
+
+        let array_elements = array.get_elements(&self.builder);
+        for (idx, expr) in exprs.iter().enumerate() {
+            let element_ptr = unsafe {
+                self.builder.build_gep(
+                    array_elements,
+                    &[self.context.i64_type().const_int(idx as u64, false)],
+                    &format!("{}[{}]", array_elements.get_name().to_string_lossy(), idx),
+                )
+            };
+
+            let expr_value = self.gen_expr(*expr)?;
+            self.builder.build_store(element_ptr, expr_value);
+        }
+
+        // Once all values have been stored in the array, update the length of the array
+        let length = array.length_ty().const_int(exprs.len() as u64, false);
+        let array_length_ptr = array.get_length_ptr(&self.builder);
+        self.builder.build_store(array_length_ptr, length);
+
+        Some(array)
+    }
+
+    /// Generates an array that contains `count` copies of `initializer`, e.g.
+    /// `[0; 5]`. The initializer is evaluated for every element, so elements
+    /// of a gc struct type don't all refer to the same object.
+    fn gen_array_repeat(
+        &mut self,
+        expr: ExprId,
+        initializer: ExprId,
+        count: ExprId,
+    ) -> Option<RuntimeArrayValue<'ink>> {
+        let count = self.gen_expr(count)?.into_int_value();
+        let array = self.gen_new_array(expr, count);
+        let array_elements = array.get_elements(&self.builder);
+
+        let context = self.context;
+        let entry_block = self.builder.get_insert_block().unwrap();
+        let cond_block = context.append_basic_block(self.fn_value, "repeatcond");
+        let loop_block = context.append_basic_block(self.fn_value, "repeat");
+        let exit_block = context.append_basic_block(self.fn_value, "afterrepeat");
+        self.builder.build_unconditional_branch(cond_block);
+
+        // Loop over all elements of the array
+        self.builder.position_at_end(cond_block);
+        let index = self.builder.build_phi(count.get_type(), "index");
+        index.add_incoming(&[(&count.get_type().const_zero(), entry_block)]);
+        let has_next = self.builder.build_int_compare(
+            IntPredicate::ULT,
+            index.as_basic_value().into_int_value(),
+            count,
+            "has_next",
+        );
+        self.builder
+            .build_conditional_branch(has_next, loop_block, exit_block);
+
+        // Evaluate the initializer and store its value in the current element. If
+        // the initializer diverges, the loop is never continued.
+        self.builder.position_at_end(loop_block);
+        if let Some(value) = self.gen_expr(initializer) {
+            let element_ptr = unsafe {
+                self.builder.build_gep(
+                    array_elements,
+                    &[index.as_basic_value().into_int_value()],
+                    &format!("{}+index", array_elements.get_name().to_string_lossy()),
+                )
+            };
+            self.builder.build_store(element_ptr, value);
+            let next_index = self.builder.build_int_add(
+                index.as_basic_value().into_int_value(),
+                count.get_type().const_int(1, false),
+                "next_index",
+            );
+            // The initializer may have added blocks, so the back edge starts at the
+            // current block instead of `loop_block`.
+            let latch_block = self.builder.get_insert_block().unwrap();
+            self.builder.build_unconditional_branch(cond_block);
+            index.add_incoming(&[(&next_index, latch_block)]);
+        }
+
+        // Once all values have been stored in the array, update the length of the array
+        self.builder.position_at_end(exit_block);
+        let array_length_ptr = array.get_length_ptr(&self.builder);
+        self.builder.build_store(array_length_ptr, count);
+
+        Some(array)
+    }
+
+    /// Allocates an array with the type of the specified expression and room
+    /// for `capacity` elements. The length of the array is not initialized.
+    fn gen_new_array(&mut self, expr: ExprId, capacity: IntValue<'ink>) -> RuntimeArrayValue<'ink> {
         let array_ty = &self.infer[expr];
         let element_ty = array_ty
             .as_array()
@@ -1488,11 +2019,6 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
         let allocator_handle = self.get_allocator_handle_ptr();
 
-        let length_value = self
-            .hir_types
-            .get_usize_type()
-            .const_int(exprs.len() as u64, false);
-
         // An object pointer adds an extra layer of indirection to allow for hot
         // reloading. To make it struct type agnostic, it is stored in a `*const
         // *mut std::ffi::c_void`.
@@ -1502,7 +2028,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 new_array_fn_ptr,
                 &[
                     type_info_ptr.into(),
-                    length_value.into(),
+                    capacity.into(),
                     allocator_handle.into(),
                 ],
                 "ref",
@@ -1511,6 +2037,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .left()
             .unwrap()
             .into_pointer_value();
+        self.gen_return_if_null(untyped_array_ptr);
 
         // Cast the object pointer to the array struct type
         let array_ty = self.hir_types.get_array_type(element_ty);
@@ -1525,28 +2052,8 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             )
             .into_pointer_value();
 
-        let array = RuntimeArrayValue::from_ptr(array_ptr, array_ty)
-            .expect("unable to convert pointer to typed reference");
-        let array_elements = array.get_elements(&self.builder);
-        for (idx, expr) in exprs.iter().enumerate() {
-            let element_ptr = unsafe {
-                self.builder.build_gep(
-                    array_elements,
-                    &[self.context.i64_type().const_int(idx as u64, false)],
-                    &format!("{}[{}]", array_elements.get_name().to_string_lossy(), idx),
-                )
-            };
-
-            let expr_value = self.gen_expr(*expr)?;
-            self.builder.build_store(element_ptr, expr_value);
-        }
-
-        // Once all values have been stored in the array, update the length of the array
-        let length = array.length_ty().const_int(exprs.len() as u64, false);
-        let array_length_ptr = array.get_length_ptr(&self.builder);
-        self.builder.build_store(array_length_ptr, length);
-
-        Some(array)
+        RuntimeArrayValue::from_ptr(array_ptr, array_ty)
+            .expect("unable to convert pointer to typed reference")
     }
 
     /// Generates an index into an array
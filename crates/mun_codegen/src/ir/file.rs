@@ -90,6 +90,12 @@ pub(crate) fn gen_file_ir<'ink>(
 
     // Generate the function bodies
     for (hir_function, llvm_function) in functions.iter() {
+        let _span = tracing::info_span!(
+            "codegen_fn",
+            function = %hir_function.name(code_gen.db)
+        )
+        .entered();
+
         let mut code_gen = BodyIrGenerator::new(
             code_gen.context,
             code_gen.db,
@@ -100,6 +106,8 @@ pub(crate) fn gen_file_ir<'ink>(
             external_globals.clone(),
             &code_gen.hir_types,
             module_group,
+            &llvm_module,
+            code_gen.overflow_behavior,
         );
 
         code_gen.gen_fn_body();
@@ -117,6 +125,8 @@ pub(crate) fn gen_file_ir<'ink>(
             external_globals.clone(),
             &code_gen.hir_types,
             module_group,
+            &llvm_module,
+            code_gen.overflow_behavior,
         );
 
         code_gen.gen_fn_wrapper();
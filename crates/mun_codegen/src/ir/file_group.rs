@@ -57,6 +57,7 @@ pub(crate) fn gen_file_group_ir<'ink>(
                     code_gen.db,
                     &mut intrinsics_map,
                     &mut needs_alloc,
+                    code_gen.overflow_behavior,
                     &f.body(code_gen.db),
                     &f.infer(code_gen.db),
                 );
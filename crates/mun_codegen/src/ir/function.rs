@@ -1,8 +1,9 @@
 use inkwell::{
+    attributes::{Attribute, AttributeLoc},
     passes::{PassManager, PassManagerBuilder},
     values::FunctionValue,
 };
-use mun_hir::HirDatabase;
+use mun_hir::{HirDatabase, InlineAttr};
 
 use crate::{ir::ty::HirTypeCache, Module, OptimizationLevel};
 
@@ -35,7 +36,21 @@ pub(crate) fn gen_prototype<'db, 'ink>(
 ) -> FunctionValue<'ink> {
     let name = func.name(db).to_string();
     let ir_ty = types.get_function_type(func);
-    module.add_function(&name, ir_ty, None)
+    let value = module.add_function(&name, ir_ty, None);
+
+    if let Some(inline_attr) = func.inline_attr(db) {
+        let attribute_name = match inline_attr {
+            InlineAttr::Hint => "inlinehint",
+            InlineAttr::Always => "alwaysinline",
+            InlineAttr::Never => "noinline",
+        };
+        let attribute = module
+            .get_context()
+            .create_enum_attribute(Attribute::get_named_enum_kind_id(attribute_name), 0);
+        value.add_attribute(AttributeLoc::Function, attribute);
+    }
+
+    value
 }
 
 /// Generates a `FunctionValue` for a `mun_hir::Function` that is usable from
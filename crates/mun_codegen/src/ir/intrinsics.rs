@@ -1,11 +1,15 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use inkwell::{context::Context, targets::TargetData, types::FunctionType};
-use mun_hir::{Body, Expr, ExprId, HirDatabase, InferenceResult, ValueNs};
+use mun_hir::{
+    ArithOp, BinaryOp, Body, Expr, ExprId, HirDatabase, InferenceResult, StructMemoryKind, Ty,
+    TyKind, UnaryOp, ValueNs,
+};
 
 use crate::{
     intrinsics::{self, Intrinsic},
     ir::dispatch_table::FunctionPrototype,
+    OverflowBehavior,
 };
 
 // Use a `BTreeMap` to guarantee deterministically ordered output
@@ -24,6 +28,39 @@ fn collect_intrinsic<'ink>(
         .or_insert_with(|| intrinsic.ir_type(context, target));
 }
 
+/// Returns true if the specified type is a garbage collected struct.
+fn is_gc_struct(db: &dyn HirDatabase, ty: &Ty) -> bool {
+    ty.as_struct().map_or(false, |s| {
+        s.data(db.upcast()).memory_kind == StructMemoryKind::Gc
+    })
+}
+
+/// If `expr` is the left-hand side of an assignment that stores a reference to
+/// a garbage collected struct in a field of another garbage collected struct,
+/// returns the receiver of that field. Such stores require a write barrier.
+pub(crate) fn write_barrier_receiver(
+    db: &dyn HirDatabase,
+    body: &Body,
+    infer: &InferenceResult,
+    expr: ExprId,
+) -> Option<ExprId> {
+    match &body[expr] {
+        Expr::Field {
+            expr: receiver_expr,
+            ..
+        } if is_gc_struct(db, &infer[*receiver_expr]) && is_gc_struct(db, &infer[expr]) => {
+            Some(*receiver_expr)
+        }
+        _ => None,
+    }
+}
+
+/// Returns true if `op` is an operation of which the behavior on integer
+/// overflow is determined by the [`OverflowBehavior`].
+pub(crate) fn is_overflowing_op(op: ArithOp) -> bool {
+    matches!(op, ArithOp::Add | ArithOp::Subtract | ArithOp::Multiply)
+}
+
 /// Iterates over all expressions and stores information on which intrinsics
 /// they use in `entries`.
 #[allow(clippy::too_many_arguments)]
@@ -33,6 +70,7 @@ fn collect_expr<'ink>(
     db: &'_ dyn HirDatabase,
     intrinsics: &mut IntrinsicsMap<'ink>,
     needs_alloc: &mut bool,
+    overflow_behavior: OverflowBehavior,
     expr_id: ExprId,
     body: &Arc<Body>,
     infer: &InferenceResult,
@@ -69,11 +107,70 @@ fn collect_expr<'ink>(
         }
     }
 
-    if let Expr::Array(_) = expr {
+    if let Expr::Array(_) | Expr::ArrayRepeat { .. } = expr {
         collect_intrinsic(context, target, &intrinsics::new_array, intrinsics);
         *needs_alloc = true;
     }
 
+    if let Expr::BinaryOp {
+        lhs,
+        op: Some(BinaryOp::Assignment { op: None }),
+        ..
+    } = expr
+    {
+        if write_barrier_receiver(db, body, infer, *lhs).is_some() {
+            collect_intrinsic(context, target, &intrinsics::write_barrier, intrinsics);
+            *needs_alloc = true;
+        }
+    }
+
+    if let Expr::Field {
+        expr: receiver_expr,
+        ..
+    } = expr
+    {
+        if is_gc_struct(db, &infer[*receiver_expr]) {
+            collect_intrinsic(context, target, &intrinsics::null_dereference, intrinsics);
+        }
+    }
+
+    if let Expr::BinaryOp {
+        lhs,
+        op: Some(BinaryOp::ArithOp(op) | BinaryOp::Assignment { op: Some(op) }),
+        ..
+    } = expr
+    {
+        if overflow_behavior == OverflowBehavior::Checked
+            && is_overflowing_op(*op)
+            && matches!(infer[*lhs].interned(), TyKind::Int(_))
+        {
+            collect_intrinsic(
+                context,
+                target,
+                &intrinsics::arithmetic_overflow,
+                intrinsics,
+            );
+        }
+    }
+
+    if let Expr::UnaryOp {
+        expr: operand,
+        op: UnaryOp::Neg,
+    } = expr
+    {
+        if overflow_behavior == OverflowBehavior::Checked
+            && !matches!(body[*operand], Expr::Literal(_))
+            && matches!(infer[*operand].interned(), TyKind::Int(_))
+        {
+            collect_intrinsic(
+                context,
+                target,
+                &intrinsics::arithmetic_overflow,
+                intrinsics,
+            );
+        }
+    }
+
     // Recurse further
     expr.walk_child_exprs(|expr_id| {
         collect_expr(
@@ -82,6 +179,7 @@ fn collect_expr<'ink>(
             db,
             intrinsics,
             needs_alloc,
+            overflow_behavior,
             expr_id,
             body,
             infer,
@@ -90,12 +188,14 @@ fn collect_expr<'ink>(
 }
 
 /// Collects all intrinsics from the specified `body`.
+#[allow(clippy::too_many_arguments)]
 pub fn collect_fn_body<'ink>(
     context: &'ink Context,
     target: TargetData,
     db: &dyn HirDatabase,
     intrinsics: &mut IntrinsicsMap<'ink>,
     needs_alloc: &mut bool,
+    overflow_behavior: OverflowBehavior,
     body: &Arc<Body>,
     infer: &InferenceResult,
 ) {
@@ -105,6 +205,7 @@ pub fn collect_fn_body<'ink>(
         db,
         intrinsics,
         needs_alloc,
+        overflow_behavior,
         body.body_expr(),
         body,
         infer,
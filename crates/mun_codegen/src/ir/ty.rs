@@ -93,7 +93,8 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
             .opaque_struct_type(&struct_ty.name(self.db).to_string());
         self.types.borrow_mut().insert(ty, ir_ty);
 
-        // Fill the struct members
+        // Fill the struct members. Fields are always laid out in declaration order, which is
+        // also the layout that `#[repr(C)]` guarantees.
         let field_types: Vec<_> = struct_ty
             .fields(self.db)
             .into_iter()
@@ -308,6 +309,45 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
         }
     }
 
+    /// Returns the size and the alignment in bytes of the memory layout of the
+    /// specified type. The layout of a struct is the layout of its fields,
+    /// regardless of its memory kind. Types without a value, like `never`,
+    /// have the layout of the empty type.
+    pub fn get_layout(&self, ty: &Ty) -> (u64, u32) {
+        let ir_type: BasicTypeEnum<'ink> = match ty.interned() {
+            TyKind::Struct(s) => self.get_struct_type(*s).into(),
+            _ => self
+                .get_basic_type(ty)
+                .unwrap_or_else(|| self.get_empty_type().into()),
+        };
+
+        (
+            self.target_data.get_abi_size(&ir_type),
+            self.target_data.get_abi_alignment(&ir_type),
+        )
+    }
+
+    /// Returns the offset, the size and the alignment in bytes of every field
+    /// of the specified struct, in declaration order. Fields that refer to a
+    /// garbage collected struct are laid out as a handle to the object.
+    pub fn get_field_layouts(&self, struct_ty: mun_hir::Struct) -> Vec<(u64, u64, u32)> {
+        let ir_type = self.get_struct_type(struct_ty);
+        ir_type
+            .get_field_types()
+            .iter()
+            .enumerate()
+            .map(|(idx, field_type)| {
+                (
+                    self.target_data
+                        .offset_of_element(&ir_type, idx as u32)
+                        .expect("field index out of bounds"),
+                    self.target_data.get_abi_size(field_type),
+                    self.target_data.get_abi_alignment(field_type),
+                )
+            })
+            .collect()
+    }
+
     /// Returns the empty type
     pub fn get_empty_type(&self) -> StructType<'ink> {
         self.context.struct_type(&[], false)
@@ -156,7 +156,7 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
                 Some(mun_hir::CallableDef::Struct(_)) => (),
                 None => panic!("expected a callable expression"),
             }
-        } else if let mun_hir::Expr::Array(..) = expr {
+        } else if let mun_hir::Expr::Array(..) | mun_hir::Expr::ArrayRepeat { .. } = expr {
             self.collect_type(self.hir_types.type_id(&infer[expr_id]));
         }
 
@@ -96,6 +96,7 @@ pub struct FunctionPrototype<'ink> {
 pub struct FunctionDefinition<'ink> {
     pub prototype: FunctionPrototype<'ink>,
     pub fn_ptr: Value<'ink, *const fn()>,
+    pub source_file: Value<'ink, *const u8>,
 }
 
 #[derive(AsValue)]
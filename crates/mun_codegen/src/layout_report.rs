@@ -0,0 +1,57 @@
+//! Generates a human readable report of the memory layout of the structs of a
+//! module group, to help diagnose interop issues.
+
+use std::fmt::Write;
+
+use inkwell::context::Context;
+use mun_hir::{HirDisplay, ModuleDef, StructMemoryKind};
+
+use crate::{code_gen::CodeGenContext, CodeGenDatabase, ModuleGroup};
+
+/// Generates a report of the size and alignment of every struct in the
+/// `module_group`, and the offset, size and alignment of each of its fields.
+///
+/// The layout is computed for the target of the database, using the same
+/// types that are used to generate code.
+pub fn generate_struct_layout_report(
+    db: &dyn CodeGenDatabase,
+    module_group: &ModuleGroup,
+) -> String {
+    let context = Context::create();
+    let code_gen = CodeGenContext::new(&context, db);
+    let hir_db = code_gen.db;
+
+    let mut report = String::new();
+    for s in module_group
+        .iter()
+        .flat_map(|module| module.declarations(hir_db))
+        .filter_map(|def| match def {
+            ModuleDef::Struct(s) => Some(s),
+            _ => None,
+        })
+    {
+        let memory_kind = match s.data(hir_db.upcast()).memory_kind {
+            StructMemoryKind::Gc => "gc",
+            StructMemoryKind::Value => "value",
+        };
+        let (size, alignment) = code_gen.hir_types.get_layout(&s.ty(hir_db));
+        writeln!(
+            report,
+            "struct({memory_kind}) {}: size {size}, align {alignment}",
+            s.full_name(hir_db)
+        )
+        .unwrap();
+
+        let layouts = code_gen.hir_types.get_field_layouts(s);
+        for (field, (offset, size, alignment)) in s.fields(hir_db).into_iter().zip(layouts) {
+            writeln!(
+                report,
+                "    {}: {}: offset {offset}, size {size}, align {alignment}",
+                field.name(hir_db),
+                field.ty(hir_db).display(hir_db)
+            )
+            .unwrap();
+        }
+    }
+    report
+}
@@ -1,9 +1,12 @@
 pub use inkwell::{builder::Builder, context::Context, module::Module, OptimizationLevel};
 
 pub use crate::{
-    assembly::{AssemblyIr, TargetAssembly},
+    assembly::{AssemblyIr, AssemblyObject, TargetAssembly},
+    c_header::generate_c_header,
     code_gen::AssemblyBuilder,
-    db::{CodeGenDatabase, CodeGenDatabaseStorage},
+    db::{CodeGenDatabase, CodeGenDatabaseStorage, OverflowBehavior},
+    layout_report::generate_struct_layout_report,
+    metadata::generate_metadata,
     module_group::ModuleGroup,
     module_partition::{ModuleGroupId, ModulePartition},
 };
@@ -24,8 +27,11 @@ mod test;
 pub mod value;
 
 mod apple;
+mod c_header;
 pub(crate) mod intrinsics;
+mod layout_report;
 mod linker;
+mod metadata;
 mod module_group;
 mod module_partition;
 pub(crate) mod type_info;
@@ -0,0 +1,215 @@
+//! Generates the function and type metadata of a module group, which is also
+//! stored in a dedicated section of its assembly.
+
+use std::collections::HashSet;
+
+use inkwell::context::Context;
+use itertools::Itertools;
+use mun_abi as abi;
+use mun_hir::{HirDatabase, ModuleDef, TyKind};
+use rustc_hash::FxHashSet;
+
+use crate::{
+    code_gen::CodeGenContext,
+    ir::{file_group::gen_file_group_ir, ty::HirTypeCache},
+    type_info::HasStaticTypeId,
+    CodeGenDatabase, ModuleGroup, ModulePartition,
+};
+
+/// Generates the metadata of the assembly of the `module_group`. This is the
+/// same [`abi::AssemblyMetadata`] that is stored in the assembly, but it is
+/// generated without generating the function bodies or any machine code, which
+/// enables other packages to quickly type-check against it.
+pub fn generate_metadata(
+    db: &dyn CodeGenDatabase,
+    module_group: &ModuleGroup,
+) -> abi::AssemblyMetadata {
+    let context = Context::create();
+    let code_gen = CodeGenContext::new(&context, db);
+    let hir_db = code_gen.db;
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+
+    let mut function_definitions = HashSet::new();
+    let mut type_definitions = HashSet::new();
+    for def in module_group
+        .iter()
+        .flat_map(|module| module.declarations(hir_db))
+    {
+        match def {
+            ModuleDef::Function(f)
+                if !f.is_extern(hir_db) && module_group.should_export_fn(hir_db, f) =>
+            {
+                function_definitions.insert(f);
+            }
+            ModuleDef::Struct(s) => {
+                type_definitions.insert(s.ty(hir_db));
+            }
+            _ => {}
+        }
+    }
+
+    let dependencies = assembly_dependencies(
+        hir_db,
+        &db.module_partition(),
+        module_group,
+        &group_ir.referenced_modules,
+    );
+
+    assembly_metadata(
+        hir_db,
+        &function_definitions,
+        &type_definitions,
+        &code_gen.hir_types,
+        &dependencies,
+    )
+}
+
+/// Returns the relative paths of the assemblies that the assembly of the
+/// `module_group` depends on: the assemblies of the modules it references and
+/// of its direct child modules.
+pub(crate) fn assembly_dependencies(
+    db: &dyn HirDatabase,
+    module_partition: &ModulePartition,
+    module_group: &ModuleGroup,
+    referenced_modules: &FxHashSet<mun_hir::Module>,
+) -> Vec<String> {
+    let direct_children = module_group
+        .iter()
+        .flat_map(|module| module.children(db))
+        .collect();
+    referenced_modules
+        .union(&direct_children)
+        .filter_map(|&module| module_partition.group_for_module(module))
+        .collect::<FxHashSet<_>>()
+        .into_iter()
+        .map(|group_id| module_partition[group_id].relative_file_path().to_string())
+        .collect()
+}
+
+/// Constructs the metadata of an assembly that defines the specified functions
+/// and types.
+pub(crate) fn assembly_metadata(
+    db: &dyn HirDatabase,
+    function_definitions: &HashSet<mun_hir::Function>,
+    type_definitions: &HashSet<mun_hir::Ty>,
+    hir_types: &HirTypeCache<'_, '_>,
+    dependencies: &[String],
+) -> abi::AssemblyMetadata {
+    let functions = function_definitions
+        .iter()
+        .sorted_by_cached_key(|f| f.full_name(db))
+        .map(|f| {
+            let fn_sig = f.ty(db).callable_sig(db).unwrap();
+            let return_type = if fn_sig.ret().is_empty() {
+                <() as HasStaticTypeId>::type_id().name.clone()
+            } else {
+                hir_types.type_id(fn_sig.ret()).name.clone()
+            };
+
+            abi::FunctionMetadata {
+                name: f.full_name(db),
+                arg_types: fn_sig
+                    .params()
+                    .iter()
+                    .map(|ty| hir_types.type_id(ty).name.clone())
+                    .collect(),
+                return_type,
+            }
+        })
+        .collect();
+
+    let structs = type_definitions
+        .iter()
+        .map(|ty| match ty.interned() {
+            TyKind::Struct(s) => abi::StructMetadata {
+                name: s.full_name(db),
+                memory_kind: s.data(db.upcast()).memory_kind,
+                fields: s
+                    .fields(db)
+                    .into_iter()
+                    .map(|field| {
+                        (
+                            field.name(db).to_string(),
+                            hir_types.type_id(&field.ty(db)).name.clone(),
+                        )
+                    })
+                    .collect(),
+            },
+            _ => unreachable!("unsupported export type"),
+        })
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .collect();
+
+    abi::AssemblyMetadata {
+        abi_version: abi::ABI_VERSION,
+        functions,
+        structs,
+        dependencies: dependencies.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mun_abi as abi;
+
+    use crate::{metadata::generate_metadata, mock::MockDatabase, CodeGenDatabase};
+
+    fn metadata(text: &str) -> abi::AssemblyMetadata {
+        let (db, file_id) = MockDatabase::with_single_file(text);
+        let module_partition = db.module_partition();
+        let module_group_id = module_partition.group_for_file(file_id).unwrap();
+        generate_metadata(&db, &module_partition[module_group_id])
+    }
+
+    #[test]
+    fn structs_and_functions() {
+        let metadata = metadata(
+            r#"
+        pub struct(value) Vec2 { x: f32, y: f32 }
+        pub struct Node { pos: Vec2, children: [Node] }
+        pub struct(value) Pair(i32, bool);
+
+        pub fn length(v: Vec2) -> f32 { v.x + v.y }
+        pub fn reset(node: Node, _: bool) {}
+        fn private(a: u8) -> u8 { a }
+        "#,
+        );
+        assert_eq!(metadata.abi_version, abi::ABI_VERSION);
+
+        let functions: Vec<_> = metadata
+            .functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.arg_types.clone(), f.return_type.as_str()))
+            .collect();
+        assert_eq!(
+            functions,
+            [
+                ("length", vec!["Vec2".to_owned()], "core::f32"),
+                (
+                    "reset",
+                    vec!["Node".to_owned(), "core::bool".to_owned()],
+                    "core::empty"
+                ),
+            ]
+        );
+
+        let structs: Vec<_> = metadata
+            .structs
+            .iter()
+            .map(|s| (s.name.as_str(), s.memory_kind, s.fields.len()))
+            .collect();
+        assert_eq!(
+            structs,
+            [
+                ("Node", abi::StructMemoryKind::Gc, 2),
+                ("Pair", abi::StructMemoryKind::Value, 2),
+                ("Vec2", abi::StructMemoryKind::Value, 2),
+            ]
+        );
+        assert_eq!(
+            metadata.structs[0].fields[1],
+            ("children".to_owned(), "[Node]".to_owned())
+        );
+        assert!(metadata.dependencies.is_empty());
+    }
+}
@@ -1,12 +1,12 @@
 use std::sync::Arc;
 
-use mun_hir::{FileId, HirDatabase, SourceDatabase, SourceRoot, SourceRootId};
+use mun_hir::{AstDatabase, FileId, HirDatabase, SourceDatabase, SourceRoot, SourceRootId};
 use mun_paths::RelativePathBuf;
 use mun_target::spec::Target;
 use parking_lot::Mutex;
 
 use crate::{
-    db::{CodeGenDatabase, CodeGenDatabaseStorage},
+    db::{CodeGenDatabase, CodeGenDatabaseStorage, OverflowBehavior},
     OptimizationLevel,
 };
 
@@ -71,7 +71,11 @@ impl Default for MockDatabase {
             events: Mutex::default(),
         };
         db.set_optimization_level(OptimizationLevel::Default);
+        db.set_overflow_behavior(OverflowBehavior::default());
+        db.set_single_assembly(false);
         db.set_target(Target::host_target().unwrap());
+        db.set_nesting_limit(mun_syntax::DEFAULT_NESTING_LIMIT);
+        db.set_script_mode(false);
         db
     }
 }
@@ -88,6 +92,7 @@ impl MockDatabase {
         let rel_path = RelativePathBuf::from("mod.mun");
         let file_id = FileId(0);
         db.set_file_text(file_id, text);
+        db.set_incremental_parse(file_id, None);
         db.set_file_source_root(file_id, source_root_id);
         source_root.insert_file(file_id, rel_path);
 
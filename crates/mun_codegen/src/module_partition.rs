@@ -73,6 +73,17 @@ impl Index<ModuleGroupId> for ModulePartition {
 /// Builds a module partition from the contents of the database
 pub(crate) fn build_partition(db: &dyn CodeGenDatabase) -> Arc<ModulePartition> {
     let mut partition = ModulePartition::default();
+    if db.single_assembly() {
+        let modules = mun_hir::Package::all(db.upcast())
+            .into_iter()
+            .flat_map(|package| package.modules(db.upcast()));
+        partition.add_group(
+            db.upcast(),
+            ModuleGroup::new(db.upcast(), String::from("mod"), modules),
+        );
+        return Arc::new(partition);
+    }
+
     for module in mun_hir::Package::all(db.upcast())
         .into_iter()
         .flat_map(|package| package.modules(db.upcast()))
@@ -2,7 +2,9 @@ use std::cell::RefCell;
 
 use inkwell::{context::Context, OptimizationLevel};
 use mun_hir::{
-    diagnostics::DiagnosticSink, with_fixture::WithFixture, HirDatabase, SourceDatabase, Upcast,
+    diagnostics::{DiagnosticSink, Severity},
+    with_fixture::WithFixture,
+    HirDatabase, SourceDatabase, Upcast,
 };
 use mun_target::spec::Target;
 
@@ -10,7 +12,7 @@ use crate::{
     code_gen::{AssemblyBuilder, CodeGenContext},
     ir::{file::gen_file_ir, file_group::gen_file_group_ir},
     mock::MockDatabase,
-    CodeGenDatabase,
+    CodeGenDatabase, ModuleGroup,
 };
 
 #[test]
@@ -69,6 +71,65 @@ fn multi_file() {
     );
 }
 
+#[test]
+fn pub_package_fn_export() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn main() -> i32 {
+        foo::get_value()
+    }
+
+    //- /foo.mun
+    pub(package) fn get_value() -> i32 {
+        3
+    }
+    ",
+    );
+    db.set_target(Target::host_target().unwrap());
+
+    let modules = mun_hir::Package::all(db.upcast())[0].modules(db.upcast());
+    let functions: Vec<mun_hir::Function> = modules
+        .iter()
+        .flat_map(|module| module.declarations(db.upcast()))
+        .filter_map(|def| match def {
+            mun_hir::ModuleDef::Function(f) => Some(f),
+            _ => None,
+        })
+        .collect();
+    let function = |name: &str| {
+        *functions
+            .iter()
+            .find(|f| f.name(db.upcast()).to_string() == name)
+            .unwrap()
+    };
+    let main = function("main");
+    let get_value = function("get_value");
+
+    // The `pub(package)` function is callable from the other file
+    let mut diagnostics = Vec::new();
+    let mut sink = DiagnosticSink::new(|diag| diagnostics.push(diag.message()));
+    for module in &modules {
+        module.diagnostics(db.upcast(), &mut sink);
+    }
+    drop(sink);
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+
+    // By default every module is compiled into its own assembly, so the function
+    // has to be exported to be linked from `mod.mun`
+    let partition = db.module_partition();
+    let group_id = partition
+        .group_for_module(get_value.module(db.upcast()))
+        .unwrap();
+    assert!(partition[group_id].should_export_fn(db.upcast(), get_value));
+
+    // If the whole package is part of a single assembly, only the `pub` function
+    // is exported
+    let package_group = ModuleGroup::new(db.upcast(), String::from("package"), modules);
+    assert!(package_group.should_export_fn(db.upcast(), main));
+    assert!(!package_group.should_export_fn(db.upcast(), get_value));
+}
+
 #[test]
 fn issue_262() {
     test_snapshot(
@@ -1086,6 +1147,11 @@ fn test_snapshot_with_optimization(name: &str, text: &str, opt: OptimizationLeve
     // Build and extra diagnostics
     let messages = RefCell::new(Vec::new());
     let mut sink = DiagnosticSink::new(|diag| {
+        // Warnings do not prevent code generation
+        if diag.severity() == Severity::Warning {
+            return;
+        }
+
         let file_id = diag.source().file_id;
         let line_index = db.line_index(file_id);
         let source_root_id = db.file_source_root(file_id);
@@ -1,5 +1,5 @@
 use mun_abi as abi;
-use mun_libloader::MunLibrary;
+use mun_libloader::{AssemblyMetadata, MunLibrary};
 use mun_test::CompileTestDriver;
 
 // TODO: add integration test for ModuleInfo's path
@@ -177,3 +177,40 @@ fn test_abi_compatibility() {
     )
     "#);
 }
+
+#[test]
+fn test_read_assembly_metadata() {
+    let driver = CompileTestDriver::from_file(
+        r#"
+    pub fn foo(a: f64, b: i32) -> i32 { b }
+    pub fn bar() {}
+
+    pub struct Foo(f64, f64);
+    pub struct(value) Bar { a: i32 }
+    "#,
+    );
+
+    let metadata = AssemblyMetadata::read(driver.lib_path())
+        .expect("Failed to read metadata of generated Mun library.");
+
+    let functions: Vec<_> = metadata
+        .functions()
+        .iter()
+        .map(|f| (f.name.as_str(), f.arg_types.len()))
+        .collect();
+    assert_eq!(functions, [("bar", 0), ("foo", 2)]);
+
+    let structs: Vec<_> = metadata
+        .structs()
+        .iter()
+        .map(|s| (s.name.as_str(), s.memory_kind, s.fields.len()))
+        .collect();
+    assert_eq!(
+        structs,
+        [
+            ("Bar", abi::StructMemoryKind::Value, 1),
+            ("Foo", abi::StructMemoryKind::Gc, 2)
+        ]
+    );
+    assert_eq!(metadata.dependencies().count(), 0);
+}
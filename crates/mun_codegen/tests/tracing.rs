@@ -0,0 +1,92 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+use mun_test::CompileTestDriver;
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+/// A span that was recorded by the [`CapturingSubscriber`].
+#[derive(Debug)]
+struct RecordedSpan {
+    name: &'static str,
+    fields: Vec<(String, String)>,
+}
+
+impl Visit for RecordedSpan {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.fields
+            .push((field.name().to_owned(), format!("{value:?}")));
+    }
+}
+
+/// A minimal `Subscriber` that records the name and fields of every span that
+/// is created.
+#[derive(Default)]
+struct CapturingSubscriber {
+    next_id: AtomicU64,
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+}
+
+impl Subscriber for CapturingSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &span::Attributes<'_>) -> span::Id {
+        let mut span = RecordedSpan {
+            name: attributes.metadata().name(),
+            fields: Vec::new(),
+        };
+        attributes.record(&mut span);
+        self.spans.lock().unwrap().push(span);
+        span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+#[test]
+fn compiler_phases_emit_spans() {
+    let subscriber = CapturingSubscriber::default();
+    let spans = subscriber.spans.clone();
+
+    tracing::subscriber::with_default(subscriber, || {
+        CompileTestDriver::from_file(
+            r#"
+        pub fn add(a: i32, b: i32) -> i32 { a + b }
+        "#,
+        );
+    });
+
+    let spans = spans.lock().unwrap();
+    let find = |name: &str| {
+        spans
+            .iter()
+            .find(|span| span.name == name)
+            .unwrap_or_else(|| panic!("no `{name}` span was recorded"))
+    };
+
+    assert_eq!(find("parse").fields[0].0, "file_id");
+    assert_eq!(find("name_resolution").fields[0].0, "package_id");
+    assert_eq!(
+        find("infer").fields,
+        vec![(String::from("function"), String::from("add"))]
+    );
+    find("codegen");
+    assert_eq!(
+        find("codegen_fn").fields,
+        vec![(String::from("function"), String::from("add"))]
+    );
+}
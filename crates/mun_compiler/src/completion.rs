@@ -0,0 +1,216 @@
+use mun_hir::{
+    semantics::{ScopeDef, Semantics},
+    FileId, HirDatabase, HirDisplay, ModuleDef,
+};
+use mun_syntax::{ast, AstNode, TextSize};
+
+/// The kind of item that is suggested by a [`CompletionItem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompletionItemKind {
+    /// A local variable or parameter
+    Local,
+    /// A function
+    Function,
+    /// A field of a struct
+    Field,
+    /// A struct
+    Struct,
+    /// A type alias
+    TypeAlias,
+    /// A builtin type, e.g. `i32`
+    BuiltinType,
+    /// A module
+    Module,
+}
+
+/// A suggestion to complete the text at a position in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionItem {
+    /// The text of the suggestion
+    pub label: String,
+
+    /// The kind of item that is suggested
+    pub kind: CompletionItemKind,
+
+    /// Additional information about the item, e.g. the type of a local
+    pub detail: Option<String>,
+}
+
+/// Computes the completions at `offset` in the specified file.
+///
+/// After a `.` the fields of the receiver are suggested. Otherwise all names
+/// in scope are suggested, or only types if `offset` is in a type annotation.
+pub(crate) fn completions(
+    db: &dyn HirDatabase,
+    file_id: FileId,
+    offset: TextSize,
+) -> Vec<CompletionItem> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(file_id);
+    let token = match file.syntax().token_at_offset(offset).left_biased() {
+        Some(token) => token,
+        None => return Vec::new(),
+    };
+    let parent = match token.parent() {
+        Some(parent) => parent,
+        None => return Vec::new(),
+    };
+
+    // Complete the fields of the receiver of a field expression, e.g. `foo.$0`
+    if let Some(field_expr) = parent.ancestors().find_map(ast::FieldExpr::cast) {
+        if let Some(receiver) = field_expr.expr() {
+            if token.text_range().start() >= receiver.syntax().text_range().end() {
+                return field_completions(db, &sema, &receiver);
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+    sema.scope_at_offset(&parent, offset)
+        .visit_names_at(&token, &mut |name, def| {
+            let (kind, detail) = match def {
+                ScopeDef::ModuleDef(ModuleDef::Module(_)) => (CompletionItemKind::Module, None),
+                ScopeDef::ModuleDef(ModuleDef::Function(_)) => (CompletionItemKind::Function, None),
+                ScopeDef::ModuleDef(ModuleDef::Struct(_)) => (CompletionItemKind::Struct, None),
+                ScopeDef::ModuleDef(ModuleDef::TypeAlias(_)) => {
+                    (CompletionItemKind::TypeAlias, None)
+                }
+                ScopeDef::ModuleDef(ModuleDef::PrimitiveType(_)) => {
+                    (CompletionItemKind::BuiltinType, None)
+                }
+                ScopeDef::Local(local) => {
+                    let ty = local.ty(db);
+                    let detail = (!ty.is_unknown()).then(|| ty.display(db).to_string());
+                    (CompletionItemKind::Local, detail)
+                }
+                ScopeDef::Unknown => return,
+            };
+
+            result.push(CompletionItem {
+                label: name.to_string(),
+                kind,
+                detail,
+            });
+        });
+
+    result.sort_by(|a, b| a.label.cmp(&b.label));
+    result
+}
+
+/// Returns completions for the fields of `receiver`.
+fn field_completions(
+    db: &dyn HirDatabase,
+    sema: &Semantics<'_>,
+    receiver: &ast::Expr,
+) -> Vec<CompletionItem> {
+    sema.receiver_fields(receiver)
+        .into_iter()
+        .map(|field| CompletionItem {
+            label: field.name(db).to_string(),
+            kind: CompletionItemKind::Field,
+            detail: Some(field.ty(db).display(db).to_string()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Config, Driver, PathOrInline, RelativePathBuf};
+
+    use super::{CompletionItem, CompletionItemKind};
+
+    /// Returns the completions at the position marked with `$0`.
+    fn completions(text: &str) -> Vec<CompletionItem> {
+        let offset = text.find("$0").expect("missing cursor marker `$0`");
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: text.replace("$0", ""),
+        };
+        let (driver, file_id) = Driver::with_file(Config::default(), input).unwrap();
+        driver.completions(file_id, (offset as u32).into())
+    }
+
+    #[test]
+    fn struct_fields() {
+        let items = completions(
+            r#"
+        struct Foo {
+            bar: i32,
+            baz: bool,
+        }
+
+        fn main(foo: Foo) {
+            foo.$0
+        }
+        "#,
+        );
+        assert_eq!(
+            items,
+            vec![
+                CompletionItem {
+                    label: String::from("bar"),
+                    kind: CompletionItemKind::Field,
+                    detail: Some(String::from("i32")),
+                },
+                CompletionItem {
+                    label: String::from("baz"),
+                    kind: CompletionItemKind::Field,
+                    detail: Some(String::from("bool")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn names_in_scope() {
+        let items = completions(
+            r#"
+        struct Foo;
+
+        fn bar() {}
+
+        fn main(a: i32) {
+            let b = 3;
+            $0
+        }
+        "#,
+        );
+        let labels: Vec<_> = items
+            .iter()
+            .filter(|item| item.kind != CompletionItemKind::BuiltinType)
+            .map(|item| (item.label.as_str(), item.kind))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("Foo", CompletionItemKind::Struct),
+                ("a", CompletionItemKind::Local),
+                ("b", CompletionItemKind::Local),
+                ("bar", CompletionItemKind::Function),
+                ("main", CompletionItemKind::Function),
+            ]
+        );
+    }
+
+    #[test]
+    fn types_in_annotation() {
+        let items = completions(
+            r#"
+        struct Foo;
+
+        fn bar() {}
+
+        fn main() {
+            let a: $0
+        }
+        "#,
+        );
+        assert!(items
+            .iter()
+            .any(|item| item.label == "Foo" && item.kind == CompletionItemKind::Struct));
+        assert!(items
+            .iter()
+            .any(|item| item.label == "i32" && item.kind == CompletionItemKind::BuiltinType));
+        assert!(items.iter().all(|item| item.label != "bar"));
+    }
+}
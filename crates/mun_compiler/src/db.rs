@@ -1,3 +1,5 @@
+use std::sync::Mutex;
+
 use mun_codegen::{CodeGenDatabase, CodeGenDatabaseStorage};
 use mun_hir::{salsa, HirDatabase, Upcast};
 
@@ -14,6 +16,30 @@ use crate::Config;
 )]
 pub struct CompilerDatabase {
     storage: salsa::Storage<Self>,
+    query_stats: Mutex<QueryStats>,
+}
+
+/// Statistics about the salsa queries that have been evaluated by a
+/// [`CompilerDatabase`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueryStats {
+    /// The queries that were (re)computed, formatted as `query_name(key)`.
+    pub executed: Vec<String>,
+
+    /// The number of times a memoized query result was reused because none of
+    /// its inputs changed.
+    pub cached: usize,
+}
+
+impl QueryStats {
+    /// Returns true if any query with the specified name was (re)computed.
+    pub fn was_executed(&self, query_name: &str) -> bool {
+        self.executed.iter().any(|query| {
+            query
+                .strip_prefix(query_name)
+                .map_or(false, |key| key.starts_with('('))
+        })
+    }
 }
 
 impl Upcast<dyn mun_hir::AstDatabase> for CompilerDatabase {
@@ -51,6 +77,7 @@ impl CompilerDatabase {
     pub fn new(config: &Config) -> Self {
         let mut db = CompilerDatabase {
             storage: salsa::Storage::default(),
+            query_stats: Mutex::default(),
         };
 
         // Set the initial configuration
@@ -63,7 +90,43 @@ impl CompilerDatabase {
     pub fn set_config(&mut self, config: &Config) {
         self.set_target(config.target.clone());
         self.set_optimization_level(config.optimization_lvl);
+        self.set_overflow_behavior(config.overflow_behavior);
+        self.set_single_assembly(config.single_assembly);
+        self.set_nesting_limit(config.nesting_limit);
+        self.set_script_mode(config.script);
+    }
+
+    /// Returns the statistics of all queries that were evaluated since the
+    /// last time this function was called.
+    pub fn take_query_stats(&self) -> QueryStats {
+        std::mem::take(
+            &mut *self
+                .query_stats
+                .lock()
+                .expect("query stats lock is poisoned"),
+        )
     }
 }
 
-impl salsa::Database for CompilerDatabase {}
+impl salsa::Database for CompilerDatabase {
+    fn salsa_event(&self, event: salsa::Event) {
+        // Format the query key before acquiring the lock, formatting might
+        // access the database.
+        let executed = match event.kind {
+            salsa::EventKind::WillExecute { database_key } => {
+                Some(format!("{:?}", database_key.debug(self)))
+            }
+            salsa::EventKind::DidValidateMemoizedValue { .. } => None,
+            _ => return,
+        };
+
+        let mut stats = self
+            .query_stats
+            .lock()
+            .expect("query stats lock is poisoned");
+        match executed {
+            Some(query) => stats.executed.push(query),
+            None => stats.cached += 1,
+        }
+    }
+}
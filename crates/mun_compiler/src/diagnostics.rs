@@ -1,3 +1,34 @@
+pub use mun_hir::diagnostics::Severity;
+use mun_hir::FileId;
+use mun_syntax::{Location, TextRange};
+
+/// A diagnostic emitted by the compiler, without any rendering applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The file that contains the diagnostic
+    pub file_id: FileId,
+
+    /// The location of the diagnostic in the file
+    pub range: TextRange,
+
+    /// The severity of the diagnostic
+    pub severity: Severity,
+
+    /// A short identifier of the kind of diagnostic, e.g. `UnresolvedValue`
+    pub code: &'static str,
+
+    /// The primary message of the diagnostic
+    pub message: String,
+}
+
+/// Converts the location of a syntax error to a range.
+pub(crate) fn location_to_range(location: Location) -> TextRange {
+    match location {
+        Location::Offset(offset) => TextRange::at(offset, 1.into()),
+        Location::Range(range) => range,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
@@ -2,7 +2,7 @@ use std::{collections::HashMap, sync::Arc};
 
 use annotate_snippets::{Annotation, AnnotationType, Renderer, Slice, Snippet, SourceAnnotation};
 use mun_diagnostics::DiagnosticForWith;
-use mun_hir::{line_index::LineIndex, FileId, HirDatabase};
+use mun_hir::{diagnostics::Severity, line_index::LineIndex, FileId, HirDatabase};
 use mun_paths::RelativePathBuf;
 use mun_syntax::SyntaxError;
 
@@ -52,22 +52,36 @@ pub(crate) fn emit_syntax_error(
     write!(writer, "{display}")
 }
 
-/// Emits all diagnostics that are a result of HIR validation.
+/// Emits a diagnostic that is a result of HIR validation with the specified
+/// `severity`.
 pub(crate) fn emit_hir_diagnostic(
     diagnostic: &dyn mun_hir::Diagnostic,
+    severity: Severity,
     db: &impl HirDatabase,
     file_id: FileId,
     display_colors: bool,
     writer: &mut dyn std::io::Write,
 ) -> std::io::Result<()> {
+    let annotation_type = match severity {
+        Severity::Error => AnnotationType::Error,
+        Severity::Warning => AnnotationType::Warning,
+    };
     diagnostic.with_diagnostic(db, |diagnostic| {
-        emit_diagnostic(diagnostic, db, file_id, display_colors, writer)
+        emit_diagnostic(
+            diagnostic,
+            annotation_type,
+            db,
+            file_id,
+            display_colors,
+            writer,
+        )
     })
 }
 
 /// Emits a diagnostic by writting a snippet to the specified `writer`.
 fn emit_diagnostic(
     diagnostic: &dyn mun_diagnostics::Diagnostic,
+    annotation_type: AnnotationType,
     db: &impl HirDatabase,
     file_id: FileId,
     display_colors: bool,
@@ -139,7 +153,7 @@ fn emit_diagnostic(
         title: Some(Annotation {
             id: None,
             label: Some(&title),
-            annotation_type: AnnotationType::Error,
+            annotation_type,
         }),
         slices: annotations
             .iter()
@@ -169,7 +183,7 @@ fn emit_diagnostic(
                                 usize::from(annotation.range.end()) - line_offset,
                             ),
                             label: annotation.message.as_str(),
-                            annotation_type: AnnotationType::Error,
+                            annotation_type,
                         })
                         .collect(),
                     fold: true,
@@ -1,20 +1,38 @@
 //! `Driver` is a stateful compiler frontend that enables incremental
 //! compilation by retaining state from previous compilation.
 
-use mun_codegen::{AssemblyIr, CodeGenDatabase, ModuleGroup, TargetAssembly};
+use mun_codegen::{
+    generate_c_header, generate_metadata, generate_struct_layout_report, AssemblyIr,
+    AssemblyObject, CodeGenDatabase, ModuleGroup, TargetAssembly,
+};
+use mun_diagnostics::DiagnosticForWith;
 use mun_hir::{
-    AstDatabase, DiagnosticSink, FileId, Module, PackageSet, SourceDatabase, SourceRoot,
-    SourceRootId, Upcast,
+    AstDatabase, CfgOptions, DiagnosticSink, FileId, Module, PackageSet, SourceDatabase,
+    SourceRoot, SourceRootId, Upcast,
 };
 use mun_paths::RelativePathBuf;
+use mun_syntax::{TextRange, TextSize};
+use ra_ap_text_edit::Indel;
 
 use crate::{
-    compute_source_relative_path, db::CompilerDatabase, ensure_package_output_dir, is_source_file,
+    completion::{self, CompletionItem},
+    compute_source_relative_path,
+    db::{CompilerDatabase, QueryStats},
+    diagnostics::{location_to_range, Diagnostic, Severity},
+    ensure_package_output_dir,
+    goto_definition::{self, NavigationTarget},
+    hover::{self, HoverInfo},
+    is_source_file,
+    references::{self, Reference},
+    rename::{self, RenameError, TextEdit},
+    timings::{self, CompileTimings},
     PathOrInline, RelativePath,
 };
 
 mod config;
 mod display_color;
+#[cfg(test)]
+mod tests;
 
 use std::{
     collections::HashMap,
@@ -33,6 +51,13 @@ use crate::diagnostics_snippets::{emit_hir_diagnostic, emit_syntax_error};
 
 pub const WORKSPACE: SourceRootId = SourceRootId(0);
 
+/// The sources of the built-in prelude, relative to the source directory. The
+/// public items of the `prelude` module are in scope in every module.
+const PRELUDE_FILES: &[(&str, &str)] = &[("prelude/math.mun", include_str!("prelude/math.mun"))];
+
+/// The extension of the metadata files written by the [`Driver`].
+pub const METADATA_EXTENSION: &str = "munmeta";
+
 pub struct Driver {
     db: CompilerDatabase,
     out_dir: PathBuf,
@@ -45,12 +70,15 @@ pub struct Driver {
     module_to_temp_assembly_path: HashMap<Module, PathBuf>,
 
     emit_ir: bool,
+    emit_metadata: bool,
+    deny_warnings: bool,
+    cfg_options: CfgOptions,
 }
 
 impl Driver {
     /// Constructs a driver with a specific configuration.
     pub fn with_config(config: Config, out_dir: PathBuf) -> Self {
-        Self {
+        let mut driver = Self {
             db: CompilerDatabase::new(&config),
             out_dir,
             source_root: SourceRoot::default(),
@@ -59,7 +87,18 @@ impl Driver {
             next_file_id: 0,
             module_to_temp_assembly_path: HashMap::default(),
             emit_ir: config.emit_ir,
+            emit_metadata: config.emit_metadata,
+            deny_warnings: config.deny_warnings,
+            cfg_options: config.features.iter().map(String::as_str).collect(),
+        };
+
+        if config.prelude {
+            for &(path, contents) in PRELUDE_FILES {
+                driver.add_file(path, contents.to_owned());
+            }
         }
+
+        driver
     }
 
     /// Constructs a driver with a configuration and a single file.
@@ -83,6 +122,7 @@ impl Driver {
         let file_id = FileId(driver.next_file_id as u32);
         driver.next_file_id += 1;
         driver.db.set_file_text(file_id, Arc::from(text));
+        driver.db.set_incremental_parse(file_id, None);
         driver.db.set_file_source_root(file_id, WORKSPACE);
         driver.source_root.insert_file(file_id, rel_path.clone());
         driver
@@ -90,7 +130,7 @@ impl Driver {
             .set_source_root(WORKSPACE, Arc::new(driver.source_root.clone()));
 
         let mut package_set = PackageSet::default();
-        package_set.add_package(WORKSPACE);
+        package_set.add_package_with_cfg(WORKSPACE, driver.cfg_options.clone());
         driver.db.set_packages(Arc::new(package_set));
 
         driver.path_to_file_id.insert(rel_path, file_id);
@@ -119,37 +159,81 @@ impl Driver {
         if !source_directory.is_dir() {
             anyhow::bail!("the source directory does not exist")
         }
+        driver.add_source_directory(&source_directory)?;
+
+        Ok((package, driver))
+    }
+
+    /// Constructs a driver for all source files in `source_directory`. Every
+    /// file is treated as a module of the same package, so files can refer to
+    /// each other's items. All modules are compiled into a single assembly.
+    pub fn with_directory<P: AsRef<Path>>(
+        source_directory: P,
+        config: Config,
+    ) -> Result<Driver, anyhow::Error> {
+        let config = Config {
+            single_assembly: true,
+            ..config
+        };
+
+        let source_directory = source_directory.as_ref();
+        if !source_directory.is_dir() {
+            anyhow::bail!(
+                "the source directory '{}' does not exist",
+                source_directory.display()
+            )
+        }
 
-        for source_file_path in iter_source_files(&source_directory) {
-            let relative_path = compute_source_relative_path(&source_directory, &source_file_path)?;
+        let out_dir = config.out_dir.clone().unwrap_or_else(|| {
+            std::env::current_dir().expect("could not determine current working directory")
+        });
+
+        let mut driver = Driver::with_config(config, out_dir);
+        driver.add_source_directory(source_directory)?;
+
+        Ok(driver)
+    }
+
+    /// Stores the information of all source files in `source_directory` in the
+    /// database. All files that could not be read are reported together.
+    fn add_source_directory(&mut self, source_directory: &Path) -> Result<(), anyhow::Error> {
+        let mut errors = Vec::new();
+        for source_file_path in iter_source_files(source_directory) {
+            let relative_path = compute_source_relative_path(source_directory, &source_file_path)?;
 
             // Load the contents of the file
-            let file_contents = std::fs::read_to_string(&source_file_path).map_err(|e| {
-                anyhow::anyhow!(
-                    "could not read contents of '{}': {}",
-                    source_file_path.display(),
-                    e
-                )
-            })?;
-
-            let file_id = driver.alloc_file_id(&relative_path)?;
-            driver.db.set_file_text(file_id, Arc::from(file_contents));
-            driver.db.set_file_source_root(file_id, WORKSPACE);
-            driver
-                .source_root
-                .insert_file(file_id, relative_path.clone());
+            let file_contents = match std::fs::read_to_string(&source_file_path) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    errors.push(format!(
+                        "could not read contents of '{}': {}",
+                        source_file_path.display(),
+                        e
+                    ));
+                    continue;
+                }
+            };
+
+            let file_id = self.alloc_file_id(&relative_path)?;
+            self.db.set_file_text(file_id, Arc::from(file_contents));
+            self.db.set_incremental_parse(file_id, None);
+            self.db.set_file_source_root(file_id, WORKSPACE);
+            self.source_root.insert_file(file_id, relative_path.clone());
+        }
+
+        if !errors.is_empty() {
+            anyhow::bail!("{}", errors.join("\n"));
         }
 
         // Store the source root in the database
-        driver
-            .db
-            .set_source_root(WORKSPACE, Arc::new(driver.source_root.clone()));
+        self.db
+            .set_source_root(WORKSPACE, Arc::new(self.source_root.clone()));
 
         let mut package_set = PackageSet::default();
-        package_set.add_package(WORKSPACE);
-        driver.db.set_packages(Arc::new(package_set));
+        package_set.add_package_with_cfg(WORKSPACE, self.cfg_options.clone());
+        self.db.set_packages(Arc::new(package_set));
 
-        Ok((package, driver))
+        Ok(())
     }
 }
 
@@ -202,6 +286,43 @@ impl Driver {
             .ok_or_else(|| anyhow::anyhow!("the path '{}' is unknown", path.as_ref()))?;
         self.db
             .set_file_text(*file_id, Arc::from(text.as_ref().to_owned()));
+        self.db.set_incremental_parse(*file_id, None);
+        Ok(())
+    }
+
+    /// Replaces the text in `range` of the specified file with `new_text`.
+    ///
+    /// Only the part of the syntax tree that is affected by the edit is
+    /// reparsed, if possible. Returns an error if `range` does not lie within
+    /// the text of the file or does not start and end on a character boundary.
+    pub fn apply_edit(
+        &mut self,
+        file_id: FileId,
+        range: TextRange,
+        new_text: &str,
+    ) -> anyhow::Result<()> {
+        let mut text = self.db.file_text(file_id).to_string();
+        let start: usize = range.start().into();
+        let end: usize = range.end().into();
+        if end > text.len() {
+            anyhow::bail!(
+                "the range {:?} lies outside of the text of the file, which has length {}",
+                range,
+                text.len()
+            );
+        }
+        if !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+            anyhow::bail!("the range {:?} does not lie on character boundaries", range);
+        }
+
+        let parse = self.db.parse(file_id).reparse_with(
+            &Indel::replace(range, new_text.to_owned()),
+            self.db.script_mode(),
+            self.db.nesting_limit(),
+        );
+        text.replace_range(start..end, new_text);
+        self.db.set_file_text(file_id, Arc::from(text));
+        self.db.set_incremental_parse(file_id, Some(parse));
         Ok(())
     }
 }
@@ -243,10 +364,18 @@ impl Driver {
                     module.diagnostics(
                         self.db.upcast(),
                         &mut DiagnosticSink::new(|d| {
-                            has_error = true;
-                            if let Err(e) =
-                                emit_hir_diagnostic(d, &self.db, file_id, emit_colors, writer)
-                            {
+                            let severity = self.severity_of(d);
+                            if severity == Severity::Error {
+                                has_error = true;
+                            }
+                            if let Err(e) = emit_hir_diagnostic(
+                                d,
+                                severity,
+                                &self.db,
+                                file_id,
+                                emit_colors,
+                                writer,
+                            ) {
                                 error = Some(e);
                             };
                         }),
@@ -264,6 +393,56 @@ impl Driver {
         Ok(has_error)
     }
 
+    /// Returns all diagnostics of the specified file without rendering them.
+    pub fn diagnostics(&self, file_id: FileId) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = self
+            .db
+            .parse(file_id)
+            .errors()
+            .iter()
+            .map(|syntax_error| Diagnostic {
+                file_id,
+                range: location_to_range(syntax_error.location()),
+                severity: Severity::Error,
+                code: "SyntaxError",
+                message: syntax_error.to_string(),
+            })
+            .collect();
+
+        let module = mun_hir::Package::all(self.db.upcast())
+            .into_iter()
+            .flat_map(|package| package.modules(self.db.upcast()))
+            .find(|module| module.file_id(self.db.upcast()) == Some(file_id));
+        if let Some(module) = module {
+            module.diagnostics(
+                self.db.upcast(),
+                &mut DiagnosticSink::new(|d| {
+                    let code = d.code();
+                    let severity = self.severity_of(d);
+                    diagnostics.push(d.with_diagnostic(&self.db, |d| Diagnostic {
+                        file_id,
+                        range: d.range(),
+                        severity,
+                        code,
+                        message: d.title(),
+                    }));
+                }),
+            );
+        }
+
+        diagnostics
+    }
+
+    /// Returns the severity with which the specified diagnostic is reported.
+    /// Warnings are promoted to errors if warnings are denied.
+    fn severity_of(&self, diagnostic: &dyn mun_hir::Diagnostic) -> Severity {
+        if self.deny_warnings {
+            Severity::Error
+        } else {
+            diagnostic.severity()
+        }
+    }
+
     /// Returns all diagnostics as a human readable string
     pub fn emit_diagnostics_to_string(
         &self,
@@ -283,6 +462,65 @@ impl Driver {
     }
 }
 
+impl Driver {
+    /// Returns the completions at `offset` in the specified file, e.g. the
+    /// names in scope or the fields of a struct after a `.`.
+    pub fn completions(&self, file_id: FileId, offset: TextSize) -> Vec<CompletionItem> {
+        completion::completions(self.db.upcast(), file_id, offset)
+    }
+
+    /// Returns the type and documentation of the identifier at `offset` in the
+    /// specified file, e.g. the type of a local or the signature of a
+    /// function.
+    pub fn hover(&self, file_id: FileId, offset: TextSize) -> Option<HoverInfo> {
+        hover::hover(self.db.upcast(), file_id, offset)
+    }
+
+    /// Returns the location of the definition that the identifier at `offset`
+    /// in the specified file refers to.
+    pub fn goto_definition(&self, file_id: FileId, offset: TextSize) -> Option<NavigationTarget> {
+        goto_definition::goto_definition(self.db.upcast(), file_id, offset)
+    }
+
+    /// Returns the ranges of the definition of the symbol at `offset` in the
+    /// specified file and of all references to it.
+    pub fn references(&self, file_id: FileId, offset: TextSize) -> Vec<Reference> {
+        references::references(self.db.upcast(), file_id, offset)
+    }
+
+    /// Computes the edits required to rename the symbol at `offset` in the
+    /// specified file to `new_name`. Returns an error if `new_name` is not a
+    /// valid identifier or if it collides with an existing binding.
+    pub fn rename(
+        &self,
+        file_id: FileId,
+        offset: TextSize,
+        new_name: &str,
+    ) -> Result<Vec<TextEdit>, RenameError> {
+        rename::rename(self.db.upcast(), file_id, offset, new_name)
+    }
+}
+
+impl Driver {
+    /// Returns which queries were recomputed and how many memoized query
+    /// results were reused since the last call to this function. This is
+    /// useful to verify that an edit did not invalidate more than expected.
+    pub fn query_stats(&self) -> QueryStats {
+        self.db.take_query_stats()
+    }
+
+    /// Compiles the module group that contains the specified file and returns
+    /// the time spent in parsing, name resolution, type checking, and code
+    /// generation. Parsing, name resolution, and type checking cover the entire
+    /// package of the file.
+    ///
+    /// Results of previous compilations are reused, so the timings only
+    /// include the work that is required since the last compilation.
+    pub fn timed_compile(&self, file_id: FileId) -> Result<CompileTimings, anyhow::Error> {
+        timings::timed_compile(&self.db, file_id)
+    }
+}
+
 impl Driver {
     /// Get the path where the driver will write the assembly for the specified
     /// file.
@@ -338,14 +576,19 @@ impl Driver {
     pub fn write_all_assemblies(&mut self, force: bool) -> Result<(), anyhow::Error> {
         let _lock = self.acquire_filesystem_output_lock();
 
-        // Create a copy of all current files
-        for package in mun_hir::Package::all(self.db.upcast()) {
-            for module in package.modules(self.db.upcast()) {
-                if self.emit_ir {
-                    self.write_assembly_ir(module)?;
-                } else {
-                    self.write_target_assembly(module, force)?;
-                }
+        // Create a copy of all current files. All modules of a group share the
+        // same output, so it's written for the first module of every group.
+        let module_partition = self.db.module_partition();
+        for module in module_partition
+            .iter()
+            .filter_map(|(_, module_group)| module_group.iter().next())
+        {
+            if self.emit_metadata {
+                self.write_assembly_metadata(module)?;
+            } else if self.emit_ir {
+                self.write_assembly_ir(module)?;
+            } else {
+                self.write_target_assembly(module, force)?;
             }
         }
 
@@ -426,6 +669,172 @@ impl Driver {
         Ok(true)
     }
 
+    /// Writes the optimized LLVM IR of the module group that contains the
+    /// specified file to the output location, next to where its assembly is
+    /// written. Returns the path of the written IR file.
+    pub fn write_llvm_ir(&self, file_id: FileId) -> Result<PathBuf, anyhow::Error> {
+        let _lock = self.acquire_filesystem_output_lock();
+
+        let module_partition = self.db.module_partition();
+        let module_group_id = module_partition
+            .group_for_file(file_id)
+            .ok_or_else(|| anyhow::anyhow!("could not find file in module partition"))?;
+
+        let ir_path = self
+            .path_for_module_group(&module_partition[module_group_id])
+            .with_extension(AssemblyIr::EXTENSION);
+        self.db.assembly_ir(module_group_id).copy_to(&ir_path)?;
+
+        Ok(ir_path)
+    }
+
+    /// Writes a relocatable object file of the module group that contains the
+    /// specified file to the output location. Contrary to an assembly, the
+    /// object file is not linked which enables statically linking it into
+    /// another binary. Like an assembly, the object file stores the ABI
+    /// metadata in a dedicated section. Returns the path of the written object
+    /// file.
+    pub fn write_object(&self, file_id: FileId) -> Result<PathBuf, anyhow::Error> {
+        let _lock = self.acquire_filesystem_output_lock();
+
+        let module_partition = self.db.module_partition();
+        let module_group_id = module_partition
+            .group_for_file(file_id)
+            .ok_or_else(|| anyhow::anyhow!("could not find file in module partition"))?;
+
+        let object_path = self
+            .path_for_module_group(&module_partition[module_group_id])
+            .with_extension(AssemblyObject::EXTENSION);
+        self.db
+            .assembly_object(module_group_id)
+            .copy_to(&object_path)?;
+
+        Ok(object_path)
+    }
+
+    /// Writes the module group that contains the specified file as a platform
+    /// dynamic library (e.g. `.so`, `.dll` or `.dylib`) to the output
+    /// location, so it can be loaded with `dlopen` by hosts that don't use the
+    /// Mun Runtime. The library exports the `pub` functions, the functions
+    /// marked with `#[export]` and `get_info`, which returns the ABI metadata
+    /// of the assembly.
+    /// Returns the path of the written library.
+    pub fn write_shared_library(&self, file_id: FileId) -> Result<PathBuf, anyhow::Error> {
+        let _lock = self.acquire_filesystem_output_lock();
+
+        let module_partition = self.db.module_partition();
+        let module_group_id = module_partition
+            .group_for_file(file_id)
+            .ok_or_else(|| anyhow::anyhow!("could not find file in module partition"))?;
+
+        let group_path = self.path_for_module_group(&module_partition[module_group_id]);
+        let file_name = group_path
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("module group has no file name"))?
+            .to_string_lossy();
+        let target = self.db.target();
+        let library_path = group_path.with_file_name(format!(
+            "{}{file_name}{}",
+            target.options.dll_prefix, target.options.dll_suffix
+        ));
+        self.db
+            .target_assembly(module_group_id)
+            .copy_to(&library_path)?;
+
+        Ok(library_path)
+    }
+
+    /// Returns the compiled assembly of the module group that contains the
+    /// specified file, or `None` if the file is not part of any module group.
+    pub fn target_assembly(&self, file_id: FileId) -> Option<Arc<TargetAssembly>> {
+        let module_group_id = self.db.module_partition().group_for_file(file_id)?;
+        Some(self.db.target_assembly(module_group_id))
+    }
+
+    /// Writes a C header with the prototypes of all `#[export]` functions and the
+    /// layout of all structs of the module group that contains the specified
+    /// file to the output location. Returns the path of the written header.
+    pub fn write_c_header(&self, file_id: FileId) -> Result<PathBuf, anyhow::Error> {
+        let _lock = self.acquire_filesystem_output_lock();
+
+        let module_partition = self.db.module_partition();
+        let module_group_id = module_partition
+            .group_for_file(file_id)
+            .ok_or_else(|| anyhow::anyhow!("could not find file in module partition"))?;
+        let module_group = &module_partition[module_group_id];
+
+        let header_path = self.path_for_module_group(module_group).with_extension("h");
+        std::fs::write(
+            &header_path,
+            generate_c_header(self.db.upcast(), module_group),
+        )?;
+
+        Ok(header_path)
+    }
+
+    /// Returns a report of the memory layout of all structs of the module group
+    /// that contains the specified file: the size and alignment of each
+    /// struct, and the offset, size and alignment of each of its fields. The
+    /// report is empty if the file is not part of any module group.
+    pub fn struct_layout_report(&self, file_id: FileId) -> String {
+        let module_partition = self.db.module_partition();
+        module_partition
+            .group_for_file(file_id)
+            .map(|module_group_id| {
+                generate_struct_layout_report(&self.db, &module_partition[module_group_id])
+            })
+            .unwrap_or_default()
+    }
+
+    /// Writes the metadata of the module group that contains the specified file
+    /// to the output location. The file contains the serialized
+    /// `mun_abi::AssemblyMetadata` of the assembly, which can be read with
+    /// `AssemblyMetadata::from_bytes`, and is generated without generating any
+    /// machine code. Returns the path of the written metadata file.
+    pub fn write_metadata(&self, file_id: FileId) -> Result<PathBuf, anyhow::Error> {
+        let _lock = self.acquire_filesystem_output_lock();
+
+        let module_partition = self.db.module_partition();
+        let module_group_id = module_partition
+            .group_for_file(file_id)
+            .ok_or_else(|| anyhow::anyhow!("could not find file in module partition"))?;
+
+        self.write_module_group_metadata(&module_partition[module_group_id])
+    }
+
+    /// Generates the metadata for the specified module and stores it in the
+    /// output location.
+    fn write_assembly_metadata(&mut self, module: mun_hir::Module) -> Result<(), anyhow::Error> {
+        log::trace!("writing assembly metadata for {:?}", module);
+
+        // Find the module group to which the module belongs
+        let module_partition = self.db.module_partition();
+        let module_group_id = module_partition
+            .group_for_module(module)
+            .expect("could not find the module in the module partition");
+
+        self.write_module_group_metadata(&module_partition[module_group_id])?;
+
+        Ok(())
+    }
+
+    /// Writes the metadata of the specified module group to the output
+    /// location and returns the path of the written file.
+    fn write_module_group_metadata(
+        &self,
+        module_group: &ModuleGroup,
+    ) -> Result<PathBuf, anyhow::Error> {
+        let metadata_path = self
+            .path_for_module_group(module_group)
+            .with_extension(METADATA_EXTENSION);
+        std::fs::write(
+            &metadata_path,
+            generate_metadata(&self.db, module_group).to_bytes(),
+        )?;
+
+        Ok(metadata_path)
+    }
+
     /// Generates IR for the specified module and stores it in the output
     /// location.
     fn write_assembly_ir(&mut self, module: mun_hir::Module) -> Result<(), anyhow::Error> {
@@ -467,6 +876,7 @@ impl Driver {
             .get(path.as_ref())
             .expect("writing to a file that is not part of the source root should never happen");
         self.db.set_file_text(file_id, Arc::from(contents));
+        self.db.set_incremental_parse(file_id, None);
         file_id
     }
 
@@ -476,6 +886,7 @@ impl Driver {
 
         // Insert the new file
         self.db.set_file_text(file_id, Arc::from(contents));
+        self.db.set_incremental_parse(file_id, None);
         self.db.set_file_source_root(file_id, WORKSPACE);
 
         // Update the source root
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-pub use mun_codegen::OptimizationLevel;
+pub use mun_codegen::{OptimizationLevel, OverflowBehavior};
 use mun_target::spec::Target;
 
 /// Describes all the permanent settings that are used during compilations.
@@ -12,12 +12,45 @@ pub struct Config {
     /// The optimization level to use for the IR generation.
     pub optimization_lvl: OptimizationLevel,
 
+    /// The behavior of integer arithmetic that overflows.
+    pub overflow_behavior: OverflowBehavior,
+
+    /// Whether or not to compile all modules into a single assembly. By
+    /// default every module is compiled into its own assembly.
+    pub single_assembly: bool,
+
     /// The optional output directory to store all outputs. If no directory is
     /// specified all output is stored in a temporary directory.
     pub out_dir: Option<PathBuf>,
 
     /// Whether or not to emit an IR file instead of a munlib.
     pub emit_ir: bool,
+
+    /// Whether or not to only emit a metadata file that describes the
+    /// signatures of the functions and structs, instead of a munlib.
+    pub emit_metadata: bool,
+
+    /// The features that are enabled for `#[cfg(feature = "...")]` attributes.
+    pub features: Vec<String>,
+
+    /// The maximum depth of nested expressions. Deeper nested expressions are
+    /// reported as an error.
+    pub nesting_limit: usize,
+
+    /// Whether or not to compile the sources as scripts. A script can contain
+    /// statements after its declarations, which are wrapped in an implicit
+    /// `pub fn main`.
+    pub script: bool,
+
+    /// Whether or not to treat warnings as errors. If enabled, any warning
+    /// causes the compilation to fail.
+    pub deny_warnings: bool,
+
+    /// Whether or not to compile the built-in prelude together with the
+    /// sources. The prelude provides common functions, e.g. `math::sqrt`, that
+    /// are available in every module without a `use`. It occupies the
+    /// `prelude` module of the package.
+    pub prelude: bool,
 }
 
 impl Default for Config {
@@ -28,8 +61,16 @@ impl Default for Config {
             // triple.
             target: target.unwrap(),
             optimization_lvl: OptimizationLevel::Default,
+            overflow_behavior: OverflowBehavior::default(),
+            single_assembly: false,
             out_dir: None,
             emit_ir: false,
+            emit_metadata: false,
+            features: Vec::new(),
+            nesting_limit: mun_syntax::DEFAULT_NESTING_LIMIT,
+            script: false,
+            deny_warnings: false,
+            prelude: false,
         }
     }
 }
@@ -0,0 +1,781 @@
+use std::time::Duration;
+
+use mun_hir::AstDatabase;
+use mun_syntax::{ast, AstNode, TextRange};
+use object::{Object, ObjectSection, ObjectSymbol};
+
+use crate::{
+    Config, Diagnostic, DisplayColor, Driver, PathOrInline, RelativePathBuf, Severity,
+    TargetAssembly, METADATA_EXTENSION,
+};
+
+#[test]
+fn whitespace_edit_does_not_recompute_type_inference() {
+    let (mut driver, _) = Driver::with_file(
+        Config::default(),
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: r#"
+    pub fn foo() -> i32 { 1 }
+
+    pub fn bar(a: i32) -> i32 { a + 2 }
+    "#
+            .to_owned(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        driver
+            .emit_diagnostics_to_string(DisplayColor::Disable)
+            .unwrap(),
+        None
+    );
+    let stats = driver.query_stats();
+    assert!(stats.was_executed("infer"), "{stats:#?}");
+
+    driver
+        .set_file_text(
+            "main.mun",
+            r#"
+    pub fn foo() -> i32 { 1 }
+
+    pub fn bar(a: i32) -> i32 {
+        a   +   2
+    }
+    "#,
+        )
+        .unwrap();
+
+    assert_eq!(
+        driver
+            .emit_diagnostics_to_string(DisplayColor::Disable)
+            .unwrap(),
+        None
+    );
+    let stats = driver.query_stats();
+    assert!(stats.was_executed("parse"), "{stats:#?}");
+    assert!(!stats.was_executed("infer"), "{stats:#?}");
+    assert!(stats.cached > 0, "{stats:#?}");
+}
+
+#[test]
+fn structured_diagnostics() {
+    let (driver, file_id) = Driver::with_file(
+        Config::default(),
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: "fn main() { let _a: i32 = b; }".to_owned(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        driver.diagnostics(file_id),
+        vec![Diagnostic {
+            file_id,
+            range: TextRange::new(26.into(), 27.into()),
+            severity: Severity::Error,
+            code: "UnresolvedValue",
+            message: String::from("cannot find value `b` in this scope"),
+        }]
+    );
+}
+
+#[test]
+fn unreachable_code_warning() {
+    let (driver, file_id) = Driver::with_file(
+        Config::default(),
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: "pub fn main() -> i32 { return 5; let _a = 3; }".to_owned(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        driver.diagnostics(file_id),
+        vec![Diagnostic {
+            file_id,
+            range: TextRange::new(33.into(), 44.into()),
+            severity: Severity::Warning,
+            code: "UnreachableCode",
+            message: String::from("unreachable code"),
+        }]
+    );
+
+    // Warnings do not cause compilation to fail
+    let mut output = Vec::new();
+    assert!(!driver
+        .emit_diagnostics(&mut output, DisplayColor::Disable)
+        .unwrap());
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("unreachable code"));
+}
+
+#[test]
+fn deny_warnings() {
+    let compile = |deny_warnings| {
+        let config = Config {
+            deny_warnings,
+            ..Config::default()
+        };
+        let (driver, file_id) = Driver::with_file(
+            config,
+            PathOrInline::Inline {
+                rel_path: RelativePathBuf::from("main.mun"),
+                contents: "pub fn main() -> i32 { return 5; let _a = 3; }".to_owned(),
+            },
+        )
+        .unwrap();
+        let has_errors = driver
+            .emit_diagnostics(&mut Vec::new(), DisplayColor::Disable)
+            .unwrap();
+        let severities: Vec<_> = driver
+            .diagnostics(file_id)
+            .into_iter()
+            .map(|diagnostic| diagnostic.severity)
+            .collect();
+        (has_errors, severities)
+    };
+
+    assert_eq!(compile(false), (false, vec![Severity::Warning]));
+    assert_eq!(compile(true), (true, vec![Severity::Error]));
+}
+
+#[test]
+fn recursive_structs() {
+    let compile = |contents: &str| {
+        let out_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            out_dir: Some(out_dir.path().to_path_buf()),
+            ..Config::default()
+        };
+        let (mut driver, file_id) = Driver::with_file(
+            config,
+            PathOrInline::Inline {
+                rel_path: RelativePathBuf::from("mod.mun"),
+                contents: contents.to_owned(),
+            },
+        )
+        .unwrap();
+        let has_errors = driver
+            .emit_diagnostics(&mut Vec::new(), DisplayColor::Disable)
+            .unwrap();
+        if !has_errors {
+            driver.write_all_assemblies(true).unwrap();
+            assert!(driver.assembly_output_path_from_file(file_id).is_file());
+        }
+        has_errors
+    };
+
+    assert!(compile("struct(value) A { b: A }"));
+    assert!(!compile("pub struct(gc) A { b: A }"));
+}
+
+#[test]
+fn write_llvm_ir() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let config = Config {
+        out_dir: Some(out_dir.path().to_path_buf()),
+        ..Config::default()
+    };
+    let (driver, file_id) = Driver::with_file(
+        config,
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: r#"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+
+    pub fn sub(a: i32, b: i32) -> i32 { a - b }
+    "#
+            .to_owned(),
+        },
+    )
+    .unwrap();
+
+    let ir_path = driver.write_llvm_ir(file_id).unwrap();
+    assert_eq!(ir_path, driver.ir_output_path_from_file(file_id));
+
+    let ir = std::fs::read_to_string(ir_path).unwrap();
+    assert!(ir.contains("@add("), "{ir}");
+    assert!(ir.contains("@sub("), "{ir}");
+}
+
+#[test]
+fn write_object() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let config = Config {
+        out_dir: Some(out_dir.path().to_path_buf()),
+        ..Config::default()
+    };
+    let (driver, file_id) = Driver::with_file(
+        config,
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: r#"
+    #[export]
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+
+    pub fn sub(a: i32, b: i32) -> i32 { a - b }
+    "#
+            .to_owned(),
+        },
+    )
+    .unwrap();
+
+    let object_path = driver.write_object(file_id).unwrap();
+    assert_eq!(object_path.extension().unwrap(), "o");
+
+    let data = std::fs::read(object_path).unwrap();
+    let object = object::File::parse(&*data).unwrap();
+    let is_defined_global = |name: &str| {
+        object.symbols().any(|symbol| {
+            // Some targets (e.g. macOS) prefix symbols with an underscore
+            let symbol_name = symbol.name().unwrap();
+            (symbol_name == name || symbol_name.strip_prefix('_') == Some(name))
+                && symbol.is_definition()
+                && symbol.is_global()
+        })
+    };
+
+    // Functions that are not exported are absent or local
+    assert!(is_defined_global("add"));
+    assert!(!is_defined_global("sub"));
+    assert!(is_defined_global("get_info"));
+
+    // The metadata of the assembly is stored in a dedicated section
+    let section = object
+        .section_by_name(mun_abi::METADATA_SECTION_NAME)
+        .or_else(|| object.section_by_name(mun_abi::MACHO_METADATA_SECTION_NAME))
+        .expect("missing metadata section");
+    let data = section.data().unwrap();
+    let len = data.iter().rposition(|&b| b != 0).map_or(0, |idx| idx + 1);
+    let metadata = mun_abi::AssemblyMetadata::from_bytes(&data[..len]).unwrap();
+    assert!(metadata.functions.iter().any(|f| f.name == "add"));
+}
+
+#[test]
+fn write_c_header() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let config = Config {
+        out_dir: Some(out_dir.path().to_path_buf()),
+        ..Config::default()
+    };
+    let (driver, file_id) = Driver::with_file(
+        config,
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: r#"
+    #[export]
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+
+    pub fn sub(a: i32, b: i32) -> i32 { a - b }
+    "#
+            .to_owned(),
+        },
+    )
+    .unwrap();
+
+    let header_path = driver.write_c_header(file_id).unwrap();
+    assert_eq!(header_path.extension().unwrap(), "h");
+
+    let header = std::fs::read_to_string(header_path).unwrap();
+    assert!(
+        header.contains("int32_t add(int32_t a, int32_t b);"),
+        "{header}"
+    );
+    assert!(!header.contains("sub("), "{header}");
+}
+
+#[test]
+fn struct_layout_report() {
+    let (driver, file_id) = Driver::with_file(
+        Config::default(),
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: r#"
+    struct Foo { a: i32, b: bool }
+    "#
+            .to_owned(),
+        },
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(driver.struct_layout_report(file_id), @r###"
+    struct(gc) Foo: size 8, align 4
+        a: i32: offset 0, size 4, align 4
+        b: bool: offset 4, size 1, align 1
+    "###);
+}
+
+#[test]
+fn write_metadata() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let config = Config {
+        out_dir: Some(out_dir.path().to_path_buf()),
+        emit_metadata: true,
+        ..Config::default()
+    };
+    let (mut driver, file_id) = Driver::with_file(
+        config,
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: r#"
+    pub struct(value) Vec2 { x: f32, y: f32 }
+
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    pub fn length(v: Vec2) -> f32 { v.x + v.y }
+    "#
+            .to_owned(),
+        },
+    )
+    .unwrap();
+
+    driver.write_all_assemblies(false).unwrap();
+
+    let metadata_path = driver.write_metadata(file_id).unwrap();
+    assert_eq!(metadata_path.extension().unwrap(), METADATA_EXTENSION);
+
+    let metadata =
+        mun_abi::AssemblyMetadata::from_bytes(&std::fs::read(metadata_path).unwrap()).unwrap();
+    let functions: Vec<_> = metadata
+        .functions
+        .iter()
+        .map(|f| {
+            (
+                f.name.as_str(),
+                f.arg_types.join(", "),
+                f.return_type.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        functions,
+        [
+            ("add", String::from("core::i32, core::i32"), "core::i32"),
+            ("length", String::from("Vec2"), "core::f32"),
+        ]
+    );
+    assert_eq!(metadata.structs.len(), 1);
+    assert_eq!(
+        metadata.structs[0].fields,
+        [
+            (String::from("x"), String::from("core::f32")),
+            (String::from("y"), String::from("core::f32"))
+        ]
+    );
+
+    // No machine code should have been generated
+    assert!(!driver.assembly_output_path_from_file(file_id).exists());
+}
+
+#[test]
+fn cfg_features() {
+    let source = r#"
+    #[cfg(feature = "debug")]
+    pub fn log_level() -> i32 { 2 }
+
+    #[cfg(not(feature = "debug"))]
+    pub fn log_level() -> i32 { 0 }
+
+    #[cfg(feature = "debug")]
+    pub fn dump() {}
+    "#;
+
+    let compile = |features: &[&str]| {
+        let out_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            out_dir: Some(out_dir.path().to_path_buf()),
+            features: features.iter().map(|&feature| feature.to_owned()).collect(),
+            ..Config::default()
+        };
+        let (driver, file_id) = Driver::with_file(
+            config,
+            PathOrInline::Inline {
+                rel_path: RelativePathBuf::from("mod.mun"),
+                contents: source.to_owned(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            driver
+                .emit_diagnostics_to_string(DisplayColor::Disable)
+                .unwrap(),
+            None
+        );
+        std::fs::read_to_string(driver.write_llvm_ir(file_id).unwrap()).unwrap()
+    };
+
+    let ir = compile(&[]);
+    assert!(ir.contains("ret i32 0"), "{ir}");
+    assert!(!ir.contains("@dump("), "{ir}");
+
+    let ir = compile(&["debug"]);
+    assert!(ir.contains("ret i32 2"), "{ir}");
+    assert!(ir.contains("@dump("), "{ir}");
+}
+
+#[test]
+fn export_attribute() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let config = Config {
+        out_dir: Some(out_dir.path().to_path_buf()),
+        ..Config::default()
+    };
+    let (driver, file_id) = Driver::with_file(
+        config,
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: r#"
+    #[export]
+    pub fn entry() -> i32 { helper() + 1 }
+
+    pub fn helper() -> i32 { 1 }
+    "#
+            .to_owned(),
+        },
+    )
+    .unwrap();
+
+    let ir = std::fs::read_to_string(driver.write_llvm_ir(file_id).unwrap()).unwrap();
+    let definition = |name: &str| {
+        ir.lines()
+            .find(|line| line.starts_with("define") && line.contains(&format!("@{name}(")))
+            .unwrap_or_else(|| panic!("missing definition of `{name}`:\n{ir}"))
+    };
+    assert!(!definition("entry").contains("private"), "{ir}");
+    assert!(definition("helper").contains("private"), "{ir}");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn shared_library_exports() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let config = Config {
+        out_dir: Some(out_dir.path().to_path_buf()),
+        ..Config::default()
+    };
+    let (driver, file_id) = Driver::with_file(
+        config,
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: r#"
+    #[export]
+    fn entry() -> i32 { helper() + internal() }
+
+    pub fn helper() -> i32 { 1 }
+
+    fn internal() -> i32 { 1 }
+    "#
+            .to_owned(),
+        },
+    )
+    .unwrap();
+
+    let path = driver.write_shared_library(file_id).unwrap();
+    assert_eq!(path.extension().and_then(|ext| ext.to_str()), Some("so"));
+
+    let library = unsafe { libloading::Library::new(&path) }.unwrap();
+    let has_symbol = |name: &[u8]| unsafe { library.get::<*const ()>(name) }.is_ok();
+    assert!(has_symbol(b"entry"));
+    assert!(has_symbol(b"helper"));
+    assert!(has_symbol(b"get_info"));
+    assert!(!has_symbol(b"internal"));
+}
+
+#[test]
+fn inline_attribute() {
+    let out_dir = tempfile::tempdir().unwrap();
+    let config = Config {
+        out_dir: Some(out_dir.path().to_path_buf()),
+        ..Config::default()
+    };
+    let (driver, file_id) = Driver::with_file(
+        config,
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: r#"
+    #[inline]
+    pub fn square(x: i32) -> i32 { x * x }
+
+    #[inline(never)]
+    pub fn cube(x: i32) -> i32 { x * x * x }
+
+    pub fn sum_of_squares(n: i32) -> i32 {
+        let sum = 0;
+        let i = 0;
+        while i < n {
+            sum += square(i) + cube(i);
+            i += 1;
+        }
+        sum
+    }
+    "#
+            .to_owned(),
+        },
+    )
+    .unwrap();
+
+    let ir = std::fs::read_to_string(driver.write_llvm_ir(file_id).unwrap()).unwrap();
+
+    // Returns the function attributes of the function with the specified name
+    let attributes = |name: &str| {
+        let definition = ir
+            .lines()
+            .find(|line| line.starts_with("define") && line.contains(&format!("@{name}(")))
+            .unwrap_or_else(|| panic!("missing definition of `{name}`:\n{ir}"));
+        let group = definition
+            .split_whitespace()
+            .find(|word| word.starts_with('#'))
+            .unwrap_or_else(|| panic!("`{name}` has no attributes:\n{ir}"));
+        ir.lines()
+            .find_map(|line| line.strip_prefix(&format!("attributes {group} = ")))
+            .unwrap_or_else(|| panic!("missing attribute group {group}:\n{ir}"))
+            .to_owned()
+    };
+    assert!(attributes("square").contains("inlinehint"), "{ir}");
+    assert!(attributes("cube").contains("noinline"), "{ir}");
+}
+
+#[test]
+fn with_directory() {
+    let source_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        source_dir.path().join("mod.mun"),
+        r#"
+    use package::foo::bar;
+
+    pub fn main() -> i32 { bar() }
+    pub fn baz() -> i32 { 1 }
+    "#,
+    )
+    .unwrap();
+    std::fs::write(
+        source_dir.path().join("foo.mun"),
+        r#"
+    pub fn bar() -> i32 { package::baz() + 1 }
+    "#,
+    )
+    .unwrap();
+
+    let out_dir = tempfile::tempdir().unwrap();
+    let config = Config {
+        out_dir: Some(out_dir.path().to_path_buf()),
+        ..Config::default()
+    };
+    let mut driver = Driver::with_directory(source_dir.path(), config).unwrap();
+
+    assert_eq!(
+        driver
+            .emit_diagnostics_to_string(DisplayColor::Disable)
+            .unwrap(),
+        None
+    );
+
+    driver.write_all_assemblies(false).unwrap();
+    let assembly_paths = ["mod.mun", "foo.mun"].map(|file_name| {
+        let file_id = driver.get_file_id_for_path(file_name).unwrap();
+        driver.assembly_output_path_from_file(file_id)
+    });
+    assert_eq!(assembly_paths[0], assembly_paths[1]);
+    assert!(assembly_paths[0].is_file());
+
+    let assembly_count = std::fs::read_dir(out_dir.path())
+        .unwrap()
+        .filter(|entry| {
+            entry.as_ref().unwrap().path().extension()
+                == Some(std::ffi::OsStr::new(TargetAssembly::EXTENSION))
+        })
+        .count();
+    assert_eq!(assembly_count, 1);
+}
+
+#[test]
+fn with_missing_directory() {
+    let source_dir = tempfile::tempdir().unwrap();
+    assert!(Driver::with_directory(source_dir.path().join("missing"), Config::default()).is_err());
+}
+
+#[test]
+fn nesting_limit() {
+    let (driver, file_id) = Driver::with_file(
+        Config::default(),
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: format!(
+                "pub fn main() -> i32 {{ {}1{} }}",
+                "(".repeat(10_000),
+                ")".repeat(10_000)
+            ),
+        },
+    )
+    .unwrap();
+
+    let diagnostics = driver.diagnostics(file_id);
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code == "SyntaxError"
+                && diagnostic.message == "expression is nested too deeply"),
+        "{diagnostics:?}"
+    );
+
+    let config = Config {
+        nesting_limit: 1,
+        ..Config::default()
+    };
+    let (driver, file_id) = Driver::with_file(
+        config,
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: String::from("pub fn main() -> i32 { (1) }"),
+        },
+    )
+    .unwrap();
+    assert!(!driver.diagnostics(file_id).is_empty());
+}
+
+#[test]
+fn apply_edit() {
+    let (mut driver, file_id) = Driver::with_file(
+        Config::default(),
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: "fn main() { let _a: i32 = b; }".to_owned(),
+        },
+    )
+    .unwrap();
+    assert_eq!(driver.diagnostics(file_id).len(), 1);
+
+    driver
+        .apply_edit(file_id, TextRange::new(26.into(), 27.into()), "5")
+        .unwrap();
+    assert_eq!(driver.diagnostics(file_id), vec![]);
+}
+
+#[test]
+fn apply_edit_reuses_unchanged_syntax() {
+    let (mut driver, file_id) = Driver::with_file(
+        Config::default(),
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: "fn foo() -> i32 { 1 }\nfn bar() -> i32 { 2 }".to_owned(),
+        },
+    )
+    .unwrap();
+    let functions = |driver: &Driver| {
+        driver
+            .db
+            .parse(file_id)
+            .tree()
+            .syntax()
+            .descendants()
+            .filter_map(ast::FunctionDef::cast)
+            .collect::<Vec<_>>()
+    };
+    let before = functions(&driver);
+
+    driver
+        .apply_edit(file_id, TextRange::new(18.into(), 19.into()), "3")
+        .unwrap();
+    let after = functions(&driver);
+    assert_eq!(
+        after[0].syntax().text().to_string(),
+        "fn foo() -> i32 { 3 }"
+    );
+    assert!(std::ptr::eq(
+        &*before[1].syntax().green(),
+        &*after[1].syntax().green()
+    ));
+}
+
+#[test]
+fn apply_edit_invalid_range() {
+    let (mut driver, file_id) = Driver::with_file(
+        Config::default(),
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: "fn main() { let _a = \"é\"; }".to_owned(),
+        },
+    )
+    .unwrap();
+
+    assert!(driver
+        .apply_edit(file_id, TextRange::new(25.into(), 40.into()), "")
+        .is_err());
+    assert!(driver
+        .apply_edit(file_id, TextRange::new(23.into(), 24.into()), "e")
+        .is_err());
+    assert!(driver.diagnostics(file_id).is_empty());
+}
+
+#[test]
+fn prelude() {
+    let source = r#"
+    pub fn hypot(a: f64, b: f64) -> f64 {
+        math::sqrt(a * a + b * b)
+    }
+    "#;
+    let input = || PathOrInline::Inline {
+        rel_path: RelativePathBuf::from("mod.mun"),
+        contents: source.to_owned(),
+    };
+
+    // Without the prelude, `math` is not in scope
+    let (driver, _) = Driver::with_file(Config::default(), input()).unwrap();
+    assert!(driver
+        .emit_diagnostics_to_string(DisplayColor::Disable)
+        .unwrap()
+        .is_some());
+
+    let config = Config {
+        prelude: true,
+        ..Config::default()
+    };
+    let (driver, _) = Driver::with_file(config, input()).unwrap();
+    assert_eq!(
+        driver
+            .emit_diagnostics_to_string(DisplayColor::Disable)
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn timed_compile() {
+    let (driver, file_id) = Driver::with_file(
+        Config::default(),
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: r#"
+    pub struct Vec2 {
+        x: f32,
+        y: f32,
+    }
+
+    pub fn dot(a: Vec2, b: Vec2) -> f32 {
+        a.x * b.x + a.y * b.y
+    }
+
+    pub fn fibonacci(n: i64) -> i64 {
+        if n <= 1 {
+            n
+        } else {
+            fibonacci(n - 1) + fibonacci(n - 2)
+        }
+    }
+    "#
+            .to_owned(),
+        },
+    )
+    .unwrap();
+
+    let timings = driver.timed_compile(file_id).unwrap();
+    assert!(timings.parsing > Duration::ZERO, "{timings:?}");
+    assert!(timings.name_resolution > Duration::ZERO, "{timings:?}");
+    assert!(timings.type_checking > Duration::ZERO, "{timings:?}");
+    assert!(timings.codegen > Duration::ZERO, "{timings:?}");
+}
@@ -0,0 +1,165 @@
+use mun_hir::{
+    semantics::{Definition, Semantics},
+    FileId, HasSource, HirDatabase, InFile, ModuleDef, Upcast,
+};
+use mun_syntax::{
+    ast::{self, NameOwner},
+    AstNode, SyntaxKind, TextRange, TextSize,
+};
+
+/// The location of the definition of an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NavigationTarget {
+    /// The file that contains the definition
+    pub file_id: FileId,
+
+    /// The range of the entire definition, e.g. a complete function
+    pub full_range: TextRange,
+
+    /// The range of the name of the definition, if it has one
+    pub focus_range: Option<TextRange>,
+}
+
+impl NavigationTarget {
+    /// Constructs a `NavigationTarget` for the specified node and its name.
+    fn from_named(src: InFile<impl NameOwner>) -> Self {
+        NavigationTarget {
+            file_id: src.file_id,
+            full_range: src.value.syntax().text_range(),
+            focus_range: src.value.name().map(|name| name.syntax().text_range()),
+        }
+    }
+
+    /// Constructs a `NavigationTarget` for the source of the specified
+    /// definition. Returns `None` if the definition has no source, e.g. a
+    /// builtin type.
+    pub(crate) fn from_definition(db: &dyn HirDatabase, def: Definition) -> Option<Self> {
+        let target = match def {
+            Definition::Local(local) => {
+                let src = local.source(db)?;
+                let focus_range = ast::BindPat::cast(src.value.syntax().clone())
+                    .and_then(|pat| pat.name())
+                    .map(|name| name.syntax().text_range());
+                NavigationTarget {
+                    file_id: src.file_id,
+                    full_range: src.value.syntax().text_range(),
+                    focus_range,
+                }
+            }
+            Definition::Field(field) => NavigationTarget::from_named(field.source(db.upcast())),
+            Definition::ModuleDef(ModuleDef::Function(func)) => {
+                NavigationTarget::from_named(func.source(db.upcast()))
+            }
+            Definition::ModuleDef(ModuleDef::Struct(strukt)) => {
+                NavigationTarget::from_named(strukt.source(db.upcast()))
+            }
+            Definition::ModuleDef(ModuleDef::TypeAlias(alias)) => {
+                NavigationTarget::from_named(alias.source(db.upcast()))
+            }
+            Definition::ModuleDef(ModuleDef::PrimitiveType(_) | ModuleDef::Module(_)) => {
+                return None
+            }
+        };
+        Some(target)
+    }
+}
+
+/// Returns the location of the definition of the identifier at `offset` in the
+/// specified file.
+pub(crate) fn goto_definition(
+    db: &dyn HirDatabase,
+    file_id: FileId,
+    offset: TextSize,
+) -> Option<NavigationTarget> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(file_id);
+    let token = file
+        .syntax()
+        .token_at_offset(offset)
+        .find(|token| token.kind() == SyntaxKind::IDENT)?;
+    let name_ref = ast::NameRef::cast(token.parent()?)?;
+    let def = sema.classify_name_ref(&name_ref)?;
+    NavigationTarget::from_definition(db, def)
+}
+
+#[cfg(test)]
+mod tests {
+    use mun_syntax::TextRange;
+
+    use super::NavigationTarget;
+    use crate::{Config, Driver, PathOrInline, RelativePathBuf};
+
+    /// Returns the definition of the identifier at the position marked with
+    /// `$0`, together with the text of the file without the marker.
+    fn goto_definition(text: &str) -> (Option<NavigationTarget>, String) {
+        let offset = text.find("$0").expect("missing cursor marker `$0`");
+        let text = text.replace("$0", "");
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: text.clone(),
+        };
+        let (driver, file_id) = Driver::with_file(Config::default(), input).unwrap();
+        let target = driver.goto_definition(file_id, (offset as u32).into());
+        if let Some(target) = target {
+            assert_eq!(target.file_id, file_id);
+        }
+        (target, text)
+    }
+
+    fn text_of(text: &str, range: TextRange) -> &str {
+        &text[std::ops::Range::<usize>::from(range)]
+    }
+
+    #[test]
+    fn function_call() {
+        let (target, text) = goto_definition(
+            r#"
+        fn add_one(a: i32) -> i32 { a + 1 }
+
+        fn main() {
+            let x = add_$0one(3);
+        }
+        "#,
+        );
+        let target = target.unwrap();
+        assert_eq!(
+            text_of(&text, target.full_range).trim(),
+            "fn add_one(a: i32) -> i32 { a + 1 }"
+        );
+        assert_eq!(text_of(&text, target.focus_range.unwrap()), "add_one");
+    }
+
+    #[test]
+    fn local() {
+        let (target, text) = goto_definition(
+            r#"
+        fn main(a: i32) -> i32 {
+            let b = 3;
+            a + $0b
+        }
+        "#,
+        );
+        let target = target.unwrap();
+        assert_eq!(text_of(&text, target.full_range), "b");
+        let offset = text.find("let b").unwrap() + 4;
+        assert_eq!(
+            target.focus_range,
+            Some(TextRange::at((offset as u32).into(), 1.into()))
+        );
+    }
+
+    #[test]
+    fn struct_field() {
+        let (target, text) = goto_definition(
+            r#"
+        struct Foo { bar: i32 }
+
+        fn main(foo: Foo) -> i32 {
+            foo.b$0ar
+        }
+        "#,
+        );
+        let target = target.unwrap();
+        assert_eq!(text_of(&text, target.full_range), "bar: i32");
+    }
+}
@@ -0,0 +1,160 @@
+use mun_hir::{
+    semantics::{Definition, Semantics},
+    FileId, HasSource, HirDatabase, HirDisplay, ModuleDef, Upcast,
+};
+use mun_syntax::{
+    ast::{self, AstToken, DocCommentsOwner},
+    AstNode, SyntaxKind, TextRange, TextSize,
+};
+
+/// Information about the item under the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoverInfo {
+    /// The range of the hovered identifier
+    pub range: TextRange,
+
+    /// The type of the item, e.g. the type of a local or the signature of a
+    /// function
+    pub ty: String,
+
+    /// The documentation of the item, if any
+    pub docs: Option<String>,
+}
+
+/// Computes the hover information of the identifier at `offset` in the
+/// specified file.
+pub(crate) fn hover(db: &dyn HirDatabase, file_id: FileId, offset: TextSize) -> Option<HoverInfo> {
+    let sema = Semantics::new(db);
+    let file = sema.parse(file_id);
+    let token = file
+        .syntax()
+        .token_at_offset(offset)
+        .find(|token| token.kind() == SyntaxKind::IDENT)?;
+    let parent = token.parent()?;
+
+    let def = if let Some(name) = ast::Name::cast(parent.clone()) {
+        sema.classify_name(&name)?
+    } else {
+        sema.classify_name_ref(&ast::NameRef::cast(parent)?)?
+    };
+    let (ty, docs) = match def {
+        Definition::Local(local) => (local.ty(db).display(db).to_string(), None),
+        Definition::Field(field) => (
+            field.ty(db).display(db).to_string(),
+            docs(&field.source(db.upcast()).value),
+        ),
+        Definition::ModuleDef(def) => module_def_hover(db, def)?,
+    };
+
+    Some(HoverInfo {
+        range: token.text_range(),
+        ty,
+        docs,
+    })
+}
+
+/// Returns the type and documentation of the specified item.
+fn module_def_hover(db: &dyn HirDatabase, def: ModuleDef) -> Option<(String, Option<String>)> {
+    let result = match def {
+        ModuleDef::Function(func) => (
+            func.ty(db).display(db).to_string(),
+            docs(&func.source(db.upcast()).value),
+        ),
+        ModuleDef::Struct(strukt) => (
+            strukt.ty(db).display(db).to_string(),
+            docs(&strukt.source(db.upcast()).value),
+        ),
+        ModuleDef::TypeAlias(alias) => (
+            format!(
+                "type {} = {}",
+                alias.name(db),
+                alias.target_type(db).display(db)
+            ),
+            docs(&alias.source(db.upcast()).value),
+        ),
+        ModuleDef::PrimitiveType(ty) => (ty.to_string(), None),
+        ModuleDef::Module(_) => return None,
+    };
+    Some(result)
+}
+
+/// Returns the text of the doc comments of `owner` without their prefixes, or
+/// `None` if there are none.
+fn docs(owner: &impl DocCommentsOwner) -> Option<String> {
+    let lines: Vec<String> = owner
+        .doc_comments()
+        .filter(|comment| comment.kind().doc.is_some())
+        .map(|comment| {
+            let text = &comment.text()[comment.prefix().len()..];
+            let text = text.strip_suffix("*/").unwrap_or(text);
+            text.strip_prefix(' ').unwrap_or(text).trim_end().to_owned()
+        })
+        .collect();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HoverInfo;
+    use crate::{Config, Driver, PathOrInline, RelativePathBuf};
+
+    /// Returns the hover information at the position marked with `$0`.
+    fn hover(text: &str) -> Option<HoverInfo> {
+        let offset = text.find("$0").expect("missing cursor marker `$0`");
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: text.replace("$0", ""),
+        };
+        let (driver, file_id) = Driver::with_file(Config::default(), input).unwrap();
+        driver.hover(file_id, (offset as u32).into())
+    }
+
+    #[test]
+    fn local_binding() {
+        let info = hover(
+            r#"
+        fn main() {
+            let $0x = 3;
+        }
+        "#,
+        )
+        .unwrap();
+        assert_eq!(info.ty, "i32");
+        assert_eq!(info.docs, None);
+    }
+
+    #[test]
+    fn function_call() {
+        let info = hover(
+            r#"
+        /// Adds one to `a`.
+        fn add_one(a: i32) -> i32 { a + 1 }
+
+        fn main() {
+            let x = add_$0one(3);
+        }
+        "#,
+        )
+        .unwrap();
+        assert_eq!(info.ty, "function add_one(i32) -> i32");
+        assert_eq!(info.docs.as_deref(), Some("Adds one to `a`."));
+    }
+
+    #[test]
+    fn no_identifier() {
+        assert_eq!(
+            hover(
+                r#"
+        fn main() {
+            let x = $0 3;
+        }
+        "#
+            ),
+            None
+        );
+    }
+}
@@ -1,27 +1,41 @@
 //! This library contains the code required to go from source code to binaries.
 #![allow(clippy::enum_variant_names)] // This is a HACK because we use salsa
 
+mod completion;
 mod db;
 pub mod diagnostics;
 mod diagnostics_snippets;
 mod driver;
+mod goto_definition;
+mod hover;
+mod references;
+mod rename;
+mod timings;
 
 use std::{
     ffi::OsStr,
     io::stderr,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 pub use annotate_snippets::AnnotationType;
-pub use mun_codegen::OptimizationLevel;
+pub use mun_codegen::{OptimizationLevel, OverflowBehavior, TargetAssembly};
 pub use mun_hir::FileId;
 pub use mun_paths::{RelativePath, RelativePathBuf};
 use mun_project::Package;
 pub use mun_target::spec::Target;
 
 pub use crate::{
-    db::CompilerDatabase,
-    driver::{Config, DisplayColor, Driver},
+    completion::{CompletionItem, CompletionItemKind},
+    db::{CompilerDatabase, QueryStats},
+    diagnostics::{Diagnostic, Severity},
+    driver::{Config, DisplayColor, Driver, METADATA_EXTENSION},
+    goto_definition::NavigationTarget,
+    hover::HoverInfo,
+    references::Reference,
+    rename::{RenameError, TextEdit},
+    timings::CompileTimings,
 };
 
 #[derive(Debug, Clone)]
@@ -105,6 +119,32 @@ pub fn compile_manifest(
     Ok(true)
 }
 
+/// Compiles the specified source text into an assembly. Returns all
+/// diagnostics instead if the source contains errors. Warnings do not prevent
+/// the source from being compiled.
+pub fn compile_str(text: &str) -> Result<Arc<TargetAssembly>, Vec<Diagnostic>> {
+    let (driver, file_id) = Driver::with_file(
+        Config::default(),
+        PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: text.to_owned(),
+        },
+    )
+    .expect("loading inline source text cannot fail");
+
+    let diagnostics = driver.diagnostics(file_id);
+    if diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.severity == Severity::Error)
+    {
+        return Err(diagnostics);
+    }
+
+    Ok(driver
+        .target_assembly(file_id)
+        .expect("the file is part of the module partition"))
+}
+
 /// Determines the relative path of a file to the source directory.
 pub fn compute_source_relative_path(
     source_dir: &Path,
@@ -130,7 +170,7 @@ pub fn compute_source_relative_path(
 mod test {
     use std::path::Path;
 
-    use crate::{compute_source_relative_path, is_source_file, RelativePath};
+    use crate::{compile_str, compute_source_relative_path, is_source_file, RelativePath};
 
     #[test]
     fn test_is_source_file() {
@@ -152,4 +192,30 @@ mod test {
             RelativePath::new("foo/bar/main.mun")
         );
     }
+
+    #[test]
+    fn test_compile_str() {
+        assert!(compile_str("pub fn main() -> i32 { 5 }").is_ok());
+    }
+
+    #[test]
+    fn test_compile_str_with_warnings() {
+        assert!(compile_str("pub fn main() -> i32 { return 5; 6 }").is_ok());
+    }
+
+    #[test]
+    fn test_compile_str_malformed() {
+        for text in [
+            "fn",
+            "pub fn main() -> i32 {",
+            "pub fn main() -> i32 { true }",
+            "struct Foo { a: Bar }",
+            "fn main() { let a = ((((1 + ; }",
+            "}}}}",
+            "pub fn main() -> i32 { main(1) }",
+        ] {
+            let diagnostics = compile_str(text).expect_err(text);
+            assert!(!diagnostics.is_empty(), "{text}");
+        }
+    }
 }
@@ -0,0 +1,152 @@
+use mun_hir::{
+    semantics::{Definition, Semantics},
+    FileId, HirDatabase,
+};
+use mun_syntax::{ast, AstNode, SyntaxKind, TextRange, TextSize};
+
+use crate::goto_definition::NavigationTarget;
+
+/// A range in a source file that refers to, or defines, a symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reference {
+    /// The file that contains the reference
+    pub file_id: FileId,
+
+    /// The range of the name that refers to the symbol
+    pub range: TextRange,
+}
+
+/// Returns the definition of the identifier at `offset` in the specified file.
+/// The identifier can either be a reference to a symbol or the name of its
+/// definition.
+pub(crate) fn definition_at(
+    sema: &Semantics<'_>,
+    file_id: FileId,
+    offset: TextSize,
+) -> Option<Definition> {
+    let file = sema.parse(file_id);
+    let token = file
+        .syntax()
+        .token_at_offset(offset)
+        .find(|token| token.kind() == SyntaxKind::IDENT)?;
+    let parent = token.parent()?;
+    if let Some(name) = ast::Name::cast(parent.clone()) {
+        sema.classify_name(&name)
+    } else {
+        sema.classify_name_ref(&ast::NameRef::cast(parent)?)
+    }
+}
+
+/// Returns the ranges of the name of the definition of the symbol at `offset`
+/// and of all references to it, across all packages.
+pub(crate) fn references(
+    db: &dyn HirDatabase,
+    file_id: FileId,
+    offset: TextSize,
+) -> Vec<Reference> {
+    let sema = Semantics::new(db);
+    let def = match definition_at(&sema, file_id, offset) {
+        Some(def) => def,
+        None => return Vec::new(),
+    };
+
+    let declaration = NavigationTarget::from_definition(db, def).and_then(|target| {
+        target.focus_range.map(|range| Reference {
+            file_id: target.file_id,
+            range,
+        })
+    });
+
+    let mut result: Vec<Reference> = declaration
+        .into_iter()
+        .chain(
+            sema.find_references(def)
+                .into_iter()
+                .map(|name_ref| Reference {
+                    file_id: name_ref.file_id,
+                    range: name_ref.value.syntax().text_range(),
+                }),
+        )
+        .collect();
+    result.sort_by_key(|reference| (reference.file_id, reference.range.start()));
+    result.dedup();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Config, Driver, PathOrInline, RelativePathBuf};
+
+    /// Returns the text of all references to the symbol at the position marked
+    /// with `$0`, together with their start offsets.
+    fn references(text: &str) -> Vec<(String, usize)> {
+        let offset = text.find("$0").expect("missing cursor marker `$0`");
+        let text = text.replace("$0", "");
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: text.clone(),
+        };
+        let (driver, file_id) = Driver::with_file(Config::default(), input).unwrap();
+        driver
+            .references(file_id, (offset as u32).into())
+            .into_iter()
+            .map(|reference| {
+                assert_eq!(reference.file_id, file_id);
+                let range = std::ops::Range::<usize>::from(reference.range);
+                (text[range.clone()].to_owned(), range.start)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn function_calls() {
+        let text = r#"
+        fn add_one(a: i32) -> i32 { a + 1 }
+
+        fn main() -> i32 {
+            add_$0one(3) + add_one(4)
+        }
+        "#;
+        let refs = references(text);
+        let text = text.replace("$0", "");
+        let starts: Vec<_> = text.match_indices("add_one").map(|(idx, _)| idx).collect();
+        assert_eq!(
+            refs,
+            starts
+                .into_iter()
+                .map(|start| (String::from("add_one"), start))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn local_from_definition() {
+        let refs = references(
+            r#"
+        fn main(a: i32) -> i32 {
+            let $0b = a;
+            let c = b * 2;
+            b + c
+        }
+        "#,
+        );
+        assert_eq!(refs.len(), 3);
+        assert!(refs.iter().all(|(text, _)| text == "b"));
+    }
+
+    #[test]
+    fn struct_field() {
+        let refs = references(
+            r#"
+        struct Foo { bar: i32 }
+
+        fn main(foo: Foo) -> i32 {
+            let baz = Foo { bar: 3 };
+            foo.b$0ar + baz.bar
+        }
+        "#,
+        );
+        assert_eq!(refs.len(), 4);
+        assert!(refs.iter().all(|(text, _)| text == "bar"));
+    }
+}
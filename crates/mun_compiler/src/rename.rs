@@ -0,0 +1,348 @@
+use mun_hir::{
+    semantics::{Definition, ScopeDef, Semantics},
+    FileId, HirDatabase, ModuleDef,
+};
+use mun_syntax::{ast, tokenize, AstNode, SyntaxKind, SyntaxNode, TextRange, TextSize};
+
+use crate::{goto_definition::NavigationTarget, references::definition_at};
+
+/// A replacement of a range of text in a source file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextEdit {
+    /// The file to apply the edit to
+    pub file_id: FileId,
+
+    /// The range of text to replace
+    pub range: TextRange,
+
+    /// The text to insert in place of `range`
+    pub new_text: String,
+}
+
+/// An error that can occur when renaming a symbol.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RenameError {
+    #[error("no symbol to rename at the specified position")]
+    NoSymbol,
+
+    #[error("`{0}` cannot be renamed")]
+    NotRenameable(String),
+
+    #[error("`{0}` is not a valid identifier")]
+    InvalidName(String),
+
+    #[error("renaming to `{0}` collides with an existing binding")]
+    NameCollision(String),
+}
+
+/// Computes the edits required to rename the symbol at `offset` in the
+/// specified file to `new_name`.
+pub(crate) fn rename(
+    db: &dyn HirDatabase,
+    file_id: FileId,
+    offset: TextSize,
+    new_name: &str,
+) -> Result<Vec<TextEdit>, RenameError> {
+    let sema = Semantics::new(db);
+    let def = definition_at(&sema, file_id, offset).ok_or(RenameError::NoSymbol)?;
+    if !is_identifier(new_name) {
+        return Err(RenameError::InvalidName(new_name.to_owned()));
+    }
+
+    let name = def.name(db).map(|name| name.to_string());
+    let declaration = NavigationTarget::from_definition(db, def)
+        .and_then(|target| Some((target.file_id, target.focus_range?)));
+    let (name, (declaration_file, declaration_range)) = match (name, declaration) {
+        (Some(name), Some(declaration)) => (name, declaration),
+        (name, _) => return Err(RenameError::NotRenameable(name.unwrap_or_default())),
+    };
+    if name == new_name {
+        return Ok(Vec::new());
+    }
+
+    let references = sema.find_references(def);
+
+    // Make sure that the new name doesn't collide with an existing definition,
+    // either where the symbol is defined or in any of the scopes that the
+    // symbol is referred from.
+    let collides_at_references = || {
+        references.iter().any(|name_ref| {
+            let node = name_ref.value.syntax();
+            collides_in_scope(&sema, node, node.text_range().start(), def, new_name)
+        })
+    };
+    let collides = match def {
+        Definition::Field(field) => field
+            .parent_struct(db)
+            .fields(db)
+            .into_iter()
+            .any(|field| field.name(db).to_string() == new_name),
+        Definition::ModuleDef(module_def) => {
+            let module = match module_def {
+                ModuleDef::Function(func) => func.module(db),
+                ModuleDef::Struct(strukt) => strukt.module(db),
+                ModuleDef::TypeAlias(alias) => alias.module(db),
+                ModuleDef::Module(_) | ModuleDef::PrimitiveType(_) => {
+                    return Err(RenameError::NotRenameable(name))
+                }
+            };
+            module
+                .declarations(db)
+                .into_iter()
+                .any(|decl| decl != module_def && declares(db, decl, new_name))
+                || collides_at_references()
+        }
+        Definition::Local(_) => {
+            let root = sema.parse(declaration_file);
+            let node = root
+                .syntax()
+                .token_at_offset(declaration_range.start())
+                .right_biased()
+                .and_then(|token| token.parent())
+                .unwrap_or_else(|| root.syntax().clone());
+            collides_in_scope(&sema, &node, declaration_range.start(), def, new_name)
+                || collides_at_references()
+        }
+    };
+    if collides {
+        return Err(RenameError::NameCollision(new_name.to_owned()));
+    }
+
+    let declaration = TextEdit {
+        file_id: declaration_file,
+        range: declaration_range,
+        new_text: new_name.to_owned(),
+    };
+    let mut edits: Vec<TextEdit> = std::iter::once(declaration)
+        .chain(references.iter().map(|name_ref| TextEdit {
+            file_id: name_ref.file_id,
+            range: name_ref.value.syntax().text_range(),
+            new_text: reference_text(def, &name_ref.value, new_name),
+        }))
+        .collect();
+    edits.sort_by_key(|edit| (edit.file_id, edit.range.start()));
+    edits.dedup();
+    Ok(edits)
+}
+
+/// Returns the text that replaces the reference `name_ref` when renaming `def`
+/// to `new_name`.
+///
+/// The name of a shorthand field of a struct literal, e.g. `bar` in
+/// `Foo { bar }`, refers to both a field and a local. Renaming either of them
+/// expands the shorthand so the other one keeps its name.
+fn reference_text(def: Definition, name_ref: &ast::NameRef, new_name: &str) -> String {
+    let is_shorthand = name_ref
+        .syntax()
+        .parent()
+        .and_then(ast::RecordField::cast)
+        .map_or(false, |field| field.expr().is_none());
+    match def {
+        Definition::Field(_) if is_shorthand => format!("{new_name}: {}", name_ref.text()),
+        Definition::Local(_) if is_shorthand => format!("{}: {new_name}", name_ref.text()),
+        _ => new_name.to_owned(),
+    }
+}
+
+/// Returns true if `name` is a single identifier that is not a keyword.
+fn is_identifier(name: &str) -> bool {
+    matches!(tokenize(name).as_slice(), [token] if token.kind == SyntaxKind::IDENT)
+}
+
+/// Returns true if `def` is named `name`.
+fn declares(db: &dyn HirDatabase, def: ModuleDef, name: &str) -> bool {
+    Definition::ModuleDef(def)
+        .name(db)
+        .map_or(false, |def_name| def_name.to_string() == name)
+}
+
+/// Returns true if a symbol other than `def` is visible as `name` at `offset`.
+fn collides_in_scope(
+    sema: &Semantics<'_>,
+    node: &SyntaxNode,
+    offset: TextSize,
+    def: Definition,
+    name: &str,
+) -> bool {
+    let mut collides = false;
+    sema.scope_at_offset(node, offset)
+        .visit_all_names(&mut |scope_name, scope_def| {
+            let scope_def = match scope_def {
+                ScopeDef::ModuleDef(it) => Definition::ModuleDef(it),
+                ScopeDef::Local(it) => Definition::Local(it),
+                ScopeDef::Unknown => return,
+            };
+            if scope_def != def && scope_name.to_string() == name {
+                collides = true;
+            }
+        });
+    collides
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenameError;
+    use crate::{Config, Driver, PathOrInline, RelativePathBuf};
+
+    /// Renames the symbol at the position marked with `$0` to `new_name` and
+    /// returns the resulting text.
+    fn rename(text: &str, new_name: &str) -> Result<String, RenameError> {
+        let offset = text.find("$0").expect("missing cursor marker `$0`");
+        let mut text = text.replace("$0", "");
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: text.clone(),
+        };
+        let (driver, file_id) = Driver::with_file(Config::default(), input).unwrap();
+        let edits = driver.rename(file_id, (offset as u32).into(), new_name)?;
+        for edit in edits.iter().rev() {
+            assert_eq!(edit.file_id, file_id);
+            text.replace_range(std::ops::Range::<usize>::from(edit.range), &edit.new_text);
+        }
+        Ok(text)
+    }
+
+    #[test]
+    fn function() {
+        let text = rename(
+            r#"
+        fn add_one(a: i32) -> i32 { a + 1 }
+
+        fn main() -> i32 {
+            add_$0one(3) + add_one(4)
+        }
+        "#,
+            "increment",
+        )
+        .unwrap();
+        assert_eq!(
+            text,
+            r#"
+        fn increment(a: i32) -> i32 { a + 1 }
+
+        fn main() -> i32 {
+            increment(3) + increment(4)
+        }
+        "#
+        );
+    }
+
+    #[test]
+    fn function_collision() {
+        assert_eq!(
+            rename(
+                r#"
+        fn add_one(a: i32) -> i32 { a + 1 }
+
+        fn add_two(a: i32) -> i32 { a + 2 }
+
+        fn main() -> i32 {
+            add_$0one(3) + add_one(4)
+        }
+        "#,
+                "add_two",
+            ),
+            Err(RenameError::NameCollision(String::from("add_two")))
+        );
+    }
+
+    #[test]
+    fn local_collision() {
+        assert_eq!(
+            rename(
+                r#"
+        fn main(a: i32) -> i32 {
+            let $0b = 3;
+            a + b
+        }
+        "#,
+                "a",
+            ),
+            Err(RenameError::NameCollision(String::from("a")))
+        );
+    }
+
+    #[test]
+    fn struct_field() {
+        let text = rename(
+            r#"
+        struct Foo { a: i32 }
+
+        fn main(a: i32) -> i32 {
+            let foo = Foo { a: 3 };
+            let bar = Foo { a };
+            foo.$0a + bar.a
+        }
+        "#,
+            "b",
+        )
+        .unwrap();
+        assert_eq!(
+            text,
+            r#"
+        struct Foo { b: i32 }
+
+        fn main(a: i32) -> i32 {
+            let foo = Foo { b: 3 };
+            let bar = Foo { b: a };
+            foo.b + bar.b
+        }
+        "#
+        );
+    }
+
+    #[test]
+    fn local_in_shorthand_field() {
+        let text = rename(
+            r#"
+        struct Foo { a: i32 }
+
+        fn main($0a: i32) -> Foo {
+            Foo { a }
+        }
+        "#,
+            "b",
+        )
+        .unwrap();
+        assert_eq!(
+            text,
+            r#"
+        struct Foo { a: i32 }
+
+        fn main(b: i32) -> Foo {
+            Foo { a: b }
+        }
+        "#
+        );
+    }
+
+    #[test]
+    fn struct_field_collision() {
+        assert_eq!(
+            rename(
+                r#"
+        struct Foo { a: i32, b: i32 }
+
+        fn main(foo: Foo) -> i32 {
+            foo.$0a
+        }
+        "#,
+                "b",
+            ),
+            Err(RenameError::NameCollision(String::from("b")))
+        );
+    }
+
+    #[test]
+    fn invalid_name() {
+        assert_eq!(
+            rename(
+                r#"
+        fn $0main() {}
+        "#,
+                "fn",
+            ),
+            Err(RenameError::InvalidName(String::from("fn")))
+        );
+    }
+}
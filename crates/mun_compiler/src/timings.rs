@@ -0,0 +1,89 @@
+use std::time::{Duration, Instant};
+
+use mun_codegen::CodeGenDatabase;
+use mun_hir::{AstDatabase, FileId, Module, ModuleDef, Upcast};
+
+use crate::db::CompilerDatabase;
+
+/// The time spent in each phase of the compilation of a module group.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CompileTimings {
+    /// The time spent parsing the source files of the package
+    pub parsing: Duration,
+
+    /// The time spent collecting the items of all modules of the package and
+    /// resolving their imports
+    pub name_resolution: Duration,
+
+    /// The time spent lowering and inferring the types of all function bodies
+    /// of the package
+    pub type_checking: Duration,
+
+    /// The time spent generating and linking the assembly of the module group
+    pub codegen: Duration,
+}
+
+impl CompileTimings {
+    /// Returns the total time spent in all phases.
+    pub fn total(&self) -> Duration {
+        self.parsing + self.name_resolution + self.type_checking + self.codegen
+    }
+}
+
+/// Returns the time it takes to execute `f`.
+fn time(f: impl FnOnce()) -> Duration {
+    let start = Instant::now();
+    f();
+    start.elapsed()
+}
+
+/// Compiles the module group that contains the specified file one phase at a
+/// time and measures the duration of each phase.
+pub(crate) fn timed_compile(
+    db: &CompilerDatabase,
+    file_id: FileId,
+) -> Result<CompileTimings, anyhow::Error> {
+    let module_group_id = db
+        .module_partition()
+        .group_for_file(file_id)
+        .ok_or_else(|| anyhow::anyhow!("could not find file in module partition"))?;
+    let modules = Module::from_file(db.upcast(), file_id)
+        .ok_or_else(|| anyhow::anyhow!("could not find module of file"))?
+        .package()
+        .modules(db.upcast());
+
+    let parsing = time(|| {
+        for file_id in modules
+            .iter()
+            .filter_map(|module| module.file_id(db.upcast()))
+        {
+            db.parse(file_id);
+        }
+    });
+
+    let mut declarations = Vec::new();
+    let name_resolution = time(|| {
+        for module in modules.iter() {
+            declarations.extend(module.declarations(db.upcast()));
+        }
+    });
+
+    let type_checking = time(|| {
+        for declaration in declarations {
+            if let ModuleDef::Function(func) = declaration {
+                func.infer(db.upcast());
+            }
+        }
+    });
+
+    let codegen = time(|| {
+        db.target_assembly(module_group_id);
+    });
+
+    Ok(CompileTimings {
+        parsing,
+        name_resolution,
+        type_checking,
+        codegen,
+    })
+}
@@ -0,0 +1,230 @@
+//! Attributes are metadata attached to items, e.g. `#[cfg(feature = "debug")]`.
+
+use mun_syntax::{ast, AstNode, SmolStr, SyntaxElement, SyntaxKind};
+
+use crate::cfg::CfgOptions;
+
+/// The attributes attached to an item
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attrs(Vec<Attr>);
+
+/// A single attribute, e.g. `#[inline(never)]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attr {
+    /// The name of the attribute, e.g. `inline`
+    pub name: SmolStr,
+
+    /// The arguments between the parentheses, e.g. `never`, or `None` if the
+    /// attribute has no parentheses.
+    pub args: Option<Vec<AttrArg>>,
+    /// The position of the attribute among the attributes of its item, which
+    /// maps the attribute back to its syntax.
+    pub index: usize,
+}
+
+/// An argument of an attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttrArg {
+    /// A single word, e.g. `never` in `#[inline(never)]`
+    Word(SmolStr),
+
+    /// A key-value pair, e.g. `feature = "debug"` in `#[cfg(feature =
+    /// "debug")]`
+    KeyValue { key: SmolStr, value: SmolStr },
+
+    /// A nested list of arguments, e.g. `not(feature = "debug")`
+    List { name: SmolStr, args: Vec<AttrArg> },
+}
+
+/// The inlining behavior requested with an `#[inline]` attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InlineAttr {
+    /// `#[inline]`, suggests that the function should be inlined
+    Hint,
+
+    /// `#[inline(always)]`
+    Always,
+
+    /// `#[inline(never)]`
+    Never,
+}
+
+/// The memory layout requested with a `#[repr]` attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReprAttr {
+    /// `#[repr(C)]`, fields are laid out in declaration order, compatible with
+    /// C
+    C,
+}
+
+/// The level of a lint, configured with an `#[allow(..)]`, `#[warn(..)]` or
+/// `#[deny(..)]` attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    /// The lint is not reported
+    Allow,
+
+    /// The lint is reported as a warning
+    Warn,
+
+    /// The lint is reported as an error
+    Deny,
+}
+
+impl LintLevel {
+    /// Returns the lint level that is configured by the attribute with the
+    /// specified name, or `None` if it is not a lint attribute.
+    pub fn from_attr_name(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(LintLevel::Allow),
+            "warn" => Some(LintLevel::Warn),
+            "deny" => Some(LintLevel::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// The names of the lints whose level can be configured with lint attributes
+pub const KNOWN_LINTS: &[&str] = &["unreachable_code", "unused_variables"];
+
+impl Attrs {
+    /// Lowers the attributes of the specified item.
+    pub(crate) fn from_ast(owner: &impl ast::AttrsOwner) -> Self {
+        Self(
+            owner
+                .attrs()
+                .enumerate()
+                .filter_map(|(index, attr)| Attr::from_ast(&attr, index))
+                .collect(),
+        )
+    }
+
+    /// Returns an iterator over all attributes.
+    pub fn iter(&self) -> impl Iterator<Item = &Attr> {
+        self.0.iter()
+    }
+
+    /// Returns an iterator over all attributes with the specified name.
+    pub fn by_name<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Attr> + 'a {
+        self.0.iter().filter(move |attr| attr.name == name)
+    }
+
+    /// Returns true if an attribute with the specified name is present.
+    pub fn has(&self, name: &str) -> bool {
+        self.by_name(name).next().is_some()
+    }
+
+    /// Returns the inlining behavior requested with an `#[inline]` attribute.
+    /// Returns `None` if there is no such attribute or if it is malformed.
+    pub fn inline(&self) -> Option<InlineAttr> {
+        let attr = self.by_name("inline").next()?;
+        match attr.args.as_deref() {
+            None => Some(InlineAttr::Hint),
+            Some([AttrArg::Word(arg)]) if arg == "always" => Some(InlineAttr::Always),
+            Some([AttrArg::Word(arg)]) if arg == "never" => Some(InlineAttr::Never),
+            _ => None,
+        }
+    }
+
+    /// Returns the memory layout requested with a `#[repr]` attribute.
+    /// Returns `None` if there is no such attribute or if it is malformed.
+    pub fn repr(&self) -> Option<ReprAttr> {
+        let attr = self.by_name("repr").next()?;
+        match attr.args.as_deref() {
+            Some([AttrArg::Word(arg)]) if arg == "C" => Some(ReprAttr::C),
+            _ => None,
+        }
+    }
+
+    /// Returns the level of the specified lint as configured by the lint
+    /// attributes, or `None` if none of them mention the lint. Later
+    /// attributes take precedence over earlier ones.
+    pub fn lint_level(&self, lint: &str) -> Option<LintLevel> {
+        self.0
+            .iter()
+            .filter(|attr| {
+                attr.args.iter().flatten().any(|arg| match arg {
+                    AttrArg::Word(name) => name == lint,
+                    _ => false,
+                })
+            })
+            .filter_map(|attr| LintLevel::from_attr_name(&attr.name))
+            .last()
+    }
+
+    /// Returns true if none of the `#[cfg(..)]` attributes disable the item.
+    /// Malformed predicates are ignored.
+    pub fn is_cfg_enabled(&self, cfg_options: &CfgOptions) -> bool {
+        self.by_name("cfg").all(|attr| match attr.args.as_deref() {
+            Some([predicate]) => cfg_options.check(predicate) != Some(false),
+            _ => true,
+        })
+    }
+}
+
+impl Attr {
+    /// Lowers the specified attribute, which is at position `index` among the
+    /// attributes of its item. Returns `None` if the attribute has no simple
+    /// name.
+    fn from_ast(attr: &ast::Attr, index: usize) -> Option<Self> {
+        let path = attr.path()?;
+        if path.qualifier().is_some() {
+            return None;
+        }
+
+        let name = SmolStr::from(path.segment()?.name_ref()?.text().as_str());
+        let args = attr
+            .token_tree()
+            .map(|token_tree| lower_args(token_tree.syntax().children_with_tokens()));
+
+        Some(Attr { name, args, index })
+    }
+}
+
+/// Lowers the elements of a token tree, including its delimiters, into a list
+/// of arguments. Elements that do not form a valid argument are skipped.
+fn lower_args(elements: impl Iterator<Item = SyntaxElement>) -> Vec<AttrArg> {
+    let mut elements = elements
+        .filter(|element| !element.kind().is_trivia())
+        // Skip the opening delimiter
+        .skip(1)
+        .peekable();
+
+    let mut args = Vec::new();
+    while let Some(element) = elements.next() {
+        let Some(token) = element.as_token() else {
+            continue;
+        };
+
+        let kind = token.kind();
+        if kind != SyntaxKind::IDENT && !kind.is_keyword() {
+            continue;
+        }
+
+        let name = SmolStr::from(token.text());
+        match elements.peek().map(SyntaxElement::kind) {
+            Some(SyntaxKind::EQ) => {
+                elements.next();
+                if let Some(value) = elements.next_if(|value| value.kind() == SyntaxKind::STRING) {
+                    let text = value.as_token().expect("a string is a token").text();
+                    args.push(AttrArg::KeyValue {
+                        key: name,
+                        value: text.trim_matches('"').into(),
+                    });
+                }
+            }
+            Some(SyntaxKind::TOKEN_TREE) => {
+                let token_tree = elements.next().and_then(SyntaxElement::into_node);
+                args.push(AttrArg::List {
+                    name,
+                    args: token_tree
+                        .map(|node| lower_args(node.children_with_tokens()))
+                        .unwrap_or_default(),
+                });
+            }
+            _ => args.push(AttrArg::Word(name)),
+        }
+    }
+
+    args
+}
@@ -0,0 +1,61 @@
+//! Conditional compilation of items with `#[cfg(..)]` attributes.
+
+use mun_syntax::SmolStr;
+use rustc_hash::FxHashSet;
+
+use crate::attrs::AttrArg;
+
+/// The options against which `#[cfg(..)]` predicates of a package are
+/// evaluated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CfgOptions {
+    features: FxHashSet<SmolStr>,
+}
+
+impl CfgOptions {
+    /// Enables the feature with the specified name.
+    pub fn enable_feature(&mut self, feature: impl Into<SmolStr>) {
+        self.features.insert(feature.into());
+    }
+
+    /// Returns true if the feature with the specified name is enabled.
+    pub fn is_feature_enabled(&self, feature: &str) -> bool {
+        self.features.contains(feature)
+    }
+
+    /// Evaluates a `#[cfg(..)]` predicate. The following predicates are
+    /// supported:
+    ///
+    /// * `feature = "name"`
+    /// * `not(predicate)`
+    /// * `all(predicate, ...)`
+    /// * `any(predicate, ...)`
+    ///
+    /// Returns `None` if the predicate is malformed.
+    pub(crate) fn check(&self, predicate: &AttrArg) -> Option<bool> {
+        match predicate {
+            AttrArg::KeyValue { key, value } if key == "feature" => {
+                Some(self.is_feature_enabled(value))
+            }
+            AttrArg::List { name, args } => match (name.as_str(), args.as_slice()) {
+                ("not", [predicate]) => self.check(predicate).map(|enabled| !enabled),
+                ("all", predicates) => predicates.iter().try_fold(true, |enabled, predicate| {
+                    Some(self.check(predicate)? && enabled)
+                }),
+                ("any", predicates) => predicates.iter().try_fold(false, |enabled, predicate| {
+                    Some(self.check(predicate)? || enabled)
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<S: Into<SmolStr>> FromIterator<S> for CfgOptions {
+    fn from_iter<T: IntoIterator<Item = S>>(features: T) -> Self {
+        Self {
+            features: features.into_iter().map(Into::into).collect(),
+        }
+    }
+}
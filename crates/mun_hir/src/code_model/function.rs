@@ -4,11 +4,12 @@ use mun_syntax::{ast, ast::TypeAscriptionOwner};
 
 use super::Module;
 use crate::{
+    attrs::{Attrs, InlineAttr, LintLevel},
     expr::{validator::ExprValidator, BodySourceMap},
     has_module::HasModule,
     ids::{FunctionId, Lookup},
+    name::AsName,
     name_resolution::Namespace,
-    resolve::HasResolver,
     type_ref::{LocalTypeRefId, TypeRefMap, TypeRefSourceMap},
     visibility::RawVisibility,
     Body, DefDatabase, DiagnosticSink, FileId, HasSource, HasVisibility, HirDatabase, InFile,
@@ -30,11 +31,15 @@ impl From<FunctionId> for Function {
 pub struct FunctionData {
     name: Name,
     params: Vec<LocalTypeRefId>,
+    param_names: Vec<Option<Name>>,
+    param_has_default: Vec<bool>,
     visibility: RawVisibility,
     ret_type: LocalTypeRefId,
     type_ref_map: TypeRefMap,
     type_ref_source_map: TypeRefSourceMap,
     is_extern: bool,
+    is_script_body: bool,
+    attrs: Attrs,
 }
 
 impl FunctionData {
@@ -47,10 +52,17 @@ impl FunctionData {
         let mut type_ref_builder = TypeRefMap::builder();
 
         let mut params = Vec::new();
+        let mut param_names = Vec::new();
+        let mut param_has_default = Vec::new();
         if let Some(param_list) = src.param_list() {
             for param in param_list.params() {
                 let type_ref = type_ref_builder.alloc_from_node_opt(param.ascribed_type().as_ref());
                 params.push(type_ref);
+                param_names.push(param.pat().and_then(|pat| match pat.kind() {
+                    ast::PatKind::BindPat(bind_pat) => bind_pat.name().map(|name| name.as_name()),
+                    ast::PatKind::PlaceholderPat(_) => None,
+                }));
+                param_has_default.push(param.default_value().is_some());
             }
         }
 
@@ -65,10 +77,14 @@ impl FunctionData {
         Arc::new(FunctionData {
             name: func.name.clone(),
             params,
+            param_names,
+            param_has_default,
             ret_type,
             type_ref_map,
             type_ref_source_map,
             is_extern: func.is_extern,
+            is_script_body: src.is_script_body(),
+            attrs: func.attrs.clone(),
             visibility: item_tree[func.visibility].clone(),
         })
     }
@@ -81,6 +97,18 @@ impl FunctionData {
         &self.params
     }
 
+    /// Returns the names of the parameters, or `None` for parameters that are
+    /// not bound to a name, e.g. `_`.
+    pub fn param_names(&self) -> &[Option<Name>] {
+        &self.param_names
+    }
+
+    /// Returns for every parameter whether it has a default value, in which
+    /// case it can be omitted when calling the function.
+    pub fn param_has_default(&self) -> &[bool] {
+        &self.param_has_default
+    }
+
     pub fn visibility(&self) -> &RawVisibility {
         &self.visibility
     }
@@ -89,6 +117,13 @@ impl FunctionData {
         &self.ret_type
     }
 
+    /// Returns true if this is the implicit `main` function of a script. The
+    /// return type of such a function is inferred from its body instead of
+    /// [`FunctionData::ret_type`].
+    pub fn is_script_body(&self) -> bool {
+        self.is_script_body
+    }
+
     pub fn type_ref_source_map(&self) -> &TypeRefSourceMap {
         &self.type_ref_source_map
     }
@@ -101,6 +136,11 @@ impl FunctionData {
     pub fn is_extern(&self) -> bool {
         self.is_extern
     }
+
+    /// Returns the attributes of this function.
+    pub fn attrs(&self) -> &Attrs {
+        &self.attrs
+    }
 }
 
 impl Function {
@@ -158,9 +198,7 @@ impl Function {
     }
 
     pub fn ret_type(self, db: &dyn HirDatabase) -> Ty {
-        let resolver = self.id.resolver(db.upcast());
-        let data = self.data(db.upcast());
-        Ty::from_hir(db, &resolver, &data.type_ref_map, data.ret_type).0
+        db.callable_sig(self.into()).ret().clone()
     }
 
     pub fn infer(self, db: &dyn HirDatabase) -> Arc<InferenceResult> {
@@ -171,6 +209,27 @@ impl Function {
         db.fn_data(self.id).is_extern
     }
 
+    /// Returns true if this function is marked with `#[export]`, which makes
+    /// it visible as a symbol of the linked assembly and includes it in
+    /// generated C headers.
+    pub fn is_exported(self, db: &dyn HirDatabase) -> bool {
+        db.fn_data(self.id).attrs.has("export")
+    }
+
+    /// Returns the inlining behavior requested with an `#[inline]` attribute.
+    pub fn inline_attr(self, db: &dyn HirDatabase) -> Option<InlineAttr> {
+        db.fn_data(self.id).attrs.inline()
+    }
+
+    /// Returns the level of the specified lint within this function. Lints
+    /// are reported as warnings unless a lint attribute configures otherwise.
+    pub fn lint_level(self, db: &dyn HirDatabase, lint: &str) -> LintLevel {
+        db.fn_data(self.id)
+            .attrs
+            .lint_level(lint)
+            .unwrap_or(LintLevel::Warn)
+    }
+
     pub(crate) fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
         db.body_with_source_map(self.id.into()).1
     }
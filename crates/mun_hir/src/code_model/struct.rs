@@ -9,6 +9,7 @@ use mun_syntax::{
 
 use super::Module;
 use crate::{
+    attrs::{Attrs, ReprAttr},
     has_module::HasModule,
     ids::{Lookup, StructId},
     name::AsName,
@@ -54,6 +55,11 @@ impl Field {
         self.parent.data(db.upcast()).fields[self.id].name.clone()
     }
 
+    /// Returns the struct that contains this field
+    pub fn parent_struct(self, _db: &dyn HirDatabase) -> Struct {
+        self.parent
+    }
+
     /// Returns the index of this field in the parent
     pub fn index(self, _db: &dyn HirDatabase) -> u32 {
         self.id.into_raw().into()
@@ -99,6 +105,11 @@ impl Struct {
         .collect()
     }
 
+    /// Returns the memory layout requested with a `#[repr]` attribute, if any.
+    pub fn repr(self, db: &dyn HirDatabase) -> Option<ReprAttr> {
+        self.data(db.upcast()).repr
+    }
+
     pub fn fields(self, db: &dyn HirDatabase) -> Vec<Field> {
         self.data(db.upcast())
             .fields
@@ -129,6 +140,8 @@ impl Struct {
         lower.add_diagnostics(db, self.file_id(db), data.type_ref_source_map(), sink);
         let validator = validator::StructValidator::new(self, db, self.file_id(db));
         validator.validate_privacy(sink);
+        validator.validate_recursion(sink);
+        validator.validate_duplicate_fields(sink);
     }
 }
 
@@ -179,6 +192,8 @@ pub struct StructData {
     pub fields: Arena<FieldData>,
     pub kind: StructKind,
     pub memory_kind: StructMemoryKind,
+    /// The memory layout requested with a `#[repr]` attribute
+    pub repr: Option<ReprAttr>,
     type_ref_map: TypeRefMap,
     type_ref_source_map: TypeRefSourceMap,
 }
@@ -195,6 +210,8 @@ impl StructData {
             .map(|s| s.kind())
             .unwrap_or_default();
 
+        let repr = Attrs::from_ast(&src).repr();
+
         let mut type_ref_builder = TypeRefMap::builder();
         let (fields, kind) = match src.kind() {
             ast::StructKind::Record(r) => {
@@ -232,6 +249,7 @@ impl StructData {
             fields,
             kind,
             memory_kind,
+            repr,
             type_ref_map,
             type_ref_source_map,
         })
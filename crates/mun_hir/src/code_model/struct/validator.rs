@@ -1,7 +1,17 @@
-use super::Struct;
+use mun_syntax::{
+    ast::{self, NameOwner},
+    AstPtr,
+};
+use rustc_hash::FxHashSet;
+
+use super::{Struct, StructMemoryKind};
 use crate::{
-    diagnostics::ExportedPrivate, resolve::HasResolver, visibility::RawVisibility, DiagnosticSink,
-    FileId, HasVisibility, HirDatabase, Ty, Visibility,
+    diagnostics::{DuplicateField, ExportedPrivate, RecursiveValueType},
+    name::AsName,
+    resolve::HasResolver,
+    ty::TyKind,
+    visibility::RawVisibility,
+    DiagnosticSink, FileId, HasSource, HasVisibility, HirDatabase, Ty, Visibility,
 };
 
 #[cfg(test)]
@@ -57,4 +67,74 @@ impl<'a> StructValidator<'a> {
                 });
             });
     }
+
+    /// Validates that every field of a record struct has a unique name.
+    pub fn validate_duplicate_fields(&self, sink: &mut DiagnosticSink<'_>) {
+        let src = self.strukt.source(self.db.upcast());
+        let ast::StructKind::Record(fields) = src.value.kind() else {
+            return;
+        };
+
+        let mut names = FxHashSet::default();
+        for field in fields.fields() {
+            let Some(name) = field.name().map(|name| name.as_name()) else {
+                continue;
+            };
+
+            if !names.insert(name.clone()) {
+                sink.push(DuplicateField {
+                    file: self.file_id,
+                    name: name.to_string(),
+                    definition: AstPtr::new(&field),
+                });
+            }
+        }
+    }
+
+    /// Validates that a value struct does not contain itself, either directly
+    /// or through the fields of other value structs. The fields of a `gc`
+    /// struct are stored as references, so recursion through a `gc` struct is
+    /// allowed.
+    pub fn validate_recursion(&self, sink: &mut DiagnosticSink<'_>) {
+        let struct_data = self.strukt.data(self.db.upcast());
+        if struct_data.memory_kind != StructMemoryKind::Value {
+            return;
+        }
+
+        let lower = self.strukt.lower(self.db);
+        let mut visited = FxHashSet::default();
+        for (_, field_data) in struct_data.fields.iter() {
+            let field_ty = &lower[field_data.type_ref];
+            if self.contains_by_value(field_ty, &mut visited) {
+                sink.push(RecursiveValueType {
+                    file: self.file_id,
+                    type_ref: struct_data
+                        .type_ref_source_map()
+                        .type_ref_syntax(field_data.type_ref)
+                        .unwrap(),
+                });
+            }
+        }
+    }
+
+    /// Returns true if `ty` stores an instance of the validated struct inline.
+    fn contains_by_value(&self, ty: &Ty, visited: &mut FxHashSet<Struct>) -> bool {
+        let strukt = match ty.interned() {
+            TyKind::Struct(s) => *s,
+            _ => return false,
+        };
+
+        if strukt.data(self.db.upcast()).memory_kind != StructMemoryKind::Value {
+            return false;
+        } else if strukt == self.strukt {
+            return true;
+        } else if !visited.insert(strukt) {
+            return false;
+        }
+
+        strukt
+            .fields(self.db)
+            .into_iter()
+            .any(|field| self.contains_by_value(&field.ty(self.db), visited))
+    }
 }
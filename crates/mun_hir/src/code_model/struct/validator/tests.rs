@@ -40,3 +40,45 @@ fn test_private_leak_struct_fields() {
     394..397: can't leak private type
     "###);
 }
+
+#[test]
+fn test_recursive_value_struct() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+
+    struct(value) Foo {
+        a: i32,
+        foo: Foo,
+    }
+
+    struct(value) Bar(Baz);
+    struct(value) Baz(Bar);
+
+    // valid, recursion goes through a gc struct
+    struct(gc) Node {
+        value: i32,
+        next: Node,
+    }
+
+    struct(value) Wrapper(Node);
+    struct(gc) Qux(Wrapper);
+    "#),
+    @r###"
+    55..58: recursive value type has infinite size
+    89..92: recursive value type has infinite size
+    117..120: recursive value type has infinite size
+    "###);
+}
+
+#[test]
+fn test_duplicate_field() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    struct Foo {
+        a: i32,
+        b: f64,
+        a: u8,
+    }
+    "#),
+    @"41..46: field `a` is already declared");
+}
@@ -3,7 +3,7 @@
 use std::sync::Arc;
 
 use mun_paths::RelativePathBuf;
-use mun_syntax::{ast, Parse, SourceFile};
+use mun_syntax::{ast, AstNode, Parse, SourceFile};
 use mun_target::{abi, spec::Target};
 
 use crate::{
@@ -65,6 +65,22 @@ pub trait SourceDatabase: salsa::Database {
 /// `SourceDatabase` into an Abstract Syntax Tree (AST).
 #[salsa::query_group(AstDatabaseStorage)]
 pub trait AstDatabase: SourceDatabase {
+    /// Returns the maximum depth of nested expressions the parser accepts
+    #[salsa::input]
+    fn nesting_limit(&self) -> usize;
+
+    /// Returns true if files are parsed as scripts, which allows top-level
+    /// statements. These statements form the body of an implicit `main`
+    /// function.
+    #[salsa::input]
+    fn script_mode(&self) -> bool;
+
+    /// Returns the syntax tree that was produced by incrementally reparsing the
+    /// file after its last edit, if any. The tree is only used if its text
+    /// matches the contents of the file.
+    #[salsa::input]
+    fn incremental_parse(&self, file_id: FileId) -> Option<Parse<ast::SourceFile>>;
+
     /// Parses the file into the syntax tree.
     #[salsa::invoke(parse_query)]
     fn parse(&self, file_id: FileId) -> Parse<ast::SourceFile>;
@@ -160,8 +176,18 @@ pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> {
 }
 
 fn parse_query(db: &dyn AstDatabase, file_id: FileId) -> Parse<SourceFile> {
+    let _span = tracing::info_span!("parse", file_id = file_id.0).entered();
     let text = db.file_text(file_id);
-    SourceFile::parse(&text)
+    if let Some(parse) = db.incremental_parse(file_id) {
+        if parse.tree().syntax().text() == text.as_ref() {
+            return parse;
+        }
+    }
+    if db.script_mode() {
+        SourceFile::parse_script(&text, db.nesting_limit())
+    } else {
+        SourceFile::parse_with_nesting_limit(&text, db.nesting_limit())
+    }
 }
 
 fn line_index_query(db: &dyn SourceDatabase, file_id: FileId) -> Arc<LineIndex> {
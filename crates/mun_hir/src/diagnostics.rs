@@ -4,6 +4,16 @@ use mun_syntax::{ast, AstPtr, SmolStr, SyntaxNode, SyntaxNodePtr, TextRange};
 
 use crate::{code_model::StructKind, in_file::InFile, FileId, HirDatabase, IntTy, Name, Ty};
 
+/// The severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// The code cannot be compiled
+    Error,
+
+    /// The code can be compiled but likely contains a mistake
+    Warning,
+}
+
 /// Diagnostic defines `mun_hir` API for errors and warnings.
 ///
 /// It is used as a `dyn` object, which you can downcast to concrete
@@ -15,8 +25,18 @@ use crate::{code_model::StructKind, in_file::InFile, FileId, HirDatabase, IntTy,
 /// not include source locations. Such internal diagnostics are transformed into
 /// an instance of `Diagnostic` on demand.
 pub trait Diagnostic: Any + Send + Sync + fmt::Debug + 'static {
+    /// Returns a short identifier of the kind of diagnostic, e.g.
+    /// `UnresolvedValue`.
+    fn code(&self) -> &'static str {
+        let type_name = std::any::type_name::<Self>();
+        type_name.rsplit("::").next().unwrap_or(type_name)
+    }
     fn message(&self) -> String;
     fn source(&self) -> InFile<SyntaxNodePtr>;
+    /// Returns the severity of the diagnostic. Most diagnostics are errors.
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
     fn highlight_range(&self) -> TextRange {
         self.source().value.range()
     }
@@ -118,6 +138,28 @@ impl Diagnostic for UnresolvedType {
     }
 }
 
+/// An error that is emitted for a tuple type other than the unit type, e.g.
+/// `(i32, bool)`.
+#[derive(Debug)]
+pub struct UnsupportedTupleType {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for UnsupportedTupleType {
+    fn message(&self) -> String {
+        "tuple types other than `()` are not supported".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct CyclicType {
     pub file: FileId,
@@ -199,6 +241,62 @@ impl Diagnostic for ExportedPrivate {
     }
 }
 
+/// An error that is emitted when a value struct contains itself, directly or
+/// through other value structs. Such a struct would have an infinite size.
+#[derive(Debug)]
+pub struct RecursiveValueType {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for RecursiveValueType {
+    fn message(&self) -> String {
+        "recursive value type has infinite size".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a field of a struct is declared more than
+/// once.
+///
+/// ```mun
+/// struct Foo {
+///     a: i32,
+///     a: f32, // Duplicate field
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DuplicateField {
+    pub file: FileId,
+    pub name: String,
+    pub definition: AstPtr<ast::RecordFieldDef>,
+}
+
+impl Diagnostic for DuplicateField {
+    fn message(&self) -> String {
+        format!("field `{}` is already declared", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.definition.syntax_node_ptr())
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct ParameterCountMismatch {
     pub file: FileId,
@@ -224,6 +322,75 @@ impl Diagnostic for ParameterCountMismatch {
     }
 }
 
+/// An error that is emitted when an argument is passed by a name that does
+/// not match any of the parameters of the called function.
+#[derive(Debug)]
+pub struct NoSuchParameter {
+    pub file: FileId,
+    pub arg: SyntaxNodePtr,
+    pub name: Name,
+}
+
+impl Diagnostic for NoSuchParameter {
+    fn message(&self) -> String {
+        format!("this function has no parameter named `{}`", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.arg.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when more than one argument is passed to the same
+/// parameter.
+#[derive(Debug)]
+pub struct DuplicateArgument {
+    pub file: FileId,
+    pub arg: SyntaxNodePtr,
+    pub name: Name,
+}
+
+impl Diagnostic for DuplicateArgument {
+    fn message(&self) -> String {
+        format!("parameter `{}` is specified more than once", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.arg.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when no argument is passed to a parameter that
+/// does not have a default value, e.g. `baz(b: 3)` for `fn baz(a: i32, b: i32)`.
+#[derive(Debug)]
+pub struct MissingArgument {
+    pub file: FileId,
+    pub call: SyntaxNodePtr,
+    pub name: Name,
+}
+
+impl Diagnostic for MissingArgument {
+    fn message(&self) -> String {
+        format!("missing argument for parameter `{}`", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.call.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct MismatchedType {
     pub file: FileId,
@@ -399,6 +566,26 @@ impl Diagnostic for ReturnMissingExpression {
     }
 }
 
+#[derive(Debug)]
+pub struct MissingReturn {
+    pub file: FileId,
+    pub body: SyntaxNodePtr,
+}
+
+impl Diagnostic for MissingReturn {
+    fn message(&self) -> String {
+        "function may reach its end without returning a value".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.body.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct BreakOutsideLoop {
     pub file: FileId,
@@ -419,6 +606,46 @@ impl Diagnostic for BreakOutsideLoop {
     }
 }
 
+#[derive(Debug)]
+pub struct NonConstArrayRepeatCount {
+    pub file: FileId,
+    pub count: SyntaxNodePtr,
+}
+
+impl Diagnostic for NonConstArrayRepeatCount {
+    fn message(&self) -> String {
+        "the count of an array repeat expression must be a constant".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.count.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidNull {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for InvalidNull {
+    fn message(&self) -> String {
+        "`null` can only be used where a gc struct is expected".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct BreakWithValueOutsideLoop {
     pub file: FileId,
@@ -427,7 +654,8 @@ pub struct BreakWithValueOutsideLoop {
 
 impl Diagnostic for BreakWithValueOutsideLoop {
     fn message(&self) -> String {
-        "`break` with value can only appear in a `loop`".to_owned()
+        "`break` with value can only appear in a `loop` or a `while` with an `else` branch"
+            .to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
@@ -749,6 +977,54 @@ impl Diagnostic for InvalidLiteral {
     }
 }
 
+/// An error that is emitted for an unknown or malformed escape sequence in a
+/// string literal (e.g. `"\q"`)
+#[derive(Debug)]
+pub struct InvalidEscape {
+    pub literal: InFile<AstPtr<ast::Literal>>,
+    pub range: TextRange,
+}
+
+impl Diagnostic for InvalidEscape {
+    fn message(&self) -> String {
+        "invalid escape sequence".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.literal.clone().map(Into::into)
+    }
+
+    fn highlight_range(&self) -> TextRange {
+        self.range
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for generic arguments in an expression, e.g.
+/// `foo::<i32>()`. Only the builtin `size_of` and `align_of` functions accept
+/// a generic argument.
+#[derive(Debug)]
+pub struct GenericArgumentsNotSupported {
+    pub expr: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for GenericArgumentsNotSupported {
+    fn message(&self) -> String {
+        "generic arguments are not supported".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.expr.clone()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct FreeTypeAliasWithoutTypeRef {
     pub type_alias_def: InFile<SyntaxNodePtr>,
@@ -864,3 +1140,129 @@ impl Diagnostic for InvalidSelfTyImpl {
         self
     }
 }
+
+/// A warning that is emitted for code that can never be executed, e.g. a
+/// statement after a `return`.
+#[derive(Debug)]
+pub struct UnreachableCode {
+    pub file: FileId,
+    pub stmt: SyntaxNodePtr,
+    pub severity: Severity,
+}
+
+impl Diagnostic for UnreachableCode {
+    fn message(&self) -> String {
+        "unreachable code".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.stmt.clone())
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A warning that is emitted for a variable that is bound by a `let` statement
+/// but never used.
+#[derive(Debug)]
+pub struct UnusedVariable {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+    pub name: Name,
+    pub severity: Severity,
+}
+
+impl Diagnostic for UnusedVariable {
+    fn message(&self) -> String {
+        format!("unused variable: `{}`", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.pat.clone())
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A warning that is emitted for a lint attribute that refers to a lint that
+/// doesn't exist, e.g. `#[allow(foo)]`.
+#[derive(Debug)]
+pub struct UnknownLint {
+    pub file: FileId,
+    pub attr: SyntaxNodePtr,
+    pub name: SmolStr,
+}
+
+impl Diagnostic for UnknownLint {
+    fn message(&self) -> String {
+        format!("unknown lint: `{}`", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.attr.clone())
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when the default value of a parameter is not a
+/// constant, e.g. `fn foo(a: i32 = bar())`.
+#[derive(Debug)]
+pub struct NonConstantDefaultValue {
+    pub file: FileId,
+    pub value: SyntaxNodePtr,
+}
+
+impl Diagnostic for NonConstantDefaultValue {
+    fn message(&self) -> String {
+        "default parameter values must be constant".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.value.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a parameter without a default value follows a
+/// parameter with one, e.g. `fn foo(a: i32 = 1, b: i32)`.
+#[derive(Debug)]
+pub struct DefaultParamNotLast {
+    pub file: FileId,
+    pub param: SyntaxNodePtr,
+}
+
+impl Diagnostic for DefaultParamNotLast {
+    fn message(&self) -> String {
+        "parameters without a default value cannot follow parameters with one".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.param.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
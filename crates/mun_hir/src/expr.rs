@@ -1,12 +1,18 @@
-use std::{borrow::Cow, ops::Index, str::FromStr, sync::Arc};
+use std::{
+    borrow::Cow,
+    iter::Peekable,
+    ops::Index,
+    str::{CharIndices, FromStr},
+    sync::Arc,
+};
 
 use either::Either;
 use la_arena::{Arena, ArenaMap, Idx};
 pub use mun_syntax::ast::PrefixOp as UnaryOp;
 use mun_syntax::{
     ast,
-    ast::{ArgListOwner, BinOp, LoopBodyOwner, NameOwner, TypeAscriptionOwner},
-    AstNode, AstPtr,
+    ast::{ArgListOwner, AstToken, BinOp, LoopBodyOwner, NameOwner, TypeAscriptionOwner},
+    AstNode, AstPtr, TextRange, TextSize,
 };
 use rustc_hash::FxHashMap;
 
@@ -31,6 +37,7 @@ pub type PatId = Idx<Pat>;
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ExprDiagnostic {
     LiteralError { expr: ExprId, err: LiteralError },
+    GenericArguments { expr: ExprId },
 }
 
 /// The body of an item (function, const etc.).
@@ -46,6 +53,9 @@ pub struct Body {
     ///
     /// If this `Body` is for the body of a constant, this will just be empty.
     params: Vec<(PatId, LocalTypeRefId)>,
+    /// The default values of the function's parameters, in the same order as
+    /// `params`.
+    param_defaults: Vec<Option<ExprId>>,
     /// The `ExprId` of the actual body expression.
     body_expr: ExprId,
     ret_type: LocalTypeRefId,
@@ -82,6 +92,12 @@ impl Body {
         &self.params
     }
 
+    /// Returns the default values of the parameters, in the same order as
+    /// [`Body::params`].
+    pub fn param_defaults(&self) -> &[Option<ExprId>] {
+        &self.param_defaults
+    }
+
     pub fn body_expr(&self) -> ExprId {
         self.body_expr
     }
@@ -222,6 +238,7 @@ pub enum Literal {
     String(String),
     Bool(bool),
     Int(LiteralInt),
+    Null,
     Float(LiteralFloat),
 }
 
@@ -243,6 +260,10 @@ pub enum LiteralError {
     /// Trying to add floating point suffix to a literal that is not a floating
     /// point number
     NonDecimalFloat(u32),
+
+    /// Encountered an unknown or malformed escape sequence in a string. The
+    /// range is relative to the start of the literal.
+    InvalidEscape(TextRange),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -278,6 +299,9 @@ pub enum Expr {
     Call {
         callee: ExprId,
         args: Vec<ExprId>,
+        /// The names of the trailing arguments in `args` that are passed by
+        /// name, e.g. `rect(1, height: 2)`.
+        named_args: Vec<Name>,
     },
     Path(Path),
     If {
@@ -314,6 +338,7 @@ pub enum Expr {
     While {
         condition: ExprId,
         body: ExprId,
+        else_branch: Option<ExprId>,
     },
     RecordLit {
         type_id: LocalTypeRefId,
@@ -325,7 +350,29 @@ pub enum Expr {
         name: Name,
     },
     Array(Vec<ExprId>),
+    /// An array that contains `count` copies of `initializer`, e.g. `[0; 5]`.
+    /// `count` must be a constant, e.g. `2 + 3` or `size_of::<Foo>()`.
+    ArrayRepeat {
+        initializer: ExprId,
+        count: ExprId,
+    },
     Literal(Literal),
+    /// A query of the memory layout of a type that is evaluated at compile
+    /// time, e.g. `size_of::<Foo>()`.
+    TypeLayout {
+        type_ref: LocalTypeRefId,
+        query: LayoutQuery,
+    },
+}
+
+/// The property of the memory layout of a type that is queried by
+/// [`Expr::TypeLayout`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LayoutQuery {
+    /// `size_of::<T>()`: the size of the type in bytes
+    Size,
+    /// `align_of::<T>()`: the alignment of the type in bytes
+    Align,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -371,7 +418,7 @@ pub enum ArithOp {
 impl Expr {
     pub fn walk_child_exprs(&self, mut f: impl FnMut(ExprId)) {
         match self {
-            Expr::Missing | Expr::Path(_) | Expr::Literal(_) => {}
+            Expr::Missing | Expr::Path(_) | Expr::Literal(_) | Expr::TypeLayout { .. } => {}
             Expr::Block { statements, tail } => {
                 for stmt in statements {
                     match stmt {
@@ -387,7 +434,7 @@ impl Expr {
                     f(*expr);
                 }
             }
-            Expr::Call { callee, args } => {
+            Expr::Call { callee, args, .. } => {
                 f(*callee);
                 for arg in args {
                     f(*arg);
@@ -419,9 +466,16 @@ impl Expr {
             Expr::Loop { body } => {
                 f(*body);
             }
-            Expr::While { condition, body } => {
+            Expr::While {
+                condition,
+                body,
+                else_branch,
+            } => {
                 f(*condition);
                 f(*body);
+                if let Some(else_expr) = else_branch {
+                    f(*else_expr);
+                }
             }
             Expr::RecordLit { fields, spread, .. } => {
                 for field in fields {
@@ -440,6 +494,10 @@ impl Expr {
                     f(*expr);
                 }
             }
+            Expr::ArrayRepeat { initializer, count } => {
+                f(*initializer);
+                f(*count);
+            }
         }
     }
 }
@@ -466,6 +524,7 @@ pub(crate) struct ExprCollector<'a> {
     pats: Arena<Pat>,
     source_map: BodySourceMap,
     params: Vec<(PatId, LocalTypeRefId)>,
+    param_defaults: Vec<Option<ExprId>>,
     body_expr: Option<ExprId>,
     ret_type: Option<LocalTypeRefId>,
     type_ref_builder: TypeRefMapBuilder,
@@ -482,6 +541,7 @@ impl<'a> ExprCollector<'a> {
             pats: Arena::default(),
             source_map: BodySourceMap::default(),
             params: Vec::new(),
+            param_defaults: Vec::new(),
             body_expr: None,
             ret_type: None,
             type_ref_builder: TypeRefMap::builder(),
@@ -536,6 +596,11 @@ impl<'a> ExprCollector<'a> {
                     .type_ref_builder
                     .alloc_from_node_opt(param.ascribed_type().as_ref());
                 self.params.push((param_pat, param_type));
+
+                let param_default = param
+                    .default_value()
+                    .map(|default_value| self.collect_expr(default_value));
+                self.param_defaults.push(param_default);
             }
         }
 
@@ -613,6 +678,7 @@ impl<'a> ExprCollector<'a> {
                     let lit = Literal::Bool(value);
                     self.alloc_expr(Expr::Literal(lit), syntax_ptr)
                 }
+                ast::LiteralKind::Null => self.alloc_expr(Expr::Literal(Literal::Null), syntax_ptr),
                 ast::LiteralKind::IntNumber(lit) => {
                     let (text, suffix) = lit.split_into_parts();
                     let (lit, errors) = integer_lit(text, suffix);
@@ -637,9 +703,16 @@ impl<'a> ExprCollector<'a> {
 
                     expr_id
                 }
-                ast::LiteralKind::String(_lit) => {
-                    let lit = Literal::String(String::default());
-                    self.alloc_expr(Expr::Literal(lit), syntax_ptr)
+                ast::LiteralKind::String(lit) => {
+                    let (lit, errors) = string_lit(lit.text());
+                    let expr_id = self.alloc_expr(Expr::Literal(lit), syntax_ptr);
+
+                    for err in errors {
+                        self.diagnostics
+                            .push(ExprDiagnostic::LiteralError { expr: expr_id, err });
+                    }
+
+                    expr_id
                 }
             },
             ast::ExprKind::PrefixExpr(e) => {
@@ -762,11 +835,20 @@ impl<'a> ExprCollector<'a> {
                 }
             }
             ast::ExprKind::PathExpr(e) => {
+                let has_type_args = e.path().map_or(false, |path| {
+                    std::iter::successors(Some(path), ast::Path::qualifier)
+                        .any(|path| path.segment().and_then(|s| s.type_arg_list()).is_some())
+                });
                 let path = e
                     .path()
                     .and_then(Path::from_ast)
                     .map_or(Expr::Missing, Expr::Path);
-                self.alloc_expr(path, syntax_ptr)
+                let expr_id = self.alloc_expr(path, syntax_ptr);
+                if has_type_args {
+                    self.diagnostics
+                        .push(ExprDiagnostic::GenericArguments { expr: expr_id });
+                }
+                expr_id
             }
             ast::ExprKind::RecordLit(e) => {
                 let type_id = self
@@ -851,18 +933,44 @@ impl<'a> ExprCollector<'a> {
                 inner
             }
             ast::ExprKind::CallExpr(e) => {
+                if let Some(expr) = self.collect_type_layout(&e, syntax_ptr) {
+                    return expr;
+                }
+
                 let callee = self.collect_expr_opt(e.expr());
-                let args = if let Some(arg_list) = e.arg_list() {
-                    arg_list.args().map(|e| self.collect_expr(e)).collect()
-                } else {
-                    Vec::new()
-                };
-                self.alloc_expr(Expr::Call { callee, args }, syntax_ptr)
-            }
-            ast::ExprKind::ArrayExpr(e) => {
-                let exprs = e.exprs().map(|expr| self.collect_expr(expr)).collect();
-                self.alloc_expr(Expr::Array(exprs), syntax_ptr)
+                let mut args = Vec::new();
+                let mut named_args = Vec::new();
+                if let Some(arg_list) = e.arg_list() {
+                    args.extend(arg_list.args().map(|e| self.collect_expr(e)));
+                    for named_arg in arg_list.named_args() {
+                        named_args.push(
+                            named_arg
+                                .name_ref()
+                                .map_or_else(Name::missing, |nr| nr.as_name()),
+                        );
+                        args.push(self.collect_expr_opt(named_arg.expr()));
+                    }
+                }
+                self.alloc_expr(
+                    Expr::Call {
+                        callee,
+                        args,
+                        named_args,
+                    },
+                    syntax_ptr,
+                )
             }
+            ast::ExprKind::ArrayExpr(e) => match e.kind() {
+                ast::ArrayExprKind::ElementList(exprs) => {
+                    let exprs = exprs.map(|expr| self.collect_expr(expr)).collect();
+                    self.alloc_expr(Expr::Array(exprs), syntax_ptr)
+                }
+                ast::ArrayExprKind::Repeat { initializer, count } => {
+                    let initializer = self.collect_expr_opt(initializer);
+                    let count = self.collect_expr_opt(count);
+                    self.alloc_expr(Expr::ArrayRepeat { initializer, count }, syntax_ptr)
+                }
+            },
             ast::ExprKind::IndexExpr(e) => {
                 let base = self.collect_expr_opt(e.base());
                 let index = self.collect_expr_opt(e.index());
@@ -871,6 +979,40 @@ impl<'a> ExprCollector<'a> {
         }
     }
 
+    /// Lowers a call to one of the builtin functions that query the memory
+    /// layout of a type, e.g. `size_of::<Foo>()`. Returns `None` if the call
+    /// is not such a builtin.
+    fn collect_type_layout(
+        &mut self,
+        e: &ast::CallExpr,
+        syntax_ptr: AstPtr<ast::Expr>,
+    ) -> Option<ExprId> {
+        let ast::ExprKind::PathExpr(callee) = e.expr()?.kind() else {
+            return None;
+        };
+        let path = callee.path()?;
+        if path.qualifier().is_some() {
+            return None;
+        }
+
+        let segment = path.segment()?;
+        let query = match segment.name_ref()?.text().as_str() {
+            "size_of" => LayoutQuery::Size,
+            "align_of" => LayoutQuery::Align,
+            _ => return None,
+        };
+
+        let mut type_args = segment.type_arg_list()?.types();
+        let type_ref = type_args.next()?;
+        let has_args = e.arg_list().map_or(false, |args| args.args().next().is_some());
+        if type_args.next().is_some() || has_args {
+            return None;
+        }
+
+        let type_ref = self.type_ref_builder.alloc_from_node(&type_ref);
+        Some(self.alloc_expr(Expr::TypeLayout { type_ref, query }, syntax_ptr))
+    }
+
     fn collect_condition_opt(&mut self, cond: Option<ast::Condition>) -> ExprId {
         if let Some(cond) = cond {
             self.collect_condition(cond)
@@ -920,7 +1062,15 @@ impl<'a> ExprCollector<'a> {
         let syntax_node_ptr = AstPtr::new(&expr.clone().into());
         let condition = self.collect_condition_opt(expr.condition());
         let body = self.collect_block_opt(expr.loop_body());
-        self.alloc_expr(Expr::While { condition, body }, syntax_node_ptr)
+        let else_branch = expr.else_branch().map(|b| self.collect_block(b));
+        self.alloc_expr(
+            Expr::While {
+                condition,
+                body,
+                else_branch,
+            },
+            syntax_node_ptr,
+        )
     }
 
     fn finish(mut self) -> (Body, BodySourceMap) {
@@ -930,6 +1080,7 @@ impl<'a> ExprCollector<'a> {
             exprs: self.exprs,
             pats: self.pats,
             params: self.params,
+            param_defaults: self.param_defaults,
             body_expr: self.body_expr.expect("A body should have been collected"),
             type_refs,
             ret_type: self
@@ -1040,17 +1191,146 @@ fn integer_lit(str: &str, suffix: Option<&str>) -> (Literal, Vec<LiteralError>)
     (Literal::Int(LiteralInt { kind, value }), errors)
 }
 
+/// Parses the given string literal, including its quotes, and decodes its
+/// escape sequences
+fn string_lit(str: &str) -> (Literal, Vec<LiteralError>) {
+    let mut chars = str.char_indices().peekable();
+    let quote = chars.next().map(|(_, c)| c);
+
+    let mut value = String::new();
+    let mut errors = Vec::new();
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            if Some(c) == quote && chars.peek().is_none() {
+                break;
+            }
+            value.push(c);
+            continue;
+        }
+
+        let escaped = match chars.next() {
+            Some((_, 'n')) => Some('\n'),
+            Some((_, 't')) => Some('\t'),
+            Some((_, 'r')) => Some('\r'),
+            Some((_, '0')) => Some('\0'),
+            Some((_, c @ ('\\' | '"' | '\''))) => Some(c),
+            Some((_, 'u')) => unicode_escape(&mut chars),
+            _ => None,
+        };
+
+        match escaped {
+            Some(c) => value.push(c),
+            None => {
+                let end = chars.peek().map_or(str.len(), |&(end, _)| end);
+                errors.push(LiteralError::InvalidEscape(TextRange::new(
+                    TextSize::from(start as u32),
+                    TextSize::from(end as u32),
+                )));
+            }
+        }
+    }
+
+    (Literal::String(value), errors)
+}
+
+/// Decodes the `{...}` part of a unicode escape sequence (e.g. `\u{1F600}`).
+/// If the sequence is invalid, the rest of it up to and including the closing
+/// `}` is consumed so it is not added to the value of the string.
+fn unicode_escape(chars: &mut Peekable<CharIndices<'_>>) -> Option<char> {
+    chars.next_if(|&(_, c)| c == '{')?;
+
+    let mut value = 0u32;
+    let mut digits = 0;
+    let mut is_valid = true;
+    loop {
+        match chars.peek() {
+            Some(&(_, '}')) => {
+                chars.next();
+                break;
+            }
+            Some(&(_, c)) if c.is_ascii_hexdigit() => {
+                value = value.saturating_mul(16).saturating_add(c.to_digit(16)?);
+                digits += 1;
+                chars.next();
+            }
+            // A quote or backslash ends an unterminated sequence
+            Some(&(_, '"' | '\'' | '\\')) | None => return None,
+            Some(_) => {
+                is_valid = false;
+                chars.next();
+            }
+        }
+    }
+
+    if is_valid && (1..=6).contains(&digits) {
+        char::from_u32(value)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use mun_syntax::TextRange;
+
     use crate::{
         expr::{
-            float_lit, integer_lit, LiteralError, LiteralFloat, LiteralFloatKind, LiteralInt,
-            LiteralIntKind,
+            float_lit, integer_lit, string_lit, LiteralError, LiteralFloat, LiteralFloatKind,
+            LiteralInt, LiteralIntKind,
         },
         primitive_type::{PrimitiveFloat, PrimitiveInt},
         Literal,
     };
 
+    #[test]
+    fn test_string_literals() {
+        assert_eq!(
+            string_lit(r#""foo""#),
+            (Literal::String(String::from("foo")), vec![])
+        );
+        assert_eq!(
+            string_lit("\"line\nbreak\"").0,
+            Literal::String(String::from("line\nbreak"))
+        );
+        assert_eq!(
+            string_lit(r#""a\nb\tc\\d\"e\u{1F600}""#),
+            (
+                Literal::String(String::from("a\nb\tc\\d\"e\u{1F600}")),
+                vec![]
+            )
+        );
+        assert_eq!(
+            string_lit(r#""a\qb""#),
+            (
+                Literal::String(String::from("ab")),
+                vec![LiteralError::InvalidEscape(TextRange::new(
+                    2.into(),
+                    4.into()
+                ))]
+            )
+        );
+        assert_eq!(
+            string_lit(r#""a\u{zz}b""#),
+            (
+                Literal::String(String::from("ab")),
+                vec![LiteralError::InvalidEscape(TextRange::new(
+                    2.into(),
+                    8.into()
+                ))]
+            )
+        );
+        assert_eq!(
+            string_lit(r#""\u{110000}""#),
+            (
+                Literal::String(String::new()),
+                vec![LiteralError::InvalidEscape(TextRange::new(
+                    1.into(),
+                    11.into()
+                ))]
+            )
+        );
+    }
+
     #[test]
     fn test_integer_literals() {
         assert_eq!(
@@ -1368,8 +1648,8 @@ mod diagnostics {
     use crate::{
         code_model::DefWithBody,
         diagnostics::{
-            DiagnosticSink, IntLiteralTooLarge, InvalidFloatingPointLiteral, InvalidLiteral,
-            InvalidLiteralSuffix,
+            DiagnosticSink, GenericArgumentsNotSupported, IntLiteralTooLarge, InvalidEscape,
+            InvalidFloatingPointLiteral, InvalidLiteral, InvalidLiteralSuffix,
         },
         HirDatabase,
     };
@@ -1414,8 +1694,24 @@ mod diagnostics {
                                 base: *base,
                             });
                         }
+                        LiteralError::InvalidEscape(range) => {
+                            let range = *range + literal.value.syntax_node_ptr().range().start();
+                            sink.push(InvalidEscape { literal, range });
+                        }
                     }
                 }
+                ExprDiagnostic::GenericArguments { expr } => {
+                    let expr = source_map
+                        .expr_syntax(*expr)
+                        .expect("could not retrieve expr from source map")
+                        .map(|expr_src| {
+                            expr_src
+                                .left()
+                                .expect("could not retrieve expr from ExprSource")
+                                .syntax_node_ptr()
+                        });
+                    sink.push(GenericArgumentsNotSupported { expr });
+                }
             }
         }
     }
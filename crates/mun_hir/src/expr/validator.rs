@@ -15,8 +15,12 @@ use crate::{
     Visibility,
 };
 
+mod lint_attrs;
 mod literal_out_of_range;
+mod param_defaults;
 mod uninitialized_access;
+mod unreachable_code;
+mod unused_variables;
 
 #[cfg(test)]
 mod tests;
@@ -42,8 +46,12 @@ impl<'a> ExprValidator<'a> {
     }
 
     pub fn validate_body(&self, sink: &mut DiagnosticSink<'_>) {
+        self.validate_lint_attrs(sink);
         self.validate_literal_ranges(sink);
+        self.validate_param_defaults(sink);
         self.validate_uninitialized_access(sink);
+        self.validate_unreachable_code(sink);
+        self.validate_unused_variables(sink);
         self.validate_extern(sink);
         self.validate_privacy(sink);
     }
@@ -0,0 +1,41 @@
+use mun_syntax::{ast::AttrsOwner, AstNode, SyntaxNodePtr};
+
+use super::ExprValidator;
+use crate::{
+    attrs::{AttrArg, LintLevel, KNOWN_LINTS},
+    code_model::src::HasSource,
+    diagnostics::{DiagnosticSink, UnknownLint},
+};
+
+impl<'d> ExprValidator<'d> {
+    /// Validates that the lint attributes of the function only refer to known
+    /// lints.
+    pub(super) fn validate_lint_attrs(&self, sink: &mut DiagnosticSink<'_>) {
+        let data = self.func.data(self.db.upcast());
+        let lint_attrs = data
+            .attrs()
+            .iter()
+            .filter(|attr| LintLevel::from_attr_name(&attr.name).is_some());
+        for attr in lint_attrs {
+            for arg in attr.args.iter().flatten() {
+                match arg {
+                    AttrArg::Word(name) if KNOWN_LINTS.contains(&name.as_str()) => {}
+                    AttrArg::Word(name) => {
+                        let src = self.func.source(self.db.upcast());
+                        let attr_node = src
+                            .value
+                            .attrs()
+                            .nth(attr.index)
+                            .expect("lowered attribute must have a syntax node");
+                        sink.push(UnknownLint {
+                            file: src.file_id,
+                            attr: SyntaxNodePtr::new(attr_node.syntax()),
+                            name: name.clone(),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
@@ -1,21 +1,46 @@
+use std::collections::HashSet;
+
 use super::ExprValidator;
 use crate::{
     diagnostics::{DiagnosticSink, LiteralOutOfRange},
     ty::{ResolveBitness, TyKind},
-    Expr, HirDisplay, Literal,
+    Expr, ExprId, HirDisplay, Literal, UnaryOp,
 };
 
 impl<'a> ExprValidator<'a> {
     /// Iterates over all expressions to determine if one of the literals has a
-    /// value that is out of range of its type.
+    /// value that is out of range of its type. A literal that is negated, e.g.
+    /// `-128_i8`, may have a value up to and including the magnitude of the
+    /// minimum value of its type.
     pub fn validate_literal_ranges(&self, sink: &mut DiagnosticSink<'_>) {
-        self.body[self.body.body_expr].walk_child_exprs(move |expr_id| {
-            let expr = &self.body[expr_id];
+        let negated_literals: HashSet<ExprId> = self
+            .body
+            .exprs()
+            .filter_map(|(_, expr)| match expr {
+                Expr::UnaryOp {
+                    expr,
+                    op: UnaryOp::Neg,
+                } => Some(*expr),
+                _ => None,
+            })
+            .collect();
+
+        for (expr_id, expr) in self.body.exprs() {
             if let Expr::Literal(Literal::Int(lit)) = &expr {
                 let ty = &self.infer[expr_id];
                 match ty.interned() {
                     TyKind::Int(int_ty) => {
-                        if lit.value > int_ty.resolve(&self.db.target_data_layout()).max() {
+                        let int_ty_resolved = int_ty.resolve(&self.db.target_data_layout());
+                        let max = if !negated_literals.contains(&expr_id) {
+                            int_ty_resolved.max()
+                        } else if int_ty.signedness.is_signed() {
+                            int_ty_resolved.max() + 1
+                        } else {
+                            // Negating an unsigned integer is already reported during type
+                            // inference.
+                            continue;
+                        };
+                        if lit.value > max {
                             let literal = self
                                 .body_source_map
                                 .expr_syntax(expr_id)
@@ -39,6 +64,6 @@ impl<'a> ExprValidator<'a> {
                     ),
                 }
             }
-        });
+        }
     }
 }
@@ -0,0 +1,54 @@
+use super::ExprValidator;
+use crate::{
+    diagnostics::{DefaultParamNotLast, DiagnosticSink, NonConstantDefaultValue},
+    Expr, ExprId, UnaryOp,
+};
+
+impl<'d> ExprValidator<'d> {
+    /// Validates that the default values of the parameters are constant and
+    /// that parameters with a default value come last. Default values are
+    /// evaluated at the call site, so only literals and negated literals are
+    /// allowed.
+    pub(super) fn validate_param_defaults(&self, sink: &mut DiagnosticSink<'_>) {
+        let params = self.body.params().iter().zip(self.body.param_defaults());
+        let mut has_default_values = false;
+        for ((pat, _), default_value) in params {
+            if default_value.is_some() {
+                has_default_values = true;
+            } else if has_default_values {
+                if let Some(src) = self.body_source_map.pat_syntax(*pat) {
+                    sink.push(DefaultParamNotLast {
+                        file: src.file_id,
+                        param: src.value.syntax_node_ptr(),
+                    });
+                }
+            }
+        }
+
+        for &default_value in self.body.param_defaults().iter().flatten() {
+            if !self.is_constant(default_value) {
+                let value = self
+                    .body_source_map
+                    .expr_syntax(default_value)
+                    .expect("could not retrieve expr from source map")
+                    .value
+                    .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                sink.push(NonConstantDefaultValue {
+                    file: self.func.file_id(self.db),
+                    value,
+                });
+            }
+        }
+    }
+
+    fn is_constant(&self, expr: ExprId) -> bool {
+        match &self.body[expr] {
+            Expr::Literal(_) => true,
+            Expr::UnaryOp {
+                expr,
+                op: UnaryOp::Neg,
+            } => matches!(self.body[*expr], Expr::Literal(_)),
+            _ => false,
+        }
+    }
+}
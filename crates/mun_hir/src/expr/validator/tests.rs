@@ -9,7 +9,10 @@ fn test_uninitialized_access() {
         let a:i64;
         let b = a + 3;
     }
-    "#), @"38..39: use of possibly-uninitialized variable"
+    "#), @r###"
+    38..39: use of possibly-uninitialized variable
+    34..35: unused variable: `b`
+    "###
     );
 }
 
@@ -47,7 +50,15 @@ fn test_uninitialized_access_if() {
         let b = a + 4;  // `a` is not initialized but this is dead code anyway
     }
     "#,
-    ), @"191..192: use of possibly-uninitialized variable");
+    ), @r###"
+    73..74: unused variable: `b`
+    191..192: use of possibly-uninitialized variable
+    187..188: unused variable: `b`
+    306..307: unused variable: `b`
+    436..437: unused variable: `b`
+    535..549: unreachable code
+    539..540: unused variable: `b`
+    "###);
 }
 
 #[test]
@@ -60,7 +71,10 @@ fn test_uninitialized_access_while() {
         let c = a + 4;  // `a` is possibly-unitialized
     }
     "#,
-    ), @"86..87: use of possibly-uninitialized variable");
+    ), @r###"
+    86..87: use of possibly-uninitialized variable
+    82..83: unused variable: `c`
+    "###);
 }
 
 #[test]
@@ -181,3 +195,137 @@ fn test_type_alias_with_private_type_alias() {
     "#,
     ), @"17..36: type alias `Foo` is private");
 }
+
+#[test]
+fn test_unreachable_code() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn foo(a: i32) -> i32 {
+        return a;
+        let b = a + 1;
+        b
+    }
+
+    fn bar() {
+        loop {
+            break;
+            bar()
+        }
+    }
+
+    fn baz(a: i32) -> i32 {
+        if a > 0 { return 1; } else { return 0; }
+        a
+    }
+
+    fn qux() {
+        loop {}
+        qux();
+    }
+    "#,
+    ), @r###"
+    42..56: unreachable code
+    111..116: unreachable code
+    200..201: unreachable code
+    232..237: unreachable code
+    "###);
+}
+
+#[test]
+fn test_invalid_string_escape() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn foo() {
+        let a = "foo\qbar\n\u{zz}";
+    }
+    "#,
+    ), @r###"
+    27..29: invalid escape sequence
+    34..40: invalid escape sequence
+    19..20: unused variable: `a`
+    "###);
+}
+
+#[test]
+fn test_generic_arguments() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn foo() -> i32 { 1 }
+    fn bar() -> i32 {
+        foo::<i32>()
+    }
+    "#,
+    ), @"44..54: generic arguments are not supported");
+}
+
+#[test]
+fn test_lint_attrs() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    #[allow(unreachable_code)]
+    fn foo() -> i32 {
+        return 1;
+        2
+    }
+
+    fn bar() -> i32 {
+        return 1;
+        2
+    }
+
+    #[deny(does_not_exist)]
+    fn baz() {}
+    "#,
+    ), @r###"
+    104..105: unreachable code
+    109..132: unknown lint: `does_not_exist`
+    "###);
+}
+
+#[test]
+fn test_unused_variables() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn foo(a: i32) -> i32 {
+        let b = a + 1;
+        let c = 2;
+        let _d = 3;
+        b
+    }
+
+    #[allow(unused_variables)]
+    fn bar() {
+        let a = 1;
+    }
+
+    #[deny(unused_variables)]
+    fn baz() {
+        let a = 1;
+    }
+    "#,
+    ), @r###"
+    51..52: unused variable: `c`
+    184..185: unused variable: `a`
+    "###);
+}
+
+#[test]
+fn test_param_defaults() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn foo(a: i32 = 1, b: f64 = -2.5, c: i32 = bar()) -> i32 { a }
+    fn bar() -> i32 { 5 }
+    fn baz(a: i32, b: i32 = 2) {}
+    fn main() {
+        foo();
+        foo(1, c: 3);
+        baz(b: 3);
+    }
+    fn qux(a: i32 = 1, b: i32) {}
+    "#,
+    ), @r###"
+    43..48: default parameter values must be constant
+    160..169: missing argument for parameter `a`
+    192..193: parameters without a default value cannot follow parameters with one
+    "###);
+}
@@ -43,7 +43,7 @@ impl<'d> ExprValidator<'d> {
     ) {
         let body = self.body.clone();
         match &body[expr] {
-            Expr::Call { callee, args } => {
+            Expr::Call { callee, args, .. } => {
                 self.validate_expr_access(sink, initialized_patterns, *callee, expr_side);
                 for arg in args.iter() {
                     self.validate_expr_access(sink, initialized_patterns, *arg, expr_side);
@@ -153,7 +153,11 @@ impl<'d> ExprValidator<'d> {
             Expr::Loop { body } => {
                 self.validate_expr_access(sink, initialized_patterns, *body, ExprKind::Normal);
             }
-            Expr::While { condition, body } => {
+            Expr::While {
+                condition,
+                body,
+                else_branch,
+            } => {
                 self.validate_expr_access(sink, initialized_patterns, *condition, ExprKind::Normal);
                 self.validate_expr_access(
                     sink,
@@ -161,6 +165,14 @@ impl<'d> ExprValidator<'d> {
                     *body,
                     ExprKind::Normal,
                 );
+                if let Some(else_branch) = else_branch {
+                    self.validate_expr_access(
+                        sink,
+                        &mut initialized_patterns.clone(),
+                        *else_branch,
+                        ExprKind::Normal,
+                    );
+                }
             }
             Expr::RecordLit { fields, spread, .. } => {
                 for field in fields.iter() {
@@ -184,7 +196,11 @@ impl<'d> ExprValidator<'d> {
                     self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
                 }
             }
-            Expr::Literal(_) | Expr::Missing => {}
+            Expr::ArrayRepeat { initializer, count } => {
+                self.validate_expr_access(sink, initialized_patterns, *initializer, ExprKind::Normal);
+                self.validate_expr_access(sink, initialized_patterns, *count, ExprKind::Normal);
+            }
+            Expr::Literal(_) | Expr::Missing | Expr::TypeLayout { .. } => {}
         }
     }
 
@@ -0,0 +1,88 @@
+use mun_syntax::{AstNode, SyntaxKind, SyntaxNodePtr};
+
+use super::ExprValidator;
+use crate::{
+    diagnostics::{DiagnosticSink, Severity, UnreachableCode},
+    Expr, ExprId, LintLevel, Statement,
+};
+
+impl<'d> ExprValidator<'d> {
+    /// Validates that no statements follow a statement that diverges within a block. A
+    /// statement diverges if its type is inferred to be `never`, e.g. a `return`, a `loop`
+    /// without a `break`, or an `if` of which all branches diverge.
+    pub(super) fn validate_unreachable_code(&self, sink: &mut DiagnosticSink<'_>) {
+        let severity = match self.func.lint_level(self.db, "unreachable_code") {
+            LintLevel::Allow => return,
+            LintLevel::Warn => Severity::Warning,
+            LintLevel::Deny => Severity::Error,
+        };
+        self.validate_unreachable_code_in_expr(sink, self.body.body_expr, severity);
+    }
+
+    fn validate_unreachable_code_in_expr(
+        &self,
+        sink: &mut DiagnosticSink<'_>,
+        expr: ExprId,
+        severity: Severity,
+    ) {
+        if let Expr::Block { statements, tail } = &self.body[expr] {
+            let diverging_stmt = statements
+                .iter()
+                .position(|stmt| self.statement_diverges(stmt));
+
+            // Only the first unreachable statement is reported
+            if let Some(idx) = diverging_stmt {
+                let unreachable = match statements.get(idx + 1) {
+                    Some(stmt) => self.statement_syntax(stmt),
+                    None => tail.and_then(|tail| self.expr_syntax(tail)),
+                };
+                if let Some(stmt) = unreachable {
+                    sink.push(UnreachableCode {
+                        file: self.func.file_id(self.db),
+                        stmt,
+                        severity,
+                    });
+                }
+            }
+        }
+
+        self.body[expr].walk_child_exprs(|child| {
+            self.validate_unreachable_code_in_expr(sink, child, severity)
+        });
+    }
+
+    /// Returns true if control flow never continues past the given statement
+    fn statement_diverges(&self, stmt: &Statement) -> bool {
+        let expr = match stmt {
+            Statement::Expr(expr) => Some(*expr),
+            Statement::Let { initializer, .. } => *initializer,
+        };
+        expr.map_or(false, |expr| self.infer[expr].is_never())
+    }
+
+    /// Returns the syntax of the given statement
+    fn statement_syntax(&self, stmt: &Statement) -> Option<SyntaxNodePtr> {
+        match stmt {
+            Statement::Expr(expr) => self.expr_syntax(*expr),
+            Statement::Let { pat, .. } => {
+                let src = self.body_source_map.pat_syntax(*pat)?;
+                let root = self.db.parse(src.file_id).syntax_node();
+                let let_stmt = src
+                    .value
+                    .to_node(&root)
+                    .syntax()
+                    .ancestors()
+                    .find(|node| node.kind() == SyntaxKind::LET_STMT)?;
+                Some(SyntaxNodePtr::new(&let_stmt))
+            }
+        }
+    }
+
+    /// Returns the syntax of the given expression
+    fn expr_syntax(&self, expr: ExprId) -> Option<SyntaxNodePtr> {
+        self.body_source_map.expr_syntax(expr).map(|src| {
+            src.value
+                .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+        })
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+
+use super::ExprValidator;
+use crate::{
+    diagnostics::{DiagnosticSink, Severity, UnusedVariable},
+    resolve::{resolver_for_expr, ValueNs},
+    Expr, LintLevel, Pat,
+};
+
+impl<'d> ExprValidator<'d> {
+    /// Validates that every variable that is bound by a `let` statement is
+    /// used. Variables whose name starts with an underscore are ignored.
+    pub(super) fn validate_unused_variables(&self, sink: &mut DiagnosticSink<'_>) {
+        let severity = match self.func.lint_level(self.db, "unused_variables") {
+            LintLevel::Allow => return,
+            LintLevel::Warn => Severity::Warning,
+            LintLevel::Deny => Severity::Error,
+        };
+
+        // Find all bindings that are referred to by a path
+        let used_patterns: HashSet<_> = self
+            .body
+            .exprs()
+            .filter_map(|(expr, data)| match data {
+                Expr::Path(path) => {
+                    let resolver = resolver_for_expr(self.db.upcast(), self.body.owner(), expr);
+                    match resolver.resolve_path_as_value_fully(self.db.upcast(), path) {
+                        Some((ValueNs::LocalBinding(pat), _)) => Some(pat),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect();
+
+        let params: HashSet<_> = self.body.params().iter().map(|(pat, _)| *pat).collect();
+        for (pat, data) in self.body.pats() {
+            let name = match data {
+                Pat::Bind { name } => name,
+                _ => continue,
+            };
+            if params.contains(&pat)
+                || used_patterns.contains(&pat)
+                || name.to_string().starts_with('_')
+            {
+                continue;
+            }
+
+            if let Some(src) = self.body_source_map.pat_syntax(pat) {
+                sink.push(UnusedVariable {
+                    file: src.file_id,
+                    pat: src.value.syntax_node_ptr(),
+                    name: name.clone(),
+                    severity,
+                });
+            }
+        }
+    }
+}
@@ -17,6 +17,7 @@ use la_arena::{Arena, Idx};
 use mun_syntax::ast;
 
 use crate::{
+    attrs::Attrs,
     path::ImportAlias,
     source_id::{AstIdNode, FileAstId},
     type_ref::{LocalTypeRefId, TypeRefMap},
@@ -302,6 +303,7 @@ pub struct Function {
     pub name: Name,
     pub visibility: RawVisibilityId,
     pub is_extern: bool,
+    pub attrs: Attrs,
     pub types: TypeRefMap,
     pub params: IdRange<Param>,
     pub ret_type: LocalTypeRefId,
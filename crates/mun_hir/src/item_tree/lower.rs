@@ -14,8 +14,10 @@ use super::{
     Struct, TypeAlias,
 };
 use crate::{
+    attrs::Attrs,
+    cfg::CfgOptions,
     item_tree::Import,
-    name::AsName,
+    name::{name, AsName},
     source_id::AstIdMap,
     type_ref::{TypeRefMap, TypeRefMapBuilder},
     visibility::RawVisibility,
@@ -44,6 +46,7 @@ impl<N: ItemTreeNode> From<Idx<N>> for LocalItemTreeId<N> {
 
 pub(super) struct Context {
     file: FileId,
+    cfg_options: CfgOptions,
     source_ast_id_map: Arc<AstIdMap>,
     data: ItemTreeData,
     diagnostics: Vec<diagnostics::ItemTreeDiagnostic>,
@@ -52,8 +55,19 @@ pub(super) struct Context {
 impl Context {
     /// Constructs a new `Context` for the specified file
     pub(super) fn new(db: &dyn DefDatabase, file: FileId) -> Self {
+        // Items are evaluated against the options of the package that contains
+        // the file
+        let source_root = db.file_source_root(file);
+        let packages = db.packages();
+        let cfg_options = packages
+            .iter()
+            .find(|&package| packages[package].source_root == source_root)
+            .map(|package| packages[package].cfg_options.clone())
+            .unwrap_or_default();
+
         Self {
             file,
+            cfg_options,
             source_ast_id_map: db.ast_id_map(file),
             data: ItemTreeData::default(),
             diagnostics: Vec::new(),
@@ -113,6 +127,10 @@ impl Context {
 
     /// Lowers a single module item
     fn lower_mod_item(&mut self, item: &ast::ModuleItem) -> Option<ModItems> {
+        if !self.is_cfg_enabled(item) {
+            return None;
+        }
+
         match item.kind() {
             ast::ModuleItemKind::FunctionDef(ast) => self.lower_function(&ast).map(Into::into),
             ast::ModuleItemKind::StructDef(ast) => self.lower_struct(&ast).map(Into::into),
@@ -152,8 +170,12 @@ impl Context {
 
     /// Lowers a function
     fn lower_function(&mut self, func: &ast::FunctionDef) -> Option<LocalItemTreeId<Function>> {
-        let name = func.name()?.as_name();
-        let visibility = lower_visibility(func);
+        // The top-level statements of a script form an implicit `pub fn main`
+        let (name, visibility) = if func.is_script_body() {
+            (name![main], RawVisibilityId::PUB)
+        } else {
+            (func.name()?.as_name(), lower_visibility(func))
+        };
         let mut types = TypeRefMap::builder();
 
         // Lower all the params
@@ -178,6 +200,7 @@ impl Context {
         };
 
         let is_extern = func.is_extern();
+        let attrs = Attrs::from_ast(func);
 
         let (types, _types_source_map) = types.finish();
         let ast_id = self.source_ast_id_map.ast_id(func);
@@ -185,6 +208,7 @@ impl Context {
             name,
             visibility,
             is_extern,
+            attrs,
             types,
             params,
             ret_type,
@@ -309,12 +333,22 @@ impl Context {
     }
 
     fn lower_associated_item(&mut self, item: &ast::AssociatedItem) -> Option<AssociatedItem> {
+        if !self.is_cfg_enabled(item) {
+            return None;
+        }
+
         let item: AssociatedItem = match item.kind() {
             ast::AssociatedItemKind::FunctionDef(ast) => self.lower_function(&ast).map(Into::into),
         }?;
         Some(item)
     }
 
+    /// Returns true if the `#[cfg(..)]` attributes of the specified item
+    /// enable it for the package that contains this file.
+    fn is_cfg_enabled(&self, item: &impl ast::AttrsOwner) -> bool {
+        Attrs::from_ast(item).is_cfg_enabled(&self.cfg_options)
+    }
+
     /// Returns the `Idx` of the next `Field`
     fn next_field_idx(&self) -> Idx<Field> {
         let idx: u32 = self.data.fields.len().try_into().expect("too many fields");
@@ -168,6 +168,7 @@ impl Printer<'_> {
             name,
             visibility,
             is_extern,
+            attrs: _,
             types,
             params,
             ret_type,
@@ -79,6 +79,26 @@ fn test_impls() {
     .unwrap());
 }
 
+#[test]
+fn test_cfg() {
+    insta::assert_snapshot!(print_item_tree(
+        r#"
+    #[cfg(feature = "debug")]
+    fn foo() -> i32 {}
+    #[cfg(not(feature = "debug"))]
+    fn foo() -> i64 {}
+    #[cfg(any(feature = "debug", not(feature = "trace")))]
+    struct Bar {}
+    impl Baz {
+        #[cfg(all(feature = "debug", feature = "trace"))]
+        fn baz() {}
+        fn qux() {}
+    }
+    "#
+    )
+    .unwrap());
+}
+
 #[test]
 fn test_duplicate_import() {
     insta::assert_snapshot!(print_item_tree(
@@ -13,6 +13,8 @@ pub use self::code_model::{
     TypeAlias,
 };
 pub use crate::{
+    attrs::{Attr, AttrArg, Attrs, InlineAttr, LintLevel, ReprAttr, KNOWN_LINTS},
+    cfg::CfgOptions,
     db::{
         AstDatabase, AstDatabaseStorage, DefDatabase, DefDatabaseStorage, HirDatabase,
         HirDatabaseStorage, InternDatabase, InternDatabaseStorage, SourceDatabase,
@@ -21,8 +23,8 @@ pub use crate::{
     diagnostics::{Diagnostic, DiagnosticSink},
     display::HirDisplay,
     expr::{
-        ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, ExprScopes, Literal, LogicOp, Ordering, Pat,
-        PatId, RecordLitField, Statement, UnaryOp,
+        ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, ExprScopes, LayoutQuery, Literal, LogicOp,
+        Ordering, Pat, PatId, RecordLitField, Statement, UnaryOp,
     },
     ids::{ItemLoc, ModuleId},
     in_file::InFile,
@@ -31,7 +33,7 @@ pub use crate::{
     name_resolution::PerNs,
     package_set::{PackageId, PackageSet},
     path::{Path, PathKind},
-    primitive_type::{FloatBitness, IntBitness, Signedness},
+    primitive_type::{FloatBitness, IntBitness, PrimitiveType, Signedness},
     resolve::{resolver_for_expr, resolver_for_scope, Resolver, TypeNs, ValueNs},
     ty::{
         lower::CallableDef, FloatTy, InferenceResult, IntTy, ResolveBitness, Substitution, Ty,
@@ -43,6 +45,8 @@ use crate::{name::AsName, source_id::AstIdMap};
 
 #[macro_use]
 mod macros;
+mod attrs;
+mod cfg;
 mod code_model;
 mod db;
 pub mod diagnostics;
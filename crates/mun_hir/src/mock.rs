@@ -56,6 +56,8 @@ impl Default for MockDatabase {
             events: Mutex::default(),
         };
         db.set_target(Target::host_target().unwrap());
+        db.set_nesting_limit(mun_syntax::DEFAULT_NESTING_LIMIT);
+        db.set_script_mode(false);
         db
     }
 }
@@ -63,7 +63,9 @@ impl PackageDefs {
         (res.resolved_def, res.segment_index)
     }
 
-    /// Resolves the specified `name` from within the specified `module`
+    /// Resolves the specified `name` from within the specified `module`. If
+    /// the module doesn't define the name, it is looked up in the prelude and
+    /// then in the builtin scope.
     fn resolve_name_in_module(
         &self,
         _db: &dyn DefDatabase,
@@ -72,9 +74,23 @@ impl PackageDefs {
     ) -> PerNs<(ItemDefinitionId, Visibility)> {
         self[module]
             .get(name)
+            .or(self.resolve_name_in_prelude(name))
             .or(BUILTIN_SCOPE.get(name).copied().unwrap_or_else(PerNs::none))
     }
 
+    /// Resolves the specified `name` from the public items of the prelude.
+    pub(crate) fn resolve_name_in_prelude(
+        &self,
+        name: &Name,
+    ) -> PerNs<(ItemDefinitionId, Visibility)> {
+        match self.prelude() {
+            Some(prelude) => self[prelude]
+                .get(name)
+                .and_then(|(def, vis)| (vis == Visibility::Public).then_some((def, vis))),
+            None => PerNs::none(),
+        }
+    }
+
     /// Resolves the specified `path` from within the specified `module`. Also
     /// returns whether or not additions to the `PackageDef` would change
     /// the result or whether a fixed point has been reached. This is useful
@@ -9,6 +9,7 @@ use la_arena::ArenaMap;
 use crate::{
     item_scope::ItemScope,
     module_tree::{LocalModuleId, ModuleTree},
+    name::name,
     DefDatabase, DiagnosticSink, PackageId,
 };
 
@@ -28,9 +29,20 @@ impl PackageDefs {
         db: &dyn DefDatabase,
         package: PackageId,
     ) -> Arc<PackageDefs> {
+        let _span = tracing::info_span!("name_resolution", package_id = package.0).entered();
         Arc::new(collector::collect(db, package))
     }
 
+    /// Returns the prelude module of the package, if any. The prelude is the
+    /// `prelude` module in the root of the package, of which all public items
+    /// are in scope in every module of the package.
+    pub(crate) fn prelude(&self) -> Option<LocalModuleId> {
+        self.module_tree[self.module_tree.root]
+            .children
+            .get(&name![prelude])
+            .copied()
+    }
+
     /// Adds all the diagnostics for the specified `module` to the `sink`.
     pub fn add_diagnostics(
         &self,
@@ -2,13 +2,16 @@ use std::ops::Index;
 
 use rustc_hash::FxHashMap;
 
-use crate::SourceRootId;
+use crate::{cfg::CfgOptions, SourceRootId};
 
 /// Information regarding a package
 #[derive(Debug, Clone)]
 pub struct PackageData {
     /// The source root that holds the source files
     pub source_root: SourceRootId,
+
+    /// The options used to evaluate `#[cfg(..)]` attributes
+    pub cfg_options: CfgOptions,
 }
 
 /// Represents the id of a single package, all packages have a unique id, the
@@ -25,7 +28,20 @@ pub struct PackageSet {
 impl PackageSet {
     /// Adds a new package to the package set
     pub fn add_package(&mut self, source_root: SourceRootId) -> PackageId {
-        let data = PackageData { source_root };
+        self.add_package_with_cfg(source_root, CfgOptions::default())
+    }
+
+    /// Adds a new package to the package set of which `#[cfg(..)]` attributes
+    /// are evaluated against the specified options
+    pub fn add_package_with_cfg(
+        &mut self,
+        source_root: SourceRootId,
+        cfg_options: CfgOptions,
+    ) -> PackageId {
+        let data = PackageData {
+            source_root,
+            cfg_options,
+        };
         let package_id = PackageId(self.arena.len() as u32);
         self.arena.insert(package_id, data);
         package_id
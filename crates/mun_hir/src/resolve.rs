@@ -316,6 +316,14 @@ impl Scope {
                 m.package_defs[m.module_id]
                     .entries()
                     .for_each(|(name, def)| visitor(name.clone(), ScopeDef::PerNs(def)));
+                if let Some(prelude) = m.package_defs.prelude() {
+                    m.package_defs[prelude].entries().for_each(|(name, _)| {
+                        let def = m.package_defs.resolve_name_in_prelude(name);
+                        if !def.is_none() {
+                            visitor(name.clone(), ScopeDef::PerNs(def));
+                        }
+                    });
+                }
                 BUILTIN_SCOPE.iter().for_each(|(name, &def)| {
                     visitor(name.clone(), ScopeDef::PerNs(def));
                 });
@@ -14,17 +14,19 @@ mod source_to_def;
 
 use std::cell::RefCell;
 
-use mun_syntax::{ast, AstNode, SyntaxNode, TextSize};
+use mun_syntax::{ast, match_ast, AstNode, SyntaxKind, SyntaxNode, SyntaxToken, TextSize};
 use rustc_hash::FxHashMap;
 use smallvec::SmallVec;
 
 use crate::{
     ids::{DefWithBodyId, ItemDefinitionId},
+    name::AsName,
     resolve,
     resolve::HasResolver,
     semantics::source_to_def::{SourceToDefCache, SourceToDefContainer, SourceToDefContext},
     source_analyzer::SourceAnalyzer,
-    FileId, HirDatabase, InFile, ModuleDef, Name, PatId, PerNs, Resolver, Ty, Visibility,
+    Field, FileId, Function, HirDatabase, InFile, ModuleDef, Name, Package, Pat, PatId, PerNs,
+    Resolver, Struct, Ty, TypeAlias, Visibility,
 };
 
 /// The primary API to get semantic information, like types, from syntax trees.
@@ -72,6 +74,169 @@ impl<'db> Semantics<'db> {
         self.analyze(expr.syntax()).type_of_expr(self.db, expr)
     }
 
+    /// Returns the type of the given pattern
+    pub fn type_of_pat(&self, pat: &ast::Pat) -> Option<Ty> {
+        self.analyze(pat.syntax()).type_of_pat(self.db, pat)
+    }
+
+    /// Resolves the given path to the local or item it refers to
+    pub fn resolve_path(&self, path: &ast::Path) -> Option<PathResolution> {
+        self.analyze(path.syntax()).resolve_path(self.db, path)
+    }
+
+    /// Returns the function that is defined by the given function definition
+    pub fn function_to_def(&self, src: &ast::FunctionDef) -> Option<Function> {
+        let src = self.find_file(src.syntax().clone()).with_value(src.clone());
+        self.with_source_to_def_context(|ctx| ctx.fn_to_def(src))
+            .map(Function::from)
+    }
+
+    /// Returns the struct that is defined by the given struct definition
+    pub fn struct_to_def(&self, src: &ast::StructDef) -> Option<Struct> {
+        let src = self.find_file(src.syntax().clone()).with_value(src.clone());
+        self.with_source_to_def_context(|ctx| ctx.struct_to_def(src))
+            .map(Struct::from)
+    }
+
+    /// Returns the type alias that is defined by the given type alias
+    /// definition
+    pub fn type_alias_to_def(&self, src: &ast::TypeAliasDef) -> Option<TypeAlias> {
+        let src = self.find_file(src.syntax().clone()).with_value(src.clone());
+        self.with_source_to_def_context(|ctx| ctx.type_alias_to_def(src))
+            .map(TypeAlias::from)
+    }
+
+    /// Returns the local that is bound by the given pattern
+    pub fn bind_pat_to_def(&self, pat: &ast::BindPat) -> Option<Local> {
+        self.analyze(pat.syntax()).resolve_bind_pat(self.db, pat)
+    }
+
+    /// Returns the field that is accessed by the given field expression, e.g.
+    /// `foo.bar`
+    pub fn resolve_field(&self, field_expr: &ast::FieldExpr) -> Option<Field> {
+        let strukt = self.type_of_expr(&field_expr.expr()?)?.as_struct()?;
+        strukt.field(self.db, &field_expr.field_access()?.as_name())
+    }
+
+    /// Returns the fields that can be accessed on the given expression, e.g.
+    /// to complete `foo.$0`. Returns an empty list if the expression is not a
+    /// struct.
+    pub fn receiver_fields(&self, receiver: &ast::Expr) -> Vec<Field> {
+        self.type_of_expr(receiver)
+            .and_then(|ty| ty.as_struct())
+            .map(|strukt| strukt.fields(self.db))
+            .unwrap_or_default()
+    }
+
+    /// Returns the field that is initialized by the given field of a struct
+    /// literal, e.g. `bar` in `Foo { bar: 3 }`
+    pub fn resolve_record_field(&self, field: &ast::RecordField) -> Option<Field> {
+        let record_lit = field.syntax().ancestors().find_map(ast::RecordLit::cast)?;
+        let strukt = self
+            .type_of_expr(&ast::Expr::from(record_lit))?
+            .as_struct()?;
+        strukt.field(self.db, &field.name_ref()?.as_name())
+    }
+
+    /// Returns the local that initializes the given shorthand field of a
+    /// struct literal, e.g. `bar` in `Foo { bar }`. Returns `None` if the field
+    /// is not a shorthand.
+    pub fn resolve_record_field_shorthand(&self, field: &ast::RecordField) -> Option<Local> {
+        if field.expr().is_some() {
+            return None;
+        }
+        self.analyze(field.syntax())
+            .resolve_local(self.db, &field.name_ref()?)
+    }
+
+    /// Returns the definition that is introduced by the given name, e.g. the
+    /// name of a function definition or a `let` binding.
+    pub fn classify_name(&self, name: &ast::Name) -> Option<Definition> {
+        let parent = name.syntax().parent()?;
+        match_ast! {
+            match parent {
+                ast::BindPat(it) => self.bind_pat_to_def(&it).map(Definition::Local),
+                ast::FunctionDef(it) => self.function_to_def(&it).map(|it| Definition::ModuleDef(it.into())),
+                ast::StructDef(it) => self.struct_to_def(&it).map(|it| Definition::ModuleDef(it.into())),
+                ast::TypeAliasDef(it) => self.type_alias_to_def(&it).map(|it| Definition::ModuleDef(it.into())),
+                ast::RecordFieldDef(_it) => {
+                    let strukt = parent.ancestors().find_map(ast::StructDef::cast)?;
+                    self.struct_to_def(&strukt)?
+                        .field(self.db, &name.as_name())
+                        .map(Definition::Field)
+                },
+                _ => None,
+            }
+        }
+    }
+
+    /// Returns the definition that the given name reference refers to.
+    pub fn classify_name_ref(&self, name_ref: &ast::NameRef) -> Option<Definition> {
+        let parent = name_ref.syntax().parent()?;
+        if let Some(field_expr) = ast::FieldExpr::cast(parent.clone()) {
+            return self.resolve_field(&field_expr).map(Definition::Field);
+        }
+        if let Some(field) = ast::RecordField::cast(parent.clone()) {
+            return self.resolve_record_field(&field).map(Definition::Field);
+        }
+
+        let path = ast::PathSegment::cast(parent)?.parent_path();
+        let def = match self.resolve_path(&path)? {
+            PathResolution::Local(local) => Definition::Local(local),
+            PathResolution::Def(def) => Definition::ModuleDef(def),
+        };
+        Some(def)
+    }
+
+    /// Returns all name references in the packages of the database that refer
+    /// to the specified definition.
+    ///
+    /// Only name references with the same text as the name of the definition
+    /// are resolved, which keeps the search cheap for large packages.
+    pub fn find_references(&self, def: Definition) -> Vec<InFile<ast::NameRef>> {
+        let name = match def.name(self.db) {
+            Some(name) => name.to_string(),
+            None => return Vec::new(),
+        };
+
+        let file_ids = Package::all(self.db)
+            .into_iter()
+            .flat_map(|package| package.modules(self.db))
+            .filter_map(|module| module.file_id(self.db));
+
+        let mut result = Vec::new();
+        for file_id in file_ids {
+            let file = self.parse(file_id);
+            result.extend(
+                file.syntax()
+                    .descendants()
+                    .filter_map(ast::NameRef::cast)
+                    .filter(|name_ref| name_ref.text() == name.as_str())
+                    .filter(|name_ref| self.refers_to(name_ref, def))
+                    .map(|name_ref| InFile::new(file_id, name_ref)),
+            );
+        }
+        result
+    }
+
+    /// Returns true if the given name reference refers to `def`. The name of
+    /// a shorthand field of a struct literal, e.g. `bar` in `Foo { bar }`,
+    /// refers to both the field and the local that initializes it.
+    fn refers_to(&self, name_ref: &ast::NameRef, def: Definition) -> bool {
+        if self.classify_name_ref(name_ref) == Some(def) {
+            return true;
+        }
+        match def {
+            Definition::Local(local) => name_ref
+                .syntax()
+                .parent()
+                .and_then(ast::RecordField::cast)
+                .and_then(|field| self.resolve_record_field_shorthand(&field))
+                .map_or(false, |it| it == local),
+            _ => false,
+        }
+    }
+
     /// Returns the source analyzer for the given node.
     fn analyze(&self, node: &SyntaxNode) -> SourceAnalyzer {
         self.build_analyzer(node, None)
@@ -162,6 +327,36 @@ pub struct SemanticsScope<'a> {
     resolver: Resolver,
 }
 
+/// The local or item that a path refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathResolution {
+    Local(Local),
+    Def(ModuleDef),
+}
+
+/// An item, field or local that can be referred to by name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Definition {
+    ModuleDef(ModuleDef),
+    Field(Field),
+    Local(Local),
+}
+
+impl Definition {
+    /// Returns the name of the definition
+    pub fn name(self, db: &dyn HirDatabase) -> Option<Name> {
+        match self {
+            Definition::ModuleDef(ModuleDef::Module(module)) => module.name(db),
+            Definition::ModuleDef(ModuleDef::Function(func)) => Some(func.name(db)),
+            Definition::ModuleDef(ModuleDef::Struct(strukt)) => Some(strukt.name(db)),
+            Definition::ModuleDef(ModuleDef::TypeAlias(alias)) => Some(alias.name(db)),
+            Definition::ModuleDef(ModuleDef::PrimitiveType(_)) => None,
+            Definition::Field(field) => Some(field.name(db)),
+            Definition::Local(local) => local.name(db),
+        }
+    }
+}
+
 /// Represents an element in a scope
 pub enum ScopeDef {
     ModuleDef(ModuleDef),
@@ -192,6 +387,16 @@ impl ScopeDef {
 
         items
     }
+
+    /// Returns true if this item is a type, e.g. a struct or a builtin type.
+    pub fn is_type(&self) -> bool {
+        matches!(
+            self,
+            ScopeDef::ModuleDef(
+                ModuleDef::Struct(_) | ModuleDef::TypeAlias(_) | ModuleDef::PrimitiveType(_)
+            )
+        )
+    }
 }
 
 /// A local variable in a body
@@ -207,6 +412,23 @@ impl Local {
         let infer = db.infer(self.parent);
         infer[self.pat_id].clone()
     }
+
+    /// Returns the name of this local
+    pub fn name(self, db: &dyn HirDatabase) -> Option<Name> {
+        let body = db.body(self.parent);
+        match &body[self.pat_id] {
+            Pat::Bind { name } => Some(name.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the pattern that defines this local
+    pub fn source(self, db: &dyn HirDatabase) -> Option<InFile<ast::Pat>> {
+        let (_, source_map) = db.body_with_source_map(self.parent);
+        let src = source_map.pat_syntax(self.pat_id)?;
+        let root = src.file_syntax(db.upcast());
+        Some(src.map(|ptr| ptr.to_node(&root)))
+    }
 }
 
 impl<'a> SemanticsScope<'a> {
@@ -233,4 +455,42 @@ impl<'a> SemanticsScope<'a> {
             visit(name, def);
         });
     }
+    /// Call the `visit` function for every named item in the scope that can
+    /// be referred to at `token`. Only types are visited if `token` is in a
+    /// type annotation, see [`is_type_position`].
+    pub fn visit_names_at(&self, token: &SyntaxToken, visit: &mut dyn FnMut(Name, ScopeDef)) {
+        let types_only = is_type_position(token);
+        self.visit_all_names(&mut |name, def| {
+            if !types_only || def.is_type() {
+                visit(name, def);
+            }
+        });
+    }
+}
+
+/// Returns true if `token` is part of a type annotation, or directly follows
+/// the start of one, e.g. `let a: $0` or `fn foo() -> $0`.
+pub fn is_type_position(token: &SyntaxToken) -> bool {
+    let mut token = token.clone();
+    while token.kind().is_trivia() {
+        token = match token.prev_token() {
+            Some(prev) => prev,
+            None => return false,
+        };
+    }
+
+    let parent = match token.parent() {
+        Some(parent) => parent,
+        None => return false,
+    };
+    match token.kind() {
+        SyntaxKind::COLON => matches!(
+            parent.kind(),
+            SyntaxKind::LET_STMT | SyntaxKind::PARAM | SyntaxKind::RECORD_FIELD_DEF
+        ),
+        SyntaxKind::THIN_ARROW => parent.kind() == SyntaxKind::RET_TYPE,
+        _ => parent
+            .ancestors()
+            .any(|node| node.kind() == SyntaxKind::PATH_TYPE),
+    }
 }
@@ -45,7 +45,7 @@ impl SourceToDefContext<'_, '_> {
     }
 
     /// Find the `FunctionId` associated with the specified syntax tree node.
-    fn fn_to_def(&mut self, src: InFile<ast::FunctionDef>) -> Option<FunctionId> {
+    pub(super) fn fn_to_def(&mut self, src: InFile<ast::FunctionDef>) -> Option<FunctionId> {
         let container = self.find_container(src.as_ref().map(AstNode::syntax))?;
         let db = self.db;
         let def_map = &*self
@@ -55,6 +55,31 @@ impl SourceToDefContext<'_, '_> {
         def_map.functions.get(&src).copied()
     }
 
+    /// Find the `StructId` associated with the specified syntax tree node.
+    pub(super) fn struct_to_def(&mut self, src: InFile<ast::StructDef>) -> Option<StructId> {
+        let container = self.find_container(src.as_ref().map(AstNode::syntax))?;
+        let db = self.db;
+        let def_map = &*self
+            .cache
+            .entry(container)
+            .or_insert_with(|| container.source_to_def_map(db));
+        def_map.structs.get(&src).copied()
+    }
+
+    /// Find the `TypeAliasId` associated with the specified syntax tree node.
+    pub(super) fn type_alias_to_def(
+        &mut self,
+        src: InFile<ast::TypeAliasDef>,
+    ) -> Option<TypeAliasId> {
+        let container = self.find_container(src.as_ref().map(AstNode::syntax))?;
+        let db = self.db;
+        let def_map = &*self
+            .cache
+            .entry(container)
+            .or_insert_with(|| container.source_to_def_map(db));
+        def_map.type_aliases.get(&src).copied()
+    }
+
     /// Finds the `ModuleId` associated with the specified `file`
     fn file_to_def(&self, file_id: FileId) -> Option<ModuleId> {
         let source_root_id = self.db.file_source_root(file_id);
@@ -5,8 +5,10 @@ use mun_syntax::{ast, AstNode, SyntaxNode, TextRange, TextSize};
 use crate::{
     expr::{scope::LocalScopeId, BodySourceMap},
     ids::DefWithBodyId,
-    resolver_for_scope, Body, ExprId, ExprScopes, FileId, HirDatabase, InFile, InferenceResult,
-    Resolver, Ty,
+    resolver_for_scope,
+    semantics::{Local, PathResolution},
+    Body, ExprId, ExprScopes, FileId, Function, HirDatabase, InFile, InferenceResult, Path,
+    Resolver, Struct, Ty, TypeAlias, TypeNs, ValueNs,
 };
 
 /// A `SourceAnalyzer` is a wrapper which exposes the HIR API in terms of the
@@ -18,6 +20,9 @@ pub(crate) struct SourceAnalyzer {
     /// The resolver used to resolve names
     pub(crate) resolver: Resolver,
 
+    /// The definition that owns the body, if any
+    def: Option<DefWithBodyId>,
+
     /// Optional body to res
     body: Option<Arc<Body>>,
     body_source_map: Option<Arc<BodySourceMap>>,
@@ -43,6 +48,7 @@ impl SourceAnalyzer {
         let resolver = resolver_for_scope(db.upcast(), def, scope);
         SourceAnalyzer {
             resolver,
+            def: Some(def),
             body: Some(body),
             body_source_map: Some(source_map),
             infer: Some(db.infer(def)),
@@ -58,6 +64,7 @@ impl SourceAnalyzer {
     ) -> SourceAnalyzer {
         SourceAnalyzer {
             resolver,
+            def: None,
             body: None,
             body_source_map: None,
             infer: None,
@@ -72,6 +79,80 @@ impl SourceAnalyzer {
         Some(self.infer.as_ref()?[expr_id].clone())
     }
 
+    /// Returns the type of the specified pattern
+    pub(crate) fn type_of_pat(&self, _db: &dyn HirDatabase, pat: &ast::Pat) -> Option<Ty> {
+        let pat_id = self.body_source_map.as_ref()?.node_pat(pat)?;
+        Some(self.infer.as_ref()?[pat_id].clone())
+    }
+
+    /// Returns the local that is bound by the specified pattern
+    pub(crate) fn resolve_bind_pat(
+        &self,
+        _db: &dyn HirDatabase,
+        pat: &ast::BindPat,
+    ) -> Option<Local> {
+        let pat_id = self
+            .body_source_map
+            .as_ref()?
+            .node_pat(&ast::Pat::from(pat.clone()))?;
+        Some(Local {
+            parent: self.def?,
+            pat_id,
+        })
+    }
+
+    /// Resolves the specified name reference to the local it refers to, if
+    /// any.
+    pub(crate) fn resolve_local(
+        &self,
+        db: &dyn HirDatabase,
+        name_ref: &ast::NameRef,
+    ) -> Option<Local> {
+        let path = Path::from_name_ref(name_ref);
+        match self
+            .resolver
+            .resolve_path_as_value_fully(db.upcast(), &path)?
+        {
+            (ValueNs::LocalBinding(pat_id), _) => Some(Local {
+                parent: self.def?,
+                pat_id,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Resolves the specified path to the local or item it refers to. Values
+    /// take precedence over types.
+    pub(crate) fn resolve_path(
+        &self,
+        db: &dyn HirDatabase,
+        path: &ast::Path,
+    ) -> Option<PathResolution> {
+        let path = Path::from_ast(path.clone())?;
+        if let Some((value, _)) = self
+            .resolver
+            .resolve_path_as_value_fully(db.upcast(), &path)
+        {
+            return Some(match value {
+                ValueNs::LocalBinding(pat_id) => PathResolution::Local(Local {
+                    parent: self.resolver.body_owner()?,
+                    pat_id,
+                }),
+                ValueNs::FunctionId(id) => PathResolution::Def(Function::from(id).into()),
+                ValueNs::StructId(id) => PathResolution::Def(Struct::from(id).into()),
+            });
+        }
+
+        let (ty, _) = self
+            .resolver
+            .resolve_path_as_type_fully(db.upcast(), &path)?;
+        Some(PathResolution::Def(match ty {
+            TypeNs::StructId(id) => Struct::from(id).into(),
+            TypeNs::TypeAliasId(id) => TypeAlias::from(id).into(),
+            TypeNs::PrimitiveType(ty) => ty.into(),
+        }))
+    }
+
     /// Returns the expression id of the given expression or None if it could
     /// not be found.
     fn expr_id(&self, _db: &dyn HirDatabase, expr: &ast::Expr) -> Option<ExprId> {
@@ -4,7 +4,7 @@ use crate::{
     db::{DefDatabase, SourceDatabase},
     mock::MockDatabase,
     with_fixture::WithFixture,
-    PackageId,
+    Body, Module, ModuleDef, PackageId,
 };
 
 /// This function tests that the `ModuleData` of a module does not change if the
@@ -49,3 +49,38 @@ fn check_package_defs_does_not_change() {
         );
     }
 }
+
+/// This function tests that the branches of an `if` expression without braces
+/// lower to the same body as their braced form.
+#[test]
+fn if_without_braces_lowers_to_blocks() {
+    fn body_of_foo(text: &str) -> Arc<Body> {
+        let (db, file_id) = MockDatabase::with_single_file(text);
+        let module = Module::from_file(&db, file_id).unwrap();
+        let func = module
+            .declarations(&db)
+            .into_iter()
+            .find_map(|def| match def {
+                ModuleDef::Function(func) => Some(func),
+                _ => None,
+            })
+            .unwrap();
+        func.body(&db)
+    }
+
+    let braced = body_of_foo(
+        r#"
+    fn foo(a: i32) -> i32 {
+        if a > 3 { a * 2 } else if a > 1 { a } else { 0 }
+    }
+    "#,
+    );
+    let braceless = body_of_foo(
+        r#"
+    fn foo(a: i32) -> i32 {
+        if a > 3 a * 2 else if a > 1 a else 0
+    }
+    "#,
+    );
+    assert_eq!(braced, braceless);
+}
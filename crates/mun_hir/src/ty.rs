@@ -140,6 +140,14 @@ impl Ty {
         }
     }
 
+    /// Returns true if this type represents a struct that is garbage collected
+    /// and can therefore hold `null`.
+    pub fn is_gc_struct(&self, db: &dyn HirDatabase) -> bool {
+        self.as_struct().map_or(false, |s| {
+            s.data(db.upcast()).memory_kind == StructMemoryKind::Gc
+        })
+    }
+
     /// If this type represents a tuple type, returns a reference to the
     /// substitutions of the tuple.
     pub fn as_tuple(&self) -> Option<&Substitution> {
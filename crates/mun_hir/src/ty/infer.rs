@@ -12,10 +12,10 @@ use crate::{
     ty::{
         infer::{diagnostics::InferenceDiagnostic, type_variable::TypeVariableTable},
         lower::LowerDiagnostic,
-        op, Ty, TypableDef,
+        op, FnSig, Ty, TypableDef,
     },
     type_ref::LocalTypeRefId,
-    BinaryOp, Function, HirDatabase, Name, Path,
+    BinaryOp, CallableDef, CmpOp, Function, HirDatabase, Name, Path,
 };
 
 mod place_expr;
@@ -54,6 +54,9 @@ impl Default for InternedStandardTypes {
 pub struct InferenceResult {
     pub(crate) type_of_expr: ArenaMap<ExprId, Ty>,
     pub(crate) type_of_pat: ArenaMap<PatId, Ty>,
+    pub(crate) layout_query_types: ArenaMap<ExprId, Ty>,
+    pub(crate) call_argument_orders: ArenaMap<ExprId, Vec<Option<usize>>>,
+    pub(crate) return_ty: Ty,
     pub(crate) diagnostics: Vec<diagnostics::InferenceDiagnostic>,
 
     /// Interned Unknown to return references to.
@@ -79,6 +82,28 @@ impl Index<PatId> for InferenceResult {
 }
 
 impl InferenceResult {
+    /// Returns the type of which the memory layout is queried by the
+    /// [`Expr::TypeLayout`] expression `expr`.
+    pub fn layout_query_type(&self, expr: ExprId) -> Option<&Ty> {
+        self.layout_query_types.get(expr)
+    }
+
+    /// Returns, for every parameter of the function called by the
+    /// [`Expr::Call`] expression `expr`, the index of the argument that is
+    /// passed to it or `None` if the default value of the parameter is used.
+    /// This is only available for calls that pass arguments by name or omit
+    /// arguments; otherwise the arguments are passed in order.
+    pub fn call_argument_order(&self, expr: ExprId) -> Option<&[Option<usize>]> {
+        self.call_argument_orders.get(expr).map(Vec::as_slice)
+    }
+
+    /// Returns the return type of the function. This is the declared return
+    /// type, except for the implicit `main` function of a script of which the
+    /// return type is inferred from its body.
+    pub fn return_ty(&self) -> &Ty {
+        &self.return_ty
+    }
+
     /// Adds all the `InferenceDiagnostic`s of the result to the
     /// `DiagnosticSink`.
     pub(crate) fn add_diagnostics(
@@ -97,6 +122,12 @@ impl InferenceResult {
 /// types of all the expressions and patterns. Diagnostics are also reported and
 /// stored in the `InferenceResult`.
 pub fn infer_query(db: &dyn HirDatabase, def: DefWithBodyId) -> Arc<InferenceResult> {
+    let _span = match def {
+        DefWithBodyId::FunctionId(id) => {
+            tracing::info_span!("infer", function = %db.fn_data(id).name()).entered()
+        }
+    };
+
     let body = db.body(def);
     let resolver = def.resolver(db.upcast());
     let mut ctx = InferenceResultBuilder::new(db, &body, resolver);
@@ -152,6 +183,8 @@ struct InferenceResultBuilder<'a> {
 
     type_of_expr: ArenaMap<ExprId, Ty>,
     type_of_pat: ArenaMap<PatId, Ty>,
+    layout_query_types: ArenaMap<ExprId, Ty>,
+    call_argument_orders: ArenaMap<ExprId, Vec<Option<usize>>>,
     diagnostics: Vec<InferenceDiagnostic>,
 
     type_variables: TypeVariableTable,
@@ -174,6 +207,8 @@ impl<'a> InferenceResultBuilder<'a> {
         InferenceResultBuilder {
             type_of_expr: ArenaMap::default(),
             type_of_pat: ArenaMap::default(),
+            layout_query_types: ArenaMap::default(),
+            call_argument_orders: ArenaMap::default(),
             diagnostics: Vec::default(),
             active_loop: None,
             type_variables: TypeVariableTable::default(),
@@ -214,6 +249,9 @@ impl<'a> InferenceResultBuilder<'a> {
                     InferenceDiagnostic::UnresolvedType { id }
                 }
                 LowerDiagnostic::TypeIsPrivate { id } => InferenceDiagnostic::TypeIsPrivate { id },
+                LowerDiagnostic::UnsupportedTupleType { id } => {
+                    InferenceDiagnostic::UnsupportedTupleType { id }
+                }
             };
             self.diagnostics.push(diag);
         }
@@ -229,13 +267,39 @@ impl<'a> InferenceResultBuilder<'a> {
     fn infer_signature(&mut self) {
         // Iterate over all the parameters and associated types of the body and infer
         // the types of the parameters.
-        for (pat, type_ref) in self.body.params().iter() {
+        for (idx, (pat, type_ref)) in self.body.params().iter().enumerate() {
             let ty = self.resolve_type(*type_ref);
+            if let Some(&Some(default_value)) = self.body.param_defaults().get(idx) {
+                self.infer_expr_coerce(default_value, &Expectation::has_type(ty.clone()));
+            }
             self.infer_pat(*pat, ty);
         }
 
-        // Resolve the return type
-        self.return_ty = self.resolve_type(self.body.ret_type());
+        // Resolve the return type. The return type of the implicit `main` function
+        // of a script is inferred from its body.
+        self.return_ty = if self.is_script_body() {
+            self.type_variables.new_type_var()
+        } else {
+            self.resolve_type(self.body.ret_type())
+        };
+    }
+
+    /// Returns true if the body is the implicit `main` function of a script.
+    fn is_script_body(&self) -> bool {
+        match self.body.owner() {
+            DefWithBodyId::FunctionId(id) => self.db.fn_data(id).is_script_body(),
+        }
+    }
+
+    /// Returns true if `def` is the implicit `main` function of a script of
+    /// which the body is being inferred.
+    fn is_own_script_body(&self, def: CallableDef) -> bool {
+        match (def, self.body.owner()) {
+            (CallableDef::Function(f), DefWithBodyId::FunctionId(id)) => {
+                f.id == id && self.is_script_body()
+            }
+            _ => false,
+        }
     }
 
     /// Record the type of the specified pattern and all sub-patterns.
@@ -251,10 +315,24 @@ impl<'a> InferenceResultBuilder<'a> {
 
     /// Infer the types of all the expressions and sub-expressions in the body.
     fn infer_body(&mut self) {
-        self.infer_expr_coerce(
-            self.body.body_expr(),
-            &Expectation::has_type(self.return_ty.clone()),
-        );
+        let body_expr = self.body.body_expr();
+        let expected = Expectation::has_type(self.return_ty.clone());
+        let ty = self.infer_expr_inner(body_expr, &expected, &CheckParams::default());
+
+        // If a body without a trailing expression evaluates to `()` (rather than `never`)
+        // control can reach the end of the function without producing a value. A trailing
+        // expression of the wrong type is reported as a type mismatch instead.
+        if ty.is_empty()
+            && !self.has_body_tail()
+            && !self.return_ty.is_empty()
+            && !self.return_ty.is_unknown()
+            && !self.is_script_body()
+        {
+            self.diagnostics
+                .push(InferenceDiagnostic::MissingReturn { id: body_expr });
+        } else {
+            self.coerce_expr_ty(body_expr, ty, &expected);
+        }
     }
 
     /// Infers the type of the `tgt_expr`
@@ -337,7 +415,13 @@ impl<'a> InferenceResultBuilder<'a> {
                             });
                         }
                     };
-                    let rhs_expected = op::binary_op_rhs_expectation(*op, lhs_ty.clone());
+                    let rhs_expected = match op {
+                        // References to gc structs can be compared, e.g. to `null`
+                        BinaryOp::CmpOp(CmpOp::Eq { .. }) if lhs_ty.is_gc_struct(self.db) => {
+                            lhs_ty.clone()
+                        }
+                        _ => op::binary_op_rhs_expectation(*op, lhs_ty.clone()),
+                    };
                     if lhs_ty.is_known() && rhs_expected.is_unknown() {
                         self.diagnostics
                             .push(InferenceDiagnostic::CannotApplyBinaryOp {
@@ -352,10 +436,23 @@ impl<'a> InferenceResultBuilder<'a> {
                 _ => error_type(),
             },
             Expr::Block { statements, tail } => self.infer_block(statements, *tail, expected),
-            Expr::Call { callee: call, args } => self.infer_call(tgt_expr, *call, args, expected),
+            Expr::Call {
+                callee: call,
+                args,
+                named_args,
+            } => self.infer_call(tgt_expr, *call, args, named_args, expected),
             Expr::Literal(lit) => match lit {
                 Literal::String(_) => TyKind::Unknown.intern(),
                 Literal::Bool(_) => TyKind::Bool.intern(),
+                Literal::Null => {
+                    if expected.ty.is_gc_struct(self.db) {
+                        expected.ty.clone()
+                    } else {
+                        self.diagnostics
+                            .push(InferenceDiagnostic::InvalidNull { id: tgt_expr });
+                        error_type()
+                    }
+                }
                 Literal::Int(LiteralInt {
                     kind: LiteralIntKind::Suffixed(suffix),
                     ..
@@ -392,9 +489,11 @@ impl<'a> InferenceResultBuilder<'a> {
             }
             Expr::Break { expr } => self.infer_break(tgt_expr, *expr),
             Expr::Loop { body } => self.infer_loop_expr(tgt_expr, *body, expected),
-            Expr::While { condition, body } => {
-                self.infer_while_expr(tgt_expr, *condition, *body, expected)
-            }
+            Expr::While {
+                condition,
+                body,
+                else_branch,
+            } => self.infer_while_expr(tgt_expr, *condition, *body, *else_branch, expected),
             Expr::RecordLit {
                 type_id,
                 fields,
@@ -475,8 +574,8 @@ impl<'a> InferenceResultBuilder<'a> {
                         }
                     },
                     UnaryOp::Neg => match inner_ty.interned() {
+                        TyKind::Int(int_ty) if int_ty.signedness.is_signed() => inner_ty,
                         TyKind::Float(_)
-                        | TyKind::Int(_)
                         | TyKind::InferenceVar(InferTy::Int(_) | InferTy::Float(_)) => inner_ty,
                         _ => {
                             self.diagnostics
@@ -489,6 +588,11 @@ impl<'a> InferenceResultBuilder<'a> {
                     },
                 }
             }
+            Expr::TypeLayout { type_ref, .. } => {
+                let ty = self.resolve_type(*type_ref);
+                self.layout_query_types.insert(tgt_expr, ty);
+                TyKind::Int(IntTy::usize()).intern()
+            }
             Expr::Array(array) => {
                 let elem_ty = match expected.ty.interned() {
                     TyKind::Array(elem_ty) => elem_ty.clone(),
@@ -501,6 +605,24 @@ impl<'a> InferenceResultBuilder<'a> {
 
                 TyKind::Array(elem_ty).intern()
             }
+            Expr::ArrayRepeat { initializer, count } => {
+                let elem_ty = match expected.ty.interned() {
+                    TyKind::Array(elem_ty) => elem_ty.clone(),
+                    _ => self.type_variables.new_type_var(),
+                };
+
+                self.infer_expr_coerce(*initializer, &Expectation::has_type(elem_ty.clone()));
+                self.infer_expr_coerce(
+                    *count,
+                    &Expectation::has_type(TyKind::Int(IntTy::usize()).intern()),
+                );
+                if !self.is_constant(*count) {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::NonConstArrayRepeatCount { id: *count });
+                }
+
+                TyKind::Array(elem_ty).intern()
+            }
             Expr::Index { base, index } => {
                 let elem_ty = if expected.ty.is_unknown() {
                     self.type_variables.new_type_var()
@@ -528,6 +650,21 @@ impl<'a> InferenceResultBuilder<'a> {
         ty
     }
 
+    /// Returns true if the value of `expr` is known at compile time, e.g. `2 + 3`
+    /// or `size_of::<Foo>()`.
+    fn is_constant(&self, expr: ExprId) -> bool {
+        match &self.body[expr] {
+            Expr::Literal(_) | Expr::TypeLayout { .. } | Expr::Missing => true,
+            Expr::UnaryOp { expr, .. } => self.is_constant(*expr),
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: Some(BinaryOp::ArithOp(_)),
+            } => self.is_constant(*lhs) && self.is_constant(*rhs),
+            _ => false,
+        }
+    }
+
     /// Inferences the type of an if statement.
     fn infer_if(
         &mut self,
@@ -538,7 +675,9 @@ impl<'a> InferenceResultBuilder<'a> {
         else_branch: Option<ExprId>,
     ) -> Ty {
         self.infer_expr(condition, &Expectation::has_type(TyKind::Bool.intern()));
-        let then_ty = self.infer_expr_coerce(then_branch, expected);
+        let then_ty = self.infer_expr_inner(then_branch, expected, &CheckParams::default());
+        let then_diverges = then_ty.is_never();
+        let then_ty = self.coerce_expr_ty(then_branch, then_ty, expected);
         if let Some(else_branch) = else_branch {
             let else_ty = self.infer_expr_coerce(else_branch, expected);
             if let Some(ty) = self.coerce_merge_branch(&then_ty, &else_ty) {
@@ -553,7 +692,7 @@ impl<'a> InferenceResultBuilder<'a> {
                 then_ty
             }
         } else {
-            if !self.coerce(&then_ty, &Ty::unit()) {
+            if !then_diverges && !self.coerce(&then_ty, &Ty::unit()) {
                 self.diagnostics
                     .push(InferenceDiagnostic::MissingElseBranch {
                         id: tgt_expr,
@@ -570,6 +709,7 @@ impl<'a> InferenceResultBuilder<'a> {
         tgt_expr: ExprId,
         callee: ExprId,
         args: &[ExprId],
+        named_args: &[Name],
         _expected: &Expectation,
     ) -> Ty {
         let callee_ty = self.infer_expr_inner(
@@ -602,17 +742,63 @@ impl<'a> InferenceResultBuilder<'a> {
                 callee_ty
             }
             TyKind::FnDef(def, _substs) => {
-                // Found either a tuple struct literal or function
-                let sig = callee_ty.callable_sig(self.db).unwrap();
+                // Found either a tuple struct literal or function. The return type of the
+                // implicit `main` function of a script is inferred from its body, so querying
+                // its signature from within that body would be a cycle. Instead, use the
+                // return type that is being inferred.
+                let sig = if self.is_own_script_body(*def) {
+                    FnSig::from_params_and_return(Vec::new(), self.return_ty.clone())
+                } else {
+                    callee_ty.callable_sig(self.db).unwrap()
+                };
                 let (param_tys, ret_ty) = (sig.params().to_vec(), sig.ret().clone());
-                self.check_call_argument_count(
+                let arg_params = self.resolve_named_args(*def, args, named_args, param_tys.len());
+
+                // Parameters that have a default value can be omitted
+                let param_has_default = match def {
+                    CallableDef::Function(f) => {
+                        f.data(self.db.upcast()).param_has_default().to_vec()
+                    }
+                    CallableDef::Struct(_) => Vec::new(),
+                };
+                let is_missing_argument = |param: usize| {
+                    !arg_params.contains(&Some(param))
+                        && !param_has_default.get(param).copied().unwrap_or(false)
+                };
+                self.check_missing_arguments(
                     tgt_expr,
-                    def.is_struct(),
+                    *def,
                     args.len(),
                     param_tys.len(),
+                    is_missing_argument,
                 );
-                for (&arg, param_ty) in args.iter().zip(param_tys.iter()) {
-                    self.infer_expr_coerce(arg, &Expectation::has_type(param_ty.clone()));
+
+                for (&arg, param) in args.iter().zip(arg_params.iter()) {
+                    match param.and_then(|param| param_tys.get(param)) {
+                        Some(param_ty) => {
+                            self.infer_expr_coerce(arg, &Expectation::has_type(param_ty.clone()));
+                        }
+                        None => {
+                            self.infer_expr(arg, &Expectation::none());
+                        }
+                    }
+                }
+
+                // Store the order in which the arguments are passed if they are not passed in
+                // order and every parameter received exactly one argument or has a default
+                // value.
+                if (!named_args.is_empty() || args.len() < param_tys.len())
+                    && args.len() <= param_tys.len()
+                    && arg_params.iter().all(Option::is_some)
+                    && !(0..param_tys.len()).any(is_missing_argument)
+                {
+                    let mut order = vec![None; param_tys.len()];
+                    for (arg_idx, param) in arg_params.iter().enumerate() {
+                        if let Some(param) = param {
+                            order[*param] = Some(arg_idx);
+                        }
+                    }
+                    self.call_argument_orders.insert(tgt_expr, order);
                 }
 
                 ret_ty
@@ -671,6 +857,91 @@ impl<'a> InferenceResultBuilder<'a> {
         }
     }
 
+    /// Checks that every parameter without a default value received an argument. A missing
+    /// argument is reported by the name of its parameter, unless too many arguments were passed
+    /// or the parameter has no name.
+    fn check_missing_arguments(
+        &mut self,
+        tgt_expr: ExprId,
+        def: CallableDef,
+        num_args: usize,
+        num_params: usize,
+        is_missing_argument: impl Fn(usize) -> bool,
+    ) {
+        if num_args > num_params {
+            self.check_call_argument_count(tgt_expr, def.is_struct(), num_args, num_params);
+            return;
+        }
+
+        let param_names = match def {
+            CallableDef::Function(f) => f.data(self.db.upcast()).param_names().to_vec(),
+            CallableDef::Struct(_) => Vec::new(),
+        };
+        let missing_names: Option<Vec<Name>> = (0..num_params)
+            .filter(|&param| is_missing_argument(param))
+            .map(|param| param_names.get(param).cloned().flatten())
+            .collect();
+        match missing_names {
+            Some(names) => {
+                for name in names {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::MissingArgument { id: tgt_expr, name });
+                }
+            }
+            None => {
+                self.check_call_argument_count(tgt_expr, def.is_struct(), num_args, num_params);
+            }
+        }
+    }
+
+    /// Returns for every argument the index of the parameter it is passed to.
+    /// Positional arguments are passed to the parameter at the same index,
+    /// named arguments are passed to the parameter with the same name.
+    fn resolve_named_args(
+        &mut self,
+        def: CallableDef,
+        args: &[ExprId],
+        named_args: &[Name],
+        num_params: usize,
+    ) -> Vec<Option<usize>> {
+        let num_positional = args.len() - named_args.len();
+        let mut arg_params: Vec<Option<usize>> = (0..num_positional)
+            .map(|idx| (idx < num_params).then_some(idx))
+            .collect();
+        if named_args.is_empty() {
+            return arg_params;
+        }
+
+        let param_names = match def {
+            CallableDef::Function(f) => f.data(self.db.upcast()).param_names().to_vec(),
+            CallableDef::Struct(_) => Vec::new(),
+        };
+        for (&arg, name) in args[num_positional..].iter().zip(named_args) {
+            let param = param_names
+                .iter()
+                .position(|param_name| param_name.as_ref() == Some(name));
+            match param {
+                None => {
+                    self.diagnostics.push(InferenceDiagnostic::NoSuchParameter {
+                        id: arg,
+                        name: name.clone(),
+                    });
+                    arg_params.push(None);
+                }
+                Some(param) if arg_params.contains(&Some(param)) => {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::DuplicateArgument {
+                            id: arg,
+                            name: name.clone(),
+                        });
+                    arg_params.push(None);
+                }
+                Some(param) => arg_params.push(Some(param)),
+            }
+        }
+        arg_params
+    }
+
     // Checks whether the passed fields match the fields of a struct definition.
     fn check_record_lit(
         &mut self,
@@ -806,6 +1077,25 @@ impl<'a> InferenceResultBuilder<'a> {
             }
             *ty = resolved;
         }
+
+        // The operand of a negation can be an integer literal or variable of which the type is
+        // only later inferred to be unsigned, e.g. `let a: u32 = -1`.
+        for (expr, ty) in expr_types.iter() {
+            if let Expr::UnaryOp {
+                expr: operand,
+                op: UnaryOp::Neg,
+            } = &self.body[expr]
+            {
+                if matches!(ty.interned(), TyKind::Int(int_ty) if !int_ty.signedness.is_signed()) {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::CannotApplyUnaryOp {
+                            id: *operand,
+                            ty: ty.clone(),
+                        });
+                }
+            }
+        }
+
         let mut pat_types = std::mem::take(&mut self.type_of_pat);
         for (pat, ty) in pat_types.iter_mut() {
             let was_unknown = ty.is_unknown();
@@ -815,6 +1105,9 @@ impl<'a> InferenceResultBuilder<'a> {
             }
             *ty = resolved;
         }
+        let return_ty = self
+            .type_variables
+            .resolve_ty_completely(self.return_ty.clone());
         InferenceResult {
             //            method_resolutions: self.method_resolutions,
             //            field_resolutions: self.field_resolutions,
@@ -822,6 +1115,9 @@ impl<'a> InferenceResultBuilder<'a> {
             //            assoc_resolutions: self.assoc_resolutions,
             type_of_expr: expr_types,
             type_of_pat: pat_types,
+            layout_query_types: self.layout_query_types,
+            call_argument_orders: self.call_argument_orders,
+            return_ty,
             diagnostics: self.diagnostics,
             standard_types: InternedStandardTypes::default(),
         }
@@ -882,6 +1178,14 @@ impl<'a> InferenceResultBuilder<'a> {
         }
     }
 
+    /// Returns true if the function body ends with a trailing expression.
+    fn has_body_tail(&self) -> bool {
+        matches!(
+            &self.body[self.body.body_expr()],
+            Expr::Block { tail: Some(_), .. }
+        )
+    }
+
     fn infer_break(&mut self, tgt_expr: ExprId, expr: Option<ExprId>) -> Ty {
         let expected = match &self.active_loop {
             Some(ActiveLoop::Loop(_, info)) => info.clone(),
@@ -950,11 +1254,33 @@ impl<'a> InferenceResultBuilder<'a> {
         _tgt_expr: ExprId,
         condition: ExprId,
         body: ExprId,
-        _expected: &Expectation,
+        else_branch: Option<ExprId>,
+        expected: &Expectation,
     ) -> Ty {
         self.infer_expr(condition, &Expectation::has_type(TyKind::Bool.intern()));
-        self.infer_loop_block(body, ActiveLoop::While);
-        Ty::unit()
+
+        // Without an `else` branch the loop falls through with `()`, so `break` cannot carry a
+        // value.
+        let Some(else_branch) = else_branch else {
+            self.infer_loop_block(body, ActiveLoop::While);
+            return Ty::unit();
+        };
+
+        // The `else` branch is evaluated when the condition no longer holds. Its value is unified
+        // with the values of all `break` expressions in the body.
+        if let ActiveLoop::Loop(break_ty, expected) = self.infer_loop_block(
+            body,
+            ActiveLoop::Loop(TyKind::Never.intern(), expected.clone()),
+        ) {
+            let else_ty = self.infer_expr_coerce(else_branch, &expected);
+            if break_ty.is_never() {
+                else_ty
+            } else {
+                break_ty
+            }
+        } else {
+            panic!("returned active loop must be a loop")
+        }
     }
 
     #[allow(clippy::unused_self)]
@@ -1049,10 +1375,12 @@ mod diagnostics {
         code_model::{src::HasSource, StructKind},
         diagnostics::{
             AccessUnknownField, BreakOutsideLoop, BreakWithValueOutsideLoop, CannotApplyBinaryOp,
-            CannotApplyUnaryOp, CyclicType, DiagnosticSink, ExpectedFunction, FieldCountMismatch,
-            IncompatibleBranch, InvalidLhs, LiteralOutOfRange, MismatchedStructLit, MismatchedType,
-            MissingElseBranch, MissingFields, NoFields, NoSuchField, ParameterCountMismatch,
-            PrivateAccess, ReturnMissingExpression, UnresolvedType, UnresolvedValue,
+            CannotApplyUnaryOp, CyclicType, DiagnosticSink, DuplicateArgument, ExpectedFunction,
+            FieldCountMismatch, IncompatibleBranch, InvalidLhs, InvalidNull, LiteralOutOfRange,
+            MismatchedStructLit, MismatchedType, MissingArgument, MissingElseBranch, MissingFields,
+            MissingReturn, NoFields, NoSuchField, NoSuchParameter, NonConstArrayRepeatCount,
+            ParameterCountMismatch, PrivateAccess, ReturnMissingExpression, UnresolvedType,
+            UnresolvedValue, UnsupportedTupleType,
         },
         ty::infer::ExprOrPatId,
         type_ref::LocalTypeRefId,
@@ -1070,6 +1398,9 @@ mod diagnostics {
         CyclicType {
             id: LocalTypeRefId,
         },
+        UnsupportedTupleType {
+            id: LocalTypeRefId,
+        },
         ExpectedFunction {
             id: ExprId,
             found: Ty,
@@ -1079,6 +1410,18 @@ mod diagnostics {
             found: usize,
             expected: usize,
         },
+        NoSuchParameter {
+            id: ExprId,
+            name: Name,
+        },
+        DuplicateArgument {
+            id: ExprId,
+            name: Name,
+        },
+        MissingArgument {
+            id: ExprId,
+            name: Name,
+        },
         MismatchedTypes {
             id: ExprId,
             expected: Ty,
@@ -1109,12 +1452,21 @@ mod diagnostics {
         ReturnMissingExpression {
             id: ExprId,
         },
+        MissingReturn {
+            id: ExprId,
+        },
         BreakOutsideLoop {
             id: ExprId,
         },
         BreakWithValueOutsideLoop {
             id: ExprId,
         },
+        NonConstArrayRepeatCount {
+            id: ExprId,
+        },
+        InvalidNull {
+            id: ExprId,
+        },
         AccessUnknownField {
             id: ExprId,
             receiver_ty: Ty,
@@ -1187,6 +1539,10 @@ mod diagnostics {
                     let type_ref = body.type_ref_syntax(*id).expect("If this is not found, it must be a type ref generated by the library which should never be unresolved.");
                     sink.push(CyclicType { file, type_ref });
                 }
+                InferenceDiagnostic::UnsupportedTupleType { id } => {
+                    let type_ref = body.type_ref_syntax(*id).expect("If this is not found, it must be a type ref generated by the library which should never be unresolved.");
+                    sink.push(UnsupportedTupleType { file, type_ref });
+                }
                 InferenceDiagnostic::TypeIsPrivate { id } => {
                     let type_ref = body.type_ref_syntax(*id).expect("If this is not found, it must be a type ref generated by the library which should never be unresolved.");
                     sink.push(PrivateAccess {
@@ -1224,6 +1580,42 @@ mod diagnostics {
                         found: *found,
                     });
                 }
+                InferenceDiagnostic::NoSuchParameter { id, name } => {
+                    let arg = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(NoSuchParameter {
+                        file,
+                        arg,
+                        name: name.clone(),
+                    });
+                }
+                InferenceDiagnostic::DuplicateArgument { id, name } => {
+                    let arg = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(DuplicateArgument {
+                        file,
+                        arg,
+                        name: name.clone(),
+                    });
+                }
+                InferenceDiagnostic::MissingArgument { id, name } => {
+                    let call = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(MissingArgument {
+                        file,
+                        call,
+                        name: name.clone(),
+                    });
+                }
                 InferenceDiagnostic::ExpectedFunction { id, found } => {
                     let expr = body
                         .expr_syntax(*id)
@@ -1335,6 +1727,14 @@ mod diagnostics {
                         return_expr: id,
                     });
                 }
+                InferenceDiagnostic::MissingReturn { id } => {
+                    let id = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(MissingReturn { file, body: id });
+                }
                 InferenceDiagnostic::BreakOutsideLoop { id } => {
                     let id = body
                         .expr_syntax(*id)
@@ -1346,6 +1746,22 @@ mod diagnostics {
                         break_expr: id,
                     });
                 }
+                InferenceDiagnostic::NonConstArrayRepeatCount { id } => {
+                    let id = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(NonConstArrayRepeatCount { file, count: id });
+                }
+                InferenceDiagnostic::InvalidNull { id } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(InvalidNull { file, expr });
+                }
                 InferenceDiagnostic::BreakWithValueOutsideLoop { id } => {
                     let id = body
                         .expr_syntax(*id)
@@ -7,7 +7,7 @@ impl<'a> InferenceResultBuilder<'a> {
         match &self.body[expr] {
             Expr::Path(p) => self.check_place_path(resolver, p),
             Expr::Index { base, .. } => self.check_place_expression(resolver, *base),
-            Expr::Field { .. } | Expr::Array(_) => true,
+            Expr::Field { .. } | Expr::Array(_) | Expr::ArrayRepeat { .. } => true,
             _ => false,
         }
     }
@@ -87,6 +87,14 @@ impl Ty {
         let res = match &type_ref_map[type_ref] {
             TypeRef::Path(path) => Ty::from_path(db, resolver, type_ref, path, diagnostics),
             TypeRef::Error => Some(TyKind::Unknown.intern()),
+            TypeRef::Tuple(inner) if !inner.is_empty() => {
+                // Code generation only supports the unit type
+                for tr in inner {
+                    Self::from_hir_with_diagnostics(db, resolver, type_ref_map, diagnostics, *tr);
+                }
+                diagnostics.push(LowerDiagnostic::UnsupportedTupleType { id: type_ref });
+                Some(TyKind::Unknown.intern())
+            }
             TypeRef::Tuple(inner) => {
                 let inner_tys = inner.iter().map(|tr| {
                     Self::from_hir_with_diagnostics(db, resolver, type_ref_map, diagnostics, *tr)
@@ -283,7 +291,11 @@ pub(crate) fn fn_sig_for_fn(db: &dyn HirDatabase, def: Function) -> FnSig {
         .iter()
         .map(|tr| Ty::from_hir(db, &resolver, data.type_ref_map(), *tr).0)
         .collect::<Vec<_>>();
-    let ret = Ty::from_hir(db, &resolver, data.type_ref_map(), *data.ret_type()).0;
+    let ret = if data.is_script_body() {
+        db.infer(def.id.into()).return_ty().clone()
+    } else {
+        Ty::from_hir(db, &resolver, data.type_ref_map(), *data.ret_type()).0
+    };
     FnSig::from_params_and_return(params, ret)
 }
 
@@ -325,7 +337,7 @@ pub(crate) fn lower_impl_query(db: &dyn HirDatabase, impl_id: ImplId) -> Arc<Low
 
 pub mod diagnostics {
     use crate::{
-        diagnostics::{DiagnosticSink, PrivateAccess, UnresolvedType},
+        diagnostics::{DiagnosticSink, PrivateAccess, UnresolvedType, UnsupportedTupleType},
         type_ref::{LocalTypeRefId, TypeRefSourceMap},
         FileId, HirDatabase,
     };
@@ -334,6 +346,7 @@ pub mod diagnostics {
     pub(crate) enum LowerDiagnostic {
         UnresolvedType { id: LocalTypeRefId },
         TypeIsPrivate { id: LocalTypeRefId },
+        UnsupportedTupleType { id: LocalTypeRefId },
     }
 
     impl LowerDiagnostic {
@@ -353,6 +366,10 @@ pub mod diagnostics {
                     file: file_id,
                     expr: source_map.type_ref_syntax(*id).unwrap().syntax_node_ptr(),
                 }),
+                LowerDiagnostic::UnsupportedTupleType { id } => sink.push(UnsupportedTupleType {
+                    file: file_id,
+                    type_ref: source_map.type_ref_syntax(*id).unwrap(),
+                }),
             }
         }
     }
@@ -56,6 +56,73 @@ fn array_element_assignment() {
     "###);
 }
 
+#[test]
+fn array_repeat() {
+    insta::assert_snapshot!(infer(
+        r"
+    fn main() {
+        let a = [0; 5]
+        a[1] = 2u16
+    }",
+    ), @r###"
+    10..48 '{     ...2u16 }': ()
+    20..21 'a': [u16]
+    24..30 '[0; 5]': [u16]
+    25..26 '0': u16
+    28..29 '5': usize
+    35..36 'a': [u16]
+    35..39 'a[1]': u16
+    35..46 'a[1] = 2u16': ()
+    37..38 '1': i32
+    42..46 '2u16': u16
+    "###);
+}
+
+#[test]
+fn array_repeat_non_const_count() {
+    insta::assert_snapshot!(infer(
+        r"
+    fn main(n: usize) -> [i32] {
+        [0; n]
+    }",
+    ), @r###"
+    37..38: the count of an array repeat expression must be a constant
+    8..9 'n': usize
+    27..41 '{     [0; n] }': [i32]
+    33..39 '[0; n]': [i32]
+    34..35 '0': i32
+    37..38 'n': usize
+    "###);
+}
+
+#[test]
+fn infer_null() {
+    insta::assert_snapshot!(infer(
+        r"
+    struct Node { next: Node }
+
+    fn main(a: Node) -> bool {
+        a.next = null;
+        let b: i32 = null;
+        a.next == null
+    }",
+    ), @r###"
+    91..95: `null` can only be used where a gc struct is expected
+    36..37 'a': Node
+    53..117 '{     ...null }': bool
+    59..60 'a': Node
+    59..65 'a.next': Node
+    59..72 'a.next = null': ()
+    68..72 'null': Node
+    82..83 'b': i32
+    91..95 'null': {unknown}
+    101..102 'a': Node
+    101..107 'a.next': Node
+    101..115 'a.next == null': bool
+    111..115 'null': Node
+    "###);
+}
+
 #[test]
 fn array_is_place_expr() {
     insta::assert_snapshot!(infer(
@@ -288,10 +355,38 @@ fn private_access() {
     358..369: access of private type
     396..413: access of private type
     439..447: access of private type
+    20..21: unused variable: `a`
+    72..73: unused variable: `a`
+    124..125: unused variable: `a`
+    173..174: unused variable: `a`
+    228..229: unused variable: `a`
+    271..272: unused variable: `a`
+    314..315: unused variable: `a`
+    354..355: unused variable: `a`
+    478..479: unused variable: `a`
+    518..519: unused variable: `a`
+    558..559: unused variable: `a`
+    595..596: unused variable: `a`
+    638..639: unused variable: `a`
+    669..670: unused variable: `a`
+    700..701: unused variable: `a`
+    728..729: unused variable: `a`
     24..41: access of private type
     76..93: access of private type
     128..145: access of private type
     177..197: access of private type
+    20..21: unused variable: `a`
+    72..73: unused variable: `a`
+    124..125: unused variable: `a`
+    173..174: unused variable: `a`
+    20..21: unused variable: `a`
+    54..55: unused variable: `a`
+    88..89: unused variable: `a`
+    119..120: unused variable: `a`
+    156..157: unused variable: `a`
+    183..184: unused variable: `a`
+    210..211: unused variable: `a`
+    234..235: unused variable: `a`
     10..812 '{     ...o(); }': ()
     20..21 'a': Foo
     24..44 'packag...Foo {}': Foo
@@ -384,6 +479,35 @@ fn private_access() {
     "###);
 }
 
+#[test]
+fn prelude_items_in_scope() {
+    insta::assert_snapshot!(infer(
+        r"
+    //- /mod.mun
+    fn main() -> f64 {
+        math::abs(one())
+    }
+
+    //- /prelude.mun
+    pub fn one() -> f64 { 1.0 }
+
+    //- /prelude/math.mun
+    pub fn abs(x: f64) -> f64 { x }
+    "),
+    @r###"
+    17..41 '{     ...e()) }': f64
+    23..32 'math::abs': function abs(f64) -> f64
+    23..39 'math::...one())': f64
+    33..36 'one': function one() -> f64
+    33..38 'one()': f64
+    20..27 '{ 1.0 }': f64
+    22..25 '1.0': f64
+    11..12 'x': f64
+    26..31 '{ x }': f64
+    28..29 'x': f64
+    "###);
+}
+
 #[test]
 fn scoped_path() {
     insta::assert_snapshot!(infer(
@@ -474,6 +598,7 @@ fn infer_literals() {
         }
     "),
     @r###"
+    109..110: unused variable: `b`
     20..29 '{     0 }': i32
     26..27 '0': i32
     67..76 '{     0 }': u128
@@ -576,6 +701,62 @@ fn infer_suffix_literals() {
     "###);
 }
 
+#[test]
+fn infer_negative_literals() {
+    insta::assert_snapshot!(infer(
+        r"
+    fn main() {
+        let a = -128_i8;
+        let b = -5_u8;
+        let c = -129_i8;
+        let d = -2.5;
+        let e = 3 - -2;
+        let f: u32 = -1;
+        let g = 5;
+        let h: u8 = -g;
+    }
+    "),
+    @r###"
+    46..50: cannot apply unary operator
+    129..130: cannot apply unary operator
+    164..165: cannot apply unary operator
+    65..71: literal out of range for `i8`
+    20..21: unused variable: `a`
+    41..42: unused variable: `b`
+    60..61: unused variable: `c`
+    81..82: unused variable: `d`
+    99..100: unused variable: `e`
+    119..120: unused variable: `f`
+    155..156: unused variable: `h`
+    10..168 '{     ... -g; }': ()
+    20..21 'a': i8
+    24..31 '-128_i8': i8
+    25..31 '128_i8': i8
+    41..42 'b': {unknown}
+    45..50 '-5_u8': {unknown}
+    46..50 '5_u8': u8
+    60..61 'c': i8
+    64..71 '-129_i8': i8
+    65..71 '129_i8': i8
+    81..82 'd': f64
+    85..89 '-2.5': f64
+    86..89 '2.5': f64
+    99..100 'e': i32
+    103..104 '3': i32
+    103..109 '3 - -2': i32
+    107..109 '-2': i32
+    108..109 '2': i32
+    119..120 'f': u32
+    128..130 '-1': u32
+    129..130 '1': u32
+    140..141 'g': u8
+    144..145 '5': u8
+    155..156 'h': u8
+    163..165 '-g': u8
+    164..165 'g': u8
+    "###);
+}
+
 #[test]
 fn infer_invalid_struct_type() {
     insta::assert_snapshot!(infer(
@@ -585,6 +766,7 @@ fn infer_invalid_struct_type() {
     }"),
     @r###"
     23..26: undefined type
+    19..20: unused variable: `a`
     9..36 '{     ... 3}; }': ()
     19..20 'a': {unknown}
     23..33 'Foo {b: 3}': {unknown}
@@ -649,6 +831,7 @@ fn infer_return() {
     "#),
     @r###"
     21..27: `return;` in a function whose return type is not `()`
+    59..67: unreachable code
     15..70 '{     ...n 5; }': never
     21..27 'return': never
     59..67 'return 5': never
@@ -656,6 +839,91 @@ fn infer_return() {
     "###);
 }
 
+#[test]
+fn infer_unit_early_return() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn test(a: i32) {
+        if a > 4 {
+            return;
+        }
+        a;
+    }
+    "#),
+    @r###"
+    8..9 'a': i32
+    16..63 '{     ...  a; }': ()
+    22..54 'if a >...     }': ()
+    25..26 'a': i32
+    25..30 'a > 4': bool
+    29..30 '4': i32
+    31..54 '{     ...     }': never
+    41..47 'return': never
+    59..60 'a': i32
+    "###);
+}
+
+#[test]
+fn infer_missing_return() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn missing(a: i32) -> i32 {
+        if a > 0 {
+            return 1;
+        }
+    }
+
+    fn exhaustive(a: i32) -> i32 {
+        if a > 0 {
+            return 1;
+        } else {
+            return 0;
+        }
+    }
+
+    fn forever() -> i32 {
+        loop {}
+    }
+
+    fn no_tail(a: i32) -> i32 {
+        let b = a;
+    }
+    "#),
+    @r###"
+    32..66: mismatched type
+    26..68: mismatched type
+    237..255: function may reach its end without returning a value
+    11..12 'a': i32
+    26..68 '{     ...   } }': ()
+    32..66 'if a >...     }': ()
+    35..36 'a': i32
+    35..40 'a > 0': bool
+    39..40 '0': i32
+    41..66 '{     ...     }': never
+    51..59 'return 1': never
+    58..59 '1': i32
+    84..85 'a': i32
+    99..172 '{     ...   } }': i32
+    105..170 'if a >...     }': i32
+    108..109 'a': i32
+    108..113 'a > 0': bool
+    112..113 '0': i32
+    114..139 '{     ...     }': never
+    124..132 'return 1': never
+    131..132 '1': i32
+    145..170 '{     ...     }': never
+    155..163 'return 0': never
+    162..163 '0': i32
+    194..209 '{     loop {} }': never
+    200..207 'loop {}': never
+    205..207 '{}': ()
+    222..223 'a': i32
+    237..255 '{     ...= a; }': ()
+    247..248 'b': i32
+    251..252 'a': i32
+    "###);
+}
+
 #[test]
 fn infer_basics() {
     insta::assert_snapshot!(infer(
@@ -695,6 +963,11 @@ fn infer_branching() {
     @r###"
     61..74: missing else branch
     208..234: mismatched branches
+    20..21: unused variable: `a`
+    57..58: unused variable: `b`
+    120..121: unused variable: `c`
+    147..148: unused variable: `d`
+    204..205: unused variable: `e`
     10..260 '{     ...ches }': ()
     20..21 'a': i32
     24..48 'if tru... { 4 }': i32
@@ -746,6 +1019,8 @@ fn void_return() {
     }
     "#),
     @r###"
+    19..20: unused variable: `a`
+    52..53: unused variable: `c`
     9..27 '{     ...= 3; }': ()
     19..20 'a': i32
     23..24 '3': i32
@@ -893,6 +1168,101 @@ fn invalid_unary_ops() {
     "###);
 }
 
+#[test]
+fn infer_block_expr() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn foo() -> i32 {
+        let x = { let a = 1; a + 2 };
+        let y = { x; };
+        x
+    }
+    "#,
+    ), @r###"
+    60..61: unused variable: `y`
+    16..79 '{     ...   x }': i32
+    26..27 'x': i32
+    30..50 '{ let ... + 2 }': i32
+    36..37 'a': i32
+    40..41 '1': i32
+    43..44 'a': i32
+    43..48 'a + 2': i32
+    47..48 '2': i32
+    60..61 'y': ()
+    64..70 '{ x; }': ()
+    66..67 'x': i32
+    76..77 'x': i32
+    "###);
+}
+
+#[test]
+fn infer_unit_type() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn bar() {}
+    fn foo() -> () {
+        let x: () = bar();
+        let y = x;
+        y
+    }
+    "#,
+    ), @r###"
+    9..11 '{}': ()
+    27..74 '{     ...   y }': ()
+    37..38 'x': ()
+    45..48 'bar': function bar() -> ()
+    45..50 'bar()': ()
+    60..61 'y': ()
+    64..65 'x': ()
+    71..72 'y': ()
+    "###);
+}
+
+#[test]
+fn infer_unsupported_tuple_type() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn foo() {
+        let a: (i32, f64) = 1;
+        let b: () = foo();
+    }
+    "#,
+    ), @r###"
+    22..32: tuple types other than `()` are not supported
+    19..20: unused variable: `a`
+    46..47: unused variable: `b`
+    9..62 '{     ...o(); }': ()
+    19..20 'a': i32
+    35..36 '1': i32
+    46..47 'b': ()
+    54..57 'foo': function foo() -> ()
+    54..59 'foo()': ()
+    "###);
+}
+
+#[test]
+fn infer_type_layout() {
+    insta::assert_snapshot!(infer(
+        r#"
+    struct Foo { a: i64, b: i64 }
+    fn foo() -> usize {
+        let a = size_of::<Foo>();
+        let b = align_of::<Bar>();
+        a
+    }
+    "#,
+    ), @r###"
+    103..106: undefined type
+    88..89: unused variable: `b`
+    48..118 '{     ...   a }': usize
+    58..59 'a': usize
+    62..78 'size_o...Foo>()': usize
+    88..89 'b': usize
+    92..109 'align_...Bar>()': usize
+    115..116 'a': usize
+    "###);
+}
+
 #[test]
 fn infer_loop() {
     insta::assert_snapshot!(infer(
@@ -926,6 +1296,10 @@ fn infer_break() {
     71..80: mismatched type
     133..142: mismatched type
     267..276: mismatched type
+    55..83: unreachable code
+    71..80: unreachable code
+    118..119: unused variable: `a`
+    202..203: unused variable: `a`
     14..309 '{     ...type }': never
     20..25 'break': never
     55..83 'loop {...3.0; }': i32
@@ -974,7 +1348,7 @@ fn infer_while() {
     }
     "#),
     @r###"
-    109..116: `break` with value can only appear in a `loop`
+    109..116: `break` with value can only appear in a `loop` or a `while` with an `else` branch
     9..217 '{     ...; }; }': ()
     19..20 'n': i32
     23..24 '0': i32
@@ -1013,6 +1387,102 @@ fn infer_while() {
     "###);
 }
 
+#[test]
+fn infer_while_else() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn foo(n: i32) -> i32 {
+        let i = 0;
+        while i < n {
+            if i * i > n { break i; }
+            i += 1;
+        } else {
+            n
+        }
+    }
+
+    fn bar() {
+        let a = while false { break; } else {};
+        let b: f64 = while true { break 1.0; } else { 2.0 };
+    }
+    "#),
+    @r###"
+    158..159: unused variable: `a`
+    202..203: unused variable: `b`
+    7..8 'n': i32
+    22..137 '{     ...   } }': i32
+    32..33 'i': i32
+    36..37 '0': i32
+    43..135 'while ...     }': i32
+    49..50 'i': i32
+    49..54 'i < n': bool
+    53..54 'n': i32
+    55..112 '{     ...     }': ()
+    65..90 'if i *...k i; }': ()
+    68..69 'i': i32
+    68..73 'i * i': i32
+    68..77 'i * i > n': bool
+    72..73 'i': i32
+    76..77 'n': i32
+    78..90 '{ break i; }': never
+    80..87 'break i': never
+    86..87 'i': i32
+    99..100 'i': i32
+    99..105 'i += 1': ()
+    104..105 '1': i32
+    118..135 '{     ...     }': i32
+    128..129 'n': i32
+    148..252 '{     ...0 }; }': ()
+    158..159 'a': ()
+    162..192 'while ...lse {}': ()
+    168..173 'false': bool
+    174..184 '{ break; }': never
+    176..181 'break': never
+    190..192 '{}': ()
+    202..203 'b': f64
+    211..249 'while ... 2.0 }': f64
+    217..221 'true': bool
+    222..236 '{ break 1.0; }': never
+    224..233 'break 1.0': never
+    230..233 '1.0': f64
+    242..249 '{ 2.0 }': f64
+    244..247 '2.0': f64
+    "###);
+}
+
+#[test]
+fn infer_named_arguments() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn rect(width: i32, height: i32, depth: f64) -> i32 { width }
+
+    fn main() {
+        rect(1, depth: 2.0, height: 3);
+        rect(width: 1, size: 2, width: 3);
+    }
+    "#),
+    @r###"
+    136..137: this function has no parameter named `size`
+    146..147: parameter `width` is specified more than once
+    8..13 'width': i32
+    20..26 'height': i32
+    33..38 'depth': f64
+    52..61 '{ width }': i32
+    54..59 'width': i32
+    73..151 '{     ... 3); }': ()
+    79..83 'rect': function rect(i32, i32, f64) -> i32
+    79..109 'rect(1...ht: 3)': i32
+    84..85 '1': i32
+    94..97 '2.0': f64
+    107..108 '3': i32
+    115..119 'rect': function rect(i32, i32, f64) -> i32
+    115..148 'rect(w...th: 3)': i32
+    127..128 '1': i32
+    136..137 '2': i32
+    146..147 '3': i32
+    "###);
+}
+
 #[test]
 fn invalid_binary_ops() {
     insta::assert_snapshot!(infer(
@@ -1055,6 +1525,9 @@ fn struct_decl() {
     }
     "#),
     @r###"
+    106..109: unused variable: `foo`
+    124..127: unused variable: `bar`
+    142..145: unused variable: `baz`
     96..153 '{     ...Baz; }': ()
     106..109 'foo': Foo
     124..127 'bar': Bar
@@ -1098,6 +1571,17 @@ fn struct_lit() {
     615..618: mismatched struct literal kind. expected `tuple`, found `unit struct`
     712..717: mismatched struct literal kind. expected `tuple`, found `record`
     806..811: this tuple struct literal has 2 fields but 0 fields were supplied
+    82..83: unused variable: `a`
+    104..105: unused variable: `b`
+    139..140: unused variable: `c`
+    166..167: unused variable: `a`
+    266..267: unused variable: `a`
+    365..366: unused variable: `b`
+    463..464: unused variable: `b`
+    557..558: unused variable: `b`
+    611..612: unused variable: `c`
+    708..709: unused variable: `c`
+    802..803: unused variable: `c`
     72..890 '{     ...lied }': ()
     82..83 'a': Foo
     91..94 'Foo': Foo
@@ -1275,6 +1759,7 @@ fn infer_type_alias() {
     "#),
     @r###"
     43..54: undefined type
+    109..110: unused variable: `b`
     91..92 'a': Foo
     99..122 '{     ...= a; }': ()
     109..110 'b': Bar
@@ -1302,6 +1787,9 @@ fn recursive_alias() {
     14..29: the name `Foo` is defined multiple times
     40..41: cyclic type
     52..53: cyclic type
+    76..77: unused variable: `a`
+    116..117: unused variable: `b`
+    156..157: unused variable: `c`
     66..189 '{     ...type }': ()
     76..77 'a': Foo
     116..117 'b': A
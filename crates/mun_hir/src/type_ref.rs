@@ -102,7 +102,7 @@ impl TypeRefMapBuilder {
     /// Lowers the given AST type references and returns the Id of the resulting
     /// `TypeRef`.
     pub fn alloc_from_node(&mut self, node: &ast::TypeRef) -> LocalTypeRefId {
-        use mun_syntax::ast::TypeRefKind::{ArrayType, NeverType, PathType};
+        use mun_syntax::ast::TypeRefKind::{ArrayType, NeverType, PathType, TupleType};
 
         let ptr = AstPtr::new(node);
         let type_ref = match node.kind() {
@@ -112,6 +112,12 @@ impl TypeRefMapBuilder {
                 .map_or(TypeRef::Error, TypeRef::Path),
             NeverType(_) => TypeRef::Never,
             ArrayType(inner) => TypeRef::Array(self.alloc_from_node_opt(inner.type_ref().as_ref())),
+            TupleType(inner) => TypeRef::Tuple(
+                inner
+                    .fields()
+                    .map(|field| self.alloc_from_node(&field))
+                    .collect(),
+            ),
         };
         self.alloc_type_ref(type_ref, ptr)
     }
@@ -1,12 +1,12 @@
 use std::{convert::TryInto, sync::Arc};
 
 pub use crate::fixture::Fixture;
-use crate::{FileId, PackageSet, SourceDatabase, SourceRoot, SourceRootId};
+use crate::{AstDatabase, FileId, PackageSet, SourceRoot, SourceRootId};
 
-impl<DB: SourceDatabase + Default + 'static> WithFixture for DB {}
+impl<DB: AstDatabase + Default + 'static> WithFixture for DB {}
 
 /// Enables the creation of an instance from a [`Fixture`]
-pub trait WithFixture: Default + SourceDatabase + 'static {
+pub trait WithFixture: Default + AstDatabase + 'static {
     /// Constructs an instance from a fixture
     fn with_files(fixture: impl AsRef<str>) -> Self {
         let mut db = Self::default();
@@ -24,7 +24,7 @@ pub trait WithFixture: Default + SourceDatabase + 'static {
 }
 
 /// Fills the specified database with all the files from the specified `fixture`
-fn with_files(db: &mut dyn SourceDatabase, fixture: &str) -> Vec<FileId> {
+fn with_files(db: &mut dyn AstDatabase, fixture: &str) -> Vec<FileId> {
     let fixture = Fixture::parse(fixture);
 
     let mut source_root = SourceRoot::default();
@@ -34,6 +34,7 @@ fn with_files(db: &mut dyn SourceDatabase, fixture: &str) -> Vec<FileId> {
     for (idx, entry) in fixture.into_iter().enumerate() {
         let file_id = FileId(idx.try_into().expect("too many files"));
         db.set_file_text(file_id, Arc::from(entry.text));
+        db.set_incremental_parse(file_id, None);
         db.set_file_source_root(file_id, source_root_id);
         source_root.insert_file(file_id, entry.relative_path);
         files.push(file_id);
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use mun_hir::SourceDatabase;
+use mun_hir::{AstDatabase, SourceDatabase};
 
 use crate::db::AnalysisDatabase;
 
@@ -57,6 +57,7 @@ impl AnalysisDatabase {
         for (file_id, text) in change.files_changed {
             let text = text.unwrap_or_else(|| Arc::from("".to_owned()));
             self.set_file_text(file_id, text);
+            self.set_incremental_parse(file_id, None);
         }
     }
 }
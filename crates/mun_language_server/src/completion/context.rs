@@ -2,7 +2,9 @@ use mun_hir::{
     semantics::{Semantics, SemanticsScope},
     AstDatabase,
 };
-use mun_syntax::{ast, utils::find_node_at_offset, AstNode, SyntaxNode, TextRange, TextSize};
+use mun_syntax::{
+    ast, utils::find_node_at_offset, AstNode, SyntaxNode, SyntaxToken, TextRange, TextSize,
+};
 use ra_ap_text_edit::Indel;
 
 use crate::{db::AnalysisDatabase, FilePosition};
@@ -13,6 +15,9 @@ pub(super) struct CompletionContext<'a> {
     pub scope: SemanticsScope<'a>,
     pub db: &'a AnalysisDatabase,
 
+    /// The token at the position of the cursor in the original file
+    pub token: SyntaxToken,
+
     // TODO: Add this when it is used
     //pub position: FilePosition,
     /// True if the context is currently at a trivial path.
@@ -58,6 +63,7 @@ impl<'a> CompletionContext<'a> {
             sema,
             scope,
             db,
+            token,
             // TODO: add this when it is used
             //position,
             is_trivial_path: false,
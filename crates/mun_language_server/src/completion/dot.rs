@@ -1,5 +1,3 @@
-use mun_hir::Upcast;
-
 use super::{CompletionContext, Completions};
 
 /// Complete dot accesses, i.e. fields. Adds `CompletionItems` to `result`.
@@ -10,18 +8,10 @@ pub(super) fn complete_dot(result: &mut Completions, ctx: &CompletionContext<'_>
         _ => return,
     };
 
-    // Figure out the type of the expression
-    let receiver_ty = match ctx.sema.type_of_expr(dot_receiver) {
-        Some(ty) => ty,
-        _ => return,
-    };
-
     // Get all the fields of the expression
-    if let Some(strukt) = receiver_ty.as_struct() {
-        for field in strukt.fields(ctx.db.upcast()) {
-            result.add_field(ctx, field);
-        }
-    };
+    for field in ctx.sema.receiver_fields(dot_receiver) {
+        result.add_field(ctx, field);
+    }
 }
 
 #[cfg(test)]
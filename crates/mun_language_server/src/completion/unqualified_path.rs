@@ -15,8 +15,9 @@ pub(super) fn complete_unqualified_path(result: &mut Completions, ctx: &Completi
         return;
     }
 
-    // Iterate over all items in the current scope and add completions for them
-    ctx.scope.visit_all_names(&mut |name, def| {
+    // Iterate over all items in the current scope that can be referred to at the
+    // cursor and add completions for them
+    ctx.scope.visit_names_at(&ctx.token, &mut |name, def| {
         result.add_resolution(ctx, name.to_string(), &def);
     });
 }
@@ -38,4 +39,21 @@ mod tests {
             Some(CompletionKind::Reference)
         ));
     }
+
+    #[test]
+    fn test_type_position() {
+        insta::assert_snapshot!(completion_string(
+            r#"
+        struct Foo;
+
+        fn bar() {}
+
+        fn foo() {
+            let baz = 0;
+            let a: $0
+        }
+        "#,
+            Some(CompletionKind::Reference)
+        ));
+    }
 }
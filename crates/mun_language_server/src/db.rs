@@ -37,6 +37,8 @@ impl Default for AnalysisDatabase {
             storage: salsa::Storage::default(),
         };
         db.set_target(Target::host_target().expect("could not determine host target spec"));
+        db.set_nesting_limit(mun_syntax::DEFAULT_NESTING_LIMIT);
+        db.set_script_mode(false);
         db
     }
 }
@@ -1,7 +1,7 @@
 use std::cell::RefCell;
 
 use mun_diagnostics::DiagnosticForWith;
-use mun_hir::{AstDatabase, InFile, ModuleId, PackageId, SourceDatabase};
+use mun_hir::{diagnostics::Severity, AstDatabase, InFile, ModuleId, PackageId, SourceDatabase};
 use mun_syntax::{Location, TextRange};
 
 use crate::db::AnalysisDatabase;
@@ -18,7 +18,7 @@ pub struct Diagnostic {
     pub range: TextRange,
     pub additional_annotations: Vec<SourceAnnotation>,
     // pub fix: Option<SourceChange>,
-    // pub severity: Severity,
+    pub severity: Severity,
 }
 
 /// Converts a location to a a range for use in diagnostics
@@ -39,11 +39,13 @@ pub(crate) fn diagnostics(db: &AnalysisDatabase, file_id: mun_hir::FileId) -> Ve
         message: format!("parse error: {err}"),
         range: location_to_range(err.location()),
         additional_annotations: vec![],
+        severity: Severity::Error,
     }));
 
     // Add all HIR diagnostics
     let result = RefCell::new(result);
     let mut sink = mun_hir::diagnostics::DiagnosticSink::new(|d| {
+        let severity = d.severity();
         result.borrow_mut().push(d.with_diagnostic(db, |d| {
             Diagnostic {
                 message: format!("{}\n{}", d.title(), d.footer().join("\n"))
@@ -58,6 +60,7 @@ pub(crate) fn diagnostics(db: &AnalysisDatabase, file_id: mun_hir::FileId) -> Ve
                         range: annotation.range,
                     })
                     .collect(),
+                severity,
             }
         }));
     });
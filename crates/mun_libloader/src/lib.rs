@@ -1,6 +1,7 @@
 use std::{ffi::c_void, path::Path};
 
 use mun_abi as abi;
+use object::{Object, ObjectSection};
 pub use temp_library::TempLibrary;
 
 mod temp_library;
@@ -120,3 +121,70 @@ impl MunLibrary {
         set_allocator_handle_fn(allocator_ptr);
     }
 }
+
+/// An error that occurs when reading the metadata of a munlib with
+/// [`AssemblyMetadata::read`].
+#[derive(Debug, thiserror::Error)]
+pub enum MetadataError {
+    #[error("Failed to read munlib: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse munlib: {0}")]
+    InvalidObject(#[from] object::Error),
+    #[error("Missing metadata section")]
+    MissingMetadataSection,
+    #[error(transparent)]
+    MalformedMetadata(#[from] abi::MetadataError),
+    #[error("ABI version mismatch. munlib is `{found}` but runtime is `{expected}`")]
+    MismatchedAbiVersion { expected: u32, found: u32 },
+}
+
+/// The function and type metadata embedded in a munlib.
+///
+/// The metadata is read from a dedicated section of the munlib, without
+/// loading it. This is useful for tooling that only needs to inspect a munlib.
+pub struct AssemblyMetadata(abi::AssemblyMetadata);
+
+impl AssemblyMetadata {
+    /// Reads the metadata of the munlib at `library_path`.
+    pub fn read(library_path: &Path) -> Result<Self, MetadataError> {
+        let bytes = std::fs::read(library_path)?;
+        let file = object::File::parse(bytes.as_slice())?;
+        let section = file
+            .section_by_name(abi::METADATA_SECTION_NAME)
+            .or_else(|| file.section_by_name(abi::MACHO_METADATA_SECTION_NAME))
+            .ok_or(MetadataError::MissingMetadataSection)?;
+
+        // Sections can be padded with zeros, which never occur in the metadata itself
+        let data = section.data()?;
+        let len = data.iter().rposition(|&b| b != 0).map_or(0, |idx| idx + 1);
+        let metadata = abi::AssemblyMetadata::from_bytes(&data[..len])?;
+        if abi::ABI_VERSION != metadata.abi_version {
+            return Err(MetadataError::MismatchedAbiVersion {
+                expected: abi::ABI_VERSION,
+                found: metadata.abi_version,
+            });
+        }
+
+        Ok(AssemblyMetadata(metadata))
+    }
+
+    /// Returns the metadata of all functions in the munlib, sorted by name.
+    pub fn functions(&self) -> &[abi::FunctionMetadata] {
+        &self.0.functions
+    }
+
+    /// Returns the metadata of all struct types in the munlib, sorted by name.
+    pub fn structs(&self) -> &[abi::StructMetadata] {
+        &self.0.structs
+    }
+
+    /// Returns the paths of the assemblies that the munlib depends on.
+    pub fn dependencies(&self) -> impl Iterator<Item = &str> {
+        self.0.dependencies.iter().map(String::as_str)
+    }
+
+    /// Returns the underlying metadata.
+    pub fn into_inner(self) -> abi::AssemblyMetadata {
+        self.0
+    }
+}
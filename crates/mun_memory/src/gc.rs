@@ -3,7 +3,7 @@ mod mark_sweep;
 mod ptr;
 mod root_ptr;
 
-use std::{marker::PhantomData, ptr::NonNull};
+use std::{collections::HashMap, marker::PhantomData, ptr::NonNull};
 
 pub use mark_sweep::MarkSweep;
 pub use ptr::{GcPtr, HasIndirectionPtr, RawGcPtr};
@@ -15,6 +15,18 @@ use crate::r#type::Type;
 #[derive(Debug, Clone, Default)]
 pub struct Stats {
     pub allocated_memory: usize,
+    /// The number of write barriers that were invoked
+    pub write_barriers: usize,
+}
+
+/// Contains stats about the objects of a single type that are currently
+/// allocated by a GC implementation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TypeStats {
+    /// The number of allocated objects
+    pub instances: usize,
+    /// The total number of bytes allocated for the objects
+    pub allocated_memory: usize,
 }
 
 /// A trait used to trace an object type.
@@ -75,8 +87,60 @@ pub trait GcRuntime: Send + Sync {
     /// `root` was called before the object can be collected.
     fn unroot(&self, obj: GcPtr);
 
+    /// Unroots all objects, regardless of how many times they were rooted.
+    /// This invalidates all outstanding [`GcRootPtr`]s, which can be detected
+    /// by comparing their generation with [`GcRuntime::root_generation`].
+    ///
+    /// The default implementation does nothing, for runtimes that do not
+    /// support unrooting all objects at once.
+    fn unroot_all(&self) {}
+
+    /// Returns the current root generation, which is incremented every time
+    /// [`GcRuntime::unroot_all`] is called.
+    ///
+    /// The default implementation always returns `0`.
+    fn root_generation(&self) -> usize {
+        0
+    }
+
+    /// Roots the specified `obj` if the current root generation equals
+    /// `generation`. Returns whether the object was rooted.
+    ///
+    /// Implementations must perform the check and the rooting atomically with
+    /// respect to [`GcRuntime::unroot_all`]. The default implementation is
+    /// only correct for runtimes whose root generation never changes.
+    fn root_in_generation(&self, obj: GcPtr, generation: usize) -> bool {
+        let is_current = self.root_generation() == generation;
+        if is_current {
+            self.root(obj);
+        }
+        is_current
+    }
+
+    /// Unroots the specified `obj` if the current root generation equals
+    /// `generation`. Returns whether the object was unrooted.
+    ///
+    /// Implementations must perform the check and the unrooting atomically
+    /// with respect to [`GcRuntime::unroot_all`]. The default implementation
+    /// is only correct for runtimes whose root generation never changes.
+    fn unroot_in_generation(&self, obj: GcPtr, generation: usize) -> bool {
+        let is_current = self.root_generation() == generation;
+        if is_current {
+            self.unroot(obj);
+        }
+        is_current
+    }
+
     /// Returns stats about the current state of the runtime.
     fn stats(&self) -> Stats;
+
+    /// Returns stats about the allocated objects, grouped by their type.
+    ///
+    /// The default implementation returns no stats, for runtimes that do not
+    /// keep track of the types of their objects.
+    fn stats_by_type(&self) -> HashMap<Type, TypeStats> {
+        HashMap::new()
+    }
 }
 
 /// The `Observer` trait allows receiving of `Event`s.
@@ -102,6 +166,9 @@ pub enum Event {
 
     /// A GC cycle ended
     End,
+
+    /// A reference to another object was stored in an object
+    WriteBarrier(GcPtr),
 }
 
 /// A default implementation of an `Observer` which ensures that the compiler
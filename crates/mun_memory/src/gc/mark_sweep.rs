@@ -1,9 +1,10 @@
 use std::{
     alloc::{Layout, LayoutError},
     borrow::Cow,
-    collections::{HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, VecDeque},
     pin::Pin,
     ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
 use mapping::{Mapping, StructMapping};
@@ -13,7 +14,7 @@ use crate::{
     cast,
     gc::{
         array::ArrayHeader, Array as GcArray, Event, GcPtr, GcRuntime, Observer, RawGcPtr, Stats,
-        TypeTrace,
+        TypeStats, TypeTrace,
     },
     mapping::{self, resolve_struct_to_struct_edit, Action, FieldMapping, MemoryMapper},
     r#type::Type,
@@ -99,8 +100,10 @@ impl TraceEvent {
             TypeKind::Primitive(_) | TypeKind::Pointer(_) => None,
             TypeKind::Struct(s) => {
                 return if s.is_gc_struct() {
-                    let deref_ptr = unsafe { ptr.cast::<NonNull<ObjectInfo>>().as_ref() };
-                    Some(TraceEvent::Reference(*deref_ptr))
+                    // A reference to a gc struct can be null, e.g. when a field was added to a
+                    // recursive struct and has not been assigned yet.
+                    let deref_ptr = unsafe { *ptr.cast::<*mut ObjectInfo>().as_ref() };
+                    NonNull::new(deref_ptr).map(TraceEvent::Reference)
                 } else {
                     Some(TraceEvent::InlineStruct(StructTrace {
                         struct_ptr: ptr.cast(),
@@ -183,8 +186,10 @@ where
     O: Observer<Event = Event>,
 {
     objects: RwLock<HashMap<GcPtr, Pin<Box<ObjectInfo>>>>,
+    regions: RwLock<BTreeMap<usize, Region>>,
     observer: O,
     stats: RwLock<Stats>,
+    root_generation: AtomicUsize,
 }
 
 impl<O> Default for MarkSweep<O>
@@ -194,8 +199,10 @@ where
     fn default() -> Self {
         MarkSweep {
             objects: RwLock::new(HashMap::new()),
+            regions: RwLock::new(BTreeMap::new()),
             observer: O::default(),
             stats: RwLock::new(Stats::default()),
+            root_generation: AtomicUsize::new(0),
         }
     }
 }
@@ -208,8 +215,10 @@ where
     pub fn with_observer(observer: O) -> Self {
         Self {
             objects: RwLock::new(HashMap::new()),
+            regions: RwLock::new(BTreeMap::new()),
             observer,
             stats: RwLock::new(Stats::default()),
+            root_generation: AtomicUsize::new(0),
         }
     }
 
@@ -229,6 +238,44 @@ where
     }
 }
 
+/// A block of memory that contains the data of multiple objects, which is
+/// allocated when compacting the heap. The block is freed once the data of all
+/// objects in it has been freed.
+struct Region {
+    layout: Layout,
+    objects: usize,
+}
+
+/// Frees the data of an object, stored at `ptr` with the specified `layout`.
+/// If the data is stored in a region, the region is only freed after the data
+/// of all its objects has been freed.
+///
+/// # Safety
+///
+/// `ptr` must point to the data of an object that has not been freed yet.
+unsafe fn free_object_data(
+    regions: &mut BTreeMap<usize, Region>,
+    ptr: NonNull<u8>,
+    layout: Layout,
+) {
+    let address = ptr.as_ptr() as usize;
+    let region = regions
+        .range_mut(..=address)
+        .next_back()
+        .filter(|(start, region)| address < *start + region.layout.size());
+    match region {
+        Some((&start, region)) => {
+            region.objects -= 1;
+            if region.objects == 0 {
+                let layout = region.layout;
+                regions.remove(&start);
+                std::alloc::dealloc(start as *mut u8, layout);
+            }
+        }
+        None => std::alloc::dealloc(ptr.as_ptr(), layout),
+    }
+}
+
 fn alloc_obj(ty: Type) -> Pin<Box<ObjectInfo>> {
     let ptr = NonNull::new(unsafe { std::alloc::alloc_zeroed(ty.value_layout()) })
         .expect("failed to allocate memory for new object");
@@ -545,9 +592,60 @@ where
         unsafe { (*object_info).roots -= 1 };
     }
 
+    fn unroot_all(&self) {
+        let mut objects = self.objects.write();
+        for obj in objects.values_mut() {
+            unsafe { obj.as_mut().get_unchecked_mut().roots = 0 };
+        }
+        self.root_generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn root_generation(&self) -> usize {
+        self.root_generation.load(Ordering::SeqCst)
+    }
+
+    fn root_in_generation(&self, handle: GcPtr, generation: usize) -> bool {
+        // `unroot_all` increments the generation while holding the same lock
+        let _lock = self.objects.write();
+        if self.root_generation.load(Ordering::SeqCst) != generation {
+            return false;
+        }
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo = handle.into();
+
+        unsafe { (*object_info).roots += 1 };
+        true
+    }
+
+    fn unroot_in_generation(&self, handle: GcPtr, generation: usize) -> bool {
+        // `unroot_all` increments the generation while holding the same lock
+        let _lock = self.objects.write();
+        if self.root_generation.load(Ordering::SeqCst) != generation {
+            return false;
+        }
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo = handle.into();
+
+        unsafe { (*object_info).roots -= 1 };
+        true
+    }
+
     fn stats(&self) -> Stats {
         self.stats.read().clone()
     }
+
+    fn stats_by_type(&self) -> HashMap<Type, TypeStats> {
+        let objects = self.objects.read();
+        let mut stats: HashMap<Type, TypeStats> = HashMap::new();
+        for object in objects.values() {
+            let type_stats = stats.entry(object.ty.clone()).or_default();
+            type_stats.instances += 1;
+            type_stats.allocated_memory += object.layout().size();
+        }
+        stats
+    }
 }
 
 impl<O> MarkSweep<O>
@@ -596,6 +694,7 @@ where
         }
 
         // Sweep all non-reachable objects
+        let mut regions = self.regions.write();
         let size_before = objects.len();
         objects.retain(|h, obj| {
             if obj.color == Color::Black {
@@ -605,7 +704,7 @@ where
                 true
             } else {
                 let value_memory_layout = obj.layout();
-                unsafe { std::alloc::dealloc(obj.data.ptr.as_mut(), value_memory_layout) };
+                unsafe { free_object_data(&mut regions, obj.data.ptr, value_memory_layout) };
                 self.observer.event(Event::Deallocation(*h));
                 {
                     let mut stats = self.stats.write();
@@ -620,6 +719,81 @@ where
 
         size_before != size_after
     }
+
+    /// Moves the data of all objects into a single contiguous block of memory,
+    /// to reduce the fragmentation of the heap. The data is laid out in the
+    /// order of its current address. Returns `true` if any data was moved,
+    /// `false` otherwise.
+    ///
+    /// Objects refer to each other through their handles, which are updated to
+    /// point to the new location of the data, so all [`GcPtr`]s remain valid.
+    /// Pointers to the data of an object that were obtained before compacting,
+    /// e.g. through [`HasIndirectionPtr::deref`](super::HasIndirectionPtr::deref),
+    /// are invalidated.
+    pub fn compact(&self) -> bool {
+        let mut objects = self.objects.write();
+        let mut regions = self.regions.write();
+
+        let mut live_objects = objects
+            .values_mut()
+            .map(|obj| unsafe { NonNull::from(obj.as_mut().get_unchecked_mut()) })
+            .filter(|obj| unsafe { obj.as_ref().layout().size() } > 0)
+            .collect::<Vec<_>>();
+        if live_objects.is_empty() {
+            return false;
+        }
+        live_objects.sort_by_key(|obj| unsafe { obj.as_ref().data.ptr.as_ptr() as usize });
+
+        // Determine where the data of each object is moved to
+        let mut layout = Layout::new::<()>();
+        let offsets = live_objects
+            .iter()
+            .map(|obj| {
+                let (new_layout, offset) = layout
+                    .extend(unsafe { obj.as_ref().layout() })
+                    .expect("unable to create memory layout for compacted heap");
+                layout = new_layout;
+                offset
+            })
+            .collect::<Vec<_>>();
+        let layout = layout.pad_to_align();
+
+        let start = NonNull::new(unsafe { std::alloc::alloc(layout) })
+            .expect("failed to allocate memory for compacted heap");
+        for (mut obj, offset) in live_objects.iter().copied().zip(offsets) {
+            unsafe {
+                let obj = obj.as_mut();
+                let obj_layout = obj.layout();
+                let dest = NonNull::new_unchecked(start.as_ptr().add(offset));
+                std::ptr::copy_nonoverlapping(
+                    obj.data.ptr.as_ptr(),
+                    dest.as_ptr(),
+                    obj_layout.size(),
+                );
+                free_object_data(&mut regions, obj.data.ptr, obj_layout);
+                obj.data.ptr = dest;
+            }
+        }
+        regions.insert(
+            start.as_ptr() as usize,
+            Region {
+                layout,
+                objects: live_objects.len(),
+            },
+        );
+
+        true
+    }
+
+    /// Records that a reference to another object was stored in the object
+    /// referred to by `handle`.
+    pub fn write_barrier(&self, handle: GcPtr) {
+        {
+            let mut stats = self.stats.write();
+            stats.write_barriers += 1;
+        }
+        self.observer.event(Event::WriteBarrier(handle));
+    }
 }
 
 impl<O> MemoryMapper for MarkSweep<O>
@@ -634,6 +808,7 @@ where
         }
 
         fn map_array(
+            regions: &mut BTreeMap<usize, Region>,
             new_allocations: &mut Vec<Pin<Box<ObjectInfo>>>,
             conversions: &HashMap<Type, StructMapping>,
             mut src_object: NonNull<ObjectInfo>,
@@ -673,7 +848,7 @@ where
 
             unsafe {
                 let src_obj = src_object.as_mut();
-                std::alloc::dealloc(src_obj.data.ptr.as_mut(), src_obj.layout());
+                free_object_data(regions, src_obj.data.ptr, src_obj.layout());
                 *src_obj = dest_obj;
             };
         }
@@ -827,10 +1002,15 @@ where
                     // Safety: we already hold a write lock on `objects`, so this is legal.
                     let object = unsafe {
                         *get_field_ptr(src, *old_offset)
-                            .cast::<NonNull<ObjectInfo>>()
+                            .cast::<*mut ObjectInfo>()
                             .as_ref()
                     };
 
+                    // A null reference maps to a zero-initialized in-memory struct
+                    let Some(object) = NonNull::new(object) else {
+                        return;
+                    };
+
                     // Map heap-allocated struct to in-memory struct
                     map_struct(
                         new_allocations,
@@ -917,6 +1097,7 @@ where
         }
 
         let mut objects = self.objects.write();
+        let mut regions = self.regions.write();
 
         // Determine which types are still allocated with deleted types
         let deleted = objects
@@ -970,7 +1151,7 @@ where
                         dest,
                     );
 
-                    unsafe { std::alloc::dealloc(src.as_ptr(), old_layout) };
+                    unsafe { free_object_data(&mut regions, src, old_layout) };
 
                     object_info.set(ObjectInfo {
                         data: ObjectInfoData { ptr: dest },
@@ -1012,6 +1193,7 @@ where
                             resolve_struct_to_struct_edit(&old_element_ty, &new_element_ty, 0);
 
                         map_array(
+                            &mut regions,
                             &mut new_allocations,
                             &mapping.struct_mappings,
                             unsafe {
@@ -3,12 +3,16 @@ use std::sync::{Arc, Weak};
 use crate::gc::{GcPtr, GcRuntime, HasIndirectionPtr};
 
 /// A `GcPtr` that automatically roots and unroots its internal `GcPtr`.
+///
+/// The root is invalidated when all objects are unrooted with
+/// [`GcRuntime::unroot_all`], after which the handle may no longer be used.
 pub struct GcRootPtr<G>
 where
     G: GcRuntime,
 {
     handle: GcPtr,
     runtime: Weak<G>,
+    generation: usize,
 }
 
 impl<G> Clone for GcRootPtr<G>
@@ -17,11 +21,12 @@ where
 {
     fn clone(&self) -> Self {
         if let Some(runtime) = self.runtime.upgrade() {
-            runtime.as_ref().root(self.handle);
+            runtime.root_in_generation(self.handle, self.generation);
         }
         Self {
             handle: self.handle,
             runtime: self.runtime.clone(),
+            generation: self.generation,
         }
     }
 }
@@ -32,13 +37,30 @@ where
 {
     /// Constructs a new [`GcRootPtr`] from a runtime and a handle
     pub fn new(runtime: &Arc<G>, handle: GcPtr) -> Self {
-        runtime.as_ref().root(handle);
+        // Retry if all objects were unrooted between reading the generation and
+        // rooting the handle
+        let generation = loop {
+            let generation = runtime.root_generation();
+            if runtime.root_in_generation(handle, generation) {
+                break generation;
+            }
+        };
         Self {
             handle,
             runtime: Arc::downgrade(runtime),
+            generation,
         }
     }
 
+    /// Returns true if the handle is still rooted, i.e. the runtime is still
+    /// alive and its objects have not been unrooted with
+    /// [`GcRuntime::unroot_all`] since this instance was created.
+    pub fn is_valid(&self) -> bool {
+        self.runtime.upgrade().map_or(false, |runtime| {
+            runtime.root_generation() == self.generation
+        })
+    }
+
     /// Returns the runtime that owns the memory
     pub fn runtime(&self) -> &Weak<G> {
         &self.runtime
@@ -72,7 +94,7 @@ where
 {
     fn drop(&mut self) {
         if let Some(runtime) = self.runtime.upgrade() {
-            runtime.as_ref().unroot(self.handle);
+            runtime.unroot_in_generation(self.handle, self.generation);
         }
     }
 }
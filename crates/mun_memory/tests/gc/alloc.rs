@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use mun_memory::{
-    gc::{Event, GcRootPtr, GcRuntime, MarkSweep},
+    gc::{Event, GcRootPtr, GcRuntime, HasIndirectionPtr, MarkSweep},
     HasStaticType,
 };
 
@@ -68,3 +68,91 @@ fn collect_rooted() {
     assert_eq!(events.next(), Some(Event::End));
     assert_eq!(events.next(), None);
 }
+
+#[test]
+fn collect_unroot_all() {
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+    let rooted_clone = rooted.clone();
+    let handle = rooted.handle();
+    assert!(rooted.is_valid());
+
+    // Unrooting all objects should invalidate all root handles
+    runtime.unroot_all();
+    assert!(!rooted.is_valid());
+    assert!(!rooted_clone.is_valid());
+
+    // Collect unreachable objects, should collect the previously rooted handle
+    runtime.collect();
+
+    // Dropping invalidated root handles should not touch the deallocated object
+    drop(rooted);
+    drop(rooted_clone);
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(handle)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::Deallocation(handle)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
+#[test]
+fn compact() {
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+
+    // Allocate objects and only keep every other one alive
+    let mut rooted = Vec::new();
+    for i in 0..8i64 {
+        let mut handle = runtime.alloc(i64::type_info());
+        unsafe { *handle.deref_mut::<i64>() = i };
+        if i % 2 == 0 {
+            rooted.push(GcRootPtr::new(&runtime, handle));
+        }
+    }
+    runtime.collect();
+
+    assert!(runtime.compact());
+
+    // The data of the remaining objects is stored contiguously, in order
+    let mut addresses = rooted
+        .iter()
+        .map(|root| unsafe { root.handle().deref::<i64>() } as usize)
+        .collect::<Vec<_>>();
+    addresses.sort_unstable();
+    for pair in addresses.windows(2) {
+        assert_eq!(pair[1] - pair[0], std::mem::size_of::<i64>());
+    }
+
+    // The handles still refer to the original values
+    for (root, value) in rooted.iter().zip((0..8i64).step_by(2)) {
+        assert_eq!(unsafe { *root.handle().deref::<i64>() }, value);
+    }
+
+    // Compacting again moves the objects out of the previous block of memory
+    assert!(runtime.compact());
+    for (root, value) in rooted.iter().zip((0..8i64).step_by(2)) {
+        assert_eq!(unsafe { *root.handle().deref::<i64>() }, value);
+    }
+
+    // Objects in a compacted block of memory can still be collected
+    let handles = rooted
+        .into_iter()
+        .map(|root| root.unroot())
+        .collect::<Vec<_>>();
+    runtime.collect();
+    assert!(!runtime.compact());
+
+    let deallocations = runtime
+        .observer()
+        .take_all()
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::Deallocation(handle) => Some(handle),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(deallocations.len(), 8);
+    assert!(handles.iter().all(|handle| deallocations.contains(handle)));
+}
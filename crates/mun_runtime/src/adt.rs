@@ -1,30 +1,48 @@
 use std::{
+    marker::PhantomData,
     ptr::{self, NonNull},
     sync::Arc,
 };
 
+use mun_abi::Guid;
 use mun_memory::{
-    gc::{GcPtr, GcRuntime, HasIndirectionPtr},
-    Type,
+    gc::{GcPtr, GcRuntime, HasIndirectionPtr, RawGcPtr},
+    Field, Type,
 };
 
 use crate::{
-    garbage_collector::GcRootPtr,
+    garbage_collector::{GcRootPtr, InvalidRootError},
     marshal::Marshal,
     reflection::{ArgumentReflection, ReturnTypeReflection},
     GarbageCollector, Runtime,
 };
 
 /// Represents a Mun struct pointer.
+///
+/// The pointer refers to the handle of the struct rather than to its memory.
+/// The garbage collector updates the handle when it moves the memory of the
+/// struct, e.g. when compacting the heap or hot reloading a struct's type, so
+/// the pointer remains valid for as long as the struct is alive.
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct RawStruct(GcPtr);
 
 impl RawStruct {
-    /// Returns a pointer to the struct memory.
+    /// Returns a pointer to the struct memory. The pointer is invalidated when
+    /// the garbage collector moves the memory of the struct.
     pub unsafe fn get_ptr(&self) -> *const u8 {
         self.0.deref()
     }
+
+    /// Returns true if this is a null reference to a gc struct.
+    pub fn is_null(&self) -> bool {
+        RawGcPtr::from(self.0).is_null()
+    }
+
+    /// Constructs a null reference to a gc struct.
+    fn null() -> Self {
+        RawStruct(ptr::null::<*mut std::ffi::c_void>().into())
+    }
 }
 
 /// Type-agnostic wrapper for interoperability with a Mun struct. This is merely
@@ -50,6 +68,39 @@ impl<'s> StructRef<'s> {
         self.raw
     }
 
+    /// Returns the raw pointer to the garbage collector handle of the struct.
+    ///
+    /// The garbage collector does not track the returned pointer. Unless the
+    /// struct is rooted, e.g. by calling [`StructRef::root`], it can be
+    /// collected after which the pointer dangles.
+    pub fn as_ptr(&self) -> RawGcPtr {
+        self.raw.0.into()
+    }
+
+    /// Constructs a `StructRef` from a raw pointer that was previously
+    /// obtained through [`StructRef::as_ptr`]. `ty` is the type of the struct,
+    /// as obtained from the runtime.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a live struct that was allocated by the garbage
+    /// collector of `runtime`. The caller is responsible for keeping the
+    /// struct alive, i.e. rooted, for as long as the returned `StructRef` is
+    /// used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type of the struct does not equal `ty`.
+    pub unsafe fn from_raw(runtime: &'s Runtime, ptr: RawGcPtr, ty: &Type) -> Self {
+        let raw = RawStruct(ptr.into());
+        assert_eq!(
+            &runtime.gc.ptr_type(raw.0),
+            ty,
+            "the type of the struct does not match"
+        );
+        Self::new(raw, runtime)
+    }
+
     /// Roots the `StructRef`.
     pub fn root(self) -> RootedStruct {
         RootedStruct::new(&self.runtime.gc, self.raw)
@@ -60,16 +111,35 @@ impl<'s> StructRef<'s> {
         self.runtime.gc.ptr_type(self.raw.0)
     }
 
-    /// Returns the struct's field at the specified `offset`.
+    /// Returns the name of the struct's type, including the path of the module
+    /// in which it is defined, e.g. `foo::Bar`.
     ///
-    /// # Safety
-    ///
-    /// The offset must be the location of a variable of type T.
-    unsafe fn get_field_ptr_unchecked<T>(&self, offset: usize) -> NonNull<T> {
-        // SAFETY: self.raw's memory pointer is never null
-        let ptr = self.raw.get_ptr();
+    /// Type names are validated to be UTF-8 when the types of an assembly are
+    /// loaded, so no conversion is needed here.
+    pub fn type_name(&self) -> &str {
+        let ty = self.type_info();
+        // Safety: The type of the struct is kept alive by the type table of the
+        // runtime, which can only be replaced by reloading assemblies. That
+        // requires a mutable borrow of the runtime, which is prevented while this
+        // `StructRef` borrows it.
+        unsafe { &*(ty.name() as *const str) }
+    }
 
-        NonNull::new_unchecked(ptr.add(offset).cast::<T>() as *mut T)
+    /// Returns an identifier of the struct's type that is derived from its
+    /// fully qualified name and the names and types of its fields, in
+    /// declaration order. Fields of garbage collected struct types only
+    /// contribute the name of that type.
+    ///
+    /// Unlike the [`Type`] itself, the identifier is stable across
+    /// compilations and assemblies. It is not derived from the memory layout:
+    /// for instance, changing whether the struct is a `gc` or `value` struct
+    /// does not change its identifier.
+    pub fn type_id(&self) -> Guid {
+        *self
+            .type_info()
+            .as_struct()
+            .expect("type of a struct must be a struct type")
+            .guid()
     }
 
     /// Retrieves the value of the field corresponding to the specified
@@ -78,39 +148,15 @@ impl<'s> StructRef<'s> {
     where
         T: 's,
     {
-        let type_info = self.type_info();
-
-        // Safety: `as_struct` is guaranteed to return `Some` for `StructRef`s.
-        let struct_info = type_info.as_struct().unwrap();
-
-        let field_info = struct_info
-            .fields()
-            .find_by_name(field_name)
-            .ok_or_else(|| {
-                format!(
-                    "Struct `{}` does not contain field `{}`.",
-                    type_info.name(),
-                    field_name
-                )
-            })?;
-
-        if !T::accepts_type(&field_info.ty()) {
-            return Err(format!(
-                "Mismatched types for `{}::{}`. Expected: `{}`. Found: `{}`.",
-                type_info.name(),
+        // SAFETY: The struct memory matches its type.
+        unsafe {
+            get_field(
+                self.raw.get_ptr(),
+                &self.type_info(),
+                self.runtime,
                 field_name,
-                T::type_hint(),
-                field_info.ty().name(),
-            ));
-        };
-
-        // SAFETY: The offset in the ABI is always valid.
-        let field_ptr = unsafe { self.get_field_ptr_unchecked::<T::MunType>(field_info.offset()) };
-        Ok(Marshal::marshal_from_ptr(
-            field_ptr,
-            self.runtime,
-            &field_info.ty(),
-        ))
+            )
+        }
     }
 
     /// Replaces the value of the field corresponding to the specified
@@ -123,78 +169,322 @@ impl<'s> StructRef<'s> {
     where
         T: 's,
     {
-        let type_info = self.type_info();
+        // SAFETY: The struct memory matches its type.
+        unsafe {
+            replace_field(
+                self.raw.get_ptr(),
+                &self.type_info(),
+                self.runtime,
+                field_name,
+                value,
+            )
+        }
+    }
 
-        // Safety: `as_struct` is guaranteed to return `Some` for `StructRef`s.
-        let struct_info = type_info.as_struct().unwrap();
+    /// Sets the value of the field corresponding to the specified `field_name`.
+    pub fn set<T: ArgumentReflection + Marshal<'s>>(
+        &mut self,
+        field_name: &str,
+        value: T,
+    ) -> Result<(), String> {
+        // SAFETY: The struct memory matches its type.
+        unsafe {
+            set_field(
+                self.raw.get_ptr(),
+                &self.type_info(),
+                self.runtime,
+                field_name,
+                value,
+            )
+        }
+    }
 
-        let field_info = struct_info
-            .fields()
-            .find_by_name(field_name)
-            .ok_or_else(|| {
-                format!(
-                    "Struct `{}` does not contain field `{}`.",
-                    type_info.name(),
-                    field_name
-                )
-            })?;
-
-        let value_type = value.type_info(self.runtime);
-        if field_info.ty() != value_type {
-            return Err(format!(
-                "Mismatched types for `{}::{}`. Expected: `{}`. Found: `{}`.",
-                type_info.name(),
+    /// Returns a view of the struct stored in the field corresponding to the
+    /// specified `field_name`, without copying it.
+    ///
+    /// Contrary to [`StructRef::get`], which copies value structs, the view
+    /// aliases the memory of this struct. Modifying a field through the view
+    /// modifies this struct.
+    ///
+    /// The view is not an exclusive reference to the memory of the struct.
+    /// Although it borrows this `StructRef`, clones of it and other
+    /// references to the same struct can still read and modify the memory
+    /// while the view is in use.
+    pub fn field_ref(&mut self, field_name: &str) -> Result<StructFieldRef<'_>, String> {
+        // SAFETY: The struct memory matches its type.
+        unsafe {
+            field_ref(
+                self.raw.clone(),
+                0,
+                &self.type_info(),
+                self.runtime,
                 field_name,
-                value_type.name(),
-                field_info.ty()
-            ));
+            )
         }
+    }
+}
+
+/// A view of a struct stored in a field of another struct, that is obtained
+/// through [`StructRef::field_ref`]. Reading and writing the fields of the view
+/// directly accesses the memory of the parent struct.
+///
+/// The view borrows the [`StructRef`] or view it was obtained from, but it does
+/// not have exclusive access to the memory of the parent struct: other
+/// references to the same struct, e.g. clones of the [`StructRef`], can still
+/// modify it. Every access reads the memory anew through the handle of the
+/// parent struct, so such changes are observed by the view. Like a
+/// [`StructRef`], the view does not root the parent struct, so it must not be
+/// used after the parent struct was collected.
+pub struct StructFieldRef<'p> {
+    /// The gc struct that contains the memory of the view
+    raw: RawStruct,
+    /// The offset of the view in the memory of `raw`
+    offset: usize,
+    type_info: Type,
+    runtime: &'p Runtime,
+    _parent: PhantomData<&'p mut ()>,
+}
+
+impl<'p> StructFieldRef<'p> {
+    /// Returns the type information of the struct.
+    pub fn type_info(&self) -> &Type {
+        &self.type_info
+    }
+
+    /// Returns a pointer to the memory of the struct. The pointer is resolved
+    /// through the handle of the containing gc struct, as its memory might
+    /// have been moved by the garbage collector.
+    unsafe fn ptr(&self) -> *const u8 {
+        self.raw.get_ptr().add(self.offset)
+    }
 
-        // SAFETY: The offset in the ABI is always valid.
-        let field_ptr = unsafe { self.get_field_ptr_unchecked::<T::MunType>(field_info.offset()) };
-        let old = Marshal::marshal_from_ptr(field_ptr, self.runtime, &field_info.ty());
-        Marshal::marshal_to_ptr(value, field_ptr, &field_info.ty());
-        Ok(old)
+    /// Retrieves the value of the field corresponding to the specified
+    /// `field_name`.
+    pub fn get<T: ReturnTypeReflection + Marshal<'p>>(&self, field_name: &str) -> Result<T, String>
+    where
+        T: 'p,
+    {
+        // SAFETY: The view points to struct memory that matches its type.
+        unsafe { get_field(self.ptr(), &self.type_info, self.runtime, field_name) }
+    }
+
+    /// Replaces the value of the field corresponding to the specified
+    /// `field_name` and returns the old value.
+    pub fn replace<T: ArgumentReflection + Marshal<'p>>(
+        &mut self,
+        field_name: &str,
+        value: T,
+    ) -> Result<T, String>
+    where
+        T: 'p,
+    {
+        // SAFETY: The view points to struct memory that matches its type.
+        unsafe { replace_field(self.ptr(), &self.type_info, self.runtime, field_name, value) }
     }
 
     /// Sets the value of the field corresponding to the specified `field_name`.
-    pub fn set<T: ArgumentReflection + Marshal<'s>>(
+    pub fn set<T: ArgumentReflection + Marshal<'p>>(
         &mut self,
         field_name: &str,
         value: T,
     ) -> Result<(), String> {
-        let type_info = self.type_info();
+        // SAFETY: The view points to struct memory that matches its type.
+        unsafe { set_field(self.ptr(), &self.type_info, self.runtime, field_name, value) }
+    }
 
-        // Safety: `as_struct` is guaranteed to return `Some` for `StructRef`s.
-        let struct_info = type_info.as_struct().unwrap();
+    /// Returns a view of the struct stored in the field corresponding to the
+    /// specified `field_name`, without copying it.
+    pub fn field_ref(&mut self, field_name: &str) -> Result<StructFieldRef<'_>, String> {
+        // SAFETY: The view points to struct memory that matches its type.
+        unsafe {
+            field_ref(
+                self.raw.clone(),
+                self.offset,
+                &self.type_info,
+                self.runtime,
+                field_name,
+            )
+        }
+    }
+}
+
+/// Returns the information of the field corresponding to the specified
+/// `field_name` of the struct type `type_info`.
+fn find_field<'t>(type_info: &'t Type, field_name: &str) -> Result<Field<'t>, String> {
+    // Safety: `as_struct` is guaranteed to return `Some` for struct memory.
+    let struct_info = type_info.as_struct().unwrap();
+
+    struct_info
+        .fields()
+        .find_by_name(field_name)
+        .ok_or_else(|| {
+            format!(
+                "Struct `{}` does not contain field `{}`.",
+                type_info.name(),
+                field_name
+            )
+        })
+}
+
+/// Retrieves the value of the field corresponding to the specified
+/// `field_name` of the struct stored at `ptr`.
+///
+/// # Safety
+///
+/// `ptr` must point to the memory of a struct of type `type_info`.
+unsafe fn get_field<'s, T: ReturnTypeReflection + Marshal<'s>>(
+    ptr: *const u8,
+    type_info: &Type,
+    runtime: &'s Runtime,
+    field_name: &str,
+) -> Result<T, String>
+where
+    T: 's,
+{
+    let field_info = find_field(type_info, field_name)?;
+    if !T::accepts_type(&field_info.ty()) {
+        return Err(format!(
+            "Mismatched types for `{}::{}`. Expected: `{}`. Found: `{}`.",
+            type_info.name(),
+            field_name,
+            T::type_hint(),
+            field_info.ty().name(),
+        ));
+    };
+
+    // SAFETY: The offset in the ABI is always valid.
+    let field_ptr = NonNull::new_unchecked(ptr.add(field_info.offset()) as *mut T::MunType);
+    Ok(Marshal::marshal_from_ptr(
+        field_ptr,
+        runtime,
+        &field_info.ty(),
+    ))
+}
+
+/// Returns a pointer to the field corresponding to the specified `field_name`
+/// of the struct stored at `ptr`, after checking that `value` can be stored in
+/// it.
+///
+/// # Safety
+///
+/// `ptr` must point to the memory of a struct of type `type_info`.
+unsafe fn field_ptr_for_value<'t, 's, T: ArgumentReflection + Marshal<'s>>(
+    ptr: *const u8,
+    type_info: &'t Type,
+    runtime: &Runtime,
+    field_name: &str,
+    value: &T,
+) -> Result<(NonNull<T::MunType>, Field<'t>), String> {
+    let field_info = find_field(type_info, field_name)?;
+    let value_type = value.type_info(runtime);
+    if field_info.ty() != value_type {
+        return Err(format!(
+            "Mismatched types for `{}::{}`. Expected: `{}`. Found: `{}`.",
+            type_info.name(),
+            field_name,
+            value_type.name(),
+            field_info.ty()
+        ));
+    }
+
+    // SAFETY: The offset in the ABI is always valid.
+    let field_ptr = NonNull::new_unchecked(ptr.add(field_info.offset()) as *mut T::MunType);
+    Ok((field_ptr, field_info))
+}
+
+/// Sets the value of the field corresponding to the specified `field_name` of
+/// the struct stored at `ptr`.
+///
+/// # Safety
+///
+/// `ptr` must point to the memory of a struct of type `type_info`.
+unsafe fn set_field<'s, T: ArgumentReflection + Marshal<'s>>(
+    ptr: *const u8,
+    type_info: &Type,
+    runtime: &'s Runtime,
+    field_name: &str,
+    value: T,
+) -> Result<(), String> {
+    let (field_ptr, field_info) = field_ptr_for_value(ptr, type_info, runtime, field_name, &value)?;
+    Marshal::marshal_to_ptr(value, field_ptr, &field_info.ty());
+    Ok(())
+}
+
+/// Replaces the value of the field corresponding to the specified `field_name`
+/// of the struct stored at `ptr` and returns the old value.
+///
+/// # Safety
+///
+/// `ptr` must point to the memory of a struct of type `type_info`.
+unsafe fn replace_field<'s, T: ArgumentReflection + Marshal<'s>>(
+    ptr: *const u8,
+    type_info: &Type,
+    runtime: &'s Runtime,
+    field_name: &str,
+    value: T,
+) -> Result<T, String>
+where
+    T: 's,
+{
+    let (field_ptr, field_info) = field_ptr_for_value(ptr, type_info, runtime, field_name, &value)?;
+    let old = Marshal::marshal_from_ptr(field_ptr, runtime, &field_info.ty());
+    Marshal::marshal_to_ptr(value, field_ptr, &field_info.ty());
+    Ok(old)
+}
 
-        let field_info = struct_info
-            .fields()
-            .find_by_name(field_name)
-            .ok_or_else(|| {
-                format!(
-                    "Struct `{}` does not contain field `{}`.",
-                    type_info.name(),
-                    field_name
-                )
-            })?;
-
-        let value_type = value.type_info(self.runtime);
-        if field_info.ty() != value_type {
+/// Returns a view of the struct stored in the field corresponding to the
+/// specified `field_name` of the struct stored at `offset` in the memory of the
+/// gc struct `raw`.
+///
+/// # Safety
+///
+/// The memory of `raw` must contain a struct of type `type_info` at `offset`.
+unsafe fn field_ref<'p>(
+    raw: RawStruct,
+    offset: usize,
+    type_info: &Type,
+    runtime: &'p Runtime,
+    field_name: &str,
+) -> Result<StructFieldRef<'p>, String> {
+    let field_info = find_field(type_info, field_name)?;
+    let field_type = field_info.ty();
+    let is_value_struct = match field_type.as_struct() {
+        Some(struct_info) => struct_info.is_value_struct(),
+        None => {
             return Err(format!(
-                "Mismatched types for `{}::{}`. Expected: `{}`. Found: `{}`.",
+                "Field `{}::{}` is not a struct. Found: `{}`.",
                 type_info.name(),
                 field_name,
-                value_type.name(),
-                field_info.ty()
+                field_type.name(),
+            ))
+        }
+    };
+
+    // SAFETY: The offset in the ABI is always valid.
+    let field_offset = offset + field_info.offset();
+    let (raw, offset) = if is_value_struct {
+        // A value struct is stored inline
+        (raw, field_offset)
+    } else {
+        // A gc struct is stored as a reference to its memory
+        let field_raw = (*raw.get_ptr().add(field_offset).cast::<RawStruct>()).clone();
+        if field_raw.is_null() {
+            return Err(format!(
+                "Field `{}::{}` is null.",
+                type_info.name(),
+                field_name
             ));
         }
-
-        // SAFETY: The offset in the ABI is always valid.
-        let field_ptr = unsafe { self.get_field_ptr_unchecked::<T::MunType>(field_info.offset()) };
-        Marshal::marshal_to_ptr(value, field_ptr, &field_info.ty());
-        Ok(())
-    }
+        (field_raw, 0)
+    };
+
+    Ok(StructFieldRef {
+        raw,
+        offset,
+        type_info: field_type,
+        runtime,
+        _parent: PhantomData,
+    })
 }
 
 impl<'r> ArgumentReflection for StructRef<'r> {
@@ -279,6 +569,62 @@ impl<'r> ReturnTypeReflection for StructRef<'r> {
     }
 }
 
+impl<'s> Marshal<'s> for Option<StructRef<'s>> {
+    type MunType = RawStruct;
+
+    fn marshal_from<'r>(value: Self::MunType, runtime: &'r Runtime) -> Self
+    where
+        'r: 's,
+    {
+        (!value.is_null()).then(|| StructRef::new(value, runtime))
+    }
+
+    fn marshal_into<'r>(self) -> Self::MunType {
+        self.map_or_else(RawStruct::null, StructRef::into_raw)
+    }
+
+    fn marshal_from_ptr<'r>(
+        ptr: NonNull<Self::MunType>,
+        runtime: &'r Runtime,
+        type_info: &Type,
+    ) -> Option<StructRef<'s>>
+    where
+        Self: 's,
+        'r: 's,
+    {
+        let struct_info = type_info.as_struct().unwrap();
+
+        // Only references to gc structs can be null
+        if struct_info.is_gc_struct() && unsafe { ptr.as_ref() }.is_null() {
+            None
+        } else {
+            Some(StructRef::marshal_from_ptr(ptr, runtime, type_info))
+        }
+    }
+
+    fn marshal_to_ptr(value: Self, mut ptr: NonNull<Self::MunType>, type_info: &Type) {
+        match value {
+            Some(value) => StructRef::marshal_to_ptr(value, ptr, type_info),
+            None => {
+                debug_assert!(type_info.as_struct().unwrap().is_gc_struct());
+                unsafe { *ptr.as_mut() = RawStruct::null() };
+            }
+        }
+    }
+}
+
+impl<'r> ReturnTypeReflection for Option<StructRef<'r>> {
+    /// Returns true if this specified type can be stored in an instance of this
+    /// type. Only references to gc structs can be null.
+    fn accepts_type(ty: &Type) -> bool {
+        ty.as_struct().map_or(false, |s| s.is_gc_struct())
+    }
+
+    fn type_hint() -> &'static str {
+        "struct"
+    }
+}
+
 /// Type-agnostic wrapper for interoperability with a Mun struct, that has been
 /// rooted. To marshal, obtain a `StructRef` for the `RootedStruct`.
 #[derive(Clone)]
@@ -297,8 +643,24 @@ impl RootedStruct {
 
     /// Converts the `RootedStruct` into a `StructRef`, using an external shared
     /// reference to a `Runtime`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `RootedStruct` was invalidated by
+    /// [`Runtime::reset_heap`].
     pub fn as_ref<'r>(&self, runtime: &'r Runtime) -> StructRef<'r> {
+        self.try_as_ref(runtime)
+            .expect("cannot access an invalidated struct")
+    }
+
+    /// Converts the `RootedStruct` into a `StructRef`, using an external shared
+    /// reference to a `Runtime`. Returns an error if the `RootedStruct` was
+    /// invalidated by [`Runtime::reset_heap`].
+    pub fn try_as_ref<'r>(&self, runtime: &'r Runtime) -> Result<StructRef<'r>, InvalidRootError> {
         assert_eq!(Arc::as_ptr(&runtime.gc), self.handle.runtime().as_ptr());
-        StructRef::new(RawStruct(self.handle.handle()), runtime)
+        if !self.handle.is_valid() {
+            return Err(InvalidRootError);
+        }
+        Ok(StructRef::new(RawStruct(self.handle.handle()), runtime))
     }
 }
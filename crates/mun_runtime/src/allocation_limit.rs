@@ -0,0 +1,71 @@
+use std::cell::RefCell;
+
+use mun_memory::gc::GcPtr;
+
+use crate::execution_error::{set_execution_error, ExecutionError};
+
+/// The number of objects that were allocated by the current top-level
+/// invocation, and the maximum that it is allowed to allocate.
+struct AllocationCounter {
+    count: usize,
+    limit: usize,
+}
+
+thread_local! {
+    /// The allocation counter of the top-level invocation on this thread, if
+    /// its runtime limits the number of allocations.
+    static ALLOCATION_COUNTER: RefCell<Option<AllocationCounter>> = RefCell::new(None);
+}
+
+/// Stops counting allocations when the top-level invocation that started it
+/// ends.
+pub(crate) struct AllocationLimitGuard {
+    is_top_level: bool,
+}
+
+impl Drop for AllocationLimitGuard {
+    fn drop(&mut self) {
+        if self.is_top_level {
+            ALLOCATION_COUNTER.with(|counter| counter.replace(None));
+        }
+    }
+}
+
+/// Starts counting allocations against `limit`, unless allocations are already
+/// counted for an enclosing invocation. Nested invocations share the counter of
+/// the top-level invocation.
+pub(crate) fn enter(limit: Option<usize>) -> AllocationLimitGuard {
+    ALLOCATION_COUNTER.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let is_top_level = counter.is_none() && limit.is_some();
+        if let Some(limit) = limit.filter(|_| is_top_level) {
+            *counter = Some(AllocationCounter { count: 0, limit });
+        }
+        AllocationLimitGuard { is_top_level }
+    })
+}
+
+/// Allocates an object with `alloc`, counting the allocation against the limit
+/// of the current invocation.
+///
+/// Once the limit is exceeded, an [`ExecutionError::AllocationLimitExceeded`]
+/// is recorded and `None` is returned without allocating. The generated code
+/// returns from a function as soon as one of its allocations fails, after
+/// which the invocation fails with the recorded error.
+pub(crate) fn allocate(alloc: impl FnOnce() -> GcPtr) -> Option<GcPtr> {
+    let exceeded = ALLOCATION_COUNTER.with(|counter| {
+        let mut counter = counter.borrow_mut();
+        let Some(current) = counter.as_mut() else {
+            return false;
+        };
+        current.count += 1;
+        current.count > current.limit
+    });
+
+    if exceeded {
+        set_execution_error(ExecutionError::AllocationLimitExceeded);
+        None
+    } else {
+        Some(alloc())
+    }
+}
@@ -6,8 +6,8 @@ use mun_memory::{
 };
 
 use crate::{
-    garbage_collector::GcRootPtr, ArgumentReflection, GarbageCollector, Marshal,
-    ReturnTypeReflection, Runtime,
+    garbage_collector::{GcRootPtr, InvalidRootError},
+    ArgumentReflection, GarbageCollector, Marshal, ReturnTypeReflection, Runtime,
 };
 
 /// Represents a Mun array pointer.
@@ -90,6 +90,10 @@ impl<'array, T: Marshal<'array> + 'array> ArrayRef<'array, T> {
     }
 
     /// Returns an iterator to iterate over the elements of the array.
+    ///
+    /// Every element is looked up through the handle of the array when it is
+    /// returned, so the iterator remains valid if the heap is compacted by a
+    /// garbage collection while iterating.
     pub fn iter(&self) -> impl Iterator<Item = T> + 'array
     where
         T: 'array,
@@ -101,10 +105,20 @@ impl<'array, T: Marshal<'array> + 'array> ArrayRef<'array, T> {
             .array(self.raw.0)
             .expect("type of the array value must be an array");
         let element_ty = handle.element_type();
+        let raw = self.raw.0;
         let runtime = self.runtime;
-        handle
-            .elements()
-            .map(move |element_ptr| T::marshal_from_ptr(element_ptr.cast(), runtime, &element_ty))
+        (0..handle.length()).map(move |index| {
+            let handle = runtime
+                .gc
+                .as_ref()
+                .array(raw)
+                .expect("type of the array value must be an array");
+            // Safety: `index` is smaller than the length of the array
+            let element_ptr = unsafe {
+                NonNull::new_unchecked(handle.data().as_ptr().add(index * handle.element_stride()))
+            };
+            T::marshal_from_ptr(element_ptr.cast(), runtime, &element_ty)
+        })
     }
 }
 
@@ -182,11 +196,30 @@ impl<T> RootedArray<T> {
 
     /// Converts the `RootedArray` into an `ArrayRef<T>`, using an external
     /// shared reference to a `Runtime`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `RootedArray` was invalidated by
+    /// [`Runtime::reset_heap`].
     pub fn as_ref<'r>(&self, runtime: &'r Runtime) -> ArrayRef<'r, T>
+    where
+        T: Marshal<'r> + 'r,
+    {
+        self.try_as_ref(runtime)
+            .expect("cannot access an invalidated array")
+    }
+
+    /// Converts the `RootedArray` into an `ArrayRef<T>`, using an external
+    /// shared reference to a `Runtime`. Returns an error if the `RootedArray`
+    /// was invalidated by [`Runtime::reset_heap`].
+    pub fn try_as_ref<'r>(&self, runtime: &'r Runtime) -> Result<ArrayRef<'r, T>, InvalidRootError>
     where
         T: Marshal<'r> + 'r,
     {
         assert_eq!(Arc::as_ptr(&runtime.gc), self.handle.runtime().as_ptr());
-        ArrayRef::new(RawArray(self.handle.handle()), runtime)
+        if !self.handle.is_valid() {
+            return Err(InvalidRootError);
+        }
+        Ok(ArrayRef::new(RawArray(self.handle.handle()), runtime))
     }
 }
@@ -85,6 +85,19 @@ pub enum LinkFunctionsError {
     },
 }
 
+/// An entry in the dispatch table of an [`Assembly`]. Calls to functions that
+/// are not defined in the assembly itself, e.g. extern functions or functions
+/// from other assemblies, are made through these entries.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DispatchEntry {
+    /// The name of the function
+    pub name: String,
+    /// Whether a function pointer has been linked for this entry
+    pub is_resolved: bool,
+    /// The path to the library of the assembly that contains the entry
+    pub assembly_path: PathBuf,
+}
+
 /// An assembly is a hot reloadable compilation unit, consisting of one or more
 /// Mun modules.
 pub struct Assembly {
@@ -466,9 +479,55 @@ impl Assembly {
         self.library_path.as_path()
     }
 
+    /// Returns the entries of the assembly's dispatch table and whether they
+    /// have been linked.
+    pub fn dispatch_entries(&self) -> impl Iterator<Item = DispatchEntry> + '_ {
+        self.info
+            .dispatch_table
+            .iter()
+            .map(|(fn_ptr, fn_prototype)| DispatchEntry {
+                name: fn_prototype.name().to_string(),
+                is_resolved: !fn_ptr.is_null(),
+                assembly_path: self.library_path.clone(),
+            })
+    }
+
     /// Converts the `Assembly` into a `TempLibrary`, consuming the input in the
     /// process.
     pub fn into_library(self) -> TempLibrary {
         self.library
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use mun_test::CompileTestDriver;
+
+    use super::Assembly;
+    use crate::garbage_collector::GarbageCollector;
+
+    #[test]
+    fn dispatch_entries_unresolved_extern() {
+        let driver = CompileTestDriver::from_file(
+            r#"
+        extern fn add(a: i32, b: i32) -> i32;
+
+        pub fn main() -> i32 { add(1, 2) }
+        "#,
+        );
+
+        // Safety: We compiled the library ourselves, therefor loading the munlib is
+        // safe.
+        let assembly =
+            unsafe { Assembly::load(driver.lib_path(), Arc::new(GarbageCollector::default())) }
+                .expect("failed to load assembly");
+
+        let entries: Vec<_> = assembly.dispatch_entries().collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "add");
+        assert!(!entries[0].is_resolved);
+        assert_eq!(entries[0].assembly_path, driver.lib_path());
+    }
+}
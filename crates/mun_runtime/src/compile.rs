@@ -0,0 +1,63 @@
+use mun_compiler::{Config, DisplayColor, Driver, PathOrInline, RelativePathBuf};
+
+use crate::{InitError, Runtime};
+
+/// An error that occurs when compiling and loading Mun source with
+/// [`Runtime::compile_and_load_str`].
+#[derive(Debug, thiserror::Error)]
+pub enum CompileAndLoadError {
+    /// The source contains errors. Contains the rendered diagnostics.
+    #[error("failed to compile the source:\n{0}")]
+    Diagnostics(String),
+    /// The compiler failed to produce an assembly
+    #[error("failed to compile the source: {0}")]
+    Compile(Box<dyn std::error::Error + Send + Sync>),
+    /// Failed to create a temporary output directory
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Failed to construct the runtime
+    #[error(transparent)]
+    Init(#[from] InitError),
+}
+
+impl Runtime {
+    /// Compiles a single Mun source file to an assembly in a temporary
+    /// directory and constructs a runtime that has it loaded. Compiler errors
+    /// are returned as [`CompileAndLoadError::Diagnostics`].
+    ///
+    /// Hot reloading is disabled for the returned runtime, because the source
+    /// only exists in memory.
+    pub fn compile_and_load_str(source: &str) -> Result<Runtime, CompileAndLoadError> {
+        let out_dir = tempfile::TempDir::new()?;
+        let config = Config {
+            out_dir: Some(out_dir.path().to_path_buf()),
+            ..Config::default()
+        };
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("mod.mun"),
+            contents: source.to_owned(),
+        };
+
+        let (mut driver, file_id) =
+            Driver::with_file(config, input).map_err(|e| CompileAndLoadError::Compile(e.into()))?;
+        if let Some(diagnostics) = driver
+            .emit_diagnostics_to_string(DisplayColor::Disable)
+            .map_err(|e| CompileAndLoadError::Compile(e.into()))?
+        {
+            return Err(CompileAndLoadError::Diagnostics(diagnostics));
+        }
+        driver
+            .write_all_assemblies(true)
+            .map_err(|e| CompileAndLoadError::Compile(e.into()))?;
+
+        // Safety: The assembly was just compiled from Mun source by the Mun
+        // compiler. The runtime loads a copy of it, so the temporary output
+        // directory can be removed afterwards.
+        let runtime = unsafe {
+            Runtime::builder(driver.assembly_output_path_from_file(file_id))
+                .disable_hot_reload()
+                .finish()
+        }?;
+        Ok(runtime)
+    }
+}
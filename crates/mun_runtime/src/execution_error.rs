@@ -0,0 +1,39 @@
+use std::cell::Cell;
+
+/// An error that occurred while executing Mun code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ExecutionError {
+    /// An integer addition, subtraction, or multiplication overflowed in code
+    /// that was compiled with checked overflow behavior.
+    #[error("attempt to perform an arithmetic operation that overflowed")]
+    ArithmeticOverflow,
+    /// A single invocation allocated more objects than the runtime allows.
+    /// See [`RuntimeBuilder::max_allocations_per_call`].
+    ///
+    /// [`RuntimeBuilder::max_allocations_per_call`]: crate::RuntimeBuilder::max_allocations_per_call
+    #[error("attempt to allocate more objects than allowed in a single invocation")]
+    AllocationLimitExceeded,
+    /// A field was accessed through a reference to a gc struct that is `null`.
+    #[error("attempt to dereference a null reference")]
+    NullDereference,
+}
+
+thread_local! {
+    /// The first error that occurred while executing Mun code on this thread.
+    static EXECUTION_ERROR: Cell<Option<ExecutionError>> = Cell::new(None);
+}
+
+/// Records the `error` unless an earlier error was already recorded.
+pub(crate) fn set_execution_error(error: ExecutionError) {
+    EXECUTION_ERROR.with(|execution_error| {
+        if execution_error.get().is_none() {
+            execution_error.set(Some(error));
+        }
+    });
+}
+
+/// Returns and clears the recorded error.
+pub(crate) fn take_execution_error() -> Option<ExecutionError> {
+    EXECUTION_ERROR.with(Cell::take)
+}
@@ -1,7 +1,13 @@
-use std::{ffi::c_void, ptr, sync::Arc};
+use std::{
+    ffi::c_void,
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+    ptr,
+    sync::Arc,
+};
 
 use mun_abi as abi;
-use mun_memory::{type_table::TypeTable, HasStaticType, TryFromAbiError, Type};
+use mun_memory::{type_table::TypeTable, HasStaticType, TryFromAbiError, Type, TypeKind};
 
 /// A linked version of [`mun_abi::FunctionDefinition`] that has resolved all
 /// occurrences of `TypeId` with `TypeInfo`.
@@ -11,6 +17,9 @@ pub struct FunctionDefinition {
     pub prototype: FunctionPrototype,
     /// Function pointer
     pub fn_ptr: *const c_void,
+    /// Path of the source file that defines the function, relative to the
+    /// source directory, if known
+    pub source_file: Option<PathBuf>,
 }
 
 impl FunctionDefinition {
@@ -39,6 +48,7 @@ impl FunctionDefinition {
         Ok(Self {
             prototype,
             fn_ptr: fn_def.fn_ptr,
+            source_file: fn_def.source_file().map(PathBuf::from),
         })
     }
 }
@@ -105,6 +115,51 @@ impl FunctionSignature {
     }
 }
 
+/// Formats the prototype as a human-readable signature, e.g. `add(i32, i32):
+/// i32`. The return type is omitted if the function doesn't return a value.
+impl Display for FunctionPrototype {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.name)?;
+        for (idx, arg_type) in self.signature.arg_types.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+            write_type_name(f, arg_type)?;
+        }
+        f.write_str(")")?;
+        if !self.signature.return_type.equals::<()>() {
+            f.write_str(": ")?;
+            write_type_name(f, &self.signature.return_type)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the name of `ty` the way it is written in Mun source code.
+fn write_type_name(f: &mut Formatter<'_>, ty: &Type) -> fmt::Result {
+    match ty.kind() {
+        TypeKind::Primitive(_) if ty.equals::<()>() => f.write_str("()"),
+        TypeKind::Primitive(_) => {
+            let name = ty.name();
+            f.write_str(name.strip_prefix("core::").unwrap_or(name))
+        }
+        TypeKind::Struct(_) => f.write_str(ty.name()),
+        TypeKind::Pointer(pointer) => {
+            f.write_str(if pointer.is_mutable() {
+                "*mut "
+            } else {
+                "*const "
+            })?;
+            write_type_name(f, &pointer.pointee())
+        }
+        TypeKind::Array(array) => {
+            f.write_str("[")?;
+            write_type_name(f, &array.element_type())?;
+            f.write_str("]")
+        }
+    }
+}
+
 /// A value-to-`FunctionDefinition` conversion that consumes the input value.
 pub trait IntoFunctionDefinition {
     /// Performs the conversion.
@@ -122,6 +177,7 @@ macro_rules! into_function_info_impl {
                 fn into<S: Into<String>>(self, name: S) -> FunctionDefinition {
                     FunctionDefinition {
                         fn_ptr: self as *const std::ffi::c_void,
+                        source_file: None,
                         prototype: FunctionPrototype {
                             name: name.into(),
                             signature: FunctionSignature {
@@ -197,6 +253,7 @@ impl FunctionDefinitionBuilder {
                 },
             },
             fn_ptr: self.fn_ptr,
+            source_file: None,
         })
     }
 }
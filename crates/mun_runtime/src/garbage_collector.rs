@@ -1,6 +1,26 @@
+use std::time::Duration;
+
 use mun_memory::gc;
 
 /// Defines the garbage collector used by the `Runtime`.
 pub type GarbageCollector = gc::MarkSweep<gc::NoopObserver<gc::Event>>;
 
 pub type GcRootPtr = gc::GcRootPtr<GarbageCollector>;
+
+/// An error that occurs when accessing a rooted object that was invalidated by
+/// [`Runtime::reset_heap`](crate::Runtime::reset_heap).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("the rooted object was invalidated by a reset of the heap")]
+pub struct InvalidRootError;
+
+/// Statistics about a single garbage collection.
+#[derive(Clone, Debug)]
+pub struct GcCollectionStats {
+    /// The time it took to collect the garbage
+    pub pause: Duration,
+    /// The number of bytes that were reclaimed
+    pub reclaimed_bytes: usize,
+}
+
+/// A callback that is invoked after every garbage collection.
+pub type GcCallback = Box<dyn Fn(&GcCollectionStats) + Send + Sync>;
@@ -5,15 +5,24 @@
 #![warn(missing_docs)]
 
 mod assembly;
+#[cfg(feature = "compiler")]
+mod compile;
 #[macro_use]
 mod garbage_collector;
 mod adt;
+mod allocation_limit;
 mod array;
 mod dispatch_table;
+mod execution_error;
 mod function_info;
 mod marshal;
+pub mod prelude;
+mod random;
 mod reflection;
+mod shared_runtime;
+mod string_arguments;
 mod utils;
+mod value;
 
 use std::{
     cmp,
@@ -23,11 +32,12 @@ use std::{
     fmt::{Debug, Display, Formatter},
     mem::ManuallyDrop,
     path::{Path, PathBuf},
-    ptr::NonNull,
+    ptr::{self, NonNull},
     sync::{
         mpsc::{channel, Receiver},
         Arc,
     },
+    time::Instant,
 };
 
 use assembly::LoadError;
@@ -35,34 +45,60 @@ use dispatch_table::DispatchTable;
 use garbage_collector::GarbageCollector;
 use log::{debug, error, info};
 use mun_abi as abi;
+pub use mun_abi::Guid;
 use mun_memory::{
     gc::{self, Array, GcRuntime},
     type_table::TypeTable,
 };
 // Re-export some useful types so crates dont have to depend on mun_memory as well.
-pub use mun_memory::{Field, FieldData, HasStaticType, PointerType, StructType, Type};
+pub use mun_memory::{
+    gc::{RawGcPtr, TypeStats},
+    Field, FieldData, HasStaticType, PointerType, StructType, Type,
+};
 use mun_project::LOCKFILE_NAME;
 use notify::{event::ModifyKind, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use random::Random;
+use string_arguments::StringArgument;
 
+#[cfg(feature = "compiler")]
+pub use crate::compile::CompileAndLoadError;
 pub use crate::{
-    adt::{RootedStruct, StructRef},
+    adt::{RootedStruct, StructFieldRef, StructRef},
     array::{ArrayRef, RawArray, RootedArray},
-    assembly::{Assembly, LinkError, LinkFunctionsError},
+    assembly::{Assembly, DispatchEntry, LinkError, LinkFunctionsError},
+    execution_error::ExecutionError,
     function_info::{
         FunctionDefinition, FunctionPrototype, FunctionSignature, IntoFunctionDefinition,
     },
+    garbage_collector::{GcCallback, GcCollectionStats, InvalidRootError},
     marshal::Marshal,
+    prelude::RuntimeExt,
     reflection::{ArgumentReflection, ReturnTypeReflection},
+    shared_runtime::SharedRuntime,
+    value::{Value, ValueError},
 };
 
 /// Options for the construction of a [`Runtime`].
 pub struct RuntimeOptions {
     /// Path to the entry point library
     pub library_path: PathBuf,
+    /// Paths to additional libraries that are loaded and linked together with
+    /// the entry point library
+    pub additional_library_paths: Vec<PathBuf>,
     /// Custom type table used for the runtime
     pub type_table: TypeTable,
     /// Custom user injected functions
     pub user_functions: Vec<FunctionDefinition>,
+    /// Callbacks that are invoked after every garbage collection
+    pub gc_callbacks: Vec<GcCallback>,
+    /// Whether to watch the loaded assemblies for changes and hot reload them
+    pub hot_reload: bool,
+    /// Whether to compact the heap after every garbage collection
+    pub compact_heap: bool,
+    /// The seed of the pseudo-random number generator that is used by `rand`
+    pub random_seed: u64,
+    /// The maximum number of objects that a single invocation may allocate
+    pub max_allocations_per_call: Option<usize>,
 }
 
 /// Retrieve the allocator using the provided handle.
@@ -101,9 +137,8 @@ extern "C" fn new(
 
     // Safety: the Mun Compiler guarantees that `new` is never called with
     // `ptr::null()`.
-    let handle = allocator.as_ref().alloc(&type_info);
-
-    handle.into()
+    allocation_limit::allocate(|| allocator.as_ref().alloc(&type_info))
+        .map_or(ptr::null(), Into::into)
 }
 
 extern "C" fn new_array(
@@ -121,9 +156,26 @@ extern "C" fn new_array(
     // of this function.
     let allocator = ManuallyDrop::new(unsafe { get_allocator(alloc_handle) });
 
-    let handle = allocator.as_ref().alloc_array(&type_info, length);
+    allocation_limit::allocate(|| allocator.as_ref().alloc_array(&type_info, length).as_raw())
+        .map_or(ptr::null(), Into::into)
+}
+
+extern "C" fn write_barrier(obj: *const *mut ffi::c_void, alloc_handle: *mut ffi::c_void) {
+    // Safety: `write_barrier` is only called from within Mun assemblies' core
+    // logic, so we are guaranteed that the `Runtime` and its `GarbageCollector`
+    // still exist if this function is called, and will continue to do so for the
+    // duration of this function.
+    let allocator = ManuallyDrop::new(unsafe { get_allocator(alloc_handle) });
+
+    allocator.as_ref().write_barrier(obj.into());
+}
+
+extern "C" fn arithmetic_overflow() {
+    execution_error::set_execution_error(ExecutionError::ArithmeticOverflow);
+}
 
-    handle.as_raw().into()
+extern "C" fn null_dereference() {
+    execution_error::set_execution_error(ExecutionError::NullDereference);
 }
 
 /// A builder for the [`Runtime`].
@@ -138,12 +190,51 @@ impl RuntimeBuilder {
         Self {
             options: RuntimeOptions {
                 library_path: library_path.into(),
+                additional_library_paths: Vec::new(),
                 type_table: TypeTable::default(),
                 user_functions: Vec::default(),
+                gc_callbacks: Vec::default(),
+                hot_reload: true,
+                compact_heap: false,
+                random_seed: 0,
+                max_allocations_per_call: None,
             },
         }
     }
 
+    /// Constructs a new `RuntimeBuilder` for the shared libraries listed in the
+    /// manifest at `manifest_path`.
+    ///
+    /// The manifest lists the path of one library per line, relative to the
+    /// directory that contains the manifest. Empty lines and lines starting
+    /// with `#` are ignored. All libraries are loaded and linked together with
+    /// their dependencies, so the order in which they are listed doesn't
+    /// matter.
+    pub fn from_manifest<P: AsRef<Path>>(manifest_path: P) -> std::io::Result<Self> {
+        let manifest_path = manifest_path.as_ref();
+        let manifest_dir = manifest_path.parent().unwrap_or_else(|| Path::new(""));
+        let manifest = std::fs::read_to_string(manifest_path)?;
+        let mut library_paths = manifest
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| manifest_dir.join(line));
+
+        let library_path = library_paths.next().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "manifest '{}' does not list any libraries",
+                    manifest_path.display()
+                ),
+            )
+        })?;
+
+        let mut builder = Self::new(library_path);
+        builder.options.additional_library_paths = library_paths.collect();
+        Ok(builder)
+    }
+
     /// Adds a custom user function to the dispatch table.
     pub fn insert_fn<S: Into<String>, F: IntoFunctionDefinition>(
         mut self,
@@ -154,6 +245,57 @@ impl RuntimeBuilder {
         self
     }
 
+    /// Registers a callback that is invoked after every garbage collection
+    /// with statistics about the collection.
+    pub fn on_gc(mut self, callback: impl Fn(&GcCollectionStats) + Send + Sync + 'static) -> Self {
+        self.options.gc_callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// Disables hot reloading. The assemblies are loaded once and are not
+    /// watched for changes, which makes [`Runtime::update`] a no-op.
+    pub fn disable_hot_reload(mut self) -> Self {
+        self.options.hot_reload = false;
+        self
+    }
+
+    /// Enables compaction of the heap. After every garbage collection, the
+    /// memory of all remaining objects is moved into a single contiguous block
+    /// of memory, which reduces fragmentation in long-running scripts.
+    ///
+    /// [`StructRef`]s, [`ArrayRef`]s and rooted objects refer to objects
+    /// through their handles and remain valid after compaction, as do
+    /// iterators returned by [`ArrayRef::iter`]. Raw pointers to the memory of
+    /// objects, e.g. those returned by [`RawArray::get_ptr`], are invalidated.
+    pub fn enable_heap_compaction(mut self) -> Self {
+        self.options.compact_heap = true;
+        self
+    }
+
+    /// Sets the seed of the pseudo-random number generator of the runtime. Mun
+    /// code can draw numbers from the generator by declaring and calling
+    /// `extern fn rand() -> u64`. Runtimes with the same seed produce the same
+    /// sequence of numbers. If no seed is set, a seed of `0` is used.
+    pub fn set_random_seed(mut self, seed: u64) -> Self {
+        self.options.random_seed = seed;
+        self
+    }
+
+    /// Limits the number of objects that a single invocation of a Mun
+    /// function may allocate to `max`, including the allocations of nested
+    /// invocations. Allocating more objects results in an
+    /// [`ExecutionError::AllocationLimitExceeded`].
+    ///
+    /// Once the limit is exceeded no more memory is allocated. Instead, the
+    /// allocation fails and the function that tried to allocate returns
+    /// immediately, as does every function that receives its result. The
+    /// error is reported as [`InvokeError::Trap`] once the invocation
+    /// returns.
+    pub fn max_allocations_per_call(mut self, max: usize) -> Self {
+        self.options.max_allocations_per_call = Some(max);
+        self
+    }
+
     /// Constructs a [`Runtime`] with the builder's options.
     ///
     /// # Safety
@@ -203,10 +345,14 @@ pub struct Runtime {
     assemblies_to_relink: BTreeMap<PathBuf, PathBuf>,
     dispatch_table: DispatchTable,
     type_table: TypeTable,
-    watcher: RecommendedWatcher,
+    watcher: Option<RecommendedWatcher>,
     watcher_rx: Receiver<notify::Result<Event>>,
     renamed_files: HashMap<usize, PathBuf>,
     gc: Arc<GarbageCollector>,
+    gc_callbacks: Vec<GcCallback>,
+    compact_heap: bool,
+    random: Arc<Random>,
+    max_allocations_per_call: Option<usize>,
 }
 
 impl Runtime {
@@ -240,6 +386,13 @@ impl Runtime {
         let mut dispatch_table = DispatchTable::default();
         let type_table = options.type_table;
 
+        // Add the functions that the runtime provides to Mun code. These are
+        // inserted first, so they can be replaced by user functions.
+        options.user_functions.insert(
+            0,
+            IntoFunctionDefinition::into(random::rand as extern "C" fn() -> u64, "rand"),
+        );
+
         // Add internal functions
         options.user_functions.push(IntoFunctionDefinition::into(
             new as extern "C" fn(*const ffi::c_void, *mut ffi::c_void) -> *const *mut ffi::c_void,
@@ -256,13 +409,32 @@ impl Runtime {
             "new_array",
         ));
 
+        options.user_functions.push(IntoFunctionDefinition::into(
+            write_barrier as extern "C" fn(*const *mut ffi::c_void, *mut ffi::c_void),
+            "write_barrier",
+        ));
+
+        options.user_functions.push(IntoFunctionDefinition::into(
+            arithmetic_overflow as extern "C" fn(),
+            "arithmetic_overflow",
+        ));
+
+        options.user_functions.push(IntoFunctionDefinition::into(
+            null_dereference as extern "C" fn(),
+            "null_dereference",
+        ));
+
         options.user_functions.into_iter().for_each(|fn_def| {
             dispatch_table.insert_fn(fn_def.prototype.name.clone(), Arc::new(fn_def));
         });
 
-        let watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
-            tx.send(res).expect("Failed to send filesystem event.");
-        })?;
+        let watcher = if options.hot_reload {
+            Some(notify::recommended_watcher(move |res| {
+                tx.send(res).expect("Failed to send filesystem event.");
+            })?)
+        } else {
+            None
+        };
         let mut runtime = Runtime {
             assemblies: HashMap::new(),
             assemblies_to_relink: BTreeMap::new(),
@@ -272,13 +444,26 @@ impl Runtime {
             watcher_rx: rx,
             renamed_files: HashMap::new(),
             gc: Arc::new(self::garbage_collector::GarbageCollector::default()),
+            gc_callbacks: options.gc_callbacks,
+            compact_heap: options.compact_heap,
+            random: Arc::new(Random::with_seed(options.random_seed)),
+            max_allocations_per_call: options.max_allocations_per_call,
         };
 
-        runtime.add_assembly(&options.library_path)?;
+        runtime.add_assemblies(
+            std::iter::once(options.library_path.as_path()).chain(
+                options
+                    .additional_library_paths
+                    .iter()
+                    .map(PathBuf::as_path),
+            ),
+        )?;
         Ok(runtime)
     }
 
-    /// Adds an assembly corresponding to the library at `library_path`.
+    /// Adds the assemblies corresponding to the libraries at `library_paths`,
+    /// and their dependencies. All assemblies are linked together, so they can
+    /// depend on each other.
     ///
     /// # Safety
     ///
@@ -294,18 +479,23 @@ impl Runtime {
     /// unloaded.
     ///
     /// See [`Assembly::load`] for more information.
-    unsafe fn add_assembly(&mut self, library_path: &Path) -> Result<(), LinkError> {
-        let library_path = library_path
-            .canonicalize()
-            .map_err(|e| LinkError::LoadAssembly(LoadError::Other(e)))?;
-
-        if self.assemblies.contains_key(&library_path) {
-            return Err(LoadError::AlreadyExists.into());
-        }
-
+    unsafe fn add_assemblies<'p>(
+        &mut self,
+        library_paths: impl IntoIterator<Item = &'p Path>,
+    ) -> Result<(), LinkError> {
         let mut loaded = HashMap::new();
         let mut to_load = VecDeque::new();
-        to_load.push_back(library_path);
+        for library_path in library_paths {
+            let library_path = library_path
+                .canonicalize()
+                .map_err(|e| LinkError::LoadAssembly(LoadError::Other(e)))?;
+
+            if self.assemblies.contains_key(&library_path) {
+                return Err(LoadError::AlreadyExists.into());
+            }
+
+            to_load.push_back(library_path);
+        }
 
         // Load all assemblies and their dependencies
         while let Some(library_path) = to_load.pop_front() {
@@ -340,9 +530,11 @@ impl Runtime {
             Assembly::link_all(loaded.values_mut(), &self.dispatch_table, &self.type_table)?;
 
         for (library_path, assembly) in loaded {
-            self.watcher
-                .watch(library_path.parent().unwrap(), RecursiveMode::NonRecursive)
-                .expect("Path must exist as we just loaded the library");
+            if let Some(watcher) = &mut self.watcher {
+                watcher
+                    .watch(library_path.parent().unwrap(), RecursiveMode::NonRecursive)
+                    .expect("Path must exist as we just loaded the library");
+            }
 
             self.assemblies.insert(library_path, assembly);
         }
@@ -358,6 +550,25 @@ impl Runtime {
         self.dispatch_table.get_fn(function_name)
     }
 
+    /// Returns a human-readable signature of the function called
+    /// `function_name`, e.g. `add(i32, i32): i32`, or `None` if no such
+    /// function exists. This is useful for logging and interactive output.
+    pub fn function_signature_string(&self, function_name: &str) -> Option<String> {
+        self.get_function_definition(function_name)
+            .map(|function| function.prototype.to_string())
+    }
+
+    /// Returns true if `function`, previously obtained through
+    /// [`Runtime::get_function_definition`], is still the current definition
+    /// of that function. Reloading the assembly that defines a function
+    /// invalidates all of its previously obtained definitions; use
+    /// [`Runtime::get_function_definition`] to resolve it again.
+    pub fn is_function_valid(&self, function: &Arc<FunctionDefinition>) -> bool {
+        self.dispatch_table
+            .get_fn(&function.prototype.name)
+            .map_or(false, |current| Arc::ptr_eq(&current, function))
+    }
+
     /// For a given `fn_name`, find the most similar name in `fn_names`
     fn find_best_match_for_fn_name<'a>(
         fn_name: &'a str,
@@ -394,8 +605,28 @@ impl Runtime {
         self.type_table.find_type_info_by_id(type_id)
     }
 
+    /// Retrieves the identifier of the struct type corresponding to
+    /// `type_name`, if available. See [`StructRef::type_id`].
+    pub fn struct_type_id(&self, type_name: &str) -> Option<Guid> {
+        self.get_type_info_by_name(type_name)?
+            .as_struct()
+            .map(|s| *s.guid())
+    }
+
+    /// Returns whether the runtime watches its assemblies for changes and hot
+    /// reloads them.
+    pub fn hot_reload_enabled(&self) -> bool {
+        self.watcher.is_some()
+    }
+
     /// Updates the state of the runtime. This includes checking for file
-    /// changes, and reloading compiled assemblies.
+    /// changes, and reloading compiled assemblies. Returns `true` if
+    /// assemblies were reloaded.
+    ///
+    /// If hot reloading is disabled, this is a no-op that always returns
+    /// `true`: the loaded assemblies are final, so callers that wait for an
+    /// update don't wait forever.
+    ///
     /// # Safety
     ///
     /// A munlib is simply a shared object. When a library is loaded,
@@ -469,6 +700,10 @@ impl Runtime {
             )
         }
 
+        if self.watcher.is_none() {
+            return true;
+        }
+
         let mut requires_relink = false;
         while let Ok(Ok(event)) = self.watcher_rx.try_recv() {
             for path in event.paths {
@@ -529,6 +764,59 @@ impl Runtime {
         false
     }
 
+    /// Returns the path of the loaded assembly that defines the function called
+    /// `function_name`, if any.
+    pub fn assembly_path(&self, function_name: &str) -> Option<&Path> {
+        self.assemblies
+            .iter()
+            .find(|(_, assembly)| {
+                assembly
+                    .info()
+                    .symbols
+                    .functions()
+                    .iter()
+                    .any(|fn_def| fn_def.prototype.name() == function_name)
+            })
+            .map(|(path, _)| path.as_path())
+    }
+
+    /// Returns the dispatch table entries of all loaded assemblies, sorted by
+    /// assembly and function name. This is useful to diagnose linking
+    /// issues.
+    pub fn dispatch_entries(&self) -> Vec<DispatchEntry> {
+        let mut entries: Vec<_> = self
+            .assemblies
+            .values()
+            .flat_map(Assembly::dispatch_entries)
+            .collect();
+        entries.sort_by(|a, b| {
+            a.assembly_path
+                .cmp(&b.assembly_path)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        entries
+    }
+
+    /// Invokes the per-frame Mun function called `function_name` with the time
+    /// that elapsed since the previous frame, after which any modified
+    /// assemblies are reloaded. Returns `true` if a reload occurred during
+    /// this tick.
+    ///
+    /// The invoked function must have the signature `fn(f64)`.
+    ///
+    /// # Safety
+    ///
+    /// Reloading assemblies is as unsafe as calling [`Runtime::update`], see its
+    /// documentation for more information.
+    pub unsafe fn tick<'name>(
+        &mut self,
+        function_name: &'name str,
+        delta_time: f64,
+    ) -> Result<bool, InvokeErr<'name, (f64,)>> {
+        self.invoke::<(), _>(function_name, (delta_time,))?;
+        Ok(self.hot_reload_enabled() && self.update())
+    }
+
     /// Returns a shared reference to the runtime's garbage collector.
     ///
     /// We cannot return an `Arc` here, because the lifetime of data contained
@@ -540,8 +828,51 @@ impl Runtime {
     /// Collects all memory that is no longer referenced by rooted objects.
     /// Returns `true` if memory was reclaimed, `false` otherwise. This
     /// behavior will likely change in the future.
+    ///
+    /// If heap compaction was enabled through
+    /// [`RuntimeBuilder::enable_heap_compaction`], the heap is compacted after
+    /// collecting. Afterwards, the callbacks registered with
+    /// [`RuntimeBuilder::on_gc`] are invoked.
     pub fn gc_collect(&self) -> bool {
-        self.gc.collect()
+        let start_time = Instant::now();
+        let allocated_before = self.gc.stats().allocated_memory;
+        let reclaimed = self.gc.collect();
+        if self.compact_heap {
+            self.gc.compact();
+        }
+
+        if !self.gc_callbacks.is_empty() {
+            let stats = GcCollectionStats {
+                pause: start_time.elapsed(),
+                reclaimed_bytes: allocated_before.saturating_sub(self.gc.stats().allocated_memory),
+            };
+            for callback in &self.gc_callbacks {
+                callback(&stats);
+            }
+        }
+
+        reclaimed
+    }
+
+    /// Unroots all objects held by the host and collects all memory, resetting
+    /// the heap to an empty state. This is useful to isolate test cases that
+    /// share a long-lived runtime.
+    ///
+    /// Outstanding [`RootedStruct`]s and [`RootedArray`]s are invalidated;
+    /// converting them to a reference afterwards results in an
+    /// [`InvalidRootError`].
+    pub fn reset_heap(&mut self) {
+        self.gc.unroot_all();
+        self.gc_collect();
+    }
+
+    /// Returns and clears the first error that occurred while executing Mun
+    /// code on the current thread outside of an invocation through the
+    /// runtime, e.g. by calling a function pointer directly. Invocations
+    /// through the runtime report such errors as [`InvokeError::Trap`]
+    /// instead.
+    pub fn take_execution_error(&self) -> Option<ExecutionError> {
+        execution_error::take_execution_error()
     }
 
     /// Returns statistics about the garbage collector.
@@ -549,6 +880,18 @@ impl Runtime {
         self.gc.stats()
     }
 
+    /// Returns the number of allocated instances and the memory they occupy,
+    /// grouped by the name of their type.
+    pub fn memory_usage_by_type(&self) -> HashMap<String, TypeStats> {
+        let mut usage: HashMap<String, TypeStats> = HashMap::new();
+        for (ty, stats) in self.gc.stats_by_type() {
+            let type_usage = usage.entry(ty.name().to_owned()).or_default();
+            type_usage.instances += stats.instances;
+            type_usage.allocated_memory += stats.allocated_memory;
+        }
+        usage
+    }
+
     /// Constructs an array with a predefined element type.
     pub fn construct_typed_array<
         't,
@@ -607,11 +950,54 @@ impl Runtime {
         &'t self,
         iter: I,
     ) -> ArrayRef<'t, T>
+    where
+        I::IntoIter: ExactSizeIterator,
+    {
+        self.construct_array_of_type(T::type_info(), iter)
+    }
+
+    /// Constructs an array of structs of type `element_type` from an iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the structs is not of type `element_type`.
+    pub fn construct_struct_array<'t, I: IntoIterator<Item = StructRef<'t>>>(
+        &'t self,
+        element_type: &Type,
+        iter: I,
+    ) -> ArrayRef<'t, StructRef<'t>>
+    where
+        I::IntoIter: ExactSizeIterator,
+    {
+        assert!(
+            element_type.is_struct(),
+            "expected a struct type, found `{}`",
+            element_type.name()
+        );
+        let iter = iter.into_iter().map(|element| {
+            assert!(
+                &element.type_info() == element_type,
+                "expected a struct of type `{}`, found `{}`",
+                element_type.name(),
+                element.type_info().name()
+            );
+            element
+        });
+        self.construct_array_of_type(element_type, iter)
+    }
+
+    /// Constructs an array with elements of type `element_type` from an
+    /// iterator. The caller must ensure that `T` can be marshalled to
+    /// `element_type`.
+    fn construct_array_of_type<'t, T: 't + Marshal<'t>, I: IntoIterator<Item = T>>(
+        &'t self,
+        element_type: &Type,
+        iter: I,
+    ) -> ArrayRef<'t, T>
     where
         I::IntoIter: ExactSizeIterator,
     {
         let iter = iter.into_iter();
-        let element_type = T::type_info();
         let array_type = element_type.array_type();
         let array_capacity = iter
             .size_hint()
@@ -654,10 +1040,94 @@ impl Runtime {
 /// An error that might occur when calling a mun function from Rust.
 pub struct InvokeErr<'name, T> {
     msg: String,
+    kind: InvokeError,
     function_name: &'name str,
     arguments: T,
 }
 
+/// The reason why a Mun function could not be invoked from Rust.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvokeError {
+    /// There is no function with the specified name
+    FunctionNotFound {
+        /// The name of the function
+        name: String,
+        /// The name of a function with a similar name, if any
+        suggestion: Option<String>,
+    },
+    /// The number of arguments doesn't match the number of parameters of the
+    /// function
+    ArityMismatch {
+        /// The number of parameters of the function
+        expected: usize,
+        /// The number of arguments that were passed
+        found: usize,
+    },
+    /// The type of an argument doesn't match the type of its parameter
+    ArgTypeMismatch {
+        /// The index of the argument
+        index: usize,
+        /// The name of the type of the parameter
+        expected: String,
+        /// The name of the type of the argument
+        found: String,
+        /// The name of the Rust type of the argument
+        rust_type: &'static str,
+    },
+    /// The return type of the function cannot be marshalled into the requested
+    /// Rust type
+    ReturnTypeMismatch {
+        /// The type hint of the requested Rust type
+        expected: String,
+        /// The name of the return type of the function
+        found: String,
+    },
+    /// The arguments cannot be converted to the types of the parameters of the
+    /// function, e.g. when they are parsed from strings
+    UnsupportedArguments(String),
+    /// An error occurred while executing the function
+    Trap(ExecutionError),
+}
+
+impl Display for InvokeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvokeError::FunctionNotFound { name, suggestion } => {
+                write!(
+                    f,
+                    "failed to obtain function '{name}', no such function exists."
+                )?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, " There is a function with a similar name: {suggestion}")?;
+                }
+                Ok(())
+            }
+            InvokeError::ArityMismatch { expected, found } => write!(
+                f,
+                "Invalid argument count. Expected {expected} arguments, got {found}"
+            ),
+            InvokeError::ArgTypeMismatch {
+                index,
+                expected,
+                found,
+                rust_type,
+            } => write!(
+                f,
+                "Invalid argument type for parameter {index}. Expected: `{expected}`. Found: \
+                 `{found}` (Rust type `{rust_type}`)."
+            ),
+            InvokeError::ReturnTypeMismatch { expected, found } => write!(
+                f,
+                "unexpected return type, got '{found}', expected '{expected}'"
+            ),
+            InvokeError::UnsupportedArguments(msg) => f.write_str(msg),
+            InvokeError::Trap(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for InvokeError {}
+
 impl<'name, T> Debug for InvokeErr<'name, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", &self.msg)
@@ -670,6 +1140,30 @@ impl<'name, T> Display for InvokeErr<'name, T> {
     }
 }
 
+impl<'name, T> InvokeErr<'name, T> {
+    /// Constructs a new `InvokeErr` for the invocation of `function_name` with
+    /// `arguments` that failed because of `kind`.
+    fn new(kind: InvokeError, function_name: &'name str, arguments: T) -> Self {
+        let msg = match &kind {
+            InvokeError::ArityMismatch { .. } | InvokeError::ArgTypeMismatch { .. } => {
+                format!("failed to invoke '{function_name}': {kind}")
+            }
+            _ => kind.to_string(),
+        };
+        Self {
+            msg,
+            kind,
+            function_name,
+            arguments,
+        }
+    }
+
+    /// Returns the reason why the function could not be invoked.
+    pub fn kind(&self) -> &InvokeError {
+        &self.kind
+    }
+}
+
 impl<'name, T: InvokeArgs> InvokeErr<'name, T> {
     /// Retries a function invocation once, resulting in a potentially
     /// successful invocation.
@@ -732,50 +1226,81 @@ impl<'name, T: InvokeArgs> InvokeErr<'name, T> {
 pub trait InvokeArgs {
     /// Determines whether the specified function can be called with these
     /// arguments
-    fn can_invoke(&self, runtime: &Runtime, signature: &FunctionSignature) -> Result<(), String>;
-
-    /// Calls the specified function with these function arguments
+    fn can_invoke(
+        &self,
+        runtime: &Runtime,
+        signature: &FunctionSignature,
+    ) -> Result<(), InvokeError>;
+
+    /// Calls the specified function with these function arguments. If an error
+    /// occurs while executing the function, the error is returned together
+    /// with the arguments, which are marshalled back from the values that were
+    /// passed to the function.
     ///
     /// # Safety
     ///
     /// The `fn_ptr` is cast and invoked which might result in undefined
-    /// behavior.
-    unsafe fn invoke<ReturnType>(self, fn_ptr: *const c_void) -> ReturnType;
+    /// behavior. The arguments must only reference memory of `runtime`.
+    unsafe fn invoke<ReturnType>(
+        self,
+        runtime: &Runtime,
+        fn_ptr: *const c_void,
+    ) -> Result<ReturnType, (ExecutionError, Self)>
+    where
+        Self: Sized;
 }
 
 // Implement `InvokeTraits` for tuples up to and including 20 elements
 seq_macro::seq!(N in 0..=20 {#(
 seq_macro::seq!(I in 0..N {
     #[allow(clippy::extra_unused_lifetimes)]
-    impl<'arg, #(T~I: ArgumentReflection + Marshal<'arg>,)*> InvokeArgs for (#(T~I,)*) {
+    impl<'arg, #(T~I: ArgumentReflection + Marshal<'arg> + 'arg,)*> InvokeArgs for (#(T~I,)*)
+    where
+        #(T~I::MunType: Clone,)*
+    {
         #[allow(unused_variables)]
-        fn can_invoke(&self, runtime: &Runtime, signature: &FunctionSignature) -> Result<(), String> {
+        fn can_invoke(&self, runtime: &Runtime, signature: &FunctionSignature) -> Result<(), InvokeError> {
             let arg_types = &signature.arg_types;
 
             // Ensure the number of arguments match
             #[allow(clippy::len_zero)]
             if N != arg_types.len() {
-                return Err(format!("Invalid argument count. Expected {} arguments, got {}", arg_types.len(), N))
+                return Err(InvokeError::ArityMismatch { expected: arg_types.len(), found: N });
             }
 
             #(
-            if arg_types[I] != self.I.type_info(runtime) {
-                return Err(format!(
-                    "Invalid argument type at index {}. Expected: {}. Found: {}.",
-                    I,
-                    self.I.type_info(runtime).name(),
-                    arg_types[I].name(),
-                ));
+            let found = self.I.type_info(runtime);
+            if arg_types[I] != found {
+                return Err(InvokeError::ArgTypeMismatch {
+                    index: I,
+                    expected: arg_types[I].name().to_owned(),
+                    found: found.name().to_owned(),
+                    rust_type: std::any::type_name::<T~I>(),
+                });
             }
             )*
 
             Ok(())
         }
 
-        unsafe fn invoke<ReturnType>(self, fn_ptr: *const c_void) -> ReturnType {
+        #[allow(unused_variables, clippy::let_unit_value)]
+        unsafe fn invoke<ReturnType>(
+            self,
+            runtime: &Runtime,
+            fn_ptr: *const c_void,
+        ) -> Result<ReturnType, (ExecutionError, Self)> {
             #[allow(clippy::type_complexity)]
             let function: fn(#(T~I::MunType,)*) -> ReturnType = core::mem::transmute(fn_ptr);
-            function(#(self.I.marshal_into(),)*)
+            let values = (#(self.I.marshal_into(),)*);
+            let result = function(#(values.I.clone(),)*);
+            match execution_error::take_execution_error() {
+                Some(error) => {
+                    // Safety: The arguments reference memory of `runtime`, so it outlives them.
+                    let runtime: &'arg Runtime = &*(runtime as *const Runtime);
+                    Err((error, (#(T~I::marshal_from(values.I, runtime),)*)))
+                }
+                None => Ok(result),
+            }
         }
     }
 });
@@ -784,6 +1309,9 @@ seq_macro::seq!(I in 0..N {
 impl Runtime {
     /// Invokes the Mun function called `function_name` with the specified
     /// `arguments`.
+    ///
+    /// If an error occurs while executing the function, e.g. an arithmetic
+    /// overflow, an error of kind [`InvokeError::Trap`] is returned.
     pub fn invoke<
         'runtime,
         'ret,
@@ -798,55 +1326,191 @@ impl Runtime {
     where
         'runtime: 'ret,
     {
-        // Get the function information from the runtime
-        let function_info = match self.get_function_definition(function_name).ok_or_else(|| {
-            format!("failed to obtain function '{function_name}', no such function exists.")
-        }) {
-            Ok(function_info) => function_info,
-            Err(msg) => {
-                let available_names = self.dispatch_table.get_fn_names();
-                let suggested_name =
-                    Self::find_best_match_for_fn_name(function_name, available_names, None);
-
-                let suggested_message = suggested_name.map_or_else(
-                    || msg.clone(),
-                    |name| format!("{msg} There is a function with a similar name: {name}"),
-                );
+        self.invoke_impl(function_name, arguments)
+            .map_err(|(kind, arguments)| InvokeErr::new(kind, function_name, arguments))
+    }
 
-                return Err(InvokeErr {
-                    msg: suggested_message,
-                    function_name,
-                    arguments,
-                });
-            }
+    /// Invokes the Mun function called `function_name` with the specified
+    /// `arguments`, without panicking on any error.
+    ///
+    /// Contrary to [`Runtime::invoke`], only the reason why the invocation
+    /// failed is returned, not the arguments.
+    pub fn try_invoke<
+        'runtime,
+        'ret,
+        ReturnType: ReturnTypeReflection + Marshal<'ret> + 'ret,
+        ArgTypes: InvokeArgs,
+    >(
+        &'runtime self,
+        function_name: &str,
+        arguments: ArgTypes,
+    ) -> Result<ReturnType, InvokeError>
+    where
+        'runtime: 'ret,
+    {
+        self.invoke_impl(function_name, arguments)
+            .map_err(|(kind, _)| kind)
+    }
+
+    /// Validates and invokes the Mun function called `function_name` with the
+    /// specified `arguments`. If the function cannot be invoked, the reason
+    /// is returned together with the `arguments`.
+    fn invoke_impl<
+        'runtime,
+        'ret,
+        ReturnType: ReturnTypeReflection + Marshal<'ret> + 'ret,
+        ArgTypes: InvokeArgs,
+    >(
+        &'runtime self,
+        function_name: &str,
+        arguments: ArgTypes,
+    ) -> Result<ReturnType, (InvokeError, ArgTypes)>
+    where
+        'runtime: 'ret,
+    {
+        let function_info = match self.find_function(function_name) {
+            Ok(function_info) => function_info,
+            Err(kind) => return Err((kind, arguments)),
         };
 
         // Validate the arguments
-        match arguments.can_invoke(self, &function_info.prototype.signature) {
-            Ok(_) => {}
-            Err(msg) => {
-                return Err(InvokeErr {
-                    msg,
-                    function_name,
-                    arguments,
-                })
+        if let Err(kind) = arguments.can_invoke(self, &function_info.prototype.signature) {
+            return Err((kind, arguments));
+        }
+
+        if let Err(kind) = Self::check_return_type::<ReturnType>(&function_info) {
+            return Err((kind, arguments));
+        }
+
+        let _invocation = self.enter_invocation();
+        // Safety: Arguments can only reference memory of the runtime that created
+        // them, which is the runtime that executes the function.
+        let result: ReturnType::MunType =
+            unsafe { arguments.invoke(self, function_info.fn_ptr) }
+                .map_err(|(error, arguments)| (InvokeError::Trap(error), arguments))?;
+        Ok(Marshal::marshal_from(result, self))
+    }
+
+    /// Returns the definition of the function called `function_name`, or an
+    /// error that suggests a function with a similar name if there is no such
+    /// function.
+    fn find_function(&self, function_name: &str) -> Result<Arc<FunctionDefinition>, InvokeError> {
+        self.get_function_definition(function_name).ok_or_else(|| {
+            let available_names = self.dispatch_table.get_fn_names();
+            let suggestion =
+                Self::find_best_match_for_fn_name(function_name, available_names, None)
+                    .map(ToString::to_string);
+            InvokeError::FunctionNotFound {
+                name: function_name.to_owned(),
+                suggestion,
             }
-        };
+        })
+    }
 
-        // Validate the return type
-        if !ReturnType::accepts_type(&function_info.prototype.signature.return_type) {
-            return Err(InvokeErr {
-                msg: format!(
-                    "unexpected return type, got '{}', expected '{}",
-                    &function_info.prototype.signature.return_type.name(),
-                    ReturnType::type_hint()
-                ),
-                function_name,
-                arguments,
+    /// Validates that the return value of the function can be marshalled into a
+    /// `ReturnType`.
+    fn check_return_type<ReturnType: ReturnTypeReflection>(
+        function_info: &FunctionDefinition,
+    ) -> Result<(), InvokeError> {
+        let return_type = &function_info.prototype.signature.return_type;
+        if ReturnType::accepts_type(return_type) {
+            Ok(())
+        } else {
+            Err(InvokeError::ReturnTypeMismatch {
+                expected: ReturnType::type_hint().to_string(),
+                found: return_type.name().to_owned(),
+            })
+        }
+    }
+
+    /// Prepares the current thread to execute Mun code of the runtime. Errors
+    /// of previous invocations are cleared, and the state that Mun code reads
+    /// while it executes is set until the returned guards are dropped.
+    fn enter_invocation(&self) -> (random::RandomGuard, allocation_limit::AllocationLimitGuard) {
+        execution_error::take_execution_error();
+        (
+            random::enter(self.random.clone()),
+            allocation_limit::enter(self.max_allocations_per_call),
+        )
+    }
+
+    /// Invokes the Mun function called `function_name` with arguments that are
+    /// parsed from strings according to the argument types of the function.
+    /// This is useful to invoke functions that are selected at runtime, e.g.
+    /// from the command line.
+    ///
+    /// The function can take at most three arguments, each of a primitive type
+    /// other than `i128` and `u128`.
+    pub fn invoke_from_strings<
+        'runtime,
+        'ret,
+        'name,
+        ReturnType: ReturnTypeReflection + Marshal<'ret> + 'ret,
+    >(
+        &'runtime self,
+        function_name: &'name str,
+        arguments: &[&str],
+    ) -> Result<ReturnType, InvokeErr<'name, Vec<String>>>
+    where
+        'runtime: 'ret,
+    {
+        self.invoke_from_strings_impl(function_name, arguments)
+            .map_err(|kind| {
+                let arguments = arguments.iter().map(ToString::to_string).collect();
+                InvokeErr::new(kind, function_name, arguments)
+            })
+    }
+
+    fn invoke_from_strings_impl<
+        'runtime,
+        'ret,
+        ReturnType: ReturnTypeReflection + Marshal<'ret> + 'ret,
+    >(
+        &'runtime self,
+        function_name: &str,
+        arguments: &[&str],
+    ) -> Result<ReturnType, InvokeError>
+    where
+        'runtime: 'ret,
+    {
+        let function_info = self.find_function(function_name)?;
+
+        let arg_types = &function_info.prototype.signature.arg_types;
+        if arg_types.len() != arguments.len() {
+            return Err(InvokeError::ArityMismatch {
+                expected: arg_types.len(),
+                found: arguments.len(),
             });
         }
+        if arguments.len() > string_arguments::MAX_ARGUMENTS {
+            return Err(InvokeError::UnsupportedArguments(format!(
+                "functions with more than {} arguments cannot be invoked with string arguments",
+                string_arguments::MAX_ARGUMENTS
+            )));
+        }
 
-        let result: ReturnType::MunType = unsafe { arguments.invoke(function_info.fn_ptr) };
+        Self::check_return_type::<ReturnType>(&function_info)?;
+
+        // Parse every argument as the type of the parameter it is passed to
+        let arguments = arg_types
+            .iter()
+            .zip(arguments)
+            .enumerate()
+            .map(|(idx, (arg_type, arg))| {
+                StringArgument::parse(arg_type, arg)
+                    .map_err(|e| format!("argument #{}: {e}", idx + 1))
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map_err(InvokeError::UnsupportedArguments)?;
+
+        let _invocation = self.enter_invocation();
+        // Safety: The arguments were parsed according to the argument types of the
+        // function, and its return type is accepted by `ReturnType`.
+        let result: ReturnType::MunType =
+            unsafe { string_arguments::call(function_info.fn_ptr, &arguments) };
+        if let Some(error) = execution_error::take_execution_error() {
+            return Err(InvokeError::Trap(error));
+        }
         Ok(Marshal::marshal_from(result, self))
     }
 }
@@ -0,0 +1,85 @@
+//! The Mun Runtime prelude
+//!
+//! The prelude re-exports the types and traits that are commonly used when
+//! embedding the Mun Runtime, so they can all be imported at once:
+//!
+//! ```
+//! use mun_runtime::prelude::*;
+//! ```
+
+pub use crate::{
+    ArgumentReflection, ArrayRef, InvokeArgs, InvokeErr, InvokeError, Marshal,
+    ReturnTypeReflection, RootedArray, RootedStruct, Runtime, RuntimeBuilder, StructFieldRef,
+    StructRef,
+};
+
+macro_rules! runtime_ext {
+    ($($(#[$attr:meta])* fn $name:ident($($arg:ident: $ty:ident),*);)*) => {
+        /// Typed helpers to invoke Mun functions with a fixed number of
+        /// arguments, up to and including eight.
+        ///
+        /// These are equivalent to calling [`Runtime::invoke`] with a tuple of
+        /// arguments, but let the compiler infer the argument types from the
+        /// call.
+        pub trait RuntimeExt {
+            $(
+            $(#[$attr])*
+            #[allow(clippy::too_many_arguments)]
+            fn $name<'r, 'name, $($ty,)* R>(
+                &'r self,
+                function_name: &'name str,
+                $($arg: $ty,)*
+            ) -> Result<R, InvokeErr<'name, ($($ty,)*)>>
+            where
+                ($($ty,)*): InvokeArgs,
+                R: ReturnTypeReflection + Marshal<'r> + 'r;
+            )*
+        }
+
+        impl RuntimeExt for Runtime {
+            $(
+            #[allow(clippy::too_many_arguments)]
+            fn $name<'r, 'name, $($ty,)* R>(
+                &'r self,
+                function_name: &'name str,
+                $($arg: $ty,)*
+            ) -> Result<R, InvokeErr<'name, ($($ty,)*)>>
+            where
+                ($($ty,)*): InvokeArgs,
+                R: ReturnTypeReflection + Marshal<'r> + 'r,
+            {
+                self.invoke(function_name, ($($arg,)*))
+            }
+            )*
+        }
+    };
+}
+
+runtime_ext! {
+    /// Invokes the Mun function called `function_name` without arguments.
+    fn invoke0();
+
+    /// Invokes the Mun function called `function_name` with a single argument.
+    fn invoke1(a: A);
+
+    /// Invokes the Mun function called `function_name` with two arguments.
+    fn invoke2(a: A, b: B);
+
+    /// Invokes the Mun function called `function_name` with three arguments.
+    fn invoke3(a: A, b: B, c: C);
+
+    /// Invokes the Mun function called `function_name` with four arguments.
+    fn invoke4(a: A, b: B, c: C, d: D);
+
+    /// Invokes the Mun function called `function_name` with five arguments.
+    fn invoke5(a: A, b: B, c: C, d: D, e: E);
+
+    /// Invokes the Mun function called `function_name` with six arguments.
+    fn invoke6(a: A, b: B, c: C, d: D, e: E, f: F);
+
+    /// Invokes the Mun function called `function_name` with seven arguments.
+    fn invoke7(a: A, b: B, c: C, d: D, e: E, f: F, g: G);
+
+    /// Invokes the Mun function called `function_name` with eight arguments.
+    fn invoke8(a: A, b: B, c: C, d: D, e: E, f: F, g: G, h: H);
+}
@@ -0,0 +1,73 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// A seedable pseudo-random number generator based on SplitMix64. Its state is
+/// stored atomically, so it can be shared by all threads that execute Mun code
+/// of the same runtime.
+#[derive(Debug)]
+pub(crate) struct Random {
+    state: AtomicU64,
+}
+
+/// The generator that is used when Mun code is executed without a runtime, e.g.
+/// by calling a function pointer directly.
+static DEFAULT_RANDOM: Random = Random::with_seed(0);
+
+thread_local! {
+    /// The generator of the runtime that is executing Mun code on this thread.
+    static CURRENT_RANDOM: RefCell<Option<Arc<Random>>> = RefCell::new(None);
+}
+
+impl Random {
+    /// Constructs a generator that starts from the specified `seed`.
+    pub(crate) const fn with_seed(seed: u64) -> Self {
+        Self {
+            state: AtomicU64::new(seed),
+        }
+    }
+
+    /// Returns the next pseudo-random number of the sequence.
+    pub(crate) fn next_u64(&self) -> u64 {
+        const GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut z = self
+            .state
+            .fetch_add(GAMMA, Ordering::Relaxed)
+            .wrapping_add(GAMMA);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Restores the previously used generator of the thread when dropped.
+pub(crate) struct RandomGuard {
+    previous: Option<Arc<Random>>,
+}
+
+impl Drop for RandomGuard {
+    fn drop(&mut self) {
+        CURRENT_RANDOM.with(|current| *current.borrow_mut() = self.previous.take());
+    }
+}
+
+/// Uses `random` to generate the numbers returned by `rand` on the current
+/// thread, until the returned guard is dropped.
+pub(crate) fn enter(random: Arc<Random>) -> RandomGuard {
+    let previous = CURRENT_RANDOM.with(|current| current.replace(Some(random)));
+    RandomGuard { previous }
+}
+
+/// Returns the next pseudo-random number of the generator of the runtime that
+/// is executing Mun code on the current thread. Mun code calls this function
+/// through an `extern fn rand() -> u64` declaration.
+pub(crate) extern "C" fn rand() -> u64 {
+    CURRENT_RANDOM.with(|current| match current.borrow().as_ref() {
+        Some(random) => random.next_u64(),
+        None => DEFAULT_RANDOM.next_u64(),
+    })
+}
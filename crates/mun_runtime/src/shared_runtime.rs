@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use parking_lot::{Mutex, MutexGuard};
+
+use crate::{InvokeArgs, InvokeErr, Marshal, ReturnTypeReflection, Runtime};
+
+/// A [`Runtime`] that can be shared between, and invoked from, multiple
+/// threads.
+///
+/// Access to the underlying runtime is serialized, so only a single thread
+/// can mutate Mun memory at a time. This also guarantees that garbage
+/// collection and hot reloading never run concurrently with an invocation.
+#[derive(Clone)]
+pub struct SharedRuntime {
+    runtime: Arc<Mutex<Runtime>>,
+}
+
+impl SharedRuntime {
+    /// Constructs a new `SharedRuntime` from the specified `runtime`.
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            runtime: Arc::new(Mutex::new(runtime)),
+        }
+    }
+
+    /// Acquires exclusive access to the underlying [`Runtime`], blocking the
+    /// current thread until it is available.
+    pub fn lock(&self) -> MutexGuard<'_, Runtime> {
+        self.runtime.lock()
+    }
+
+    /// Invokes the Mun function called `function_name` with the specified
+    /// `arguments`.
+    ///
+    /// Only return types that do not borrow from the runtime are supported, as
+    /// the runtime is unlocked when this function returns. Use
+    /// [`SharedRuntime::lock`] to work with references to Mun memory.
+    pub fn invoke<'name, ReturnType, ArgTypes: InvokeArgs>(
+        &self,
+        function_name: &'name str,
+        arguments: ArgTypes,
+    ) -> Result<ReturnType, InvokeErr<'name, ArgTypes>>
+    where
+        ReturnType: ReturnTypeReflection + for<'r> Marshal<'r> + 'static,
+    {
+        self.lock().invoke(function_name, arguments)
+    }
+
+    /// Updates the state of the runtime. This includes checking for file
+    /// changes, and reloading compiled assemblies.
+    ///
+    /// # Safety
+    ///
+    /// See [`Runtime::update`].
+    pub unsafe fn update(&self) -> bool {
+        self.lock().update()
+    }
+
+    /// Collects all memory that is no longer referenced by rooted objects.
+    /// Returns `true` if memory was reclaimed, `false` otherwise.
+    pub fn gc_collect(&self) -> bool {
+        self.lock().gc_collect()
+    }
+}
+
+impl From<Runtime> for SharedRuntime {
+    fn from(runtime: Runtime) -> Self {
+        Self::new(runtime)
+    }
+}
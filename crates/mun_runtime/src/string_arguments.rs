@@ -0,0 +1,116 @@
+use std::ffi::c_void;
+
+use mun_memory::Type;
+
+/// The maximum number of arguments of a function that is invoked with
+/// arguments that are parsed from strings. Every combination of argument types
+/// requires its own statically typed call, so the number of arguments is
+/// bounded.
+pub(crate) const MAX_ARGUMENTS: usize = 3;
+
+/// An argument of a Mun function that was parsed from a string according to
+/// the type of the parameter it is passed to.
+#[derive(Clone, Copy)]
+pub(crate) enum StringArgument {
+    Bool(bool),
+    F32(f32),
+    F64(f64),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Isize(isize),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    Usize(usize),
+}
+
+macro_rules! string_argument {
+    ($($variant:ident($ty:ty)),+ $(,)?) => {
+        impl StringArgument {
+            /// Parses `arg` as a value of type `ty`.
+            pub fn parse(ty: &Type, arg: &str) -> Result<Self, String> {
+                $(
+                    if ty.equals::<$ty>() {
+                        return arg.parse::<$ty>().map(StringArgument::$variant).map_err(|e| {
+                            format!("could not parse '{arg}' as `{}`: {e}", stringify!($ty))
+                        });
+                    }
+                )+
+
+                Err(format!(
+                    "arguments of type `{}` cannot be parsed from strings",
+                    ty.name()
+                ))
+            }
+        }
+
+        /// Evaluates `$body` with `$value` bound to the value of the
+        /// `StringArgument` `$arg`, statically typed as the type of its variant.
+        macro_rules! with_value {
+            ($arg:expr, |$value:ident| $body:expr) => {
+                match $arg {
+                    $(StringArgument::$variant($value) => $body,)+
+                }
+            };
+        }
+    };
+}
+
+string_argument!(
+    Bool(bool),
+    F32(f32),
+    F64(f64),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Isize(isize),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    Usize(usize),
+);
+
+/// Calls the function at `fn_ptr` with the specified arguments.
+///
+/// # Safety
+///
+/// The types of `arguments` must match the argument types of the function at
+/// `fn_ptr`, and `ReturnType` must match its return type. At most
+/// [`MAX_ARGUMENTS`] arguments can be passed.
+pub(crate) unsafe fn call<ReturnType>(
+    fn_ptr: *const c_void,
+    arguments: &[StringArgument],
+) -> ReturnType {
+    match *arguments {
+        [] => {
+            let function: fn() -> ReturnType = std::mem::transmute(fn_ptr);
+            function()
+        }
+        [a] => with_value!(a, |a| call1(fn_ptr, a)),
+        [a, b] => with_value!(a, |a| with_value!(b, |b| call2(fn_ptr, a, b))),
+        [a, b, c] => with_value!(a, |a| with_value!(b, |b| with_value!(c, |c| {
+            call3(fn_ptr, a, b, c)
+        }))),
+        _ => unreachable!("functions with more than {MAX_ARGUMENTS} arguments cannot be called"),
+    }
+}
+
+unsafe fn call1<A, ReturnType>(fn_ptr: *const c_void, a: A) -> ReturnType {
+    let function: fn(A) -> ReturnType = std::mem::transmute(fn_ptr);
+    function(a)
+}
+
+unsafe fn call2<A, B, ReturnType>(fn_ptr: *const c_void, a: A, b: B) -> ReturnType {
+    let function: fn(A, B) -> ReturnType = std::mem::transmute(fn_ptr);
+    function(a, b)
+}
+
+unsafe fn call3<A, B, C, ReturnType>(fn_ptr: *const c_void, a: A, b: B, c: C) -> ReturnType {
+    let function: fn(A, B, C) -> ReturnType = std::mem::transmute(fn_ptr);
+    function(a, b, c)
+}
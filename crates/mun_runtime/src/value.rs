@@ -0,0 +1,174 @@
+use crate::StructRef;
+
+/// A dynamically typed Mun value.
+///
+/// This enables interoperability with hosts that do not know the types of
+/// values at compile time. Native Rust values can be converted into a `Value`
+/// using [`From`] and back using [`TryFrom`].
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum Value<'s> {
+    /// A signed or unsigned integer
+    Int(i128),
+    /// A floating-point number
+    Float(f64),
+    /// A boolean
+    Bool(bool),
+    /// A reference to a Mun struct
+    Struct(StructRef<'s>),
+}
+
+/// An error that occurs when converting a [`Value`] into a native Rust value.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ValueError {
+    /// The value is of a different kind than the requested type
+    #[error("expected a value of type `{expected}`, found `{found}`")]
+    MismatchedType {
+        /// The name of the requested type
+        expected: &'static str,
+        /// The kind of the value
+        found: &'static str,
+    },
+    /// The integer value does not fit in the requested integer type
+    #[error("integer `{value}` does not fit in a `{expected}`")]
+    OutOfRange {
+        /// The integer value
+        value: i128,
+        /// The name of the requested type
+        expected: &'static str,
+    },
+}
+
+impl<'s> Value<'s> {
+    /// Returns the name of the kind of value that is stored.
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            Value::Int(_) => "int",
+            Value::Float(_) => "float",
+            Value::Bool(_) => "bool",
+            Value::Struct(_) => "struct",
+        }
+    }
+
+    fn mismatched_type(&self, expected: &'static str) -> ValueError {
+        ValueError::MismatchedType {
+            expected,
+            found: self.kind_name(),
+        }
+    }
+}
+
+impl std::fmt::Debug for Value<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(value) => f.debug_tuple("Int").field(value).finish(),
+            Value::Float(value) => f.debug_tuple("Float").field(value).finish(),
+            Value::Bool(value) => f.debug_tuple("Bool").field(value).finish(),
+            Value::Struct(value) => f
+                .debug_tuple("Struct")
+                .field(&value.type_info().name())
+                .finish(),
+        }
+    }
+}
+
+macro_rules! impl_int_value {
+    ($($ty:ty),+) => {
+        $(
+            impl From<$ty> for Value<'_> {
+                fn from(value: $ty) -> Self {
+                    // All supported integer types fit in an `i128`
+                    Value::Int(value as i128)
+                }
+            }
+
+            impl TryFrom<Value<'_>> for $ty {
+                type Error = ValueError;
+
+                fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::Int(value) => {
+                            <$ty>::try_from(value).map_err(|_| ValueError::OutOfRange {
+                                value,
+                                expected: stringify!($ty),
+                            })
+                        }
+                        value => Err(value.mismatched_type(stringify!($ty))),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_int_value!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<f32> for Value<'_> {
+    fn from(value: f32) -> Self {
+        Value::Float(value.into())
+    }
+}
+
+impl From<f64> for Value<'_> {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl TryFrom<Value<'_>> for f32 {
+    type Error = ValueError;
+
+    /// Converts a floating-point value into an `f32`, rounding it to the
+    /// nearest representable value.
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Float(value) => Ok(value as f32),
+            value => Err(value.mismatched_type("f32")),
+        }
+    }
+}
+
+impl TryFrom<Value<'_>> for f64 {
+    type Error = ValueError;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Float(value) => Ok(value),
+            value => Err(value.mismatched_type("f64")),
+        }
+    }
+}
+
+impl From<bool> for Value<'_> {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl TryFrom<Value<'_>> for bool {
+    type Error = ValueError;
+
+    fn try_from(value: Value<'_>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Bool(value) => Ok(value),
+            value => Err(value.mismatched_type("bool")),
+        }
+    }
+}
+
+impl<'s> From<StructRef<'s>> for Value<'s> {
+    fn from(value: StructRef<'s>) -> Self {
+        Value::Struct(value)
+    }
+}
+
+impl<'s> TryFrom<Value<'s>> for StructRef<'s> {
+    type Error = ValueError;
+
+    fn try_from(value: Value<'s>) -> Result<Self, Self::Error> {
+        match value {
+            Value::Struct(value) => Ok(value),
+            value => Err(value.mismatched_type("struct")),
+        }
+    }
+}
@@ -19,6 +19,48 @@ fn arrays() {
     assert_eq!(result.iter().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
 }
 
+#[test]
+fn array_repeat() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn zeros() -> [i32] { [0; 5] }
+    pub fn repeat(value: f64) -> [f64] { [value; 3] }
+    pub fn empty() -> [f64] { [1.5; 0] }
+    pub fn computed() -> [i32] { [1; 2 + 3] }
+    pub fn sized() -> [u8] { [0; size_of::<i64>()] }
+
+    pub struct Counter { value: i32 }
+    pub fn distinct() -> i32 {
+        let counters = [Counter { value: 1 }; 2];
+        let first = counters[0];
+        first.value = 5;
+        counters[1].value
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: ArrayRef<'_, i32> = driver.runtime.invoke("zeros", ()).unwrap();
+    assert_eq!(result.len(), 5);
+    assert!(result.iter().all(|value| value == 0));
+
+    let result: ArrayRef<'_, f64> = driver.runtime.invoke("repeat", (1.5f64,)).unwrap();
+    assert_eq!(result.iter().collect::<Vec<_>>(), vec![1.5, 1.5, 1.5]);
+
+    let result: ArrayRef<'_, f64> = driver.runtime.invoke("empty", ()).unwrap();
+    assert_eq!(result.len(), 0);
+
+    let result: ArrayRef<'_, i32> = driver.runtime.invoke("computed", ()).unwrap();
+    assert_eq!(result.len(), 5);
+
+    let result: ArrayRef<'_, u8> = driver.runtime.invoke("sized", ()).unwrap();
+    assert_eq!(result.len(), 8);
+
+    let result: i32 = driver.runtime.invoke("distinct", ()).unwrap();
+    assert_eq!(result, 1);
+}
+
 #[test]
 fn array_of_structs() {
     let driver = CompileAndRunTestDriver::new(
@@ -114,3 +156,66 @@ fn construct_array_simple() {
     assert_eq!(array.len(), test_data.len());
     assert_eq!(array.iter().collect_vec(), test_data);
 }
+
+#[test]
+fn construct_array_of_structs() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Bar { a: i32 };
+    pub struct(value) ValueBar { a: i32 };
+
+    pub fn new_bar(a: i32) -> Bar { Bar { a } }
+    pub fn new_value_bar(a: i32) -> ValueBar { ValueBar { a } }
+
+    pub fn sum(bars: [Bar]) -> i32 { bars[0].a + bars[1].a }
+    pub fn sum_value(bars: [ValueBar]) -> i32 { bars[0].a + bars[1].a }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    let runtime = &driver.runtime;
+
+    // Gc structs are stored as handles
+    let bar_type = runtime.get_type_info_by_name("Bar").unwrap();
+    let bars: Vec<StructRef<'_>> = vec![
+        runtime.invoke("new_bar", (5i32,)).unwrap(),
+        runtime.invoke("new_bar", (7i32,)).unwrap(),
+    ];
+    let array = runtime.construct_struct_array(&bar_type, bars).root();
+
+    // Elements must remain reachable through the array
+    runtime.gc_collect();
+
+    let array = array.as_ref(runtime);
+    assert_eq!(array.len(), 2);
+    assert_eq!(
+        array
+            .iter()
+            .map(|bar| bar.get::<i32>("a").unwrap())
+            .collect::<Vec<_>>(),
+        vec![5, 7]
+    );
+    let result: i32 = runtime.invoke("sum", (array,)).unwrap();
+    assert_eq!(result, 12);
+
+    // Value structs are stored inline
+    let value_bar_type = runtime.get_type_info_by_name("ValueBar").unwrap();
+    let bars: Vec<StructRef<'_>> = vec![
+        runtime.invoke("new_value_bar", (3i32,)).unwrap(),
+        runtime.invoke("new_value_bar", (4i32,)).unwrap(),
+    ];
+    let array = runtime.construct_struct_array(&value_bar_type, bars).root();
+
+    runtime.gc_collect();
+
+    let array = array.as_ref(runtime);
+    assert_eq!(
+        array
+            .iter()
+            .map(|bar| bar.get::<i32>("a").unwrap())
+            .collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+    let result: i32 = runtime.invoke("sum_value", (array,)).unwrap();
+    assert_eq!(result, 7);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "compiler")]
+
+use mun_runtime::{CompileAndLoadError, Runtime};
+
+#[test]
+fn compile_and_load_str() {
+    let runtime = Runtime::compile_and_load_str(
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    ",
+    )
+    .expect("Failed to compile and load source");
+
+    let result: i32 = runtime.invoke("add", (3i32, 4i32)).unwrap();
+    assert_eq!(result, 7);
+}
+
+#[test]
+fn compile_and_load_str_diagnostics() {
+    let result = Runtime::compile_and_load_str(
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a + c }
+    ",
+    );
+
+    match result {
+        Err(CompileAndLoadError::Diagnostics(diagnostics)) => {
+            assert!(
+                diagnostics.contains("cannot find value `c` in this scope"),
+                "{diagnostics}"
+            );
+        }
+        Err(err) => panic!("unexpected error: {err}"),
+        Ok(_) => panic!("expected compiler errors"),
+    }
+}
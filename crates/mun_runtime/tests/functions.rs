@@ -1,6 +1,8 @@
 #[macro_use]
 mod util;
 
+use mun_compiler::{Config, OverflowBehavior};
+use mun_runtime::{ExecutionError, InvokeError, StructRef};
 use mun_test::CompileAndRunTestDriver;
 
 #[test]
@@ -121,3 +123,516 @@ fn multiple_match_exists_function() {
         )
     );
 }
+
+#[test]
+fn block_expr_value() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i32 {
+        let a = 10;
+        let x = { let a = 1; a + 2 };
+        x + a
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 13);
+}
+
+#[test]
+fn unit_type_binding() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    fn nothing() {}
+    pub fn main() -> i32 {
+        let x: () = nothing();
+        let y = x;
+        5
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 5);
+}
+
+#[test]
+fn type_layout_builtins() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    struct Foo { a: i64, b: i64 }
+    struct(value) Bar { a: u8, b: u32 }
+
+    pub fn foo_size() -> usize { size_of::<Foo>() }
+    pub fn bar_size() -> usize { size_of::<Bar>() }
+    pub fn bar_align() -> usize { align_of::<Bar>() }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let foo_size: usize = driver.runtime.invoke("foo_size", ()).unwrap();
+    assert_eq!(foo_size, 16);
+    let bar_size: usize = driver.runtime.invoke("bar_size", ()).unwrap();
+    assert_eq!(bar_size, 8);
+    let bar_align: usize = driver.runtime.invoke("bar_align", ()).unwrap();
+    assert_eq!(bar_align, 4);
+}
+
+#[test]
+fn invoke_from_strings() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    pub fn negate(a: bool) -> bool { !a }
+    pub fn scale(a: f64, b: i32) -> f64 { if b == 2 { a * 2.0 } else { a } }
+    pub fn mixed(a: u8, b: f32, c: bool) -> f32 {
+        if c && a == 1 { b } else { 0.0 }
+    }
+    pub fn many(a: i32, b: i32, c: i32, d: i32) -> i32 { a + b + c + d }
+    pub fn wide(a: i128) -> i32 { 0 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver
+        .runtime
+        .invoke_from_strings("add", &["1", "2"])
+        .unwrap();
+    assert_eq!(result, 3);
+
+    let result: bool = driver
+        .runtime
+        .invoke_from_strings("negate", &["true"])
+        .unwrap();
+    assert!(!result);
+
+    let err = driver
+        .runtime
+        .invoke_from_strings::<i32>("add", &["1", "two"])
+        .unwrap_err();
+    assert!(err.to_string().contains("argument #2"), "{err}");
+
+    assert!(driver
+        .runtime
+        .invoke_from_strings::<i32>("add", &["1"])
+        .is_err());
+
+    let result: f64 = driver
+        .runtime
+        .invoke_from_strings("scale", &["1.5", "2"])
+        .unwrap();
+    assert_eq!(result, 3.0);
+
+    let result: f32 = driver
+        .runtime
+        .invoke_from_strings("mixed", &["1", "2.5", "true"])
+        .unwrap();
+    assert_eq!(result, 2.5);
+
+    let err = driver
+        .runtime
+        .invoke_from_strings::<i32>("many", &["1", "2", "3", "4"])
+        .unwrap_err();
+    assert!(err.to_string().contains("more than 3 arguments"), "{err}");
+
+    let err = driver
+        .runtime
+        .invoke_from_strings::<i32>("wide", &["1"])
+        .unwrap_err();
+    assert!(err.to_string().contains("argument #1"), "{err}");
+}
+
+#[test]
+fn unit_early_return() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Counter { value: i32 }
+
+    pub fn new_counter(value: i32) -> Counter { Counter { value } }
+
+    pub fn clamp(counter: Counter) {
+        if counter.value < 0 {
+            counter.value = 0;
+            return;
+        }
+        counter.value = counter.value * 2;
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let counter: StructRef<'_> = driver.runtime.invoke("new_counter", (-5i32,)).unwrap();
+    let _: () = driver.runtime.invoke("clamp", (counter.clone(),)).unwrap();
+    assert_eq!(counter.get::<i32>("value").unwrap(), 0);
+
+    let counter: StructRef<'_> = driver.runtime.invoke("new_counter", (5i32,)).unwrap();
+    let _: () = driver.runtime.invoke("clamp", (counter.clone(),)).unwrap();
+    assert_eq!(counter.get::<i32>("value").unwrap(), 10);
+}
+
+#[test]
+fn named_arguments() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    fn volume(width: i32, height: i32, depth: i32) -> i32 {
+        width * 100 + height * 10 + depth
+    }
+
+    pub fn main() -> i32 {
+        volume(depth: 3, width: 1, height: 2)
+    }
+
+    pub fn mixed() -> i32 {
+        volume(4, depth: 6, height: 5)
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 123);
+    let result: i32 = driver.runtime.invoke("mixed", ()).unwrap();
+    assert_eq!(result, 456);
+}
+
+#[test]
+fn default_parameter_values() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    fn greet(times: i32, excited: bool = false, offset: i32 = -5) -> i32 {
+        let value = if excited { times * 10 } else { times };
+        value + offset
+    }
+
+    pub fn with_default() -> i32 {
+        greet(2)
+    }
+
+    pub fn without_default() -> i32 {
+        greet(2, true)
+    }
+
+    pub fn named_default() -> i32 {
+        greet(3, offset: 1)
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("with_default", ()).unwrap();
+    assert_eq!(result, -3);
+    let result: i32 = driver.runtime.invoke("without_default", ()).unwrap();
+    assert_eq!(result, 15);
+    let result: i32 = driver.runtime.invoke("named_default", ()).unwrap();
+    assert_eq!(result, 4);
+}
+
+#[test]
+fn script_mode() {
+    let driver = CompileAndRunTestDriver::from_script("3 + 4", |builder| builder)
+        .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 7);
+
+    let driver = CompileAndRunTestDriver::from_script(
+        r"
+    fn double(a: i32) -> i32 { a * 2 }
+
+    let a = 5;
+    double(a) + 1
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 11);
+
+    // A recursive call uses the return type that is inferred from the body
+    let driver = CompileAndRunTestDriver::from_script(
+        r"
+    let a = 1;
+    if a > 1 { main() } else { a }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 1);
+}
+
+#[test]
+fn overflow_behavior() {
+    const SOURCE: &str = r"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    pub fn mul(a: u8, b: u8) -> u8 { a * b }
+    ";
+
+    let driver = CompileAndRunTestDriver::new(SOURCE, |builder| builder)
+        .expect("Failed to build test driver");
+    let result: i32 = driver.runtime.invoke("add", (i32::MAX, 1)).unwrap();
+    assert_eq!(result, i32::MIN);
+    assert_eq!(driver.runtime.take_execution_error(), None);
+
+    let config = Config {
+        overflow_behavior: OverflowBehavior::Saturating,
+        ..Config::default()
+    };
+    let driver = CompileAndRunTestDriver::from_file_with_config(SOURCE, config, |builder| builder)
+        .expect("Failed to build test driver");
+    let result: i32 = driver.runtime.invoke("add", (i32::MAX, 1)).unwrap();
+    assert_eq!(result, i32::MAX);
+    let result: u8 = driver.runtime.invoke("mul", (16u8, 16u8)).unwrap();
+    assert_eq!(result, u8::MAX);
+
+    let config = Config {
+        overflow_behavior: OverflowBehavior::Checked,
+        ..Config::default()
+    };
+    let driver = CompileAndRunTestDriver::from_file_with_config(SOURCE, config, |builder| builder)
+        .expect("Failed to build test driver");
+    let result: i32 = driver.runtime.invoke("add", (1, 2)).unwrap();
+    assert_eq!(result, 3);
+    assert_eq!(driver.runtime.take_execution_error(), None);
+
+    let err = driver
+        .runtime
+        .invoke::<i32, _>("add", (i32::MAX, 1))
+        .unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &InvokeError::Trap(ExecutionError::ArithmeticOverflow)
+    );
+    let err = driver
+        .runtime
+        .invoke::<u8, _>("mul", (16u8, 16u8))
+        .unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &InvokeError::Trap(ExecutionError::ArithmeticOverflow)
+    );
+    assert_eq!(driver.runtime.take_execution_error(), None);
+}
+
+#[test]
+fn negation() {
+    const SOURCE: &str = r"
+    pub fn negate(n: i32) -> i32 { -n }
+    pub fn double_negative() -> bool { 3 - -2 == 5 }
+    pub fn min_i8() -> i8 { -128 }
+    ";
+
+    let driver = CompileAndRunTestDriver::new(SOURCE, |builder| builder)
+        .expect("Failed to build test driver");
+    let result: i32 = driver.runtime.invoke("negate", (5,)).unwrap();
+    assert_eq!(result, -5);
+    let result: bool = driver.runtime.invoke("double_negative", ()).unwrap();
+    assert!(result);
+    let result: i8 = driver.runtime.invoke("min_i8", ()).unwrap();
+    assert_eq!(result, i8::MIN);
+    let result: i32 = driver.runtime.invoke("negate", (i32::MIN,)).unwrap();
+    assert_eq!(result, i32::MIN);
+
+    let config = Config {
+        overflow_behavior: OverflowBehavior::Saturating,
+        ..Config::default()
+    };
+    let driver = CompileAndRunTestDriver::from_file_with_config(SOURCE, config, |builder| builder)
+        .expect("Failed to build test driver");
+    let result: i32 = driver.runtime.invoke("negate", (i32::MIN,)).unwrap();
+    assert_eq!(result, i32::MAX);
+
+    let config = Config {
+        overflow_behavior: OverflowBehavior::Checked,
+        ..Config::default()
+    };
+    let driver = CompileAndRunTestDriver::from_file_with_config(SOURCE, config, |builder| builder)
+        .expect("Failed to build test driver");
+    let result: i8 = driver.runtime.invoke("min_i8", ()).unwrap();
+    assert_eq!(result, i8::MIN);
+    assert_eq!(driver.runtime.take_execution_error(), None);
+    let err = driver
+        .runtime
+        .invoke::<i32, _>("negate", (i32::MIN,))
+        .unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &InvokeError::Trap(ExecutionError::ArithmeticOverflow)
+    );
+}
+
+#[test]
+fn try_invoke_errors() {
+    let config = Config {
+        overflow_behavior: OverflowBehavior::Checked,
+        ..Config::default()
+    };
+    let driver = CompileAndRunTestDriver::from_file_with_config(
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    ",
+        config,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    let runtime = &driver.runtime;
+
+    let result: Result<i32, _> = runtime.try_invoke("add", (1i32, 2i32));
+    assert_eq!(result, Ok(3));
+
+    match runtime.try_invoke::<i32, _>("ad", (1i32, 2i32)) {
+        Err(InvokeError::FunctionNotFound { name, suggestion }) => {
+            assert_eq!(name, "ad");
+            assert_eq!(suggestion.as_deref(), Some("add"));
+        }
+        result => panic!("unexpected result: {result:?}"),
+    }
+
+    match runtime.try_invoke::<i32, _>("add", (1i32,)) {
+        Err(InvokeError::ArityMismatch { expected, found }) => {
+            assert_eq!((expected, found), (2, 1));
+        }
+        result => panic!("unexpected result: {result:?}"),
+    }
+
+    match runtime.try_invoke::<i32, _>("add", (1i32, 2.0f64)) {
+        Err(InvokeError::ArgTypeMismatch {
+            index,
+            expected,
+            found,
+            ..
+        }) => {
+            assert_eq!(index, 1);
+            assert_eq!(expected, "core::i32");
+            assert_eq!(found, "core::f64");
+        }
+        result => panic!("unexpected result: {result:?}"),
+    }
+
+    match runtime.try_invoke::<f64, _>("add", (1i32, 2i32)) {
+        Err(InvokeError::ReturnTypeMismatch { expected, found }) => {
+            assert_eq!(expected, "core::f64");
+            assert_eq!(found, "core::i32");
+        }
+        result => panic!("unexpected result: {result:?}"),
+    }
+
+    assert_eq!(
+        runtime.try_invoke::<i32, _>("add", (i32::MAX, 1i32)),
+        Err(InvokeError::Trap(ExecutionError::ArithmeticOverflow))
+    );
+    assert_eq!(runtime.take_execution_error(), None);
+
+    let err = runtime
+        .invoke_from_strings::<i32>("add", &["2147483647", "1"])
+        .unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &InvokeError::Trap(ExecutionError::ArithmeticOverflow)
+    );
+
+    let err = runtime.invoke::<i32, _>("add", (1i32,)).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &InvokeError::ArityMismatch {
+            expected: 2,
+            found: 1
+        }
+    );
+}
+
+#[test]
+fn function_signature_string() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i32 }
+
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    pub fn new_foo(a: i32, values: [f64]) -> Foo { Foo { a } }
+    pub fn consume(foo: Foo) {}
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    assert_eq!(
+        runtime.function_signature_string("add").as_deref(),
+        Some("add(i32, i32): i32")
+    );
+    assert_eq!(
+        runtime.function_signature_string("new_foo").as_deref(),
+        Some("new_foo(i32, [f64]): Foo")
+    );
+    assert_eq!(
+        runtime.function_signature_string("consume").as_deref(),
+        Some("consume(Foo)")
+    );
+    assert_eq!(runtime.function_signature_string("missing"), None);
+}
+
+#[test]
+fn prelude_functions() {
+    let config = Config {
+        prelude: true,
+        ..Config::default()
+    };
+    let driver = CompileAndRunTestDriver::from_file_with_config(
+        r#"
+    pub fn hypot(a: f64, b: f64) -> f64 {
+        math::sqrt(a * a + b * b)
+    }
+
+    pub fn clamped(x: f64) -> f64 {
+        math::clamp(x, 0.0, 1.0)
+    }
+    "#,
+        config,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: f64 = driver.runtime.invoke("hypot", (3.0f64, 4.0f64)).unwrap();
+    assert_eq!(result, 5.0);
+    let result: f64 = driver.runtime.invoke("clamped", (1.5f64,)).unwrap();
+    assert_eq!(result, 1.0);
+}
+
+#[test]
+fn seeded_rand() {
+    fn sequence(seed: u64) -> Vec<u64> {
+        let driver = CompileAndRunTestDriver::new(
+            r"
+        extern fn rand() -> u64;
+
+        pub fn next() -> u64 { rand() }
+        ",
+            |builder| builder.set_random_seed(seed),
+        )
+        .expect("Failed to build test driver");
+
+        (0..8)
+            .map(|_| {
+                let value: u64 = driver.runtime.invoke("next", ()).unwrap();
+                value
+            })
+            .collect()
+    }
+
+    let first = sequence(42);
+    assert_eq!(first, sequence(42));
+    assert_ne!(first, sequence(7));
+    assert!(first.windows(2).all(|pair| pair[0] != pair[1]));
+}
@@ -1,8 +1,13 @@
 #[macro_use]
 mod util;
 
-use mun_runtime::StructRef;
-use mun_test::CompileAndRunTestDriver;
+use std::{
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use mun_runtime::{Runtime, StructRef};
+use mun_test::{CompileAndRunTestDriver, CompileTestDriver};
 
 #[test]
 fn reloadable_function_single_file() {
@@ -24,6 +29,101 @@ fn reloadable_function_single_file() {
     assert_invoke_eq!(i32, 10, driver, "main");
 }
 
+#[test]
+fn disabled_hot_reload() {
+    let mut driver = CompileTestDriver::from_file(
+        r"
+    pub fn main() -> i32 { 5 }
+    ",
+    );
+
+    // Safety: We compiled the library ourselves, therefor loading the munlib is safe.
+    let mut runtime = unsafe {
+        Runtime::builder(driver.lib_path())
+            .disable_hot_reload()
+            .finish()
+    }
+    .expect("Failed to build runtime");
+    assert!(!runtime.hot_reload_enabled());
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    pub fn main() -> i32 { 10 }
+    ",
+    );
+
+    // Give a watcher ample time to pick up the change, if there were one
+    let start_time = Instant::now();
+    while start_time.elapsed() < Duration::from_millis(500) {
+        assert!(unsafe { runtime.update() });
+        sleep(Duration::from_millis(10));
+    }
+
+    let result: i32 = runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 5);
+}
+
+#[test]
+fn disabled_hot_reload_update_is_noop() {
+    let driver = CompileTestDriver::from_file(
+        r"
+    pub fn update(dt: f64) {}
+    ",
+    );
+
+    // Safety: We compiled the library ourselves, therefor loading the munlib is safe.
+    let mut runtime = unsafe {
+        Runtime::builder(driver.lib_path())
+            .disable_hot_reload()
+            .finish()
+    }
+    .expect("Failed to build runtime");
+
+    assert!(unsafe { runtime.update() });
+    assert!(!unsafe { runtime.tick("update", 0.016) }.unwrap());
+}
+
+#[test]
+fn tick_reports_reload() {
+    let mut driver = CompileTestDriver::from_file(
+        r"
+    pub fn update(dt: f64) {}
+    pub fn value() -> i32 { 5 }
+    ",
+    );
+
+    // Safety: We compiled the library ourselves, therefor loading the munlib is safe.
+    let mut runtime =
+        unsafe { Runtime::builder(driver.lib_path()).finish() }.expect("Failed to build runtime");
+
+    for _ in 0..3 {
+        assert!(!unsafe { runtime.tick("update", 0.016) }.unwrap());
+    }
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    pub fn update(dt: f64) {}
+    pub fn value() -> i32 { 10 }
+    ",
+    );
+
+    // Keep ticking until the recompiled assembly has been picked up
+    let start_time = Instant::now();
+    while !unsafe { runtime.tick("update", 0.016) }.unwrap() {
+        assert!(
+            start_time.elapsed() < Duration::from_secs(10),
+            "runtime did not reload after recompilation within 10 seconds"
+        );
+        sleep(Duration::from_millis(1));
+    }
+
+    let value: i32 = runtime.invoke("value", ()).unwrap();
+    assert_eq!(value, 10);
+    assert!(!unsafe { runtime.tick("update", 0.016) }.unwrap());
+}
+
 #[test]
 fn reloadable_function_multi_file() {
     let mut driver = CompileAndRunTestDriver::from_fixture(
@@ -190,3 +290,35 @@ fn reloadable_struct_decl_multi_file() {
         1
     );
 }
+
+#[test]
+fn function_validity_after_reload() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i32 { 5 }
+    pub fn removed() -> i32 { 1 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let main = driver.runtime.get_function_definition("main").unwrap();
+    let removed = driver.runtime.get_function_definition("removed").unwrap();
+    assert!(driver.runtime.is_function_valid(&main));
+    assert!(driver.runtime.is_function_valid(&removed));
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    pub fn main() -> i32 { 10 }
+    ",
+    );
+
+    assert!(!driver.runtime.is_function_valid(&main));
+    assert!(!driver.runtime.is_function_valid(&removed));
+
+    // Functions that still exist can be resolved again
+    let main = driver.runtime.get_function_definition("main").unwrap();
+    assert!(driver.runtime.is_function_valid(&main));
+    assert!(driver.runtime.get_function_definition("removed").is_none());
+}
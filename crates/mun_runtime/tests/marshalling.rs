@@ -1,4 +1,6 @@
-use mun_runtime::{ArgumentReflection, Marshal, ReturnTypeReflection, StructRef};
+use mun_runtime::{
+    ArgumentReflection, Marshal, ReturnTypeReflection, StructRef, Value, ValueError,
+};
 use mun_test::CompileAndRunTestDriver;
 
 #[macro_use]
@@ -17,6 +19,27 @@ fn compile_and_run() {
     assert_invoke_eq!((), (), driver, "main");
 }
 
+#[test]
+fn argument_type_mismatch() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+        pub fn main(a: i64, b: i64) -> i64 { a + b }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: Result<i64, _> = driver.runtime.invoke("main", (1i64, 2.0f64));
+    let message = result.unwrap_err().to_string();
+    assert!(message.contains("'main'"), "{message}");
+    assert!(message.contains("parameter 1"), "{message}");
+    assert!(message.contains("Expected: `core::i64`"), "{message}");
+    assert!(
+        message.contains("Found: `core::f64` (Rust type `f64`)"),
+        "{message}"
+    );
+}
+
 #[test]
 fn return_value() {
     let driver = CompileAndRunTestDriver::new(
@@ -218,6 +241,31 @@ fn fibonacci_while() {
     assert_invoke_eq!(i64, 46368, driver, "fibonacci", 24i64);
 }
 
+#[test]
+fn while_break_value() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn find_root(n: i64, limit: i64) -> i64 {
+        let i = 0;
+        while i < limit {
+            if i * i == n {
+                break i;
+            }
+            i += 1;
+        } else {
+            -1
+        }
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i64, 4, driver, "find_root", 16i64, 10i64);
+    assert_invoke_eq!(i64, -1, driver, "find_root", 15i64, 10i64);
+    assert_invoke_eq!(i64, -1, driver, "find_root", 81i64, 5i64);
+}
+
 #[test]
 fn true_is_true() {
     let driver = CompileAndRunTestDriver::new(
@@ -735,3 +783,166 @@ fn signed_and_unsigned_rem() {
     assert_invoke_eq!(i32, -2, driver, "signed");
     assert_invoke_eq!(i32, 2, driver, "unsigned");
 }
+
+#[test]
+fn struct_from_raw_ptr() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i32, b: f64 }
+
+    pub fn foo_new(a: i32, b: f64) -> Foo { Foo { a, b } }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let foo: StructRef<'_> = driver.runtime.invoke("foo_new", (4i32, 2.5f64)).unwrap();
+    let ty = foo.type_info();
+    let ptr = foo.as_ptr();
+
+    // Keep the struct alive while it is accessed through the raw pointer
+    let rooted = foo.root();
+
+    // SAFETY: the struct is rooted and was allocated by the same runtime
+    let foo = unsafe { StructRef::from_raw(&driver.runtime, ptr, &ty) };
+    assert_eq!(foo.get::<i32>("a"), rooted.as_ref(&driver.runtime).get("a"));
+    assert_eq!(foo.get::<f64>("b"), Ok(2.5));
+}
+
+#[test]
+fn repr_c_field_offsets() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    #[repr(C)]
+    pub struct(value) Foo {
+        a: u8,
+        b: u32,
+        c: u16,
+        d: u64,
+    }
+
+    pub fn foo() -> Foo { Foo { a: 1, b: 2, c: 3, d: 4 } }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let ty = driver
+        .runtime
+        .get_type_info_by_name("Foo")
+        .expect("missing type info for `Foo`");
+    let offsets: Vec<usize> = ty
+        .as_struct()
+        .unwrap()
+        .fields()
+        .iter()
+        .map(|field| field.offset())
+        .collect();
+    assert_eq!(offsets, vec![0, 4, 8, 16]);
+
+    let foo: StructRef<'_> = driver.runtime.invoke("foo", ()).unwrap();
+    assert_eq!(foo.get::<u8>("a"), Ok(1));
+    assert_eq!(foo.get::<u32>("b"), Ok(2));
+    assert_eq!(foo.get::<u16>("c"), Ok(3));
+    assert_eq!(foo.get::<u64>("d"), Ok(4));
+}
+
+#[test]
+fn value_conversion() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Foo { a: i32 }
+
+    pub fn new_foo(a: i32) -> Foo { Foo { a } }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let value = Value::from(5i32);
+    assert!(matches!(value, Value::Int(5)));
+    assert_eq!(i32::try_from(value.clone()), Ok(5));
+    assert_eq!(i64::try_from(value.clone()), Ok(5));
+    assert_eq!(
+        bool::try_from(value),
+        Err(ValueError::MismatchedType {
+            expected: "bool",
+            found: "int"
+        })
+    );
+    assert_eq!(
+        u8::try_from(Value::from(-1i64)),
+        Err(ValueError::OutOfRange {
+            value: -1,
+            expected: "u8"
+        })
+    );
+
+    assert_eq!(u64::try_from(Value::from(u64::MAX)), Ok(u64::MAX));
+    assert_eq!(usize::try_from(Value::from(7usize)), Ok(7));
+    assert_eq!(i128::try_from(Value::from(i128::MIN)), Ok(i128::MIN));
+    assert_eq!(
+        i64::try_from(Value::from(u64::MAX)),
+        Err(ValueError::OutOfRange {
+            value: u64::MAX.into(),
+            expected: "i64"
+        })
+    );
+
+    assert_eq!(f64::try_from(Value::from(1.5f32)), Ok(1.5));
+    assert_eq!(f32::try_from(Value::from(1.5f64)), Ok(1.5));
+    assert_eq!(bool::try_from(Value::from(true)), Ok(true));
+    assert!(f64::try_from(Value::from(true)).is_err());
+
+    let foo: StructRef<'_> = driver.runtime.invoke("new_foo", (3i32,)).unwrap();
+    let value = Value::from(foo);
+    assert!(i32::try_from(value.clone()).is_err());
+    let foo = StructRef::try_from(value).unwrap();
+    assert_eq!(foo.get::<i32>("a").unwrap(), 3);
+}
+
+#[test]
+fn field_ref_aliases_parent() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct(value) Inner { a: i32, b: f32 }
+    pub struct(value) Middle { inner: Inner }
+    pub struct(gc) Outer { middle: Middle, c: i32 }
+
+    pub fn outer_new() -> Outer {
+        Outer { middle: Middle { inner: Inner { a: 1, b: 2.0 } }, c: 3 }
+    }
+
+    pub fn outer_inner_a(outer: Outer) -> i32 {
+        outer.middle.inner.a
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let mut outer: StructRef<'_> = driver.runtime.invoke("outer_new", ()).unwrap();
+    {
+        let mut middle = outer.field_ref("middle").unwrap();
+        let mut inner = middle.field_ref("inner").unwrap();
+        assert_eq!(inner.get::<i32>("a"), Ok(1));
+        assert_eq!(inner.replace("a", 5i32), Ok(1));
+        inner.set("b", 4f32).unwrap();
+    }
+
+    // The changes are visible in the parent, without copying it back
+    let inner = outer
+        .get::<StructRef<'_>>("middle")
+        .unwrap()
+        .get::<StructRef<'_>>("inner")
+        .unwrap();
+    assert_eq!(inner.get::<i32>("a"), Ok(5));
+    assert_eq!(inner.get::<f32>("b"), Ok(4.0));
+
+    // Fields that are not structs cannot be borrowed as a struct
+    assert!(outer.field_ref("c").is_err());
+    assert!(outer.field_ref("d").is_err());
+
+    let a: i32 = driver.runtime.invoke("outer_inner_a", (outer,)).unwrap();
+    assert_eq!(a, 5);
+}
@@ -1,4 +1,8 @@
-use mun_runtime::{ArrayRef, StructRef};
+use std::sync::{Arc, Mutex};
+
+use mun_runtime::{
+    ArrayRef, ExecutionError, GcCollectionStats, InvalidRootError, InvokeError, StructRef,
+};
 use mun_test::CompileAndRunTestDriver;
 
 #[macro_use]
@@ -43,6 +47,267 @@ fn gc_trace() {
     assert_eq!(runtime.gc_stats().allocated_memory, 0);
 }
 
+#[test]
+fn gc_callback() {
+    let collections: Arc<Mutex<Vec<GcCollectionStats>>> = Arc::default();
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo {
+        a: i64,
+        b: f64,
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo { a: 1, b: 2.0 }
+    }
+    "#,
+        |builder| {
+            let collections = collections.clone();
+            builder.on_gc(move |stats| collections.lock().unwrap().push(stats.clone()))
+        },
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    for _ in 0..10 {
+        let _: StructRef<'_> = runtime.invoke("new_foo", ()).unwrap();
+    }
+    assert!(collections.lock().unwrap().is_empty());
+
+    assert!(runtime.gc_collect());
+
+    let collections = collections.lock().unwrap();
+    assert_eq!(collections.len(), 1);
+    assert!(collections[0].reclaimed_bytes > 0);
+    assert_eq!(runtime.gc_stats().allocated_memory, 0);
+}
+
+#[test]
+fn reset_heap() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo {
+        a: i64,
+        b: f64,
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo { a: 1, b: 2.0 }
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let foo: StructRef<'_> = driver.runtime.invoke("new_foo", ()).unwrap();
+    let foo = foo.root();
+    let foo_clone = foo.clone();
+    let _: StructRef<'_> = driver.runtime.invoke("new_foo", ()).unwrap();
+    assert!(driver.runtime.gc_stats().allocated_memory > 0);
+
+    driver.runtime.reset_heap();
+    assert_eq!(driver.runtime.gc_stats().allocated_memory, 0);
+    assert!(driver.runtime.memory_usage_by_type().is_empty());
+
+    assert_eq!(
+        foo.try_as_ref(&driver.runtime).err(),
+        Some(InvalidRootError)
+    );
+    drop(foo);
+    drop(foo_clone);
+
+    // The runtime can still be used after a reset
+    let foo: StructRef<'_> = driver.runtime.invoke("new_foo", ()).unwrap();
+    assert_eq!(foo.get::<i64>("a"), Ok(1));
+}
+
+#[test]
+fn gc_write_barrier() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct(value) Vec2 {
+        x: f32,
+        y: f32,
+    }
+
+    pub struct Leaf {
+        value: i64,
+    }
+
+    pub struct Node {
+        pos: Vec2,
+        next: Leaf,
+    }
+
+    pub fn new_node() -> Node {
+        Node { pos: Vec2 { x: 0.0, y: 0.0 }, next: Leaf { value: 0 } }
+    }
+
+    pub fn new_leaf() -> Leaf {
+        Leaf { value: 1 }
+    }
+
+    pub fn link(a: Node, b: Leaf) {
+        a.next = b;
+    }
+
+    pub fn move_node(a: Node) {
+        a.pos = Vec2 { x: 1.0, y: 2.0 };
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let a: StructRef<'_> = runtime.invoke("new_node", ()).unwrap();
+    let b: StructRef<'_> = runtime.invoke("new_leaf", ()).unwrap();
+    let write_barriers = runtime.gc_stats().write_barriers;
+
+    let _: () = runtime.invoke("move_node", (a.clone(),)).unwrap();
+    assert_eq!(runtime.gc_stats().write_barriers, write_barriers);
+
+    let _: () = runtime.invoke("link", (a, b)).unwrap();
+    assert_eq!(runtime.gc_stats().write_barriers, write_barriers + 1);
+}
+
+#[test]
+fn gc_trace_null_reference() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Node {
+        value: i64,
+    }
+
+    pub fn new_node(value: i64) -> Node {
+        Node { value }
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let node: StructRef<'_> = driver.runtime.invoke("new_node", (5i64,)).unwrap();
+    let node = node.root();
+
+    // The newly inserted `next` field is allocated as a zero-initialized struct, whose own
+    // `next` field is a null reference.
+    driver.update_file(
+        "mod.mun",
+        r#"
+    pub struct Node {
+        value: i64,
+        next: Node,
+    }
+    "#,
+    );
+
+    let runtime = &driver.runtime;
+    let next = node.as_ref(runtime).get::<StructRef<'_>>("next").unwrap();
+    assert_eq!(next.get::<i64>("value").unwrap(), 0);
+
+    // Tracing must skip the null reference
+    assert!(!runtime.gc_collect());
+    assert_eq!(node.as_ref(runtime).get::<i64>("value").unwrap(), 5);
+
+    drop(node);
+
+    assert!(runtime.gc_collect());
+    assert_eq!(runtime.gc_stats().allocated_memory, 0);
+}
+
+#[test]
+fn get_null_struct_field() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Node {
+        value: i64,
+    }
+
+    pub fn new_node(value: i64) -> Node {
+        Node { value }
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let node: StructRef<'_> = driver.runtime.invoke("new_node", (5i64,)).unwrap();
+    let node = node.root();
+
+    driver.update_file(
+        "mod.mun",
+        r#"
+    pub struct Node {
+        value: i64,
+        next: Node,
+    }
+    "#,
+    );
+
+    let runtime = &driver.runtime;
+    let node = node.as_ref(runtime);
+    let next = node
+        .get::<Option<StructRef<'_>>>("next")
+        .unwrap()
+        .expect("`next` should have been allocated");
+
+    // The `next` field of the zero-initialized node is null
+    assert!(next.get::<Option<StructRef<'_>>>("next").unwrap().is_none());
+}
+
+#[test]
+fn null_dereference() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Node {
+        value: i64,
+        next: Node,
+    }
+
+    pub fn new_list() -> Node {
+        let last = Node { value: 2, next: null };
+        Node { value: 1, next: last }
+    }
+
+    pub fn is_last(node: Node) -> bool {
+        node.next == null
+    }
+
+    pub fn sum(node: Node) -> i64 {
+        node.value + node.next.value
+    }
+
+    pub fn third(node: Node) -> i64 {
+        node.next.next.value
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    let runtime = &driver.runtime;
+
+    let list: StructRef<'_> = runtime.invoke("new_list", ()).unwrap();
+    let last = list
+        .get::<Option<StructRef<'_>>>("next")
+        .unwrap()
+        .expect("`next` should refer to the last node");
+    assert!(last.get::<Option<StructRef<'_>>>("next").unwrap().is_none());
+
+    assert!(!runtime
+        .invoke::<bool, _>("is_last", (list.clone(),))
+        .unwrap());
+    assert!(runtime.invoke::<bool, _>("is_last", (last,)).unwrap());
+    assert_eq!(runtime.invoke::<i64, _>("sum", (list.clone(),)).unwrap(), 3);
+
+    // Dereferencing the `next` field of the last node fails the invocation
+    let err = runtime.invoke::<i64, _>("third", (list,)).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &InvokeError::Trap(ExecutionError::NullDereference)
+    );
+}
+
 #[test]
 fn map_struct_insert_field1() {
     let mut driver = CompileAndRunTestDriver::new(
@@ -2511,3 +2776,230 @@ fn test_type_table() {
     // These types should be equal
     assert_eq!(foo_bar_field_type, bar_type);
 }
+
+#[test]
+fn memory_usage_by_type() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i64, b: i64 }
+    pub struct Bar { a: i32 }
+
+    pub fn new_foo() -> Foo { Foo { a: 1, b: 2 } }
+    pub fn new_bar() -> Bar { Bar { a: 3 } }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let foos: Vec<StructRef<'_>> = (0..3)
+        .map(|_| runtime.invoke("new_foo", ()).unwrap())
+        .collect();
+    let bars: Vec<StructRef<'_>> = (0..2)
+        .map(|_| runtime.invoke("new_bar", ()).unwrap())
+        .collect();
+
+    let usage = runtime.memory_usage_by_type();
+    assert_eq!(usage.len(), 2);
+
+    let foo_usage = usage["Foo"];
+    assert_eq!(foo_usage.instances, foos.len());
+    assert_eq!(foo_usage.allocated_memory, 3 * 16);
+
+    let bar_usage = usage["Bar"];
+    assert_eq!(bar_usage.instances, bars.len());
+    assert_eq!(bar_usage.allocated_memory, 2 * 4);
+
+    assert_eq!(
+        runtime.gc_stats().allocated_memory,
+        foo_usage.allocated_memory + bar_usage.allocated_memory
+    );
+}
+
+#[test]
+fn heap_compaction() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i64, b: i64 }
+    pub struct Bar { foo: Foo }
+
+    pub fn new_foo(a: i64) -> Foo { Foo { a, b: a * 2 } }
+    pub fn new_bar(foo: Foo) -> Bar { Bar { foo } }
+    pub fn foo_sum(foo: Foo) -> i64 { foo.a + foo.b }
+    "#,
+        |builder| builder.enable_heap_compaction(),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+
+    // Allocate objects and only keep every other one alive
+    let mut foos = Vec::new();
+    for i in 0..8i64 {
+        let foo: StructRef<'_> = runtime.invoke("new_foo", (i,)).unwrap();
+        if i % 2 == 0 {
+            foos.push(foo.root());
+        }
+    }
+    let foo = foos[1].as_ref(runtime);
+    let bar: StructRef<'_> = runtime.invoke("new_bar", (foo,)).unwrap();
+    let bar = bar.root();
+
+    assert!(runtime.gc_collect());
+
+    // The remaining objects are stored contiguously
+    let mut objects = foos
+        .iter()
+        .map(|foo| (foo.as_ref(runtime), 16))
+        .chain(std::iter::once((bar.as_ref(runtime), 8)))
+        .map(|(obj, size)| (unsafe { obj.into_raw().get_ptr() } as usize, size))
+        .collect::<Vec<_>>();
+    objects.sort_unstable();
+    for pair in objects.windows(2) {
+        assert_eq!(pair[1].0, pair[0].0 + pair[0].1);
+    }
+
+    // References to the objects still read the correct values
+    for (foo, a) in foos.iter().zip((0..8i64).step_by(2)) {
+        let foo = foo.as_ref(runtime);
+        assert_eq!(foo.get::<i64>("a"), Ok(a));
+        assert_eq!(foo.get::<i64>("b"), Ok(a * 2));
+        let sum: i64 = runtime.invoke("foo_sum", (foo,)).unwrap();
+        assert_eq!(sum, a * 3);
+    }
+    let foo = bar.as_ref(runtime).get::<StructRef<'_>>("foo").unwrap();
+    assert_eq!(foo.get::<i64>("a"), Ok(2));
+}
+
+#[test]
+fn struct_ref_survives_relocation() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Child { value: i64 }
+    pub struct Parent { a: Child, b: Child }
+
+    pub fn new_child(value: i64) -> Child { Child { value } }
+    pub fn new_parent(a: i64, b: i64) -> Parent {
+        Parent { a: new_child(a), b: new_child(b) }
+    }
+    "#,
+        |builder| builder.enable_heap_compaction(),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let parent: StructRef<'_> = runtime.invoke("new_parent", (1i64, 2i64)).unwrap();
+    let parent = parent.root();
+
+    // Unrooted references to objects that are kept alive by the parent
+    let a = parent.as_ref(runtime).get::<StructRef<'_>>("a").unwrap();
+    let b = parent.as_ref(runtime).get::<StructRef<'_>>("b").unwrap();
+    let a_ptr = unsafe { a.clone().into_raw().get_ptr() };
+
+    // Interleave garbage so the collection leaves gaps in the heap
+    for i in 0..4i64 {
+        let _: StructRef<'_> = runtime.invoke("new_child", (i,)).unwrap();
+    }
+    assert!(runtime.gc_collect());
+
+    // The memory of the objects was moved, but the references still resolve
+    assert_ne!(unsafe { a.clone().into_raw().get_ptr() }, a_ptr);
+    assert_eq!(a.get::<i64>("value"), Ok(1));
+    assert_eq!(b.get::<i64>("value"), Ok(2));
+
+    let a_again = parent.as_ref(runtime).get::<StructRef<'_>>("a").unwrap();
+    assert_eq!(unsafe { a_again.into_raw().get_ptr() }, unsafe {
+        a.into_raw().get_ptr()
+    });
+}
+
+#[test]
+fn array_iter_survives_relocation() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn new_array() -> [i64] { [1, 2, 3, 4] }
+    "#,
+        |builder| builder.enable_heap_compaction(),
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let array: ArrayRef<'_, i64> = runtime.invoke("new_array", ()).unwrap();
+    let array = array.root();
+
+    // Every collection compacts the heap, which moves the data of the array
+    let mut values = Vec::new();
+    for value in array.as_ref(runtime).iter() {
+        values.push(value);
+        runtime.gc_collect();
+    }
+    assert_eq!(values, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn max_allocations_per_call() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo {
+        value: i64,
+    }
+
+    pub fn allocate(n: i64) -> i64 {
+        let sum = 0;
+        let i = 0;
+        while i < n {
+            let foo = Foo { value: i };
+            sum += foo.value;
+            i += 1;
+        }
+        sum
+    }
+
+    fn new_foo(value: i64) -> Foo {
+        Foo { value: value }
+    }
+
+    pub fn allocate_nested(n: i64) -> i64 {
+        let sum = 0;
+        let i = 0;
+        while i < n {
+            sum += new_foo(i).value;
+            i += 1;
+        }
+        sum
+    }
+    "#,
+        |builder| builder.max_allocations_per_call(10),
+    )
+    .expect("Failed to build test driver");
+    let runtime = &driver.runtime;
+
+    assert_eq!(runtime.try_invoke::<i64, _>("allocate", (10i64,)), Ok(45));
+
+    // The limit applies to each invocation separately
+    assert_eq!(runtime.try_invoke::<i64, _>("allocate", (10i64,)), Ok(45));
+
+    // Once the limit is exceeded no more memory is allocated
+    runtime.gc_collect();
+    assert_eq!(
+        runtime.try_invoke::<i64, _>("allocate", (10_000_000i64,)),
+        Err(InvokeError::Trap(ExecutionError::AllocationLimitExceeded))
+    );
+    assert_eq!(
+        runtime.gc_stats().allocated_memory,
+        10 * std::mem::size_of::<i64>()
+    );
+
+    // A function that receives a failed allocation from a function it calls
+    // returns as well
+    assert_eq!(
+        runtime.try_invoke::<i64, _>("allocate_nested", (10_000_000i64,)),
+        Err(InvokeError::Trap(ExecutionError::AllocationLimitExceeded))
+    );
+
+    let err = runtime.invoke::<i64, _>("allocate", (11i64,)).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &InvokeError::Trap(ExecutionError::AllocationLimitExceeded)
+    );
+}
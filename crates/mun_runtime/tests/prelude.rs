@@ -0,0 +1,64 @@
+use mun_runtime::prelude::*;
+use mun_test::CompileAndRunTestDriver;
+
+#[test]
+fn typed_invoke_helpers() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn answer() -> i32 { 42 }
+    pub fn negate(a: i32) -> i32 { 0 - a }
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime: &Runtime = &driver.runtime;
+    let result: i32 = runtime.invoke2("add", 2i32, 3i32).unwrap();
+    assert_eq!(result, 5);
+    let expected: i32 = runtime.invoke("add", (2i32, 3i32)).unwrap();
+    assert_eq!(result, expected);
+
+    let result: i32 = runtime.invoke1("negate", 2i32).unwrap();
+    assert_eq!(result, -2);
+
+    let result: i32 = runtime.invoke0("answer").unwrap();
+    assert_eq!(result, 42);
+
+    let err = runtime.invoke2::<i32, f64, i32>("add", 2, 3.0).unwrap_err();
+    assert!(err.to_string().contains("Invalid argument type"));
+}
+
+#[test]
+fn typed_invoke_helpers_match_tuple_invoke() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    pub fn sum(a: i32, b: i32, c: i32, d: i32, e: i32, f: i32, g: i32, h: i32) -> i32 {
+        a + b + c + d + e + f + g + h
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let typed: i32 = runtime.invoke2("add", 2i32, 3i32).unwrap();
+    let tupled: i32 = runtime.invoke("add", (2i32, 3i32)).unwrap();
+    assert_eq!(typed, tupled);
+
+    let typed: i32 = runtime
+        .invoke8("sum", 1i32, 2i32, 3i32, 4i32, 5i32, 6i32, 7i32, 8i32)
+        .unwrap();
+    let tupled: i32 = runtime
+        .invoke("sum", (1i32, 2i32, 3i32, 4i32, 5i32, 6i32, 7i32, 8i32))
+        .unwrap();
+    assert_eq!(typed, 36);
+    assert_eq!(typed, tupled);
+
+    let err = runtime.invoke1::<i32, i32>("add", 2).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "failed to invoke 'add': Invalid argument count. Expected 2 arguments, got 1"
+    );
+}
@@ -1,5 +1,7 @@
-use mun_runtime::LinkFunctionsError;
-use mun_test::CompileAndRunTestDriver;
+use std::path::Path;
+
+use mun_runtime::{LinkFunctionsError, RuntimeBuilder, StructRef};
+use mun_test::{CompileAndRunTestDriver, CompileTestDriver};
 
 #[macro_use]
 mod util;
@@ -153,6 +155,35 @@ fn from_fixture() {
     assert_invoke_eq!(i32, 5, driver, "main");
 }
 
+#[test]
+fn function_source_file() {
+    let driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    pub fn main() -> i32 { foo::foo() }
+
+    //- /src/foo.mun
+    pub fn foo() -> i32 { 5 }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let main = driver.runtime.get_function_definition("main").unwrap();
+    assert_eq!(main.source_file.as_deref(), Some(Path::new("mod.mun")));
+    let foo = driver.runtime.get_function_definition("foo::foo").unwrap();
+    assert_eq!(foo.source_file.as_deref(), Some(Path::new("foo.mun")));
+
+    let assembly_path = driver.runtime.assembly_path("foo::foo").unwrap();
+    assert_eq!(assembly_path.file_name().unwrap(), "foo.munlib");
+    assert!(driver.runtime.assembly_path("bar").is_none());
+}
+
 #[test]
 fn error_assembly_not_linkable() {
     const EXPECTED_FN_NAME: &str = "dependency";
@@ -176,6 +207,29 @@ fn error_assembly_not_linkable() {
     );
 }
 
+#[test]
+fn dispatch_entries() {
+    extern "C" fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    extern fn add(a: i32, b: i32) -> i32;
+
+    pub fn main() -> i32 { add(1, 2) }
+    ",
+        |builder| builder.insert_fn("add", add as extern "C" fn(i32, i32) -> i32),
+    )
+    .expect("Failed to build test driver");
+
+    let entries = driver.runtime.dispatch_entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, "add");
+    assert!(entries[0].is_resolved);
+    assert_invoke_eq!(i32, 3, driver, "main");
+}
+
 #[test]
 fn arg_missing_bug() {
     let driver = CompileAndRunTestDriver::new(
@@ -231,3 +285,143 @@ fn cyclic_struct() {
         .ty();
     assert_eq!(foo_foo_ty, foo_ty);
 }
+
+#[test]
+fn recursive_gc_struct() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+        pub struct(gc) Node {
+            value: i64,
+            next: Node,
+        }
+
+        pub fn next_value(node: Node) -> i64 {
+            node.next.value
+        }
+        ",
+        |builder| builder,
+    )
+    .unwrap();
+
+    let node_ty = driver.runtime.get_type_info_by_name("Node").unwrap();
+    let next_field = node_ty
+        .as_struct()
+        .unwrap()
+        .fields()
+        .find_by_name("next")
+        .unwrap();
+
+    // The recursive field is stored as a reference to another object
+    assert_eq!(next_field.ty(), node_ty);
+    assert!(next_field.ty().is_reference_type());
+    assert_eq!(next_field.offset(), std::mem::size_of::<i64>());
+    assert_eq!(
+        node_ty.value_layout().size(),
+        std::mem::size_of::<i64>() + std::mem::size_of::<*const ()>()
+    );
+}
+
+#[test]
+fn struct_type_id() {
+    let compile = |source: &str| {
+        CompileAndRunTestDriver::new(source, |builder| builder)
+            .expect("Failed to build test driver")
+    };
+
+    let driver = compile(
+        r"
+        pub struct Foo { a: i32, b: f64 }
+        pub fn foo() -> Foo { Foo { a: 1, b: 2.0 } }
+        ",
+    );
+    let identical_driver = compile(
+        r"
+        pub struct Foo { a: i32, b: f64 }
+        pub fn foo() -> Foo { Foo { a: 3, b: 4.0 } }
+        ",
+    );
+    let different_driver = compile(
+        r"
+        pub struct Foo { a: i32, b: i64 }
+        pub fn foo() -> Foo { Foo { a: 1, b: 2 } }
+        ",
+    );
+
+    let foo: StructRef<'_> = driver.runtime.invoke("foo", ()).unwrap();
+    let identical_foo: StructRef<'_> = identical_driver.runtime.invoke("foo", ()).unwrap();
+    let different_foo: StructRef<'_> = different_driver.runtime.invoke("foo", ()).unwrap();
+
+    assert_eq!(foo.type_id(), identical_foo.type_id());
+    assert_ne!(foo.type_id(), different_foo.type_id());
+
+    assert_eq!(driver.runtime.struct_type_id("Foo"), Some(foo.type_id()));
+    assert_eq!(
+        identical_driver.runtime.struct_type_id("Foo"),
+        Some(foo.type_id())
+    );
+    assert_ne!(
+        different_driver.runtime.struct_type_id("Foo"),
+        Some(foo.type_id())
+    );
+    assert_eq!(driver.runtime.struct_type_id("Bar"), None);
+}
+
+#[test]
+fn struct_type_name() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+        pub struct Foo { a: i32 }
+        pub fn foo() -> Foo { Foo { a: 1 } }
+        ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let foo: StructRef<'_> = driver.runtime.invoke("foo", ()).unwrap();
+    assert_eq!(foo.type_name(), "Foo");
+}
+
+#[test]
+fn load_from_manifest() {
+    let driver = CompileTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    use package::foo::bar;
+    pub fn main() -> i32 { bar() + 1 }
+
+    //- /src/foo.mun
+    pub fn bar() -> i32 { 5 }
+    "#,
+    );
+
+    // List the dependency before its dependent, to make sure that the order of
+    // the manifest doesn't matter.
+    let out_dir = driver.lib_path().parent().unwrap();
+    let manifest_path = out_dir.join("assemblies.txt");
+    std::fs::write(&manifest_path, "# Assemblies\nfoo.munlib\n\nmod.munlib\n").unwrap();
+
+    let runtime = unsafe {
+        RuntimeBuilder::from_manifest(&manifest_path)
+            .unwrap()
+            .finish()
+    }
+    .unwrap();
+
+    let result: i32 = runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 6);
+}
+
+#[test]
+fn empty_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest_path = dir.path().join("assemblies.txt");
+    std::fs::write(&manifest_path, "# No assemblies\n").unwrap();
+
+    let err = RuntimeBuilder::from_manifest(&manifest_path).err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -1,5 +1,42 @@
-use mun_runtime::Runtime;
+use std::thread;
+
+use mun_runtime::{Runtime, SharedRuntime};
+use mun_test::CompileTestDriver;
 
 // Ensures the [`Runtime`] is Send
 trait IsSend: Send {}
 impl IsSend for Runtime {}
+
+// Ensures the [`SharedRuntime`] is Send and Sync
+trait IsSendAndSync: Send + Sync {}
+impl IsSendAndSync for SharedRuntime {}
+
+#[test]
+fn shared_runtime_concurrent_invoke() {
+    let driver = CompileTestDriver::from_file(
+        r"
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    ",
+    );
+
+    // Safety: we compiled the library ourselves, so loading it should be safe
+    let runtime =
+        unsafe { Runtime::builder(driver.lib_path()).finish() }.expect("Failed to load runtime");
+    let runtime = SharedRuntime::new(runtime);
+
+    let threads: Vec<_> = (0..8)
+        .map(|i| {
+            let runtime = runtime.clone();
+            thread::spawn(move || {
+                for j in 0..100 {
+                    let result: i32 = runtime.invoke("add", (i, j)).unwrap();
+                    assert_eq!(result, i + j);
+                }
+            })
+        })
+        .collect();
+
+    for thread in threads {
+        thread.join().expect("thread panicked");
+    }
+}
@@ -175,14 +175,21 @@ pub unsafe extern "C" fn mun_runtime_create(
                 },
             },
             fn_ptr: def.fn_ptr,
+            source_file: None,
         })
     })
     .collect::<Result<_, _>>());
 
     let runtime_options = mun_runtime::RuntimeOptions {
         library_path: library_path.into(),
+        additional_library_paths: Vec::new(),
         user_functions,
         type_table,
+        gc_callbacks: Vec::new(),
+        hot_reload: true,
+        compact_heap: false,
+        random_seed: 0,
+        max_allocations_per_call: None,
     };
 
     let runtime = match mun_runtime::Runtime::new(runtime_options) {
@@ -202,6 +202,7 @@ pub enum LiteralKind {
     IntNumber(ast::IntNumber),
     FloatNumber(ast::FloatNumber),
     Bool(bool),
+    Null,
 }
 
 impl Literal {
@@ -227,6 +228,7 @@ impl Literal {
         match token.kind() {
             T![true] => LiteralKind::Bool(true),
             T![false] => LiteralKind::Bool(false),
+            T![null] => LiteralKind::Null,
             _ => unreachable!(),
         }
     }
@@ -257,6 +259,14 @@ impl ast::IfExpr {
     }
 }
 
+impl ast::WhileExpr {
+    /// Returns the block that is evaluated when the condition of the loop no
+    /// longer holds, e.g. `while cond { .. } else { .. }`.
+    pub fn else_branch(&self) -> Option<ast::BlockExpr> {
+        children(self).nth(1)
+    }
+}
+
 impl ast::IndexExpr {
     pub fn base(&self) -> Option<ast::Expr> {
         children(self).next()
@@ -265,3 +275,34 @@ impl ast::IndexExpr {
         children(self).nth(1)
     }
 }
+
+/// The kind of an array expression
+#[derive(Debug)]
+pub enum ArrayExprKind {
+    /// An array of the listed elements, e.g. `[a, b, c]`
+    ElementList(AstChildren<ast::Expr>),
+    /// An array that repeats a single element, e.g. `[a; 5]`
+    Repeat {
+        initializer: Option<ast::Expr>,
+        count: Option<ast::Expr>,
+    },
+}
+
+impl ast::ArrayExpr {
+    pub fn kind(&self) -> ArrayExprKind {
+        if self.is_repeat() {
+            ArrayExprKind::Repeat {
+                initializer: children(self).next(),
+                count: children(self).nth(1),
+            }
+        } else {
+            ArrayExprKind::ElementList(children(self))
+        }
+    }
+
+    fn is_repeat(&self) -> bool {
+        self.syntax()
+            .children_with_tokens()
+            .any(|it| it.kind() == T![;])
+    }
+}
@@ -61,6 +61,14 @@ impl ast::FunctionDef {
 
         TextRange::new(start, end)
     }
+
+    /// Returns true if this function holds the top-level statements of a
+    /// script. Such a function has no `fn` keyword, name, or signature.
+    pub fn is_script_body(&self) -> bool {
+        self.syntax()
+            .children_with_tokens()
+            .all(|p| p.kind() != T![fn])
+    }
 }
 
 fn text_of_first_token(node: &SyntaxNode) -> TokenText<'_> {
@@ -256,3 +264,6 @@ impl ast::TypeAliasDef {
         TextRange::new(start, end)
     }
 }
+
+impl ast::AttrsOwner for ast::ModuleItem {}
+impl ast::AttrsOwner for ast::AssociatedItem {}
@@ -42,6 +42,10 @@ impl ArgList {
     pub fn args(&self) -> impl Iterator<Item = Expr> {
         super::children(self)
     }
+
+    pub fn named_args(&self) -> impl Iterator<Item = NamedArg> {
+        super::children(self)
+    }
 }
 
 // ArrayExpr
@@ -173,6 +177,38 @@ impl AssociatedItemList {
     }
 }
 
+// Attr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Attr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Attr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, ATTR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Attr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Attr {
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+
+    pub fn token_tree(&self) -> Option<TokenTree> {
+        super::child_opt(self)
+    }
+}
+
 // BinExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -599,6 +635,7 @@ impl ast::NameOwner for FunctionDef {}
 impl ast::VisibilityOwner for FunctionDef {}
 impl ast::DocCommentsOwner for FunctionDef {}
 impl ast::ExternOwner for FunctionDef {}
+impl ast::AttrsOwner for FunctionDef {}
 impl FunctionDef {
     pub fn param_list(&self) -> Option<ParamList> {
         super::child_opt(self)
@@ -665,6 +702,7 @@ impl AstNode for Impl {
 }
 impl ast::VisibilityOwner for Impl {}
 impl ast::DocCommentsOwner for Impl {}
+impl ast::AttrsOwner for Impl {}
 impl Impl {
     pub fn associated_item_list(&self) -> Option<AssociatedItemList> {
         super::child_opt(self)
@@ -931,6 +969,38 @@ impl AstNode for NameRef {
 }
 impl NameRef {}
 
+// NamedArg
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NamedArg {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for NamedArg {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, NAMED_ARG)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(NamedArg { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl NamedArg {
+    pub fn name_ref(&self) -> Option<NameRef> {
+        super::child_opt(self)
+    }
+
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
 // NeverType
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -982,6 +1052,10 @@ impl Param {
     pub fn pat(&self) -> Option<Pat> {
         super::child_opt(self)
     }
+
+    pub fn default_value(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
 }
 
 // ParamList
@@ -1178,6 +1252,10 @@ impl PathSegment {
     pub fn name_ref(&self) -> Option<NameRef> {
         super::child_opt(self)
     }
+
+    pub fn type_arg_list(&self) -> Option<TypeArgList> {
+        super::child_opt(self)
+    }
 }
 
 // PathType
@@ -1619,12 +1697,37 @@ impl AstNode for StructDef {
 impl ast::NameOwner for StructDef {}
 impl ast::VisibilityOwner for StructDef {}
 impl ast::DocCommentsOwner for StructDef {}
+impl ast::AttrsOwner for StructDef {}
 impl StructDef {
     pub fn memory_type_specifier(&self) -> Option<MemoryTypeSpecifier> {
         super::child_opt(self)
     }
 }
 
+// TokenTree
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenTree {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TokenTree {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TOKEN_TREE)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TokenTree { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TokenTree {}
+
 // TupleFieldDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1682,6 +1785,34 @@ impl TupleFieldDefList {
     }
 }
 
+// TupleType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TupleType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TupleType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TUPLE_TYPE)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TupleType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TupleType {
+    pub fn fields(&self) -> impl Iterator<Item = TypeRef> {
+        super::children(self)
+    }
+}
+
 // TypeAliasDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1707,12 +1838,41 @@ impl AstNode for TypeAliasDef {
 impl ast::NameOwner for TypeAliasDef {}
 impl ast::VisibilityOwner for TypeAliasDef {}
 impl ast::DocCommentsOwner for TypeAliasDef {}
+impl ast::AttrsOwner for TypeAliasDef {}
 impl TypeAliasDef {
     pub fn type_ref(&self) -> Option<TypeRef> {
         super::child_opt(self)
     }
 }
 
+// TypeArgList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeArgList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TypeArgList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TYPE_ARG_LIST)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TypeArgList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TypeArgList {
+    pub fn types(&self) -> impl Iterator<Item = TypeRef> {
+        super::children(self)
+    }
+}
+
 // TypeRef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1722,7 +1882,7 @@ pub struct TypeRef {
 
 impl AstNode for TypeRef {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, PATH_TYPE | ARRAY_TYPE | NEVER_TYPE)
+        matches!(kind, PATH_TYPE | ARRAY_TYPE | NEVER_TYPE | TUPLE_TYPE)
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
@@ -1740,6 +1900,7 @@ pub enum TypeRefKind {
     PathType(PathType),
     ArrayType(ArrayType),
     NeverType(NeverType),
+    TupleType(TupleType),
 }
 impl From<PathType> for TypeRef {
     fn from(n: PathType) -> TypeRef {
@@ -1756,6 +1917,11 @@ impl From<NeverType> for TypeRef {
         TypeRef { syntax: n.syntax }
     }
 }
+impl From<TupleType> for TypeRef {
+    fn from(n: TupleType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
 
 impl TypeRef {
     pub fn kind(&self) -> TypeRefKind {
@@ -1763,6 +1929,7 @@ impl TypeRef {
             PATH_TYPE => TypeRefKind::PathType(PathType::cast(self.syntax.clone()).unwrap()),
             ARRAY_TYPE => TypeRefKind::ArrayType(ArrayType::cast(self.syntax.clone()).unwrap()),
             NEVER_TYPE => TypeRefKind::NeverType(NeverType::cast(self.syntax.clone()).unwrap()),
+            TUPLE_TYPE => TypeRefKind::TupleType(TupleType::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
     }
@@ -1793,6 +1960,7 @@ impl AstNode for Use {
     }
 }
 impl ast::VisibilityOwner for Use {}
+impl ast::AttrsOwner for Use {}
 impl Use {
     pub fn use_tree(&self) -> Option<UseTree> {
         super::child_opt(self)
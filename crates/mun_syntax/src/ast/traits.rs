@@ -80,3 +80,9 @@ pub trait ExternOwner: AstNode {
             .any(|p| p.kind() == SyntaxKind::EXTERN)
     }
 }
+
+pub trait AttrsOwner: AstNode {
+    fn attrs(&self) -> AstChildren<ast::Attr> {
+        children(self)
+    }
+}
@@ -33,7 +33,7 @@ pub use smol_str::SmolStr;
 
 pub use crate::{
     ast::{AstNode, AstToken},
-    parsing::{lexer::Token, tokenize},
+    parsing::{lexer::Token, tokenize, DEFAULT_NESTING_LIMIT},
     ptr::{AstPtr, SyntaxNodePtr},
     syntax_error::{Location, SyntaxError, SyntaxErrorKind},
     syntax_kind::SyntaxKind,
@@ -127,17 +127,52 @@ impl Parse<SourceFile> {
     }
 
     /// Parses the `SourceFile` again but with the given modification applied.
+    /// Only the token or block affected by the modification is reparsed if
+    /// possible; the rest of the tree is reused.
     pub fn reparse(&self, indel: &Indel) -> Parse<SourceFile> {
-        // TODO: Implement something smarter here.
-        self.full_reparse(indel)
+        self.reparse_with(indel, false, DEFAULT_NESTING_LIMIT)
+    }
+
+    /// Same as [`Parse::reparse`], but the `SourceFile` is parsed as a script
+    /// if `script` is true and expressions that are nested deeper than
+    /// `nesting_limit` are reported as errors. These must match the way the
+    /// `SourceFile` was originally parsed.
+    pub fn reparse_with(
+        &self,
+        indel: &Indel,
+        script: bool,
+        nesting_limit: usize,
+    ) -> Parse<SourceFile> {
+        self.incremental_reparse(indel, nesting_limit)
+            .unwrap_or_else(|| self.full_reparse(indel, script, nesting_limit))
+    }
+
+    /// Tries to reparse only the part of the `SourceFile` that is affected by
+    /// the specified modification.
+    fn incremental_reparse(
+        &self,
+        indel: &Indel,
+        nesting_limit: usize,
+    ) -> Option<Parse<SourceFile>> {
+        parsing::incremental_reparse(
+            self.tree().syntax(),
+            indel,
+            self.errors.to_vec(),
+            nesting_limit,
+        )
+        .map(|(green, errors, _reparsed_range)| Parse::new(green, errors))
     }
 
     /// Performs a "reparse" of the `SourceFile` after applying the specified
     /// modification by simply parsing the entire thing again.
-    fn full_reparse(&self, indel: &Indel) -> Parse<SourceFile> {
+    fn full_reparse(&self, indel: &Indel, script: bool, nesting_limit: usize) -> Parse<SourceFile> {
         let mut text = self.tree().syntax().text().to_string();
         indel.apply(&mut text);
-        SourceFile::parse(&text)
+        if script {
+            SourceFile::parse_script(&text, nesting_limit)
+        } else {
+            SourceFile::parse_with_nesting_limit(&text, nesting_limit)
+        }
     }
 }
 
@@ -148,7 +183,25 @@ pub use crate::ast::SourceFile;
 
 impl SourceFile {
     pub fn parse(text: &str) -> Parse<SourceFile> {
-        let (green, mut errors) = parsing::parse_text(text);
+        Self::parse_with_nesting_limit(text, DEFAULT_NESTING_LIMIT)
+    }
+
+    /// Parses the text into a syntax tree. Expressions that are nested deeper
+    /// than `nesting_limit` are reported as errors.
+    pub fn parse_with_nesting_limit(text: &str, nesting_limit: usize) -> Parse<SourceFile> {
+        let (green, errors) = parsing::parse_text(text, nesting_limit);
+        Self::validate(green, errors)
+    }
+
+    /// Parses the text as a script. A script can contain statements after its
+    /// declarations. These statements are wrapped in a function definition
+    /// without a name, see [`ast::FunctionDef::is_script_body`].
+    pub fn parse_script(text: &str, nesting_limit: usize) -> Parse<SourceFile> {
+        let (green, errors) = parsing::parse_script_text(text, nesting_limit);
+        Self::validate(green, errors)
+    }
+
+    fn validate(green: GreenNode, mut errors: Vec<SyntaxError>) -> Parse<SourceFile> {
         let root = SyntaxNode::new_root(green.clone());
         errors.extend(validation::validate(&root));
         Parse {
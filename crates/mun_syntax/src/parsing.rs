@@ -7,10 +7,20 @@ mod event;
 mod grammar;
 pub mod lexer;
 mod parser;
+mod reparsing;
 mod text_token_source;
 mod text_tree_sink;
 
 pub use lexer::tokenize;
+pub(crate) use reparsing::incremental_reparse;
+
+/// The default maximum depth of nested expressions that the parser accepts.
+/// Deeper nested expressions are reported as an error instead of overflowing
+/// the stack.
+pub const DEFAULT_NESTING_LIMIT: usize = 128;
+
+/// The error that is reported for expressions that exceed the nesting limit.
+pub(crate) const NESTING_LIMIT_EXCEEDED: &str = "expression is nested too deeply";
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ParseError(pub String);
@@ -54,25 +64,44 @@ pub trait TreeSink {
     fn error(&mut self, error: ParseError);
 }
 
-pub(crate) fn parse_text(text: &str) -> (GreenNode, Vec<SyntaxError>) {
+pub(crate) fn parse_text(text: &str, nesting_limit: usize) -> (GreenNode, Vec<SyntaxError>) {
+    let tokens = tokenize(text);
+    let mut token_source = text_token_source::TextTokenSource::new(text, &tokens);
+    let mut tree_sink = text_tree_sink::TextTreeSink::new(text, &tokens);
+    parse(&mut token_source, &mut tree_sink, nesting_limit);
+    tree_sink.finish()
+}
+
+/// Parses the text as a script, in which the declarations can be followed by
+/// statements.
+pub(crate) fn parse_script_text(text: &str, nesting_limit: usize) -> (GreenNode, Vec<SyntaxError>) {
     let tokens = tokenize(text);
     let mut token_source = text_token_source::TextTokenSource::new(text, &tokens);
     let mut tree_sink = text_tree_sink::TextTreeSink::new(text, &tokens);
-    parse(&mut token_source, &mut tree_sink);
+    parse_from_tokens(
+        &mut token_source,
+        &mut tree_sink,
+        nesting_limit,
+        grammar::script_root,
+    );
     tree_sink.finish()
 }
 
-fn parse_from_tokens<F>(token_source: &mut dyn TokenSource, tree_sink: &mut dyn TreeSink, f: F)
-where
+fn parse_from_tokens<F>(
+    token_source: &mut dyn TokenSource,
+    tree_sink: &mut dyn TreeSink,
+    nesting_limit: usize,
+    f: F,
+) where
     F: FnOnce(&mut parser::Parser<'_>),
 {
-    let mut p = parser::Parser::new(token_source);
+    let mut p = parser::Parser::new(token_source, nesting_limit);
     f(&mut p);
     let events = p.finish();
     event::process(tree_sink, events);
 }
 
 /// Parse given tokens into the given sink as a rust file.
-fn parse(token_source: &mut dyn TokenSource, tree_sink: &mut dyn TreeSink) {
-    parse_from_tokens(token_source, tree_sink, grammar::root);
+fn parse(token_source: &mut dyn TokenSource, tree_sink: &mut dyn TreeSink, nesting_limit: usize) {
+    parse_from_tokens(token_source, tree_sink, nesting_limit, grammar::root);
 }
@@ -0,0 +1,55 @@
+use super::{paths, Parser, ATTR, EOF, TOKEN_TREE};
+
+/// Parses the attributes that precede a declaration, e.g. `#[cfg(feature =
+/// "debug")]`.
+pub(super) fn outer_attrs(p: &mut Parser<'_>) {
+    while p.at(T![#]) {
+        attr(p);
+    }
+}
+
+fn attr(p: &mut Parser<'_>) {
+    assert!(p.at(T![#]));
+    let m = p.start();
+    p.bump(T![#]);
+
+    if p.eat(T!['[']) {
+        if paths::is_path_start(p) {
+            paths::type_path(p);
+        } else {
+            p.error("expected an attribute name");
+        }
+
+        if p.at(T!['(']) {
+            token_tree(p);
+        }
+
+        p.expect(T![']']);
+    } else {
+        p.error("expected `[`");
+    }
+
+    m.complete(p, ATTR);
+}
+
+/// Parses a sequence of tokens enclosed by matching delimiters.
+fn token_tree(p: &mut Parser<'_>) {
+    let closing = match p.current() {
+        T!['('] => T![')'],
+        T!['['] => T![']'],
+        T!['{'] => T!['}'],
+        _ => unreachable!(),
+    };
+
+    let m = p.start();
+    p.bump_any();
+    while !p.at(EOF) && !p.at(closing) {
+        match p.current() {
+            T!['('] | T!['['] | T!['{'] => token_tree(p),
+            T![')'] | T![']'] | T!['}'] => p.error_and_bump("unmatched delimiter"),
+            _ => p.bump_any(),
+        }
+    }
+    p.expect(closing);
+    m.complete(p, TOKEN_TREE);
+}
@@ -1,19 +1,48 @@
 use super::{
-    adt, error_block, expressions, name, name_recovery, opt_visibility, params, paths, traits,
-    types, Marker, Parser, TokenSet, EOF, ERROR, EXTERN, FUNCTION_DEF, RENAME, RET_TYPE, USE,
-    USE_TREE, USE_TREE_LIST,
+    adt, attributes, error_block, expressions, name, name_recovery, opt_visibility, params, paths,
+    traits, types, Marker, Parser, TokenSet, BLOCK_EXPR, EOF, ERROR, EXTERN, FUNCTION_DEF, RENAME,
+    RET_TYPE, USE, USE_TREE, USE_TREE_LIST,
 };
 use crate::{parsing::grammar::paths::is_use_path_start, T};
 
 pub(super) const DECLARATION_RECOVERY_SET: TokenSet =
     TokenSet::new(&[T![fn], T![pub], T![struct], T![use], T![;], T![impl]]);
 
+/// The tokens that can start a declaration.
+pub(super) const DECLARATION_FIRST: TokenSet = TokenSet::new(&[
+    T![#],
+    T![pub],
+    T![extern],
+    T![fn],
+    T![struct],
+    T![type],
+    T![use],
+    T![impl],
+]);
+
 pub(super) fn mod_contents(p: &mut Parser<'_>) {
     while !p.at(EOF) {
         declaration(p, false);
     }
 }
 
+/// Parses the declarations of a script. The statements that follow the
+/// declarations are wrapped in a `FUNCTION_DEF` without a name whose body is a
+/// `BLOCK_EXPR` without curly braces.
+pub(super) fn script_contents(p: &mut Parser<'_>) {
+    while p.at_ts(DECLARATION_FIRST) {
+        declaration(p, false);
+    }
+
+    if !p.at(EOF) {
+        let m = p.start();
+        let body = p.start();
+        expressions::script_body_contents(p);
+        body.complete(p, BLOCK_EXPR);
+        m.complete(p, FUNCTION_DEF);
+    }
+}
+
 pub(super) fn declaration(p: &mut Parser<'_>, stop_on_r_curly: bool) {
     let m = p.start();
     let m = match maybe_declaration(p, m) {
@@ -36,6 +65,7 @@ pub(super) fn declaration(p: &mut Parser<'_>, stop_on_r_curly: bool) {
 }
 
 pub(super) fn maybe_declaration(p: &mut Parser<'_>, m: Marker) -> Result<(), Marker> {
+    attributes::outer_attrs(p);
     opt_visibility(p);
 
     let m = match declarations_without_modifiers(p, m) {
@@ -1,17 +1,26 @@
 use super::{
-    error_block, expressions, name_ref_or_index, paths, patterns, types, BlockLike,
-    CompletedMarker, Marker, Parser, SyntaxKind, TokenSet, ARG_LIST, ARRAY_EXPR, BIN_EXPR,
-    BLOCK_EXPR, BREAK_EXPR, CALL_EXPR, CONDITION, EOF, ERROR, EXPR_STMT, FIELD_EXPR, FLOAT_NUMBER,
-    IDENT, IF_EXPR, INDEX, INDEX_EXPR, INT_NUMBER, LET_STMT, LITERAL, LOOP_EXPR, PAREN_EXPR,
-    PATH_EXPR, PATH_TYPE, PREFIX_EXPR, RECORD_FIELD, RECORD_FIELD_LIST, RECORD_LIT, RETURN_EXPR,
-    STRING, WHILE_EXPR,
+    declarations, error_block, expressions, name_ref, name_ref_or_index, paths, patterns, types,
+    BlockLike, CompletedMarker, Marker, Parser, SyntaxKind, TokenSet, ARG_LIST, ARRAY_EXPR,
+    BIN_EXPR, BLOCK_EXPR, BREAK_EXPR, CALL_EXPR, CONDITION, EOF, ERROR, EXPR_STMT, FIELD_EXPR,
+    FLOAT_NUMBER, IDENT, IF_EXPR, INDEX, INDEX_EXPR, INT_NUMBER, LET_STMT, LITERAL, LOOP_EXPR,
+    NAMED_ARG, PAREN_EXPR, PATH_EXPR, PATH_TYPE, PREFIX_EXPR, RECORD_FIELD, RECORD_FIELD_LIST,
+    RECORD_LIT, RETURN_EXPR, STRING, WHILE_EXPR,
+};
+use crate::parsing::{
+    grammar::{declarations::DECLARATION_FIRST, paths::PATH_FIRST},
+    NESTING_LIMIT_EXCEEDED,
 };
-use crate::parsing::grammar::paths::PATH_FIRST;
 
-pub(crate) const LITERAL_FIRST: TokenSet =
-    TokenSet::new(&[T![true], T![false], INT_NUMBER, FLOAT_NUMBER, STRING]);
+pub(crate) const LITERAL_FIRST: TokenSet = TokenSet::new(&[
+    T![true],
+    T![false],
+    T![null],
+    INT_NUMBER,
+    FLOAT_NUMBER,
+    STRING,
+]);
 
-const EXPR_RECOVERY_SET: TokenSet = TokenSet::new(&[T![let]]);
+const EXPR_RECOVERY_SET: TokenSet = TokenSet::new(&[T![let]]).union(DECLARATION_FIRST);
 
 const ATOM_EXPR_FIRST: TokenSet = LITERAL_FIRST.union(PATH_FIRST).union(TokenSet::new(&[
     IDENT,
@@ -51,10 +60,45 @@ pub(crate) fn expr_block_contents(p: &mut Parser<'_>) {
             continue;
         }
 
+        // A declaration cannot occur inside a block, so the closing `}` of the
+        // block is most likely missing. Stop here so the declaration is parsed
+        // on its own and its errors are reported independently.
+        if p.at_ts(DECLARATION_FIRST) {
+            break;
+        }
+
         stmt(p);
     }
 }
 
+/// Parses the statements of a script up to the end of the file. The last
+/// expression is not terminated by a `}` but by the end of the file.
+pub(crate) fn script_body_contents(p: &mut Parser<'_>) {
+    while !p.at(EOF) {
+        if p.eat(T![;]) {
+            continue;
+        }
+        if p.at(T!['}']) {
+            let e = p.start();
+            p.error("unmatched }");
+            p.bump(T!['}']);
+            e.complete(p, ERROR);
+            continue;
+        }
+        if p.at_ts(DECLARATION_FIRST) {
+            // Parse the misplaced declaration as a whole, so its contents are not
+            // reported as errors in statements
+            let e = p.start();
+            p.error("declarations must precede the statements of a script");
+            declarations::declaration(p, true);
+            e.complete(p, ERROR);
+            continue;
+        }
+
+        stmt_until(p, EOF);
+    }
+}
+
 /// Parses a block statement
 pub(crate) fn block(p: &mut Parser<'_>) {
     if !p.at(T!['{']) {
@@ -75,6 +119,12 @@ fn block_expr(p: &mut Parser<'_>) -> CompletedMarker {
 
 /// Parses a general statement: (let, expr, etc.)
 pub(super) fn stmt(p: &mut Parser<'_>) {
+    stmt_until(p, T!['}']);
+}
+
+/// Parses a statement in a block that is terminated by `end`. An expression
+/// that is directly followed by `end` is the tail expression of the block.
+fn stmt_until(p: &mut Parser<'_>, end: SyntaxKind) {
     let m = p.start();
 
     // Encounters let keyword, so we know it's a let stmt
@@ -86,7 +136,7 @@ pub(super) fn stmt(p: &mut Parser<'_>) {
     let (cm, _blocklike) = expr_stmt(p);
     let kind = cm.as_ref().map_or(ERROR, CompletedMarker::kind);
 
-    if p.at(T!['}']) {
+    if p.at(end) {
         if let Some(cm) = cm {
             cm.undo_completion(p).abandon(p);
             m.complete(p, kind);
@@ -136,6 +186,41 @@ fn expr_stmt(p: &mut Parser<'_>) -> (Option<CompletedMarker>, BlockLike) {
 }
 
 fn expr_bp(p: &mut Parser<'_>, r: Restrictions, bp: u8) -> (Option<CompletedMarker>, BlockLike) {
+    if !p.enter_nested() {
+        return (Some(nesting_limit_exceeded(p)), BlockLike::NotBlock);
+    }
+    let result = expr_bp_nested(p, r, bp);
+    p.exit_nested();
+    result
+}
+
+/// Reports that an expression is nested too deeply and skips all tokens up to
+/// the delimiter that closes the enclosing expression, without recursing.
+fn nesting_limit_exceeded(p: &mut Parser<'_>) -> CompletedMarker {
+    let m = p.start();
+    p.error(NESTING_LIMIT_EXCEEDED);
+    let mut depth = 0usize;
+    while !p.at(EOF) {
+        match p.current() {
+            T!['('] | T!['['] | T!['{'] => depth += 1,
+            T![')'] | T![']'] | T!['}'] => {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+        p.bump_any();
+    }
+    m.complete(p, ERROR)
+}
+
+fn expr_bp_nested(
+    p: &mut Parser<'_>,
+    r: Restrictions,
+    bp: u8,
+) -> (Option<CompletedMarker>, BlockLike) {
     // Parse left hand side of the expression
     let mut lhs = match lhs(p, r) {
         Some((lhs, blocklike)) => {
@@ -258,13 +343,21 @@ fn arg_list(p: &mut Parser<'_>) {
     assert!(p.at(T!['(']));
     let m = p.start();
     p.bump(T!['(']);
+    let mut has_named_args = false;
     while !p.at(T![')']) && !p.at(EOF) {
-        if !p.at_ts(EXPR_FIRST) {
+        if p.at(IDENT) && p.nth(1) == T![:] && !p.nth_at(1, T![::]) {
+            named_arg(p);
+            has_named_args = true;
+        } else if !p.at_ts(EXPR_FIRST) {
             p.error("expected expression");
             break;
+        } else {
+            if has_named_args {
+                p.error("positional arguments cannot follow named arguments");
+            }
+            expr(p);
         }
 
-        expr(p);
         if !p.at(T![')']) && !p.expect(T![,]) {
             break;
         }
@@ -273,6 +366,15 @@ fn arg_list(p: &mut Parser<'_>) {
     m.complete(p, ARG_LIST);
 }
 
+fn named_arg(p: &mut Parser<'_>) {
+    assert!(p.at(IDENT));
+    let m = p.start();
+    name_ref(p);
+    p.bump(T![:]);
+    expr(p);
+    m.complete(p, NAMED_ARG);
+}
+
 fn postfix_dot_expr(p: &mut Parser<'_>, lhs: CompletedMarker) -> CompletedMarker {
     assert!(p.at(T![.]));
     if p.nth(1) == IDENT && p.nth(2) == T!['('] {
@@ -368,18 +470,52 @@ fn if_expr(p: &mut Parser<'_>) -> CompletedMarker {
     let m = p.start();
     p.bump(T![if]);
     cond(p);
-    block(p);
+    branch_body(p, BRACELESS_THEN_FIRST);
     if p.at(T![else]) {
         p.bump(T![else]);
         if p.at(T![if]) {
             if_expr(p);
         } else {
-            block(p);
+            branch_body(p, EXPR_FIRST);
         }
     }
     m.complete(p, IF_EXPR)
 }
 
+/// Tokens that can start the `then` branch of an `if` expression without
+/// braces. None of these can continue the condition, so where the condition
+/// ends is never ambiguous. For instance, `if x -1 else 1` is not accepted
+/// because `x -1` would be parsed as the condition.
+const BRACELESS_THEN_FIRST: TokenSet = LITERAL_FIRST.union(TokenSet::new(&[
+    IDENT,
+    T![self],
+    T![super],
+    T![package],
+    T![!],
+    T![loop],
+    T![while],
+    T![return],
+    T![break],
+]));
+
+/// Parses the body of a branch of an `if` expression. A body that doesn't start
+/// with `{` is a single expression, e.g. `if cond a else b`, which is wrapped
+/// in a `BLOCK_EXPR` so it lowers the same as its braced form. The `then`
+/// branch can only omit its braces if it starts with one of the
+/// `BRACELESS_THEN_FIRST` tokens; the `else` branch follows a keyword and can
+/// be any expression.
+fn branch_body(p: &mut Parser<'_>, first: TokenSet) {
+    if p.at(T!['{']) {
+        block_expr(p);
+    } else if p.at_ts(first) {
+        let m = p.start();
+        expr(p);
+        m.complete(p, BLOCK_EXPR);
+    } else {
+        p.error("expected a block or an expression");
+    }
+}
+
 fn loop_expr(p: &mut Parser<'_>) -> CompletedMarker {
     assert!(p.at(T![loop]));
     let m = p.start();
@@ -420,6 +556,9 @@ fn while_expr(p: &mut Parser<'_>) -> CompletedMarker {
     p.bump(T![while]);
     cond(p);
     block(p);
+    if p.eat(T![else]) {
+        block(p);
+    }
     m.complete(p, WHILE_EXPR)
 }
 
@@ -453,12 +592,26 @@ fn array_expr(p: &mut Parser<'_>) -> CompletedMarker {
     let m = p.start();
 
     p.bump(T!['[']);
-    while !p.at(EOF) && !p.at(T![']']) {
+    if p.eat(T![']']) {
+        return m.complete(p, ARRAY_EXPR);
+    }
+
+    // The first element is followed by a `;` in a repeat expression, e.g. `[0; 5]`
+    expr(p);
+    if p.eat(T![;]) {
         expr(p);
+        p.expect(T![']']);
+        return m.complete(p, ARRAY_EXPR);
+    }
 
-        if !p.at(T![']']) && !p.expect(T![,]) {
+    while !p.at(EOF) && !p.at(T![']']) {
+        if !p.expect(T![,]) {
             break;
         }
+        if p.at(T![']']) {
+            break;
+        }
+        expr(p);
     }
     p.expect(T![']']);
 
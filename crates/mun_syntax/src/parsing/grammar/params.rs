@@ -1,4 +1,6 @@
-use super::{patterns, types, Parser, TokenSet, EOF, NAME, PARAM, PARAM_LIST, SELF_PARAM};
+use super::{
+    expressions, patterns, types, Parser, TokenSet, EOF, NAME, PARAM, PARAM_LIST, SELF_PARAM,
+};
 
 pub(super) fn param_list(p: &mut Parser<'_>) {
     list(p);
@@ -32,6 +34,9 @@ fn param(p: &mut Parser<'_>) {
     let m = p.start();
     patterns::pattern(p);
     types::ascription(p);
+    if p.eat(T![=]) {
+        expressions::expr(p);
+    }
     m.complete(p, PARAM);
 }
 
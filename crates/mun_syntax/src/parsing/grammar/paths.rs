@@ -1,4 +1,6 @@
-use super::{declarations, name_ref, Parser, TokenSet, IDENT, PATH, PATH_SEGMENT};
+use super::{
+    declarations, name_ref, types, Parser, TokenSet, EOF, IDENT, PATH, PATH_SEGMENT, TYPE_ARG_LIST,
+};
 
 pub(super) const PATH_FIRST: TokenSet =
     TokenSet::new(&[IDENT, T![super], T![self], T![package], T![::]]);
@@ -50,11 +52,14 @@ fn path(p: &mut Parser<'_>, mode: Mode, top_level: bool) {
     }
 }
 
-fn path_segment(p: &mut Parser<'_>, _mode: Mode, top_level: bool) {
+fn path_segment(p: &mut Parser<'_>, mode: Mode, top_level: bool) {
     let m = p.start();
     match p.current() {
         IDENT => {
             name_ref(p);
+            if mode == Mode::Expr && p.at(T![::]) && p.nth(2) == T![<] {
+                type_arg_list(p);
+            }
         }
         T![super] | T![package] if top_level => p.bump_any(),
         T![self] => p.bump(T![self]),
@@ -65,3 +70,18 @@ fn path_segment(p: &mut Parser<'_>, _mode: Mode, top_level: bool) {
     }
     m.complete(p, PATH_SEGMENT);
 }
+
+/// Parses a list of type arguments in an expression, e.g. `::<Foo, i32>`.
+fn type_arg_list(p: &mut Parser<'_>) {
+    let m = p.start();
+    p.bump(T![::]);
+    p.bump(T![<]);
+    while !p.at(EOF) && !p.at(T![>]) {
+        types::type_(p);
+        if !p.at(T![>]) && !p.expect(T![,]) {
+            break;
+        }
+    }
+    p.expect(T![>]);
+    m.complete(p, TYPE_ARG_LIST);
+}
@@ -1,7 +1,7 @@
-use super::{paths, Parser, TokenSet, ARRAY_TYPE, NEVER_TYPE, PATH_TYPE};
+use super::{paths, Parser, TokenSet, ARRAY_TYPE, EOF, NEVER_TYPE, PATH_TYPE, TUPLE_TYPE};
 
 pub(super) const TYPE_FIRST: TokenSet =
-    paths::PATH_FIRST.union(TokenSet::new(&[T![never], T!['[']]));
+    paths::PATH_FIRST.union(TokenSet::new(&[T![never], T!['['], T!['(']]));
 
 pub(super) const TYPE_RECOVERY_SET: TokenSet = TokenSet::new(&[T!['('], T![,], T![pub]]);
 
@@ -13,6 +13,7 @@ pub(super) fn ascription(p: &mut Parser<'_>) {
 pub(super) fn type_(p: &mut Parser<'_>) {
     match p.current() {
         T!['['] => array_type(p),
+        T!['('] => tuple_type(p),
         T![never] => never_type(p),
         _ if paths::is_path_start(p) => path_type(p),
         _ => {
@@ -42,3 +43,21 @@ fn array_type(p: &mut Parser<'_>) {
     p.expect(T![']']);
     m.complete(p, ARRAY_TYPE);
 }
+
+fn tuple_type(p: &mut Parser<'_>) {
+    assert!(p.at(T!['(']));
+    let m = p.start();
+    p.bump(T!['(']);
+    while !p.at(T![')']) && !p.at(EOF) {
+        if !p.at_ts(TYPE_FIRST) {
+            p.error_and_bump("expected a type");
+            break;
+        }
+        type_(p);
+        if !p.at(T![')']) {
+            p.expect(T![,]);
+        }
+    }
+    p.expect(T![')']);
+    m.complete(p, TUPLE_TYPE);
+}
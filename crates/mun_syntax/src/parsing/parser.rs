@@ -18,14 +18,18 @@ pub(crate) struct Parser<'t> {
     token_source: &'t mut dyn TokenSource,
     events: Vec<Event>,
     steps: Cell<u32>,
+    depth: usize,
+    nesting_limit: usize,
 }
 
 impl<'t> Parser<'t> {
-    pub(super) fn new(token_source: &'t mut dyn TokenSource) -> Parser<'t> {
+    pub(super) fn new(token_source: &'t mut dyn TokenSource, nesting_limit: usize) -> Parser<'t> {
         Parser {
             token_source,
             events: Vec::new(),
             steps: Cell::new(0),
+            depth: 0,
+            nesting_limit,
         }
     }
 
@@ -33,6 +37,21 @@ impl<'t> Parser<'t> {
         self.events
     }
 
+    /// Enters a nested grammar construct. Returns `false` if this exceeds the
+    /// nesting limit, in which case the depth is left unchanged.
+    pub(crate) fn enter_nested(&mut self) -> bool {
+        if self.depth >= self.nesting_limit {
+            return false;
+        }
+        self.depth += 1;
+        true
+    }
+
+    /// Leaves a nested grammar construct entered with `enter_nested`.
+    pub(crate) fn exit_nested(&mut self) {
+        self.depth -= 1;
+    }
+
     /// Returns the kind of the current token.
     /// If the parser has already reach the end of the input the special `EOF`
     /// kind is returned.
@@ -0,0 +1,234 @@
+//! Implementation of incremental re-parsing.
+//!
+//! We use two simple strategies for this:
+//!   - if the edit modifies only a single token (like changing an identifier's
+//!     letter), we replace only this token.
+//!   - otherwise, we search for the nearest `{}` block which contains the edit
+//!     and try to parse only this block.
+
+use ra_ap_text_edit::Indel;
+use rowan::{GreenToken, NodeOrToken};
+
+use crate::{
+    parsing::{
+        grammar, lexer::Token, text_token_source::TextTokenSource, text_tree_sink::TextTreeSink,
+        tokenize, NESTING_LIMIT_EXCEEDED,
+    },
+    syntax_node::{GreenNode, SyntaxElement, SyntaxNode},
+    validation, SyntaxError,
+    SyntaxKind::{self, BLOCK_EXPR, COMMENT, IDENT, STRING, WHITESPACE},
+    TextRange, TextSize,
+};
+
+/// Identifiers that are interpreted as keywords depending on their context.
+const CONTEXTUAL_KEYWORDS: &[&str] = &["gc", "value"];
+
+/// Tries to apply the `edit` to the tree rooted at `node` by only reparsing the
+/// part of the tree that is affected by the edit. Returns the new root and its
+/// errors, and the range of the original text that was reparsed.
+///
+/// Expressions that are nested deeper than `nesting_limit` are reported as
+/// errors.
+pub(crate) fn incremental_reparse(
+    node: &SyntaxNode,
+    edit: &Indel,
+    errors: Vec<SyntaxError>,
+    nesting_limit: usize,
+) -> Option<(GreenNode, Vec<SyntaxError>, TextRange)> {
+    if let Some((green, new_errors, old_range)) = reparse_token(node, edit) {
+        return Some((
+            green,
+            merge_errors(errors, new_errors, old_range, edit),
+            old_range,
+        ));
+    }
+
+    if let Some((green, new_errors, old_range)) = reparse_block(node, edit, nesting_limit) {
+        return Some((
+            green,
+            merge_errors(errors, new_errors, old_range, edit),
+            old_range,
+        ));
+    }
+    None
+}
+
+/// Tries to apply the `edit` by only replacing the token that contains it.
+fn reparse_token(
+    root: &SyntaxNode,
+    edit: &Indel,
+) -> Option<(GreenNode, Vec<SyntaxError>, TextRange)> {
+    let prev_token = root.covering_element(edit.delete).as_token()?.clone();
+    let prev_token_kind = prev_token.kind();
+    match prev_token_kind {
+        WHITESPACE | COMMENT | IDENT | STRING => {
+            if prev_token_kind == WHITESPACE || prev_token_kind == COMMENT {
+                // Removing a new line may extend the previous token
+                let deleted_range = edit.delete - prev_token.text_range().start();
+                if prev_token.text()[deleted_range].contains('\n') {
+                    return None;
+                }
+            }
+
+            let mut new_text = get_text_after_edit(prev_token.clone().into(), edit);
+            let new_token_kind = lex_single_syntax_kind(&new_text)?;
+            if new_token_kind != prev_token_kind
+                || (new_token_kind == IDENT && CONTEXTUAL_KEYWORDS.contains(&new_text.as_str()))
+            {
+                return None;
+            }
+
+            // Check that the edited token does not merge with the next character
+            // into a bigger token.
+            if let Some(next_char) = root.text().char_at(prev_token.text_range().end()) {
+                new_text.push(next_char);
+                if lex_single_syntax_kind(&new_text).is_some() {
+                    return None;
+                }
+                new_text.pop();
+            }
+
+            let new_token = GreenToken::new(rowan::SyntaxKind(prev_token_kind.into()), &new_text);
+            Some((
+                prev_token.replace_with(new_token),
+                Vec::new(),
+                prev_token.text_range(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Tries to apply the `edit` by only reparsing the innermost block that
+/// contains it.
+///
+/// The parser does not know how deeply the block is nested, so the nesting
+/// limit is lowered by the number of nodes that enclose the block, which is at
+/// least as large as its nesting depth. If the lowered limit is exceeded, the
+/// block is not reparsed so that a full parse decides whether the actual limit
+/// is exceeded.
+fn reparse_block(
+    root: &SyntaxNode,
+    edit: &Indel,
+    nesting_limit: usize,
+) -> Option<(GreenNode, Vec<SyntaxError>, TextRange)> {
+    let node = find_reparsable_node(root, edit.delete)?;
+    let text = get_text_after_edit(node.clone().into(), edit);
+
+    let tokens = tokenize(&text);
+    if !is_balanced(&tokens) {
+        return None;
+    }
+
+    let mut token_source = TextTokenSource::new(&text, &tokens);
+    let mut tree_sink = TextTreeSink::new(&text, &tokens);
+    let depth = node.ancestors().count();
+    super::parse_from_tokens(
+        &mut token_source,
+        &mut tree_sink,
+        nesting_limit.checked_sub(depth)?,
+        grammar::block,
+    );
+    let (green, mut new_errors) = tree_sink.finish();
+    if new_errors
+        .iter()
+        .any(|error| error.kind().to_string() == NESTING_LIMIT_EXCEEDED)
+    {
+        return None;
+    }
+
+    // The block may have ended early, e.g. at a declaration, in which case the
+    // remaining tokens are not part of the reparsed tree.
+    if green.text_len() != TextSize::of(&text) {
+        return None;
+    }
+
+    new_errors.extend(validation::validate(&SyntaxNode::new_root(green.clone())));
+
+    Some((node.replace_with(green), new_errors, node.text_range()))
+}
+
+/// Returns the text of `element` after applying the `edit` to it.
+fn get_text_after_edit(element: SyntaxElement, edit: &Indel) -> String {
+    let edit = Indel::replace(
+        edit.delete - element.text_range().start(),
+        edit.insert.clone(),
+    );
+
+    let mut text = match element {
+        NodeOrToken::Token(token) => token.text().to_string(),
+        NodeOrToken::Node(node) => node.text().to_string(),
+    };
+    edit.apply(&mut text);
+    text
+}
+
+/// Returns the kind of the token if `text` consists of exactly one token.
+fn lex_single_syntax_kind(text: &str) -> Option<SyntaxKind> {
+    match tokenize(text).as_slice() {
+        [token] if token.len == TextSize::of(text) => Some(token.kind),
+        _ => None,
+    }
+}
+
+/// Finds the innermost node covering `range` that can be reparsed on its own.
+fn find_reparsable_node(node: &SyntaxNode, range: TextRange) -> Option<SyntaxNode> {
+    let node = match node.covering_element(range) {
+        NodeOrToken::Token(token) => token.parent()?,
+        NodeOrToken::Node(node) => node,
+    };
+    node.ancestors().find(|node| node.kind() == BLOCK_EXPR)
+}
+
+/// Returns true if the tokens form a single block with balanced curly braces.
+fn is_balanced(tokens: &[Token]) -> bool {
+    if tokens.is_empty()
+        || tokens.first().unwrap().kind != T!['{']
+        || tokens.last().unwrap().kind != T!['}']
+    {
+        return false;
+    }
+    let mut balance = 0usize;
+    for t in &tokens[1..tokens.len() - 1] {
+        match t.kind {
+            T!['{'] => balance += 1,
+            T!['}'] => {
+                balance = match balance.checked_sub(1) {
+                    Some(b) => b,
+                    None => return false,
+                }
+            }
+            _ => (),
+        }
+    }
+    balance == 0
+}
+
+/// Combines the errors of the original tree with the errors of the reparsed
+/// part. Errors inside the reparsed range are dropped and errors after it are
+/// shifted by the edit.
+fn merge_errors(
+    old_errors: Vec<SyntaxError>,
+    new_errors: Vec<SyntaxError>,
+    range_before_reparse: TextRange,
+    edit: &Indel,
+) -> Vec<SyntaxError> {
+    let mut res = Vec::new();
+    let inserted_len = TextSize::of(&edit.insert);
+    let deleted_len = edit.delete.len();
+    for old_err in old_errors {
+        let location = old_err.location();
+        if location.end_offset() <= range_before_reparse.start() {
+            res.push(old_err);
+        } else if location.offset() >= range_before_reparse.end() {
+            res.push(old_err.with_location(location.add_offset(inserted_len, deleted_len)));
+        }
+    }
+    res.extend(new_errors.into_iter().map(|new_err| {
+        let location = new_err
+            .location()
+            .add_offset(range_before_reparse.start(), 0.into());
+        new_err.with_location(location)
+    }));
+    res
+}
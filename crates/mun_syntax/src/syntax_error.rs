@@ -70,6 +70,11 @@ impl SyntaxError {
     pub fn location(&self) -> Location {
         self.location.clone()
     }
+
+    /// Returns a copy of this error at a different location
+    pub fn with_location(self, location: Location) -> SyntaxError {
+        SyntaxError { location, ..self }
+    }
 }
 
 impl fmt::Display for SyntaxError {
@@ -82,6 +82,7 @@ pub enum SyntaxKind {
     AS_KW,
     USE_KW,
     NIL_KW,
+    NULL_KW,
     RETURN_KW,
     TRUE_KW,
     WHILE_KW,
@@ -126,6 +127,7 @@ pub enum SyntaxKind {
     PATH_TYPE,
     ARRAY_TYPE,
     NEVER_TYPE,
+    TUPLE_TYPE,
     LET_STMT,
     EXPR_STMT,
     PATH_EXPR,
@@ -147,6 +149,7 @@ pub enum SyntaxKind {
     BIND_PAT,
     PLACEHOLDER_PAT,
     ARG_LIST,
+    NAMED_ARG,
     NAME,
     NAME_REF,
     PATH,
@@ -161,6 +164,9 @@ pub enum SyntaxKind {
     IMPL,
     ASSOCIATED_ITEM_LIST,
     ASSOCIATED_ITEM,
+    ATTR,
+    TOKEN_TREE,
+    TYPE_ARG_LIST,
     // Technical kind so that we can cast from u16 safely
     #[doc(hidden)]
     __LAST,
@@ -343,6 +349,9 @@ macro_rules! T {
     (nil) => {
         $crate::SyntaxKind::NIL_KW
     };
+    (null) => {
+        $crate::SyntaxKind::NULL_KW
+    };
     (return) => {
         $crate::SyntaxKind::RETURN_KW
     };
@@ -421,6 +430,7 @@ impl SyntaxKind {
         | AS_KW
         | USE_KW
         | NIL_KW
+        | NULL_KW
         | RETURN_KW
         | TRUE_KW
         | WHILE_KW
@@ -563,6 +573,7 @@ impl SyntaxKind {
             AS_KW => &SyntaxInfo { name: "AS_KW" },
             USE_KW => &SyntaxInfo { name: "USE_KW" },
             NIL_KW => &SyntaxInfo { name: "NIL_KW" },
+            NULL_KW => &SyntaxInfo { name: "NULL_KW" },
             RETURN_KW => &SyntaxInfo { name: "RETURN_KW" },
             TRUE_KW => &SyntaxInfo { name: "TRUE_KW" },
             WHILE_KW => &SyntaxInfo { name: "WHILE_KW" },
@@ -607,6 +618,7 @@ impl SyntaxKind {
             PATH_TYPE => &SyntaxInfo { name: "PATH_TYPE" },
             ARRAY_TYPE => &SyntaxInfo { name: "ARRAY_TYPE" },
             NEVER_TYPE => &SyntaxInfo { name: "NEVER_TYPE" },
+            TUPLE_TYPE => &SyntaxInfo { name: "TUPLE_TYPE" },
             LET_STMT => &SyntaxInfo { name: "LET_STMT" },
             EXPR_STMT => &SyntaxInfo { name: "EXPR_STMT" },
             PATH_EXPR => &SyntaxInfo { name: "PATH_EXPR" },
@@ -628,6 +640,7 @@ impl SyntaxKind {
             BIND_PAT => &SyntaxInfo { name: "BIND_PAT" },
             PLACEHOLDER_PAT => &SyntaxInfo { name: "PLACEHOLDER_PAT" },
             ARG_LIST => &SyntaxInfo { name: "ARG_LIST" },
+            NAMED_ARG => &SyntaxInfo { name: "NAMED_ARG" },
             NAME => &SyntaxInfo { name: "NAME" },
             NAME_REF => &SyntaxInfo { name: "NAME_REF" },
             PATH => &SyntaxInfo { name: "PATH" },
@@ -642,6 +655,9 @@ impl SyntaxKind {
             IMPL => &SyntaxInfo { name: "IMPL" },
             ASSOCIATED_ITEM_LIST => &SyntaxInfo { name: "ASSOCIATED_ITEM_LIST" },
             ASSOCIATED_ITEM => &SyntaxInfo { name: "ASSOCIATED_ITEM" },
+            ATTR => &SyntaxInfo { name: "ATTR" },
+            TOKEN_TREE => &SyntaxInfo { name: "TOKEN_TREE" },
+            TYPE_ARG_LIST => &SyntaxInfo { name: "TYPE_ARG_LIST" },
             TOMBSTONE => &SyntaxInfo { name: "TOMBSTONE" },
             EOF => &SyntaxInfo { name: "EOF" },
             __LAST => &SyntaxInfo { name: "__LAST" },
@@ -661,6 +677,7 @@ impl SyntaxKind {
             "as" => AS_KW,
             "use" => USE_KW,
             "nil" => NIL_KW,
+            "null" => NULL_KW,
             "return" => RETURN_KW,
             "true" => TRUE_KW,
             "while" => WHILE_KW,
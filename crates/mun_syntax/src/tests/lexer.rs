@@ -299,7 +299,7 @@ fn strings() {
 fn keywords() {
     insta::assert_snapshot!(dump_text_tokens(
         r#"
-    break do else false for fn if in nil
+    break do else false for fn if in nil null
     return true while let mut struct class
     never loop pub super self package type
     impl
@@ -322,6 +322,8 @@ fn keywords() {
     IN_KW 2 "in"
     WHITESPACE 1 " "
     NIL_KW 3 "nil"
+    WHITESPACE 1 " "
+    NULL_KW 4 "null"
     WHITESPACE 5 "\n    "
     RETURN_KW 6 "return"
     WHITESPACE 1 " "
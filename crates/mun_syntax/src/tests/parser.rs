@@ -145,6 +145,234 @@ fn array_type() {
     );
 }
 
+#[test]
+fn attributes() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    #[cfg(feature = "debug")]
+    #[inline(never)]
+    pub fn foo() {}"#,
+    ).debug_dump(), @r##"
+    SOURCE_FILE@0..71
+      FUNCTION_DEF@0..71
+        WHITESPACE@0..5 "\n    "
+        ATTR@5..30
+          HASH@5..6 "#"
+          L_BRACKET@6..7 "["
+          PATH@7..10
+            PATH_SEGMENT@7..10
+              NAME_REF@7..10
+                IDENT@7..10 "cfg"
+          TOKEN_TREE@10..29
+            L_PAREN@10..11 "("
+            IDENT@11..18 "feature"
+            WHITESPACE@18..19 " "
+            EQ@19..20 "="
+            WHITESPACE@20..21 " "
+            STRING@21..28 "\"debug\""
+            R_PAREN@28..29 ")"
+          R_BRACKET@29..30 "]"
+        WHITESPACE@30..35 "\n    "
+        ATTR@35..51
+          HASH@35..36 "#"
+          L_BRACKET@36..37 "["
+          PATH@37..43
+            PATH_SEGMENT@37..43
+              NAME_REF@37..43
+                IDENT@37..43 "inline"
+          TOKEN_TREE@43..50
+            L_PAREN@43..44 "("
+            NEVER_KW@44..49 "never"
+            R_PAREN@49..50 ")"
+          R_BRACKET@50..51 "]"
+        WHITESPACE@51..56 "\n    "
+        VISIBILITY@56..59
+          PUB_KW@56..59 "pub"
+        WHITESPACE@59..60 " "
+        FN_KW@60..62 "fn"
+        WHITESPACE@62..63 " "
+        NAME@63..66
+          IDENT@63..66 "foo"
+        PARAM_LIST@66..68
+          L_PAREN@66..67 "("
+          R_PAREN@67..68 ")"
+        WHITESPACE@68..69 " "
+        BLOCK_EXPR@69..71
+          L_CURLY@69..70 "{"
+          R_CURLY@70..71 "}"
+    "##
+    );
+}
+
+#[test]
+fn tuple_type() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn main() -> () {
+        let a: ();
+        let b: (i32, bool);
+    }"#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..75
+      FUNCTION_DEF@0..75
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..12
+          IDENT@8..12 "main"
+        PARAM_LIST@12..14
+          L_PAREN@12..13 "("
+          R_PAREN@13..14 ")"
+        WHITESPACE@14..15 " "
+        RET_TYPE@15..20
+          THIN_ARROW@15..17 "->"
+          WHITESPACE@17..18 " "
+          TUPLE_TYPE@18..20
+            L_PAREN@18..19 "("
+            R_PAREN@19..20 ")"
+        WHITESPACE@20..21 " "
+        BLOCK_EXPR@21..75
+          L_CURLY@21..22 "{"
+          WHITESPACE@22..31 "\n        "
+          LET_STMT@31..41
+            LET_KW@31..34 "let"
+            WHITESPACE@34..35 " "
+            BIND_PAT@35..36
+              NAME@35..36
+                IDENT@35..36 "a"
+            COLON@36..37 ":"
+            WHITESPACE@37..38 " "
+            TUPLE_TYPE@38..40
+              L_PAREN@38..39 "("
+              R_PAREN@39..40 ")"
+            SEMI@40..41 ";"
+          WHITESPACE@41..50 "\n        "
+          LET_STMT@50..69
+            LET_KW@50..53 "let"
+            WHITESPACE@53..54 " "
+            BIND_PAT@54..55
+              NAME@54..55
+                IDENT@54..55 "b"
+            COLON@55..56 ":"
+            WHITESPACE@56..57 " "
+            TUPLE_TYPE@57..68
+              L_PAREN@57..58 "("
+              PATH_TYPE@58..61
+                PATH@58..61
+                  PATH_SEGMENT@58..61
+                    NAME_REF@58..61
+                      IDENT@58..61 "i32"
+              COMMA@61..62 ","
+              WHITESPACE@62..63 " "
+              PATH_TYPE@63..67
+                PATH@63..67
+                  PATH_SEGMENT@63..67
+                    NAME_REF@63..67
+                      IDENT@63..67 "bool"
+              R_PAREN@67..68 ")"
+            SEMI@68..69 ";"
+          WHITESPACE@69..74 "\n    "
+          R_CURLY@74..75 "}"
+    "#
+    );
+}
+
+#[test]
+fn type_args() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn main() {
+        let a = size_of::<Foo>();
+        let b = align_of::<(i32, bool)>();
+    }"#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..99
+      FUNCTION_DEF@0..99
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..12
+          IDENT@8..12 "main"
+        PARAM_LIST@12..14
+          L_PAREN@12..13 "("
+          R_PAREN@13..14 ")"
+        WHITESPACE@14..15 " "
+        BLOCK_EXPR@15..99
+          L_CURLY@15..16 "{"
+          WHITESPACE@16..25 "\n        "
+          LET_STMT@25..50
+            LET_KW@25..28 "let"
+            WHITESPACE@28..29 " "
+            BIND_PAT@29..30
+              NAME@29..30
+                IDENT@29..30 "a"
+            WHITESPACE@30..31 " "
+            EQ@31..32 "="
+            WHITESPACE@32..33 " "
+            CALL_EXPR@33..49
+              PATH_EXPR@33..47
+                PATH@33..47
+                  PATH_SEGMENT@33..47
+                    NAME_REF@33..40
+                      IDENT@33..40 "size_of"
+                    TYPE_ARG_LIST@40..47
+                      COLONCOLON@40..42 "::"
+                      LT@42..43 "<"
+                      PATH_TYPE@43..46
+                        PATH@43..46
+                          PATH_SEGMENT@43..46
+                            NAME_REF@43..46
+                              IDENT@43..46 "Foo"
+                      GT@46..47 ">"
+              ARG_LIST@47..49
+                L_PAREN@47..48 "("
+                R_PAREN@48..49 ")"
+            SEMI@49..50 ";"
+          WHITESPACE@50..59 "\n        "
+          LET_STMT@59..93
+            LET_KW@59..62 "let"
+            WHITESPACE@62..63 " "
+            BIND_PAT@63..64
+              NAME@63..64
+                IDENT@63..64 "b"
+            WHITESPACE@64..65 " "
+            EQ@65..66 "="
+            WHITESPACE@66..67 " "
+            CALL_EXPR@67..92
+              PATH_EXPR@67..90
+                PATH@67..90
+                  PATH_SEGMENT@67..90
+                    NAME_REF@67..75
+                      IDENT@67..75 "align_of"
+                    TYPE_ARG_LIST@75..90
+                      COLONCOLON@75..77 "::"
+                      LT@77..78 "<"
+                      TUPLE_TYPE@78..89
+                        L_PAREN@78..79 "("
+                        PATH_TYPE@79..82
+                          PATH@79..82
+                            PATH_SEGMENT@79..82
+                              NAME_REF@79..82
+                                IDENT@79..82 "i32"
+                        COMMA@82..83 ","
+                        WHITESPACE@83..84 " "
+                        PATH_TYPE@84..88
+                          PATH@84..88
+                            PATH_SEGMENT@84..88
+                              NAME_REF@84..88
+                                IDENT@84..88 "bool"
+                        R_PAREN@88..89 ")"
+                      GT@89..90 ">"
+              ARG_LIST@90..92
+                L_PAREN@90..91 "("
+                R_PAREN@91..92 ")"
+            SEMI@92..93 ";"
+          WHITESPACE@93..98 "\n    "
+          R_CURLY@98..99 "}"
+    "#
+    );
+}
+
 #[test]
 fn index_expr() {
     insta::assert_snapshot!(SourceFile::parse(
@@ -426,6 +654,53 @@ fn array_expr() {
     );
 }
 
+#[test]
+fn array_repeat_expr() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn main() {
+        let a = [0; 5];
+    }"#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..46
+      FUNCTION_DEF@0..46
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..12
+          IDENT@8..12 "main"
+        PARAM_LIST@12..14
+          L_PAREN@12..13 "("
+          R_PAREN@13..14 ")"
+        WHITESPACE@14..15 " "
+        BLOCK_EXPR@15..46
+          L_CURLY@15..16 "{"
+          WHITESPACE@16..25 "\n        "
+          LET_STMT@25..40
+            LET_KW@25..28 "let"
+            WHITESPACE@28..29 " "
+            BIND_PAT@29..30
+              NAME@29..30
+                IDENT@29..30 "a"
+            WHITESPACE@30..31 " "
+            EQ@31..32 "="
+            WHITESPACE@32..33 " "
+            ARRAY_EXPR@33..39
+              L_BRACKET@33..34 "["
+              LITERAL@34..35
+                INT_NUMBER@34..35 "0"
+              SEMI@35..36 ";"
+              WHITESPACE@36..37 " "
+              LITERAL@37..38
+                INT_NUMBER@37..38 "5"
+              R_BRACKET@38..39 "]"
+            SEMI@39..40 ";"
+          WHITESPACE@40..45 "\n    "
+          R_CURLY@45..46 "}"
+    "#
+    );
+}
+
 #[test]
 fn missing_field_expr() {
     insta::assert_snapshot!(SourceFile::parse(
@@ -1403,6 +1678,372 @@ fn function_calls() {
     "###);
 }
 
+#[test]
+fn named_arguments() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn main() {
+        foo(1, b: 2, a: x);
+        foo(a: 1, 2);
+    }
+    "#,
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..77
+      FUNCTION_DEF@0..72
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..12
+          IDENT@8..12 "main"
+        PARAM_LIST@12..14
+          L_PAREN@12..13 "("
+          R_PAREN@13..14 ")"
+        WHITESPACE@14..15 " "
+        BLOCK_EXPR@15..72
+          L_CURLY@15..16 "{"
+          WHITESPACE@16..25 "\n        "
+          EXPR_STMT@25..44
+            CALL_EXPR@25..43
+              PATH_EXPR@25..28
+                PATH@25..28
+                  PATH_SEGMENT@25..28
+                    NAME_REF@25..28
+                      IDENT@25..28 "foo"
+              ARG_LIST@28..43
+                L_PAREN@28..29 "("
+                LITERAL@29..30
+                  INT_NUMBER@29..30 "1"
+                COMMA@30..31 ","
+                WHITESPACE@31..32 " "
+                NAMED_ARG@32..36
+                  NAME_REF@32..33
+                    IDENT@32..33 "b"
+                  COLON@33..34 ":"
+                  WHITESPACE@34..35 " "
+                  LITERAL@35..36
+                    INT_NUMBER@35..36 "2"
+                COMMA@36..37 ","
+                WHITESPACE@37..38 " "
+                NAMED_ARG@38..42
+                  NAME_REF@38..39
+                    IDENT@38..39 "a"
+                  COLON@39..40 ":"
+                  WHITESPACE@40..41 " "
+                  PATH_EXPR@41..42
+                    PATH@41..42
+                      PATH_SEGMENT@41..42
+                        NAME_REF@41..42
+                          IDENT@41..42 "x"
+                R_PAREN@42..43 ")"
+            SEMI@43..44 ";"
+          WHITESPACE@44..53 "\n        "
+          EXPR_STMT@53..66
+            CALL_EXPR@53..65
+              PATH_EXPR@53..56
+                PATH@53..56
+                  PATH_SEGMENT@53..56
+                    NAME_REF@53..56
+                      IDENT@53..56 "foo"
+              ARG_LIST@56..65
+                L_PAREN@56..57 "("
+                NAMED_ARG@57..61
+                  NAME_REF@57..58
+                    IDENT@57..58 "a"
+                  COLON@58..59 ":"
+                  WHITESPACE@59..60 " "
+                  LITERAL@60..61
+                    INT_NUMBER@60..61 "1"
+                COMMA@61..62 ","
+                WHITESPACE@62..63 " "
+                LITERAL@63..64
+                  INT_NUMBER@63..64 "2"
+                R_PAREN@64..65 ")"
+            SEMI@65..66 ";"
+          WHITESPACE@66..71 "\n    "
+          R_CURLY@71..72 "}"
+      WHITESPACE@72..77 "\n    "
+    error Offset(62): positional arguments cannot follow named arguments
+    "###);
+}
+
+#[test]
+fn default_parameter_values() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn greet(name: i32, excited: bool = false, times: i32 = -1) {}
+    fn foo(a: i32 = 1, b: i32) {}
+    "#,
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..106
+      FUNCTION_DEF@0..67
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..13
+          IDENT@8..13 "greet"
+        PARAM_LIST@13..64
+          L_PAREN@13..14 "("
+          PARAM@14..23
+            BIND_PAT@14..18
+              NAME@14..18
+                IDENT@14..18 "name"
+            COLON@18..19 ":"
+            WHITESPACE@19..20 " "
+            PATH_TYPE@20..23
+              PATH@20..23
+                PATH_SEGMENT@20..23
+                  NAME_REF@20..23
+                    IDENT@20..23 "i32"
+          COMMA@23..24 ","
+          WHITESPACE@24..25 " "
+          PARAM@25..46
+            BIND_PAT@25..32
+              NAME@25..32
+                IDENT@25..32 "excited"
+            COLON@32..33 ":"
+            WHITESPACE@33..34 " "
+            PATH_TYPE@34..38
+              PATH@34..38
+                PATH_SEGMENT@34..38
+                  NAME_REF@34..38
+                    IDENT@34..38 "bool"
+            WHITESPACE@38..39 " "
+            EQ@39..40 "="
+            WHITESPACE@40..41 " "
+            LITERAL@41..46
+              FALSE_KW@41..46 "false"
+          COMMA@46..47 ","
+          WHITESPACE@47..48 " "
+          PARAM@48..63
+            BIND_PAT@48..53
+              NAME@48..53
+                IDENT@48..53 "times"
+            COLON@53..54 ":"
+            WHITESPACE@54..55 " "
+            PATH_TYPE@55..58
+              PATH@55..58
+                PATH_SEGMENT@55..58
+                  NAME_REF@55..58
+                    IDENT@55..58 "i32"
+            WHITESPACE@58..59 " "
+            EQ@59..60 "="
+            WHITESPACE@60..61 " "
+            PREFIX_EXPR@61..63
+              MINUS@61..62 "-"
+              LITERAL@62..63
+                INT_NUMBER@62..63 "1"
+          R_PAREN@63..64 ")"
+        WHITESPACE@64..65 " "
+        BLOCK_EXPR@65..67
+          L_CURLY@65..66 "{"
+          R_CURLY@66..67 "}"
+      FUNCTION_DEF@67..101
+        WHITESPACE@67..72 "\n    "
+        FN_KW@72..74 "fn"
+        WHITESPACE@74..75 " "
+        NAME@75..78
+          IDENT@75..78 "foo"
+        PARAM_LIST@78..98
+          L_PAREN@78..79 "("
+          PARAM@79..89
+            BIND_PAT@79..80
+              NAME@79..80
+                IDENT@79..80 "a"
+            COLON@80..81 ":"
+            WHITESPACE@81..82 " "
+            PATH_TYPE@82..85
+              PATH@82..85
+                PATH_SEGMENT@82..85
+                  NAME_REF@82..85
+                    IDENT@82..85 "i32"
+            WHITESPACE@85..86 " "
+            EQ@86..87 "="
+            WHITESPACE@87..88 " "
+            LITERAL@88..89
+              INT_NUMBER@88..89 "1"
+          COMMA@89..90 ","
+          WHITESPACE@90..91 " "
+          PARAM@91..97
+            BIND_PAT@91..92
+              NAME@91..92
+                IDENT@91..92 "b"
+            COLON@92..93 ":"
+            WHITESPACE@93..94 " "
+            PATH_TYPE@94..97
+              PATH@94..97
+                PATH_SEGMENT@94..97
+                  NAME_REF@94..97
+                    IDENT@94..97 "i32"
+          R_PAREN@97..98 ")"
+        WHITESPACE@98..99 " "
+        BLOCK_EXPR@99..101
+          L_CURLY@99..100 "{"
+          R_CURLY@100..101 "}"
+      WHITESPACE@101..106 "\n    "
+    "###);
+}
+
+#[test]
+fn trailing_commas() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo(a:i32,) {}
+    fn bar(self,) {}
+    fn baz() {
+        foo(1,)
+    }"#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..80
+      FUNCTION_DEF@0..22
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..19
+          L_PAREN@11..12 "("
+          PARAM@12..17
+            BIND_PAT@12..13
+              NAME@12..13
+                IDENT@12..13 "a"
+            COLON@13..14 ":"
+            PATH_TYPE@14..17
+              PATH@14..17
+                PATH_SEGMENT@14..17
+                  NAME_REF@14..17
+                    IDENT@14..17 "i32"
+          COMMA@17..18 ","
+          R_PAREN@18..19 ")"
+        WHITESPACE@19..20 " "
+        BLOCK_EXPR@20..22
+          L_CURLY@20..21 "{"
+          R_CURLY@21..22 "}"
+      FUNCTION_DEF@22..43
+        WHITESPACE@22..27 "\n    "
+        FN_KW@27..29 "fn"
+        WHITESPACE@29..30 " "
+        NAME@30..33
+          IDENT@30..33 "bar"
+        PARAM_LIST@33..40
+          L_PAREN@33..34 "("
+          SELF_PARAM@34..38
+            NAME@34..38
+              SELF_KW@34..38 "self"
+          COMMA@38..39 ","
+          R_PAREN@39..40 ")"
+        WHITESPACE@40..41 " "
+        BLOCK_EXPR@41..43
+          L_CURLY@41..42 "{"
+          R_CURLY@42..43 "}"
+      FUNCTION_DEF@43..80
+        WHITESPACE@43..48 "\n    "
+        FN_KW@48..50 "fn"
+        WHITESPACE@50..51 " "
+        NAME@51..54
+          IDENT@51..54 "baz"
+        PARAM_LIST@54..56
+          L_PAREN@54..55 "("
+          R_PAREN@55..56 ")"
+        WHITESPACE@56..57 " "
+        BLOCK_EXPR@57..80
+          L_CURLY@57..58 "{"
+          WHITESPACE@58..67 "\n        "
+          CALL_EXPR@67..74
+            PATH_EXPR@67..70
+              PATH@67..70
+                PATH_SEGMENT@67..70
+                  NAME_REF@67..70
+                    IDENT@67..70 "foo"
+            ARG_LIST@70..74
+              L_PAREN@70..71 "("
+              LITERAL@71..72
+                INT_NUMBER@71..72 "1"
+              COMMA@72..73 ","
+              R_PAREN@73..74 ")"
+          WHITESPACE@74..79 "\n    "
+          R_CURLY@79..80 "}"
+    "#);
+}
+
+#[test]
+fn trailing_commas_in_fields_and_arrays() {
+    insta::assert_snapshot!(SourceFile::parse(
+        "struct Foo { a: i32, }\nfn bar() { Foo { a: 1, }; [1, 2,] }"
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..58
+      STRUCT_DEF@0..22
+        STRUCT_KW@0..6 "struct"
+        WHITESPACE@6..7 " "
+        NAME@7..10
+          IDENT@7..10 "Foo"
+        WHITESPACE@10..11 " "
+        RECORD_FIELD_DEF_LIST@11..22
+          L_CURLY@11..12 "{"
+          WHITESPACE@12..13 " "
+          RECORD_FIELD_DEF@13..19
+            NAME@13..14
+              IDENT@13..14 "a"
+            COLON@14..15 ":"
+            WHITESPACE@15..16 " "
+            PATH_TYPE@16..19
+              PATH@16..19
+                PATH_SEGMENT@16..19
+                  NAME_REF@16..19
+                    IDENT@16..19 "i32"
+          COMMA@19..20 ","
+          WHITESPACE@20..21 " "
+          R_CURLY@21..22 "}"
+      FUNCTION_DEF@22..58
+        WHITESPACE@22..23 "\n"
+        FN_KW@23..25 "fn"
+        WHITESPACE@25..26 " "
+        NAME@26..29
+          IDENT@26..29 "bar"
+        PARAM_LIST@29..31
+          L_PAREN@29..30 "("
+          R_PAREN@30..31 ")"
+        WHITESPACE@31..32 " "
+        BLOCK_EXPR@32..58
+          L_CURLY@32..33 "{"
+          WHITESPACE@33..34 " "
+          EXPR_STMT@34..48
+            RECORD_LIT@34..47
+              PATH_TYPE@34..37
+                PATH@34..37
+                  PATH_SEGMENT@34..37
+                    NAME_REF@34..37
+                      IDENT@34..37 "Foo"
+              WHITESPACE@37..38 " "
+              RECORD_FIELD_LIST@38..47
+                L_CURLY@38..39 "{"
+                WHITESPACE@39..40 " "
+                RECORD_FIELD@40..44
+                  NAME_REF@40..41
+                    IDENT@40..41 "a"
+                  COLON@41..42 ":"
+                  WHITESPACE@42..43 " "
+                  LITERAL@43..44
+                    INT_NUMBER@43..44 "1"
+                COMMA@44..45 ","
+                WHITESPACE@45..46 " "
+                R_CURLY@46..47 "}"
+            SEMI@47..48 ";"
+          WHITESPACE@48..49 " "
+          ARRAY_EXPR@49..56
+            L_BRACKET@49..50 "["
+            LITERAL@50..51
+              INT_NUMBER@50..51 "1"
+            COMMA@51..52 ","
+            WHITESPACE@52..53 " "
+            LITERAL@53..54
+              INT_NUMBER@53..54 "2"
+            COMMA@54..55 ","
+            R_BRACKET@55..56 "]"
+          WHITESPACE@56..57 " "
+          R_CURLY@57..58 "}"
+    "###);
+}
+
 #[test]
 fn patterns() {
     insta::assert_snapshot!(SourceFile::parse(
@@ -2606,6 +3247,74 @@ fn while_expr() {
     "#);
 }
 
+#[test]
+fn while_else_expr() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo() {
+        let a = while true { break 3; } else { 4 };
+    }
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..78
+      FUNCTION_DEF@0..73
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..13
+          L_PAREN@11..12 "("
+          R_PAREN@12..13 ")"
+        WHITESPACE@13..14 " "
+        BLOCK_EXPR@14..73
+          L_CURLY@14..15 "{"
+          WHITESPACE@15..24 "\n        "
+          LET_STMT@24..67
+            LET_KW@24..27 "let"
+            WHITESPACE@27..28 " "
+            BIND_PAT@28..29
+              NAME@28..29
+                IDENT@28..29 "a"
+            WHITESPACE@29..30 " "
+            EQ@30..31 "="
+            WHITESPACE@31..32 " "
+            WHILE_EXPR@32..66
+              WHILE_KW@32..37 "while"
+              WHITESPACE@37..38 " "
+              CONDITION@38..42
+                LITERAL@38..42
+                  TRUE_KW@38..42 "true"
+              WHITESPACE@42..43 " "
+              BLOCK_EXPR@43..55
+                L_CURLY@43..44 "{"
+                WHITESPACE@44..45 " "
+                EXPR_STMT@45..53
+                  BREAK_EXPR@45..52
+                    BREAK_KW@45..50 "break"
+                    WHITESPACE@50..51 " "
+                    LITERAL@51..52
+                      INT_NUMBER@51..52 "3"
+                  SEMI@52..53 ";"
+                WHITESPACE@53..54 " "
+                R_CURLY@54..55 "}"
+              WHITESPACE@55..56 " "
+              ELSE_KW@56..60 "else"
+              WHITESPACE@60..61 " "
+              BLOCK_EXPR@61..66
+                L_CURLY@61..62 "{"
+                WHITESPACE@62..63 " "
+                LITERAL@63..64
+                  INT_NUMBER@63..64 "4"
+                WHITESPACE@64..65 " "
+                R_CURLY@65..66 "}"
+            SEMI@66..67 ";"
+          WHITESPACE@67..72 "\n    "
+          R_CURLY@72..73 "}"
+      WHITESPACE@73..78 "\n    "
+    "#);
+}
+
 #[test]
 fn struct_lit() {
     insta::assert_snapshot!(SourceFile::parse(
@@ -3663,3 +4372,275 @@ fn use_() {
     error Offset(369): expected a declaration
     "#);
 }
+
+#[test]
+fn nesting_limit() {
+    let text = format!(
+        "fn main() {{ {}1{} }}",
+        "(".repeat(10_000),
+        ")".repeat(10_000)
+    );
+    let errors: Vec<String> = SourceFile::parse(&text)
+        .errors()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(errors, vec!["expression is nested too deeply"]);
+
+    let text = "fn main() { (1) }";
+    assert!(SourceFile::parse_with_nesting_limit(text, 2)
+        .errors()
+        .is_empty());
+    assert_eq!(
+        SourceFile::parse_with_nesting_limit(text, 1).errors().len(),
+        1
+    );
+}
+
+#[test]
+fn recover_multiple_errors() {
+    insta::assert_snapshot!(SourceFile::parse(
+        "fn foo() { 1 + }\nfn bar() { (2 }"
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..32
+      FUNCTION_DEF@0..16
+        FN_KW@0..2 "fn"
+        WHITESPACE@2..3 " "
+        NAME@3..6
+          IDENT@3..6 "foo"
+        PARAM_LIST@6..8
+          L_PAREN@6..7 "("
+          R_PAREN@7..8 ")"
+        WHITESPACE@8..9 " "
+        BLOCK_EXPR@9..16
+          L_CURLY@9..10 "{"
+          WHITESPACE@10..11 " "
+          BIN_EXPR@11..14
+            LITERAL@11..12
+              INT_NUMBER@11..12 "1"
+            WHITESPACE@12..13 " "
+            PLUS@13..14 "+"
+          WHITESPACE@14..15 " "
+          R_CURLY@15..16 "}"
+      FUNCTION_DEF@16..32
+        WHITESPACE@16..17 "\n"
+        FN_KW@17..19 "fn"
+        WHITESPACE@19..20 " "
+        NAME@20..23
+          IDENT@20..23 "bar"
+        PARAM_LIST@23..25
+          L_PAREN@23..24 "("
+          R_PAREN@24..25 ")"
+        WHITESPACE@25..26 " "
+        BLOCK_EXPR@26..32
+          L_CURLY@26..27 "{"
+          WHITESPACE@27..28 " "
+          PAREN_EXPR@28..30
+            L_PAREN@28..29 "("
+            LITERAL@29..30
+              INT_NUMBER@29..30 "2"
+          WHITESPACE@30..31 " "
+          R_CURLY@31..32 "}"
+    error Offset(14): expected expression
+    error Offset(30): expected R_PAREN
+    "###);
+}
+
+#[test]
+fn recover_unterminated_block() {
+    // A block that is missing its closing `}` ends at the next declaration
+    insta::assert_snapshot!(SourceFile::parse(
+        "fn foo() { 1\nfn bar() {}"
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..24
+      FUNCTION_DEF@0..12
+        FN_KW@0..2 "fn"
+        WHITESPACE@2..3 " "
+        NAME@3..6
+          IDENT@3..6 "foo"
+        PARAM_LIST@6..8
+          L_PAREN@6..7 "("
+          R_PAREN@7..8 ")"
+        WHITESPACE@8..9 " "
+        BLOCK_EXPR@9..12
+          L_CURLY@9..10 "{"
+          WHITESPACE@10..11 " "
+          EXPR_STMT@11..12
+            LITERAL@11..12
+              INT_NUMBER@11..12 "1"
+      FUNCTION_DEF@12..24
+        WHITESPACE@12..13 "\n"
+        FN_KW@13..15 "fn"
+        WHITESPACE@15..16 " "
+        NAME@16..19
+          IDENT@16..19 "bar"
+        PARAM_LIST@19..21
+          L_PAREN@19..20 "("
+          R_PAREN@20..21 ")"
+        WHITESPACE@21..22 " "
+        BLOCK_EXPR@22..24
+          L_CURLY@22..23 "{"
+          R_CURLY@23..24 "}"
+    error Offset(12): expected R_CURLY
+    "###);
+}
+
+#[test]
+fn script_misplaced_declaration() {
+    insta::assert_snapshot!(SourceFile::parse_script(
+        "let a = 1;\nfn foo() {}\na",
+        crate::DEFAULT_NESTING_LIMIT
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..24
+      FUNCTION_DEF@0..24
+        BLOCK_EXPR@0..24
+          LET_STMT@0..10
+            LET_KW@0..3 "let"
+            WHITESPACE@3..4 " "
+            BIND_PAT@4..5
+              NAME@4..5
+                IDENT@4..5 "a"
+            WHITESPACE@5..6 " "
+            EQ@6..7 "="
+            WHITESPACE@7..8 " "
+            LITERAL@8..9
+              INT_NUMBER@8..9 "1"
+            SEMI@9..10 ";"
+          WHITESPACE@10..11 "\n"
+          ERROR@11..22
+            FUNCTION_DEF@11..22
+              FN_KW@11..13 "fn"
+              WHITESPACE@13..14 " "
+              NAME@14..17
+                IDENT@14..17 "foo"
+              PARAM_LIST@17..19
+                L_PAREN@17..18 "("
+                R_PAREN@18..19 ")"
+              WHITESPACE@19..20 " "
+              BLOCK_EXPR@20..22
+                L_CURLY@20..21 "{"
+                R_CURLY@21..22 "}"
+          WHITESPACE@22..23 "\n"
+          PATH_EXPR@23..24
+            PATH@23..24
+              PATH_SEGMENT@23..24
+                NAME_REF@23..24
+                  IDENT@23..24 "a"
+    error Offset(11): declarations must precede the statements of a script
+    "###);
+}
+
+#[test]
+fn script() {
+    insta::assert_snapshot!(SourceFile::parse_script(
+        "struct Foo;\nlet a = 3;\na + 4",
+        crate::DEFAULT_NESTING_LIMIT
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..28
+      STRUCT_DEF@0..11
+        STRUCT_KW@0..6 "struct"
+        WHITESPACE@6..7 " "
+        NAME@7..10
+          IDENT@7..10 "Foo"
+        SEMI@10..11 ";"
+      FUNCTION_DEF@11..28
+        WHITESPACE@11..12 "\n"
+        BLOCK_EXPR@12..28
+          LET_STMT@12..22
+            LET_KW@12..15 "let"
+            WHITESPACE@15..16 " "
+            BIND_PAT@16..17
+              NAME@16..17
+                IDENT@16..17 "a"
+            WHITESPACE@17..18 " "
+            EQ@18..19 "="
+            WHITESPACE@19..20 " "
+            LITERAL@20..21
+              INT_NUMBER@20..21 "3"
+            SEMI@21..22 ";"
+          WHITESPACE@22..23 "\n"
+          BIN_EXPR@23..28
+            PATH_EXPR@23..24
+              PATH@23..24
+                PATH_SEGMENT@23..24
+                  NAME_REF@23..24
+                    IDENT@23..24 "a"
+            WHITESPACE@24..25 " "
+            PLUS@25..26 "+"
+            WHITESPACE@26..27 " "
+            LITERAL@27..28
+              INT_NUMBER@27..28 "4"
+    "###);
+}
+
+#[test]
+fn if_without_braces() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo() {
+        if true 1 else -1;
+        if a -1 else 1
+    }
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..76
+      FUNCTION_DEF@0..71
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..13
+          L_PAREN@11..12 "("
+          R_PAREN@12..13 ")"
+        WHITESPACE@13..14 " "
+        BLOCK_EXPR@14..71
+          L_CURLY@14..15 "{"
+          WHITESPACE@15..24 "\n        "
+          EXPR_STMT@24..42
+            IF_EXPR@24..41
+              IF_KW@24..26 "if"
+              WHITESPACE@26..27 " "
+              CONDITION@27..31
+                LITERAL@27..31
+                  TRUE_KW@27..31 "true"
+              WHITESPACE@31..32 " "
+              BLOCK_EXPR@32..33
+                LITERAL@32..33
+                  INT_NUMBER@32..33 "1"
+              WHITESPACE@33..34 " "
+              ELSE_KW@34..38 "else"
+              WHITESPACE@38..39 " "
+              BLOCK_EXPR@39..41
+                PREFIX_EXPR@39..41
+                  MINUS@39..40 "-"
+                  LITERAL@40..41
+                    INT_NUMBER@40..41 "1"
+            SEMI@41..42 ";"
+          WHITESPACE@42..51 "\n        "
+          IF_EXPR@51..65
+            IF_KW@51..53 "if"
+            WHITESPACE@53..54 " "
+            CONDITION@54..58
+              BIN_EXPR@54..58
+                PATH_EXPR@54..55
+                  PATH@54..55
+                    PATH_SEGMENT@54..55
+                      NAME_REF@54..55
+                        IDENT@54..55 "a"
+                WHITESPACE@55..56 " "
+                MINUS@56..57 "-"
+                LITERAL@57..58
+                  INT_NUMBER@57..58 "1"
+            WHITESPACE@58..59 " "
+            ELSE_KW@59..63 "else"
+            WHITESPACE@63..64 " "
+            BLOCK_EXPR@64..65
+              LITERAL@64..65
+                INT_NUMBER@64..65 "1"
+          WHITESPACE@65..70 "\n    "
+          R_CURLY@70..71 "}"
+      WHITESPACE@71..76 "\n    "
+    error Offset(58): expected a block or an expression
+    "#);
+}
@@ -0,0 +1,160 @@
+use ra_ap_text_edit::Indel;
+
+use crate::{
+    ast::{self, AstNode},
+    parsing::{incremental_reparse, DEFAULT_NESTING_LIMIT},
+    SourceFile, TextRange, TextSize,
+};
+
+/// Applies the replacement of `range` with `insert` to `before` both
+/// incrementally and by parsing from scratch, and checks that both result in
+/// the same tree. Returns the number of characters that were reparsed
+/// incrementally.
+fn do_check(before: &str, range: TextRange, insert: &str) -> (SourceFile, SourceFile, usize) {
+    let edit = Indel::replace(range, insert.to_owned());
+    let mut after = before.to_owned();
+    edit.apply(&mut after);
+
+    let fully_reparsed = SourceFile::parse(&after);
+    let before = SourceFile::parse(before);
+    let (green, errors, reparsed_range) = incremental_reparse(
+        before.tree().syntax(),
+        &edit,
+        before.errors().to_vec(),
+        DEFAULT_NESTING_LIMIT,
+    )
+    .expect("cannot incrementally reparse");
+    let incrementally_reparsed = crate::Parse::<SourceFile>::new(green, errors);
+
+    assert_eq!(
+        fully_reparsed.debug_dump(),
+        incrementally_reparsed.debug_dump()
+    );
+
+    (
+        before.tree(),
+        incrementally_reparsed.tree(),
+        u32::from(reparsed_range.len()) as usize,
+    )
+}
+
+/// Returns the range of the first occurrence of `needle` in `text`.
+fn range_of(text: &str, needle: &str) -> TextRange {
+    let start = text.find(needle).expect("needle not found");
+    TextRange::at(TextSize::try_from(start).unwrap(), TextSize::of(needle))
+}
+
+/// Returns the function definitions in the file
+fn functions(file: &SourceFile) -> Vec<ast::FunctionDef> {
+    file.syntax()
+        .descendants()
+        .filter_map(ast::FunctionDef::cast)
+        .collect()
+}
+
+/// Returns true if both nodes share the same underlying green node
+fn is_shared(a: &impl AstNode, b: &impl AstNode) -> bool {
+    std::ptr::eq(&*a.syntax().green(), &*b.syntax().green())
+}
+
+#[test]
+fn reparse_token() {
+    let text = "fn foo(a: i32) -> i32 { a }\nfn bar() -> i32 { 5 }\n";
+
+    // Renaming an identifier only replaces that token
+    let (_, _, len) = do_check(text, range_of(text, "foo"), "foobar");
+    assert_eq!(len, "foo".len());
+
+    // Edits in whitespace and comments are also limited to the token
+    let text = "fn foo() {\n    // comment\n    5\n}\n";
+    let (_, _, len) = do_check(text, range_of(text, "comment"), "another comment");
+    assert_eq!(len, "// comment".len());
+}
+
+#[test]
+fn reparse_block() {
+    let text = "fn foo(a: i32) -> i32 {\n    a + 1\n}\n\nfn bar() -> i32 {\n    5\n}\n";
+
+    let (before, after, len) = do_check(text, range_of(text, "a + 1"), "let b = a * 2; b");
+    assert_eq!(len, "{\n    a + 1\n}".len());
+
+    // Only the edited function was reparsed, the other one is reused as is
+    let before = functions(&before);
+    let after = functions(&after);
+    assert!(!is_shared(&before[0], &after[0]));
+    assert!(is_shared(&before[1], &after[1]));
+}
+
+#[test]
+fn reparse_innermost_block() {
+    let text = "fn foo() {\n    if true {\n        1\n    } else {\n        2\n    }\n}\n";
+
+    let (_, _, len) = do_check(text, range_of(text, "1"), "3 + 4");
+    assert_eq!(len, "{\n        1\n    }".len());
+}
+
+#[test]
+fn reparse_with_errors() {
+    // Errors before and after the reparsed block are preserved and shifted
+    let text = "fn foo( {\n}\n\nfn bar() {\n    5\n}\n\nfn baz( {\n}\n";
+    do_check(text, range_of(text, "5"), "5 + 10");
+
+    // Errors in the reparsed block are replaced
+    let text = "fn foo() {\n    let a = ;\n}\n";
+    do_check(text, range_of(text, "let a = ;"), "let a = 5;");
+}
+
+#[test]
+fn unbalanced_edit_requires_full_reparse() {
+    let text = "fn foo() {\n    5\n}\n";
+    let edit = Indel::replace(range_of(text, "5"), "{ 5".to_owned());
+    let parse = SourceFile::parse(text);
+    assert!(incremental_reparse(
+        parse.tree().syntax(),
+        &edit,
+        parse.errors().to_vec(),
+        DEFAULT_NESTING_LIMIT
+    )
+    .is_none());
+
+    // Parse::reparse falls back to a full reparse
+    let mut after = text.to_owned();
+    edit.apply(&mut after);
+    assert_eq!(
+        parse.reparse(&edit).debug_dump(),
+        SourceFile::parse(&after).debug_dump()
+    );
+}
+
+#[test]
+fn reparse_script() {
+    let text = "fn foo() -> i32 {\n    5\n}\n\nlet a = foo();\na + 1\n";
+    let edit = Indel::replace(range_of(text, "5"), "{ 6".to_owned());
+    let mut after = text.to_owned();
+    edit.apply(&mut after);
+
+    let parse = SourceFile::parse_script(text, DEFAULT_NESTING_LIMIT);
+    assert_eq!(
+        parse
+            .reparse_with(&edit, true, DEFAULT_NESTING_LIMIT)
+            .debug_dump(),
+        SourceFile::parse_script(&after, DEFAULT_NESTING_LIMIT).debug_dump()
+    );
+}
+
+#[test]
+fn reparse_respects_nesting_limit() {
+    let text = "fn foo() {\n    if true {\n        1\n    }\n}\n";
+    let edit = Indel::replace(range_of(text, "1"), "((((1))))".to_owned());
+    let mut after = text.to_owned();
+    edit.apply(&mut after);
+
+    let nesting_limit = 4;
+    let parse = SourceFile::parse_with_nesting_limit(text, nesting_limit);
+    let reparsed = parse.reparse_with(&edit, false, nesting_limit);
+    assert_eq!(
+        reparsed.debug_dump(),
+        SourceFile::parse_with_nesting_limit(&after, nesting_limit).debug_dump()
+    );
+    assert!(!reparsed.errors().is_empty());
+}
@@ -78,6 +78,10 @@ pub struct TargetOptions {
     /// "lib".
     pub dll_prefix: String,
 
+    /// String to append to the name of every dynamic library. Defaults to
+    /// ".so".
+    pub dll_suffix: String,
+
     /// Whether the target toolchain is like Windows
     pub is_like_windows: bool,
     pub is_like_msvc: bool,
@@ -103,6 +107,7 @@ impl Default for TargetOptions {
             cpu: "generic".to_string(),
             features: "".to_string(),
             dll_prefix: "lib".to_string(),
+            dll_suffix: ".so".to_string(),
             is_like_windows: false,
             is_like_msvc: false,
             is_like_osx: false,
@@ -107,6 +107,7 @@ pub fn opts(os: &'static str, arch: Arch) -> TargetOptions {
         vendor: "apple".into(),
         linker_flavor: LinkerFlavor::Ld64,
         dll_prefix: "lib".to_string(),
+        dll_suffix: ".dylib".to_string(),
         is_like_osx: os == "macos",
         pre_link_args: pre_link_args(os, arch, abi),
         ..Default::default()
@@ -7,6 +7,7 @@ pub fn opts() -> TargetOptions {
         vendor: "pc".into(),
         linker_flavor: LinkerFlavor::Msvc,
         dll_prefix: "".to_string(),
+        dll_suffix: ".dll".to_string(),
         is_like_windows: true,
         is_like_msvc: true,
         ..Default::default()
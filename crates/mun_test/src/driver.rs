@@ -64,10 +64,28 @@ impl CompileTestDriver {
 
     /// Constructs a new `CompileTestDriver` from a single Mun source.
     pub fn from_file(text: &str) -> Self {
+        Self::from_file_with_config(text, Config::default())
+    }
+
+    /// Constructs a new `CompileTestDriver` from a single Mun script. The
+    /// top-level statements of the script are compiled into an implicit
+    /// `main` function.
+    pub fn from_script(text: &str) -> Self {
+        let config = Config {
+            script: true,
+            ..Config::default()
+        };
+        Self::from_file_with_config(text, config)
+    }
+
+    /// Constructs a new `CompileTestDriver` from a single Mun source that is
+    /// compiled with the specified `config`. The output directory of the
+    /// `config` is replaced by a temporary directory.
+    pub fn from_file_with_config(text: &str, config: Config) -> Self {
         let temp_dir = tempfile::TempDir::new().unwrap();
         let config = Config {
             out_dir: Some(temp_dir.path().to_path_buf()),
-            ..Config::default()
+            ..config
         };
         let input = PathOrInline::Inline {
             rel_path: RelativePathBuf::from("mod.mun"),
@@ -176,6 +194,42 @@ impl CompileAndRunTestDriver {
         Ok(Self { driver, runtime })
     }
 
+    /// Constructs a `CompileAndRunTestDriver` from a single Mun source file that
+    /// is compiled with the specified compiler `config` and a `config_fn`
+    /// that allows modification of a [`RuntimeBuilder`].
+    pub fn from_file_with_config(
+        text: &str,
+        config: Config,
+        config_fn: impl FnOnce(RuntimeBuilder) -> RuntimeBuilder,
+    ) -> Result<Self, InitError> {
+        let driver = CompileTestDriver::from_file_with_config(text, config);
+        let builder = Runtime::builder(driver.lib_path());
+
+        // Safety: We compiled the library ourselves, therefor loading the munlib is
+        // safe.
+        let build = config_fn(builder);
+        let runtime = unsafe { build.finish() }?;
+
+        Ok(Self { driver, runtime })
+    }
+
+    /// Constructs a `CompileAndRunTestDriver` from a single Mun script and a
+    /// `config_fn` that allows modification of a [`RuntimeBuilder`].
+    pub fn from_script(
+        text: &str,
+        config_fn: impl FnOnce(RuntimeBuilder) -> RuntimeBuilder,
+    ) -> Result<Self, InitError> {
+        let driver = CompileTestDriver::from_script(text);
+        let builder = Runtime::builder(driver.lib_path());
+
+        // Safety: We compiled the library ourselves, therefor loading the munlib is
+        // safe.
+        let build = config_fn(builder);
+        let runtime = unsafe { build.finish() }?;
+
+        Ok(Self { driver, runtime })
+    }
+
     /// Updates the text of the Mun source and ensures that the generated
     /// assembly has been reloaded.
     ///